@@ -0,0 +1,63 @@
+//! Device-specific types for [ADBMS1818](<https://www.analog.com/en/products/adbms1818.html>)
+//!
+//! The ADBMS1818 is register compatible to the [LTC6813](crate::ltc6813) (18 cells, 9 GPIOs) and additionally
+//! provides a read-only serial ID register, which uniquely identifies each device of the daisy chain. Cell and GPIO
+//! selections, registers and channels are the ones of the LTC6813, [ADBMS1818] is an alias of [LTC6813].
+//!
+//! ````
+//! use ltc681x::adbms1818::ADBMS1818;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, ADBMS1818, 2> = LTC681X::adbms1818(ExampleSPIBus::default(), ExampleCSPin{});
+//! assert_eq!(18, client.capabilities().cell_count);
+//! ````
+use crate::ltc6813::LTC6813;
+use crate::monitor::{Error, NoPolling, PollMethod, TransferHook, LTC681X};
+use crate::register_group::{RegisterGroup, REGISTER_GROUP_LEN};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+pub use crate::ltc6813::{CellSelection, Channel, GPIOSelection, Register};
+
+/// Device type of ADBMS1818, an alias of [LTC6813] as both share the register map
+pub type ADBMS1818 = LTC6813;
+
+/// Read-only serial ID register group (SID), see [read_serial_ids](LTC681X::read_serial_ids)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SerialId(pub [u8; REGISTER_GROUP_LEN]);
+
+impl RegisterGroup for SerialId {
+    const READ_COMMAND: u16 = 0x002C;
+
+    fn decode(data: &[u8; REGISTER_GROUP_LEN]) -> Self {
+        SerialId(*data)
+    }
+}
+
+impl<B, CS, const L: usize> LTC681X<B, CS, NoPolling, ADBMS1818, L>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+{
+    /// Creates a client instant for ADBMS1818 variant
+    pub fn adbms1818(bus: B, cs: CS) -> Self {
+        LTC681X::new(bus, cs)
+    }
+}
+
+impl<B, CS, P, const L: usize, H> LTC681X<B, CS, P, ADBMS1818, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    H: TransferHook,
+{
+    /// Reads the serial IDs, one array item per device in daisy chain
+    /// Bytes are returned in register order, index 0 = `SID[7:0]`
+    ///
+    /// Only supported by the ADBMS1818, reads of LTC6813 devices fail with a PEC error.
+    pub fn read_serial_ids(&mut self) -> Result<[[u8; 6]; L], Error<B, CS>> {
+        Ok(self.read_register_group::<SerialId>()?.map(|id| id.0))
+    }
+}
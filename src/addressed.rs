@@ -0,0 +1,94 @@
+//! Addressed (parallel) topology
+//!
+//! Addressable devices (e.g. LTC6811-2, LTC6804-2) are not daisy-chained, instead all devices share the same SPI
+//! bus. Each device has a 4-bit address set by its address pins, which is encoded in the command word
+//! (CMD0 bit 7 = 1, CMD0 bits 6:3 = address). Only the addressed device executes the command and responds.
+//!
+//! The [AddressedLTC681X] wraps a single-device client and targets individual devices. All client methods are
+//! available for the selected device. Commands without response (e.g. conversion starts) may be
+//! [broadcast](AddressedLTC681X::broadcast) to all devices at once. As all devices drive the same SDO line,
+//! broadcast reads are not supported by the devices.
+//!
+//! ````
+//! use ltc681x::addressed::AddressedLTC681X;
+//! use ltc681x::conversion::ConversionRequest;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6811::{CellSelection, LTC6811, Register};
+//! use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//!
+//! let client: LTC681X<_, _, _, LTC6811, 1> = LTC681X::ltc6811(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut devices = AddressedLTC681X::new(client);
+//!
+//! // Starting the conversion of all devices at once
+//! let request = ConversionRequest::cells(CellSelection::All).mode(ADCMode::Normal).discharge_permitted(true);
+//! devices.broadcast().start_conversion(request).unwrap();
+//!
+//! // Reading the cell voltages of device with address 3
+//! let voltages = devices.device(3).read_register(Register::CellVoltageA).unwrap();
+//! ````
+use crate::monitor::{DeviceTypes, NoHook, PollMethod, TransferHook, LTC681X};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Highest device address (4-bit)
+pub const MAX_ADDRESS: u8 = 15;
+
+/// Address mode bit (CMD0 bit 7)
+const ADDRESS_MODE: u16 = 0b1000_0000_0000_0000;
+
+/// Offset of the address within the command word
+const ADDRESS_OFFSET: u16 = 11;
+
+/// Returns the given broadcast command addressed to the given device
+pub fn address_command(command: u16, address: u8) -> u16 {
+    command | ADDRESS_MODE | (((address & MAX_ADDRESS) as u16) << ADDRESS_OFFSET)
+}
+
+/// Client for devices sharing the same SPI bus in addressed (parallel) topology
+pub struct AddressedLTC681X<B, CS, P, T, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    client: LTC681X<B, CS, P, T, 1, H>,
+}
+
+impl<B, CS, P, T, H> AddressedLTC681X<B, CS, P, T, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Wraps the given single-device client
+    pub fn new(client: LTC681X<B, CS, P, T, 1, H>) -> Self {
+        Self { client }
+    }
+
+    /// Returns the client targeting the device with the given address
+    ///
+    /// Panics if the address exceeds [MAX_ADDRESS].
+    pub fn device(&mut self, address: u8) -> &mut LTC681X<B, CS, P, T, 1, H> {
+        assert!(address <= MAX_ADDRESS, "Device address exceeds MAX_ADDRESS");
+
+        self.client.set_address(Some(address));
+        &mut self.client
+    }
+
+    /// Returns the client broadcasting commands to all devices
+    /// Only commands without response (conversion starts, writes) may be broadcast.
+    pub fn broadcast(&mut self) -> &mut LTC681X<B, CS, P, T, 1, H> {
+        self.client.set_address(None);
+        &mut self.client
+    }
+
+    /// Releases the wrapped client, which broadcasts all commands again
+    pub fn release(mut self) -> LTC681X<B, CS, P, T, 1, H> {
+        self.client.set_address(None);
+        self.client
+    }
+}
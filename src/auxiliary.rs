@@ -0,0 +1,391 @@
+//! Mapping of GPIOs to sensor roles
+//!
+//! An [AuxChannelMap] declares per device what each GPIO measures. Based on this map, the converted GPIO voltages
+//! are labeled and converted into physical values automatically.
+//!
+//! ````
+//! use ltc681x::auxiliary::{AuxChannel, AuxChannelMap, AuxValue};
+//! use ltc681x::config::GPIO;
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{Channel, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//! use ltc681x::temperature::{Thermistor, ThermistorModel, VoltageDivider};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let thermistor = Thermistor::new(
+//!     ThermistorModel::Beta { nominal_resistance: 10_000, beta: 3435 },
+//!     VoltageDivider { supply_voltage: 3_000_000, series_resistance: 10_000 },
+//! );
+//!
+//! let map = AuxChannelMap::new()
+//!     .with(GPIO::GPIO1, AuxChannel::CurrentSense { offset: 2_500_000, sensitivity: 40_000 })
+//!     .with(GPIO::GPIO2, AuxChannel::Thermistor { thermistor, ratiometric: true });
+//!
+//! let values = client.read_aux_channels(&[map], ADCMode::Normal, &mut ExampleDelay::default()).unwrap();
+//!
+//! // Unused GPIOs are skipped
+//! assert_eq!(2, values[0].len());
+//!
+//! assert_eq!(Channel::GPIO1, values[0][0].channel);
+//! assert!(matches!(values[0][0].value, AuxValue::Current(_)));
+//!
+//! assert_eq!(Channel::GPIO2, values[0][1].channel);
+//! assert!(matches!(values[0][1].value, AuxValue::Temperature(Some(_))));
+//! ````
+//!
+//! ## Snapshots
+//!
+//! Voltages collected in a [Snapshot] (e.g. by a [conversion sequence](crate::sequence)) are converted by
+//! [Snapshot::aux_values] based on the same maps. The second reference is stored by the snapshot as well, so
+//! ratiometric thermistors are supported, if the second reference was measured.
+//!
+//! ````
+//!# use ltc681x::auxiliary::{AuxChannel, AuxChannelMap, AuxValue};
+//!# use ltc681x::config::GPIO;
+//! use ltc681x::snapshot::Snapshot;
+//!
+//! let mut snapshot: Snapshot<1> = Snapshot::new();
+//! snapshot.device_mut(0).gpio_voltages[1] = Some(7867);
+//!
+//! let map = AuxChannelMap::new().with(GPIO::GPIO2, AuxChannel::Voltage);
+//! let values = snapshot.aux_values(&[map]);
+//!
+//! assert_eq!(Some(AuxValue::Voltage(786_700)), values[0][1]);
+//! // Unused GPIO
+//! assert_eq!(None, values[0][0]);
+//! ````
+//!
+//! ## External dividers
+//!
+//! Inputs measuring voltages above the GPIO range are connected via resistive dividers. The per-GPIO
+//! [AuxScaling] is applied to the GPIO voltage before the conversion according to the role, so the converted
+//! value is based on the real-world voltage. The raw register value stays accessible by [AuxMeasurement::voltage].
+//!
+//! ````
+//! use ltc681x::auxiliary::{AuxChannel, AuxChannelMap, AuxScaling, AuxValue};
+//! use ltc681x::config::GPIO;
+//!
+//! // 100 kOhm / 10 kOhm divider
+//! let map = AuxChannelMap::new()
+//!     .with(GPIO::GPIO3, AuxChannel::Voltage)
+//!     .with_scaling(GPIO::GPIO3, AuxScaling::divider(100_000, 10_000));
+//!
+//! // 1.2 V at the GPIO results in 13.2 V at the divider input
+//! assert_eq!(Some(AuxValue::Voltage(13_200_000)), map.convert_by_index(2, 1_200_000, None));
+//! ````
+use crate::config::GPIO;
+use crate::monitor::{
+    ADCMode, ChannelIndex, ChannelType, DeviceTypes, Error, PollMethod, TransferHook, Voltage, LTC681X,
+};
+use crate::snapshot::{DeviceSnapshot, Snapshot, MAX_GPIOS};
+use crate::temperature::Thermistor;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use fixed::types::I16F16;
+use heapless::Vec;
+
+/// Role of a single GPIO
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum AuxChannel {
+    /// GPIO is not converted into a measurement
+    Unused,
+    /// Raw voltage
+    Voltage,
+    /// NTC thermistor. If ratiometric, the second reference is used as excitation voltage.
+    Thermistor { thermistor: Thermistor, ratiometric: bool },
+    /// Linear current sensor
+    CurrentSense {
+        /// Output voltage in uV at zero current
+        offset: u32,
+        /// Sensitivity in uV per A
+        sensitivity: u32,
+    },
+    /// Linear relative humidity sensor
+    Humidity {
+        /// Output voltage in uV at 0 %RH
+        offset: u32,
+        /// Sensitivity in uV per %RH
+        sensitivity: u32,
+    },
+}
+
+/// Converted value of a single GPIO
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum AuxValue {
+    /// Voltage in uV
+    Voltage(u32),
+    /// Temperature in °C, None if the voltage is out of the valid range (e.g. open or shorted thermistor)
+    Temperature(Option<I16F16>),
+    /// Current in A
+    Current(I16F16),
+    /// Relative humidity in %RH
+    Humidity(I16F16),
+}
+
+impl AuxChannel {
+    /// Converts the given GPIO voltage (uV). The reference voltage (uV) is used by ratiometric thermistors.
+    /// Returns None for unused channels.
+    pub fn convert(&self, voltage: u32, reference: Option<u32>) -> Option<AuxValue> {
+        match self {
+            AuxChannel::Unused => None,
+            AuxChannel::Voltage => Some(AuxValue::Voltage(voltage)),
+            AuxChannel::Thermistor {
+                thermistor,
+                ratiometric,
+            } => {
+                let temperature = match (ratiometric, reference) {
+                    (false, _) => thermistor.temperature(voltage),
+                    (true, Some(reference)) => thermistor.ratiometric_temperature(voltage, reference),
+                    (true, None) => None,
+                };
+
+                Some(AuxValue::Temperature(temperature))
+            }
+            AuxChannel::CurrentSense { offset, sensitivity } => {
+                Some(AuxValue::Current(linear(voltage, *offset, *sensitivity)))
+            }
+            AuxChannel::Humidity { offset, sensitivity } => {
+                Some(AuxValue::Humidity(linear(voltage, *offset, *sensitivity)))
+            }
+        }
+    }
+}
+
+/// Linear scaling of a GPIO voltage: voltage * numerator / denominator + offset
+///
+/// The scaling uses integer math only. The product is calculated with 64 bits, so no precision is lost before
+/// the division, which rounds down.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AuxScaling {
+    /// Numerator of the scale factor
+    pub numerator: u32,
+
+    /// Denominator of the scale factor, zero is treated like one
+    pub denominator: u32,
+
+    /// Offset in uV, added after scaling
+    pub offset: i32,
+}
+
+impl Default for AuxScaling {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl AuxScaling {
+    /// Scaling without any effect
+    pub const IDENTITY: Self = Self {
+        numerator: 1,
+        denominator: 1,
+        offset: 0,
+    };
+
+    /// Creates a new scaling with the given ratio and offset (uV)
+    pub fn new(numerator: u32, denominator: u32, offset: i32) -> Self {
+        Self {
+            numerator,
+            denominator,
+            offset,
+        }
+    }
+
+    /// Creates the scaling of a resistive divider, the GPIO is connected to the bottom resistor
+    /// Returns the identity scaling if the bottom resistance is zero.
+    pub fn divider(top_resistance: u32, bottom_resistance: u32) -> Self {
+        if bottom_resistance == 0 {
+            return Self::IDENTITY;
+        }
+
+        Self::new(top_resistance.saturating_add(bottom_resistance), bottom_resistance, 0)
+    }
+
+    /// Applies the scaling to the given voltage (uV). Saturates at zero and u32::MAX.
+    pub fn apply(&self, voltage: u32) -> u32 {
+        let scaled = voltage as i64 * self.numerator as i64 / self.denominator.max(1) as i64;
+        (scaled + self.offset as i64).clamp(0, u32::MAX as i64) as u32
+    }
+}
+
+/// Roles of all GPIOs of a single device
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AuxChannelMap {
+    channels: [AuxChannel; MAX_GPIOS],
+
+    /// Scaling applied before the conversion
+    scaling: [AuxScaling; MAX_GPIOS],
+}
+
+impl Default for AuxChannelMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuxChannelMap {
+    /// Creates a map with all GPIOs unused
+    pub fn new() -> Self {
+        Self {
+            channels: [AuxChannel::Unused; MAX_GPIOS],
+            scaling: [AuxScaling::IDENTITY; MAX_GPIOS],
+        }
+    }
+
+    /// Declares the role of the given GPIO
+    pub fn with(mut self, pin: GPIO, role: AuxChannel) -> Self {
+        self.set(pin, role);
+        self
+    }
+
+    /// Declares the role of the given GPIO
+    pub fn set(&mut self, pin: GPIO, role: AuxChannel) {
+        self.channels[pin as usize] = role;
+    }
+
+    /// Returns the role of the given GPIO
+    pub fn get(&self, pin: GPIO) -> AuxChannel {
+        self.channels[pin as usize]
+    }
+
+    /// Returns the role of the GPIO with the given index (0 = GPIO1)
+    pub fn get_by_index(&self, index: usize) -> AuxChannel {
+        self.channels.get(index).copied().unwrap_or(AuxChannel::Unused)
+    }
+
+    /// Sets the scaling of the given GPIO, e.g. for an external divider
+    pub fn with_scaling(mut self, pin: GPIO, scaling: AuxScaling) -> Self {
+        self.set_scaling(pin, scaling);
+        self
+    }
+
+    /// Sets the scaling of the given GPIO, e.g. for an external divider
+    pub fn set_scaling(&mut self, pin: GPIO, scaling: AuxScaling) {
+        self.scaling[pin as usize] = scaling;
+    }
+
+    /// Returns the scaling of the given GPIO
+    pub fn get_scaling(&self, pin: GPIO) -> AuxScaling {
+        self.scaling[pin as usize]
+    }
+
+    /// Scales and converts the voltage (uV) of the GPIO with the given index (0 = GPIO1) according to its role
+    /// The reference voltage (uV) is used by ratiometric thermistors and not scaled.
+    pub fn convert_by_index(&self, index: usize, voltage: u32, reference: Option<u32>) -> Option<AuxValue> {
+        let scaling = self.scaling.get(index).copied().unwrap_or(AuxScaling::IDENTITY);
+        self.get_by_index(index).convert(scaling.apply(voltage), reference)
+    }
+}
+
+/// Labeled and converted value of a single GPIO
+#[derive(Debug)]
+pub struct AuxMeasurement<T: DeviceTypes> {
+    /// Corresponding GPIO channel
+    pub channel: T::Channel,
+
+    /// Raw register value, without scaling
+    /// Real voltage: voltage * 100 uV
+    pub voltage: u16,
+
+    /// Converted value according to the channel role and scaling
+    pub value: AuxValue,
+}
+
+impl<T: DeviceTypes> Copy for AuxMeasurement<T> {}
+
+impl<T: DeviceTypes> Clone for AuxMeasurement<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Converts all GPIOs, waits for completion and returns the values of all used channels
+    /// Returns one vector for each device in daisy chain
+    ///
+    /// See [wait_conversion](LTC681X::wait_conversion) for waiting and the timeout.
+    pub fn read_aux_channels<D: DelayUs<u32>>(
+        &mut self,
+        maps: &[AuxChannelMap; L],
+        mode: ADCMode,
+        delay: &mut D,
+    ) -> Result<Vec<Vec<AuxMeasurement<T>, 9>, L>, Error<B, CS>> {
+        let voltages = self.begin_gpio_conversion(mode, T::ALL_GPIOS)?.wait(delay)?;
+        Ok(map_aux_channels(&voltages, maps))
+    }
+}
+
+/// Labels and converts the GPIO voltages of all devices based on the given maps
+pub(crate) fn map_aux_channels<T: DeviceTypes, const L: usize>(
+    voltages: &Vec<Vec<Voltage<T>, 18>, L>,
+    maps: &[AuxChannelMap; L],
+) -> Vec<Vec<AuxMeasurement<T>, 9>, L> {
+    let mut result = Vec::new();
+
+    for (device, map) in voltages.iter().zip(maps.iter()) {
+        let mut measurements = Vec::new();
+
+        let reference = device
+            .iter()
+            .find(|voltage| matches!(voltage.channel.into(), ChannelType::Reference))
+            .map(|voltage| voltage.voltage as u32 * 100);
+
+        for voltage in device {
+            let index = match voltage.channel.to_gpio_index() {
+                None => continue,
+                Some(index) => index,
+            };
+
+            if let Some(value) = map.convert_by_index(index, voltage.voltage as u32 * 100, reference) {
+                let _ = measurements.push(AuxMeasurement {
+                    channel: voltage.channel,
+                    voltage: voltage.voltage,
+                    value,
+                });
+            }
+        }
+
+        let _ = result.push(measurements);
+    }
+
+    result
+}
+
+impl DeviceSnapshot {
+    /// Labels and converts the stored GPIO voltages based on the given map, index 0 = GPIO 1
+    /// None for unused or not yet measured GPIOs. Ratiometric thermistors require the second reference.
+    pub fn aux_values(&self, map: &AuxChannelMap) -> [Option<AuxValue>; MAX_GPIOS] {
+        let reference = self.reference_voltage.map(|voltage| voltage as u32 * 100);
+
+        core::array::from_fn(|index| {
+            let voltage = self.gpio_voltages[index]?;
+            map.convert_by_index(index, voltage as u32 * 100, reference)
+        })
+    }
+}
+
+impl<const L: usize> Snapshot<L> {
+    /// Labels and converts the stored GPIO voltages of all devices based on the given maps
+    /// See [DeviceSnapshot::aux_values].
+    pub fn aux_values(&self, maps: &[AuxChannelMap; L]) -> [[Option<AuxValue>; MAX_GPIOS]; L] {
+        core::array::from_fn(|index| self.devices()[index].aux_values(&maps[index]))
+    }
+}
+
+/// Converts the voltage of a linear sensor: (voltage - offset) / sensitivity
+fn linear(voltage: u32, offset: u32, sensitivity: u32) -> I16F16 {
+    if sensitivity == 0 {
+        return I16F16::ZERO;
+    }
+
+    let bits = ((voltage as i64 - offset as i64) << 16) / sensitivity as i64;
+    I16F16::from_bits(bits.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+}
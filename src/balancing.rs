@@ -0,0 +1,317 @@
+//! Safe cell balancing
+//!
+//! The [BalancingGuard] wraps the client and remembers the last written configuration. When the guard is
+//! dropped or a fatal error occurs, it best-effort writes the configuration with all discharge switches
+//! (DCC bits) turned off. So a firmware panic path doesn't leave bleed resistors cooking indefinitely.
+//!
+//! ````
+//! use ltc681x::balancing::BalancingGuard;
+//! use ltc681x::config::{Cell, Configuration};
+//! use ltc681x::conversion::ConversionRequest;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//!
+//! let client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut guard = BalancingGuard::new(client, [Configuration::default()]);
+//!
+//! let mut config = Configuration::default();
+//! config.discharge_cell(Cell::Cell3);
+//! guard.write_configuration([config]).unwrap();
+//!
+//! // Client is still accessible
+//! let request = ConversionRequest::cells(CellSelection::All).mode(ADCMode::Normal).discharge_permitted(true);
+//! guard.client().start_conversion(request).unwrap();
+//!
+//! // Discharge of cell 3 is stopped when the guard goes out of scope
+//! drop(guard);
+//! ````
+//!
+//! ## Auto-expire
+//! In case the MCU stops refreshing the configuration (e.g. hangs without running the drop code), the discharge
+//! timer of the device can turn off the switches. When configuring the control period, the guard programs the
+//! shortest [DischargeTimeout] exceeding this period on every configuration write. Periods of two hours or longer
+//! are rejected, as rounding down would turn off discharge before the next refresh.
+//!
+//! ````
+//! use ltc681x::balancing::BalancingGuard;
+//! use ltc681x::config::{Cell, Configuration};
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // Configuration is refreshed every 10 seconds, so discharge expires after 30 seconds
+//! let mut guard = BalancingGuard::new(client, [Configuration::default()]);
+//! guard.set_auto_expire(10_000).unwrap();
+//!
+//! // No timeout exceeds two hours
+//! assert!(guard.set_auto_expire(7_200_000).is_err());
+//!
+//! let mut config = Configuration::default();
+//! config.discharge_cell(Cell::Cell3);
+//! guard.write_configuration([config]).unwrap();
+//! ````
+//!
+//! ## On-time accumulation
+//! [BalancingStatistics] accumulates the discharge-enabled time per cell, so that the long-term balancing effort
+//! can be reported. Cells which need (nearly) constant balancing may indicate an abnormal self-discharge and are
+//! [flagged](BalancingStatistics::flagged_cells) based on their share of the recorded time.
+//!
+//! The accumulated times may be [persisted](BalancingStatistics::on_times) and
+//! [restored](BalancingStatistics::restore) across restarts.
+//!
+//! ````
+//! use ltc681x::balancing::{BalancingGuard, BalancingStatistics};
+//! use ltc681x::config::{Cell, Configuration};
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut guard = BalancingGuard::new(client, [Configuration::default()]);
+//! let mut statistics: BalancingStatistics<1> = BalancingStatistics::new();
+//!
+//! let mut config = Configuration::default();
+//! config.discharge_cell(Cell::Cell3);
+//! guard.write_configuration([config]).unwrap();
+//!
+//! // Called once per control period (1 second)
+//! statistics.record(guard.configuration(), 1_000);
+//!
+//! assert_eq!(1_000, statistics.on_time(0, Cell::Cell3));
+//! assert_eq!(0, statistics.on_time(0, Cell::Cell4));
+//!
+//! // Cell 3 was discharging more than 80% of the time
+//! assert_eq!(Some((0, Cell::Cell3)), statistics.flagged_cells(800).next());
+//! ````
+use crate::config::{Cell, ConfigurationRegisters, DischargeControl, DischargeTimeout, TimeoutOutOfRangeError};
+use crate::monitor::{DeviceTypes, Error, LTC681XClient, NoHook, PollMethod, TransferHook, LTC681X};
+use crate::snapshot::MAX_CELLS;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Wrapper turning off all discharge switches when dropped or on fatal errors
+pub struct BalancingGuard<B, CS, P, T, C, const L: usize, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    C: ConfigurationRegisters + DischargeControl + Clone,
+    H: TransferHook,
+{
+    /// Wrapped client, None once released
+    client: Option<LTC681X<B, CS, P, T, L, H>>,
+
+    /// Last written configuration, one item per device in daisy chain
+    configuration: [C; L],
+
+    /// Discharge timeout programmed on every write, None if auto-expire is disabled
+    auto_expire: Option<DischargeTimeout>,
+}
+
+impl<B, CS, P, T, C, const L: usize, H> BalancingGuard<B, CS, P, T, C, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    C: ConfigurationRegisters + DischargeControl + Clone,
+    H: TransferHook,
+{
+    /// Wraps the given client
+    ///
+    /// The given configuration is used as base for the fail-safe write, in case no configuration has been
+    /// written using the guard yet.
+    pub fn new(client: LTC681X<B, CS, P, T, L, H>, configuration: [C; L]) -> Self {
+        Self {
+            client: Some(client),
+            configuration,
+            auto_expire: None,
+        }
+    }
+
+    /// Enables the discharge timer on every write using the shortest timeout exceeding the given control
+    /// period (in milliseconds)
+    ///
+    /// Returns an error if the period is two hours or longer, auto-expire stays unchanged in this case.
+    /// See [DischargeTimeout::exceeding] for details.
+    pub fn set_auto_expire(&mut self, control_period_ms: u32) -> Result<(), TimeoutOutOfRangeError> {
+        self.auto_expire = Some(DischargeTimeout::exceeding(control_period_ms)?);
+        Ok(())
+    }
+
+    /// Returns the discharge timeout programmed on every write, None if auto-expire is disabled
+    pub fn get_auto_expire(&self) -> Option<DischargeTimeout> {
+        self.auto_expire
+    }
+
+    /// Writes the configuration, one array item per device in daisy chain
+    ///
+    /// In case of an error, the fail-safe configuration is written (best-effort).
+    /// When auto-expire is enabled, the discharge timer and timeout are set on each item before writing.
+    pub fn write_configuration(&mut self, mut configuration: [C; L]) -> Result<(), Error<B, CS>> {
+        if let Some(timeout) = self.auto_expire {
+            for item in configuration.iter_mut() {
+                item.enable_discharge_timer();
+                item.set_discharge_timeout(timeout);
+            }
+        }
+
+        self.configuration = configuration.clone();
+
+        let result = self.client().write_configuration(configuration);
+        if result.is_err() {
+            let _ = self.fail_safe();
+        }
+
+        result
+    }
+
+    /// Invokes the given closure with the wrapped client
+    ///
+    /// In case the closure returns an error, this is considered as fatal error and the fail-safe
+    /// configuration is written (best-effort).
+    pub fn with_client<R, F>(&mut self, f: F) -> Result<R, Error<B, CS>>
+    where
+        F: FnOnce(&mut LTC681X<B, CS, P, T, L, H>) -> Result<R, Error<B, CS>>,
+    {
+        let result = f(self.client());
+        if result.is_err() {
+            let _ = self.fail_safe();
+        }
+
+        result
+    }
+
+    /// Writes the last configuration with all discharge switches turned off
+    pub fn fail_safe(&mut self) -> Result<(), Error<B, CS>> {
+        for item in self.configuration.iter_mut() {
+            item.clear_discharge_cells();
+        }
+
+        let configuration = self.configuration.clone();
+        self.client().write_configuration(configuration)
+    }
+
+    /// Returns the last written configuration
+    pub fn configuration(&self) -> &[C; L] {
+        &self.configuration
+    }
+
+    /// Returns the wrapped client
+    pub fn client(&mut self) -> &mut LTC681X<B, CS, P, T, L, H> {
+        self.client.as_mut().expect("client already released")
+    }
+
+    /// Releases the client **without** turning off the discharge switches
+    pub fn release(mut self) -> LTC681X<B, CS, P, T, L, H> {
+        self.client.take().expect("client already released")
+    }
+}
+
+impl<B, CS, P, T, C, const L: usize, H> Drop for BalancingGuard<B, CS, P, T, C, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    C: ConfigurationRegisters + DischargeControl + Clone,
+    H: TransferHook,
+{
+    fn drop(&mut self) {
+        if self.client.is_some() {
+            let _ = self.fail_safe();
+        }
+    }
+}
+
+/// Accumulated discharge-enabled time per cell
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct BalancingStatistics<const L: usize> {
+    /// Discharge-enabled time in milliseconds, one array item per device in daisy chain
+    on_times: [[u64; MAX_CELLS]; L],
+
+    /// Total recorded time in milliseconds
+    total_time: u64,
+}
+
+impl<const L: usize> Default for BalancingStatistics<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const L: usize> BalancingStatistics<L> {
+    /// Creates new statistics without any recorded time
+    pub fn new() -> Self {
+        Self {
+            on_times: [[0; MAX_CELLS]; L],
+            total_time: 0,
+        }
+    }
+
+    /// Restores previously persisted statistics
+    /// See [on_times](Self::on_times) and [total_time](Self::total_time).
+    pub fn restore(on_times: [[u64; MAX_CELLS]; L], total_time: u64) -> Self {
+        Self { on_times, total_time }
+    }
+
+    /// Adds the elapsed time (ms) to all cells discharging according to the given configuration
+    /// One configuration item per device in daisy chain, usually the [last written](BalancingGuard::configuration)
+    /// configuration.
+    pub fn record<C: DischargeControl>(&mut self, configuration: &[C; L], elapsed_ms: u32) {
+        for (device, config) in self.on_times.iter_mut().zip(configuration.iter()) {
+            for (index, on_time) in device.iter_mut().enumerate() {
+                if config.is_cell_discharging(Cell::from(index)) {
+                    *on_time = on_time.saturating_add(elapsed_ms as u64);
+                }
+            }
+        }
+
+        self.total_time = self.total_time.saturating_add(elapsed_ms as u64);
+    }
+
+    /// Returns the discharge-enabled time (ms) of the given cell
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn on_time(&self, device: usize, cell: Cell) -> u64 {
+        self.on_times[device][cell as usize]
+    }
+
+    /// Returns the discharge-enabled time (ms) of all cells, one array item per device in daisy chain
+    pub fn on_times(&self) -> &[[u64; MAX_CELLS]; L] {
+        &self.on_times
+    }
+
+    /// Returns the total recorded time in milliseconds
+    pub fn total_time(&self) -> u64 {
+        self.total_time
+    }
+
+    /// Returns the share (per mille) of the recorded time the given cell was discharging
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn duty(&self, device: usize, cell: Cell) -> u32 {
+        if self.total_time == 0 {
+            return 0;
+        }
+
+        (self.on_time(device, cell) * 1000 / self.total_time) as u32
+    }
+
+    /// Returns device index and cell of all cells discharging longer than the given share (per mille) of the
+    /// recorded time
+    pub fn flagged_cells(&self, max_duty: u32) -> impl Iterator<Item = (usize, Cell)> + '_ {
+        (0..L)
+            .flat_map(|device| (0..MAX_CELLS).map(move |index| (device, Cell::from(index))))
+            .filter(move |(device, cell)| self.duty(*device, *cell) > max_duty)
+    }
+
+    /// Clears all recorded times
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
@@ -0,0 +1,252 @@
+//! Generic battery monitor interface
+//!
+//! The [BatteryMonitor] trait abstracts the basic operations of a cell monitoring chain, so that application and
+//! test code can be written against the trait and reused across chip generations or vendors.
+//!
+//! The [ChainMonitor] implements the trait for the LTC681X client. It holds the client, the base configuration
+//! used for discharge control and the (optional) temperature settings. Depending on the poll method, the
+//! conversion time is either awaited by the given delay or by polling the SDO line, see
+//! [wait_conversion](LTC681X::wait_conversion).
+//!
+//! ````
+//! use ltc681x::battery::{BatteryMonitor, ChainMonitor};
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{GPIOSelection, LTC6813};
+//! use ltc681x::monitor::LTC681X;
+//! use ltc681x::snapshot::MAX_CELLS;
+//! use ltc681x::temperature::{TemperatureConfig, Thermistor, ThermistorModel, VoltageDivider};
+//!
+//! let client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let thermistor = Thermistor::new(
+//!     ThermistorModel::Beta { nominal_resistance: 10_000, beta: 3435 },
+//!     VoltageDivider { supply_voltage: 3_000_000, series_resistance: 10_000 },
+//! );
+//!
+//! let mut monitor = ChainMonitor::new(client, ExampleDelay::default())
+//!     .with_temperatures(TemperatureConfig::new(GPIOSelection::Group2, thermistor));
+//!
+//! run(&mut monitor);
+//!
+//! // Application code independent of the device type
+//! fn run<M: BatteryMonitor<1>>(monitor: &mut M) {
+//!     let cells = monitor.measure_cells().ok().unwrap();
+//!     assert_eq!(Some(24979), cells.device(0).cell_voltages[0]);
+//!
+//!     let temperatures = monitor.measure_temperatures().ok().unwrap();
+//!     assert_eq!(54, temperatures[0][1].unwrap().round().to_num::<i32>());
+//!
+//!     let mut discharge = [[false; MAX_CELLS]; 1];
+//!     discharge[0][2] = true;
+//!     monitor.set_discharge(&discharge).ok().unwrap();
+//!
+//!     // The example bus returns measured voltages instead of the self-test pattern
+//!     assert_eq!([false], monitor.self_test().ok().unwrap());
+//! }
+//! ````
+use crate::config::{Cell, DischargeControl};
+use crate::monitor::{
+    ADCMode, ChannelIndex, DeviceTypes, Error, LTC681XClient, NoHook, PollMethod, TransferHook, LTC681X,
+};
+use crate::self_test::{SelfTestPattern, SelfTestReport};
+use crate::snapshot::{Snapshot, MAX_CELLS, MAX_GPIOS};
+use crate::temperature::{Temperature, TemperatureConfig};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use fixed::types::I16F16;
+use heapless::Vec;
+
+/// Device independent operations of a battery monitoring chain with L devices
+pub trait BatteryMonitor<const L: usize> {
+    type Error;
+
+    /// Measures all cells and returns the raw voltages (100 uV) of all devices
+    fn measure_cells(&mut self) -> Result<Snapshot<L>, Self::Error>;
+
+    /// Measures the temperature channels, indexed by GPIO (index 0 = GPIO 1)
+    /// Channels without temperature measurement are None.
+    fn measure_temperatures(&mut self) -> Result<[[Option<I16F16>; MAX_GPIOS]; L], Self::Error>;
+
+    /// Sets the discharge switches of all devices, indexed by cell (index 0 = cell 1)
+    fn set_discharge(&mut self, cells: &[[bool; MAX_CELLS]; L]) -> Result<(), Self::Error>;
+
+    /// Runs the self-test of all devices. Returns true for each device which passed the test.
+    fn self_test(&mut self) -> Result<[bool; L], Self::Error>;
+}
+
+/// Battery monitor based on the LTC681X client
+///
+/// The self-test runs the cell (CVST), auxiliary (AXST) and status (STATST) ADC self-tests using
+/// [SelfTestPattern::Pattern1]. A device passes if all channels of all three tests match the pattern.
+pub struct ChainMonitor<B, CS, P, T, D, const L: usize, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    client: LTC681X<B, CS, P, T, L, H>,
+
+    /// Delay used for awaiting conversions
+    pub(crate) delay: D,
+
+    /// ADC mode used for cell conversion and self-test
+    mode: ADCMode,
+
+    /// Base configuration, one item per device in daisy chain
+    configuration: [T::Configuration; L],
+
+    /// Settings for temperature measurement, None if disabled
+    temperatures: Option<TemperatureConfig<T>>,
+}
+
+impl<B, CS, P, T, D, const L: usize, H> ChainMonitor<B, CS, P, T, D, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Wraps the given client, conversions are awaited using the given delay
+    pub fn new(client: LTC681X<B, CS, P, T, L, H>, delay: D) -> Self {
+        Self {
+            client,
+            delay,
+            mode: ADCMode::Normal,
+            configuration: core::array::from_fn(|_| T::Configuration::default()),
+            temperatures: None,
+        }
+    }
+
+    /// Sets the ADC mode used for cell conversion and self-test, defaults to [ADCMode::Normal]
+    pub fn mode(mut self, mode: ADCMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the base configuration, on which the discharge switches are applied
+    pub fn with_configuration(mut self, configuration: [T::Configuration; L]) -> Self {
+        self.configuration = configuration;
+        self
+    }
+
+    /// Enables temperature measurement using the given settings
+    pub fn with_temperatures(mut self, config: TemperatureConfig<T>) -> Self {
+        self.temperatures = Some(config);
+        self
+    }
+
+    /// Returns the selected ADC mode
+    pub fn get_mode(&self) -> ADCMode {
+        self.mode
+    }
+
+    /// Returns the current configuration, including the discharge switches
+    pub fn configuration(&self) -> &[T::Configuration; L] {
+        &self.configuration
+    }
+
+    /// Returns the wrapped client
+    pub fn client(&mut self) -> &mut LTC681X<B, CS, P, T, L, H> {
+        &mut self.client
+    }
+
+    /// Consumes the monitor and returns the client
+    pub fn release(self) -> LTC681X<B, CS, P, T, L, H> {
+        self.client
+    }
+
+    /// Applies the discharge switches to the base configuration and writes it
+    fn write_discharge(&mut self, cells: &[[bool; MAX_CELLS]; L]) -> Result<(), Error<B, CS>> {
+        for (config, cells) in self.configuration.iter_mut().zip(cells.iter()) {
+            config.clear_discharge_cells();
+
+            for (index, _) in cells.iter().enumerate().take(T::CELL_COUNT).filter(|(_, active)| **active) {
+                config.discharge_cell(Cell::from(index));
+            }
+        }
+
+        self.client.write_configuration(self.configuration.clone())
+    }
+}
+
+impl<B, CS, P, T, D, const L: usize, H> BatteryMonitor<L> for ChainMonitor<B, CS, P, T, D, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    D: DelayUs<u32>,
+    H: TransferHook,
+{
+    type Error = Error<B, CS>;
+
+    fn measure_cells(&mut self) -> Result<Snapshot<L>, Self::Error> {
+        let voltages = self.client.measure_cell_voltages(self.mode, T::ALL_CELLS, &mut self.delay)?;
+
+        let mut snapshot = Snapshot::new();
+        snapshot.update(&voltages);
+        Ok(snapshot)
+    }
+
+    fn measure_temperatures(&mut self) -> Result<[[Option<I16F16>; MAX_GPIOS]; L], Self::Error> {
+        match &self.temperatures {
+            None => Ok([[None; MAX_GPIOS]; L]),
+            Some(config) => Ok(map_temperatures(
+                &self.client.read_temperatures(config, &mut self.delay)?,
+            )),
+        }
+    }
+
+    fn set_discharge(&mut self, cells: &[[bool; MAX_CELLS]; L]) -> Result<(), Self::Error> {
+        self.write_discharge(cells)
+    }
+
+    fn self_test(&mut self) -> Result<[bool; L], Self::Error> {
+        let (mode, pattern) = (self.mode, SelfTestPattern::Pattern1);
+        let mut result = [true; L];
+
+        let timing = self.client.cell_voltage_self_test(mode, pattern)?;
+        self.client.wait_conversion(timing, &mut self.delay)?;
+        apply_report(&mut result, &self.client.verify_cell_voltage_self_test(mode, pattern)?);
+
+        let timing = self.client.aux_self_test(mode, pattern)?;
+        self.client.wait_conversion(timing, &mut self.delay)?;
+        apply_report(&mut result, &self.client.verify_aux_self_test(mode, pattern)?);
+
+        let timing = self.client.status_self_test(mode, pattern)?;
+        self.client.wait_conversion(timing, &mut self.delay)?;
+        apply_report(&mut result, &self.client.verify_status_self_test(mode, pattern)?);
+
+        Ok(result)
+    }
+}
+
+/// Maps the temperatures of all devices to GPIO indices
+fn map_temperatures<T: DeviceTypes, const L: usize>(
+    temperatures: &Vec<Vec<Temperature<T>, 9>, L>,
+) -> [[Option<I16F16>; MAX_GPIOS]; L] {
+    let mut result = [[None; MAX_GPIOS]; L];
+
+    for (device, temperatures) in result.iter_mut().zip(temperatures.iter()) {
+        for temperature in temperatures {
+            if let Some(slot) = temperature.channel.to_gpio_index().and_then(|index| device.get_mut(index)) {
+                *slot = temperature.temperature;
+            }
+        }
+    }
+
+    result
+}
+
+/// Marks all devices with at least one failed channel as failed
+fn apply_report<C: Copy, const L: usize>(result: &mut [bool; L], report: &SelfTestReport<C, L>) {
+    for (device, _) in report.failures() {
+        if let Some(passed) = result.get_mut(device) {
+            *passed = false;
+        }
+    }
+}
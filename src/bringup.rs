@@ -0,0 +1,151 @@
+//! PEC-tolerant bring-up mode
+//!
+//! During hardware bring-up with marginal cabling, PEC errors would hide what the devices actually transmit.
+//! The [BringUp] mode borrows the client and returns the received data together with a `pec_valid` flag instead
+//! of failing with [Error::ChecksumMismatch].
+//!
+//! **Not intended for regular operation**, as data of corrupted frames is returned.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, Channel, LTC6813, Register};
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut debug = client.bring_up();
+//!
+//! let frames = debug.read_register(Register::CellVoltageA).unwrap();
+//! assert!(frames[0].pec_valid);
+//! assert_eq!([24979, 7867, 8878], frames[0].words());
+//!
+//! let voltages = debug.read_voltages(CellSelection::Group1).unwrap();
+//! assert_eq!(Channel::Cell1, voltages[0][0].channel);
+//! assert_eq!(24979, voltages[0][0].voltage);
+//! assert!(voltages[0][0].pec_valid);
+//! ````
+use crate::codec::DataFrame;
+use crate::monitor::{
+    decode_voltages, DeviceTypes, Error, NoHook, PollMethod, RegisterLocator, ToFullCommand, TransferHook, LTC681X,
+};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Received register data of a single device
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct CheckedRegister {
+    /// Received register data
+    pub data: [u8; 6],
+
+    /// Received PEC checksum
+    pub pec: [u8; 2],
+
+    /// True if the received PEC matches the data
+    pub pec_valid: bool,
+}
+
+impl CheckedRegister {
+    /// Returns the three 16-bit register words (little endian)
+    pub fn words(&self) -> [u16; 3] {
+        DataFrame {
+            data: self.data,
+            pec: self.pec,
+        }
+        .words()
+    }
+}
+
+impl From<DataFrame> for CheckedRegister {
+    fn from(frame: DataFrame) -> Self {
+        Self {
+            data: frame.data,
+            pec: frame.pec,
+            pec_valid: frame.is_valid(),
+        }
+    }
+}
+
+/// Voltage of a single channel, including the PEC state of the corresponding frame
+#[derive(PartialEq, Debug)]
+pub struct CheckedVoltage<T: DeviceTypes> {
+    /// Channel of the voltage
+    pub channel: T::Channel,
+
+    /// Raw register value
+    /// Real voltage: voltage * 100 uV
+    pub voltage: u16,
+
+    /// True if the PEC of the corresponding frame was valid
+    pub pec_valid: bool,
+}
+
+impl<T: DeviceTypes> Copy for CheckedVoltage<T> {}
+
+impl<T: DeviceTypes> Clone for CheckedVoltage<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Bring-up mode, borrowing the client
+pub struct BringUp<'a, B, CS, P, T, const L: usize, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    client: &'a mut LTC681X<B, CS, P, T, L, H>,
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Enters the PEC-tolerant bring-up mode
+    pub fn bring_up(&mut self) -> BringUp<'_, B, CS, P, T, L, H> {
+        BringUp { client: self }
+    }
+}
+
+impl<'a, B, CS, P, T, const L: usize, H> BringUp<'a, B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Reads the given register of all devices in daisy chain, regardless of the PEC state
+    pub fn read_register(&mut self, register: T::Register) -> Result<[CheckedRegister; L], Error<B, CS>> {
+        let frames = self.client.read_daisy_chain_frames(register.to_read_command())?;
+        Ok(frames.map(CheckedRegister::from))
+    }
+
+    /// Reads the values of the given cell or GPIO group, regardless of the PEC state
+    /// Returns one vector for each device in daisy chain
+    pub fn read_voltages<R: RegisterLocator<T> + 'static>(
+        &mut self,
+        locator: R,
+    ) -> Result<Vec<Vec<CheckedVoltage<T>, 18>, L>, Error<B, CS>> {
+        decode_voltages(
+            &locator,
+            |register| self.read_register(register),
+            |frame, address| CheckedVoltage {
+                channel: address.channel,
+                voltage: frame.words()[address.slot],
+                pec_valid: frame.pec_valid,
+            },
+        )
+    }
+
+    /// Returns the wrapped client
+    pub fn client(&mut self) -> &mut LTC681X<B, CS, P, T, L, H> {
+        self.client
+    }
+}
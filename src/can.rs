@@ -0,0 +1,227 @@
+//! CAN frame packer for measurement snapshots
+//!
+//! Packs a [Snapshot] into a sequence of CAN frames (8 bytes for classic CAN, 64 bytes for CAN-FD) and decodes
+//! them back on the receiving side.
+//!
+//! # Frame layout
+//!
+//! | Byte      | Content                                                                       |
+//! |-----------|-------------------------------------------------------------------------------|
+//! | 0         | Device index in daisy chain                                                   |
+//! | 1         | Bits 7-6: Value kind (0 = cell, 1 = GPIO), Bits 5-0: Index of the first value |
+//! | 2..N      | Consecutive values as u16 little-endian                                      |
+//!
+//! Values are raw register values with a scaling of 100 uV per LSB. Missing values are encoded as `0xFFFF`,
+//! which matches the content of cleared result registers. An 8-byte frame carries three values, a 64-byte
+//! frame 31 values. Frames without any available value are skipped.
+//!
+//! ````
+//! use ltc681x::can::{decode_frame, FrameEncoder, CLASSIC_FRAME_LEN};
+//! use ltc681x::snapshot::Snapshot;
+//!
+//! let mut snapshot: Snapshot<2> = Snapshot::new();
+//! snapshot.device_mut(0).cell_voltages[0] = Some(36_000);
+//! snapshot.device_mut(1).gpio_voltages[4] = Some(15_000);
+//!
+//! let mut received: Snapshot<2> = Snapshot::new();
+//! for frame in FrameEncoder::<2, CLASSIC_FRAME_LEN>::new(&snapshot) {
+//!     // Sending frame over CAN bus ...
+//!     decode_frame(&frame, &mut received).unwrap();
+//! }
+//!
+//! assert_eq!(snapshot, received);
+//! ````
+use crate::snapshot::{DeviceSnapshot, Snapshot, MAX_CELLS, MAX_GPIOS};
+use core::fmt::{Display, Formatter};
+
+/// Length of classic CAN frames
+pub const CLASSIC_FRAME_LEN: usize = 8;
+
+/// Maximum length of CAN-FD frames
+pub const FD_FRAME_LEN: usize = 64;
+
+/// Length of the frame header
+const HEADER_LEN: usize = 2;
+
+/// Encoded value of missing measurements
+const NOT_AVAILABLE: u16 = 0xFFFF;
+
+/// Kind of the values carried by a frame
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ValueKind {
+    /// Cell voltages
+    Cell = 0x0,
+    /// GPIO voltages
+    Gpio = 0x1,
+}
+
+impl ValueKind {
+    /// Returns the number of available slots per device
+    fn slot_count(&self) -> usize {
+        match self {
+            ValueKind::Cell => MAX_CELLS,
+            ValueKind::Gpio => MAX_GPIOS,
+        }
+    }
+
+    /// Returns the slots of the given device
+    fn slots<'a>(&self, device: &'a DeviceSnapshot) -> &'a [Option<u16>] {
+        match self {
+            ValueKind::Cell => &device.cell_voltages,
+            ValueKind::Gpio => &device.gpio_voltages,
+        }
+    }
+
+    /// Returns the mutable slots of the given device
+    fn slots_mut<'a>(&self, device: &'a mut DeviceSnapshot) -> &'a mut [Option<u16>] {
+        match self {
+            ValueKind::Cell => &mut device.cell_voltages,
+            ValueKind::Gpio => &mut device.gpio_voltages,
+        }
+    }
+}
+
+/// Error while decoding a frame
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum CanFrameError {
+    /// Frame is shorter than header + one value or has an odd length
+    InvalidLength,
+    /// Device index exceeds the daisy chain length
+    InvalidDevice(u8),
+    /// Unknown value kind
+    InvalidKind(u8),
+    /// Values exceed the available slots of the device
+    InvalidIndex(u8),
+}
+
+impl Display for CanFrameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CanFrameError::InvalidLength => write!(f, "Invalid frame length"),
+            CanFrameError::InvalidDevice(index) => write!(f, "Invalid device index {}", index),
+            CanFrameError::InvalidKind(kind) => write!(f, "Invalid value kind {}", kind),
+            CanFrameError::InvalidIndex(index) => write!(f, "Invalid value index {}", index),
+        }
+    }
+}
+
+/// Iterator over the encoded frames of a snapshot
+///
+/// L: Number of devices in daisy chain, N: Frame length
+pub struct FrameEncoder<'a, const L: usize, const N: usize> {
+    snapshot: &'a Snapshot<L>,
+    device: usize,
+    kind: ValueKind,
+    start: usize,
+}
+
+impl<'a, const L: usize, const N: usize> FrameEncoder<'a, L, N> {
+    /// Number of values per frame
+    const VALUES_PER_FRAME: usize = {
+        assert!(
+            N >= HEADER_LEN + 2 && N.is_multiple_of(2),
+            "Frame length must be even and fit at least one value"
+        );
+        assert!(L <= 256, "Device index must fit into one byte");
+        (N - HEADER_LEN) / 2
+    };
+
+    pub fn new(snapshot: &'a Snapshot<L>) -> Self {
+        let _ = Self::VALUES_PER_FRAME;
+
+        Self {
+            snapshot,
+            device: 0,
+            kind: ValueKind::Cell,
+            start: 0,
+        }
+    }
+
+    /// Moves to the next chunk of values
+    fn advance(&mut self) {
+        self.start += Self::VALUES_PER_FRAME;
+
+        if self.start < self.kind.slot_count() {
+            return;
+        }
+
+        self.start = 0;
+        match self.kind {
+            ValueKind::Cell => self.kind = ValueKind::Gpio,
+            ValueKind::Gpio => {
+                self.kind = ValueKind::Cell;
+                self.device += 1;
+            }
+        }
+    }
+}
+
+impl<'a, const L: usize, const N: usize> Iterator for FrameEncoder<'a, L, N> {
+    type Item = [u8; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.device < L {
+            let slots = self.kind.slots(self.snapshot.device(self.device));
+            let end = (self.start + Self::VALUES_PER_FRAME).min(slots.len());
+            let chunk = &slots[self.start..end];
+
+            if chunk.iter().all(|value| value.is_none()) {
+                self.advance();
+                continue;
+            }
+
+            let mut frame = [0xFF_u8; N];
+            frame[0] = self.device as u8;
+            frame[1] = (self.kind as u8) << 6 | self.start as u8;
+
+            for (index, value) in chunk.iter().enumerate() {
+                let offset = HEADER_LEN + index * 2;
+                frame[offset..offset + 2].copy_from_slice(&value.unwrap_or(NOT_AVAILABLE).to_le_bytes());
+            }
+
+            self.advance();
+            return Some(frame);
+        }
+
+        None
+    }
+}
+
+/// Decodes the given frame and stores the values in the snapshot
+///
+/// Values beyond the available slots are only accepted if they are marked as not available (padding).
+pub fn decode_frame<const L: usize>(frame: &[u8], snapshot: &mut Snapshot<L>) -> Result<(), CanFrameError> {
+    if frame.len() < HEADER_LEN + 2 || !frame.len().is_multiple_of(2) {
+        return Err(CanFrameError::InvalidLength);
+    }
+
+    let device = frame[0];
+    if device as usize >= L {
+        return Err(CanFrameError::InvalidDevice(device));
+    }
+
+    let kind = match frame[1] >> 6 {
+        0x0 => ValueKind::Cell,
+        0x1 => ValueKind::Gpio,
+        kind => return Err(CanFrameError::InvalidKind(kind)),
+    };
+
+    let start = (frame[1] & 0b0011_1111) as usize;
+    let slots = kind.slots_mut(snapshot.device_mut(device as usize));
+
+    if start >= slots.len() {
+        return Err(CanFrameError::InvalidIndex(start as u8));
+    }
+
+    for (index, bytes) in frame[HEADER_LEN..].chunks_exact(2).enumerate() {
+        let value = u16::from_le_bytes([bytes[0], bytes[1]]);
+
+        match slots.get_mut(start + index) {
+            Some(slot) => *slot = if value == NOT_AVAILABLE { None } else { Some(value) },
+            None if value == NOT_AVAILABLE => {}
+            None => return Err(CanFrameError::InvalidIndex((start + index) as u8)),
+        }
+    }
+
+    Ok(())
+}
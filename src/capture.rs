@@ -0,0 +1,182 @@
+//! Bus capture for offline analysis (requires `std` feature)
+//!
+//! [CaptureBus] wraps the SPI bus and records every frame (sent and received) with a timestamp in a
+//! simple binary format. Field captures may be decoded using [decode_capture] and replayed through the
+//! [codec](crate::codec) to reproduce PEC failures offline.
+//!
+//! # Format
+//!
+//! The capture starts with the magic bytes `LTCC` followed by one record per frame:
+//! * Timestamp in microseconds since start of capture (u64, little endian)
+//! * Direction: 0 = sent (MOSI), 1 = received (MISO) (u8)
+//! * Length of frame (u16, little endian)
+//! * Frame bytes
+//!
+//! # Example
+//!
+//! ````
+//! use ltc681x::capture::{decode_capture, CaptureBus, Direction};
+//! use ltc681x::codec;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{LTC6813, Register};
+//! use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!
+//! let bus = CaptureBus::new(ExampleSPIBus::default());
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(bus, ExampleCSPin{});
+//!
+//! client.read_register(Register::CellVoltageA).unwrap();
+//!
+//! let capture = client.release().0.take_capture();
+//! let records = decode_capture(&capture).unwrap();
+//!
+//! // Command frame + data frame, each one sent and received
+//! assert_eq!(4, records.len());
+//! assert_eq!(Direction::Sent, records[0].direction);
+//! assert_eq!(Ok(0x0004), codec::decode_command(&records[0].frame));
+//! assert!(codec::decode_data_frame(&records[3].frame).is_ok());
+//! ````
+use embedded_hal::blocking::spi::Transfer;
+use std::fmt::{Display, Formatter};
+use std::time::Instant;
+use std::vec::Vec;
+
+/// Magic bytes at the start of each capture
+pub const CAPTURE_MAGIC: [u8; 4] = *b"LTCC";
+
+/// Direction of a recorded frame
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction {
+    /// Frame sent by the controller (MOSI)
+    Sent = 0x0,
+    /// Frame received from the devices (MISO)
+    Received = 0x1,
+}
+
+/// Single recorded frame
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CaptureRecord {
+    /// Microseconds since start of capture
+    pub timestamp: u64,
+
+    /// Direction of the frame
+    pub direction: Direction,
+
+    /// Raw frame bytes
+    pub frame: Vec<u8>,
+}
+
+/// Error while decoding a capture
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CaptureError {
+    /// Capture does not start with magic bytes
+    InvalidMagic,
+    /// Capture ends within a record
+    Truncated,
+    /// Unknown direction value
+    InvalidDirection(u8),
+}
+
+impl Display for CaptureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaptureError::InvalidMagic => write!(f, "Invalid capture magic"),
+            CaptureError::Truncated => write!(f, "Capture is truncated"),
+            CaptureError::InvalidDirection(value) => write!(f, "Invalid direction value {}", value),
+        }
+    }
+}
+
+/// SPI bus wrapper recording every frame
+pub struct CaptureBus<B: Transfer<u8>> {
+    bus: B,
+    start: Instant,
+    capture: Vec<u8>,
+}
+
+impl<B: Transfer<u8>> CaptureBus<B> {
+    /// Wraps the given bus
+    pub fn new(bus: B) -> Self {
+        Self {
+            bus,
+            start: Instant::now(),
+            capture: CAPTURE_MAGIC.to_vec(),
+        }
+    }
+
+    /// Returns the recorded capture
+    pub fn capture(&self) -> &[u8] {
+        &self.capture
+    }
+
+    /// Returns the recorded capture and starts a new one
+    pub fn take_capture(&mut self) -> Vec<u8> {
+        core::mem::replace(&mut self.capture, CAPTURE_MAGIC.to_vec())
+    }
+
+    /// Releases the wrapped bus
+    pub fn release(self) -> B {
+        self.bus
+    }
+
+    fn record(&mut self, direction: Direction, frame: &[u8]) {
+        let timestamp = self.start.elapsed().as_micros() as u64;
+
+        self.capture.extend_from_slice(&timestamp.to_le_bytes());
+        self.capture.push(direction as u8);
+        self.capture.extend_from_slice(&(frame.len() as u16).to_le_bytes());
+        self.capture.extend_from_slice(frame);
+    }
+}
+
+impl<B: Transfer<u8>> Transfer<u8> for CaptureBus<B> {
+    type Error = B::Error;
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Self::Error> {
+        self.record(Direction::Sent, words);
+
+        let received = self.bus.transfer(words)?;
+        self.record(Direction::Received, received);
+
+        Ok(received)
+    }
+}
+
+/// Decodes the given capture into records
+pub fn decode_capture(capture: &[u8]) -> Result<Vec<CaptureRecord>, CaptureError> {
+    if capture.len() < CAPTURE_MAGIC.len() || capture[..CAPTURE_MAGIC.len()] != CAPTURE_MAGIC {
+        return Err(CaptureError::InvalidMagic);
+    }
+
+    let mut records = Vec::new();
+    let mut remaining = &capture[CAPTURE_MAGIC.len()..];
+
+    while !remaining.is_empty() {
+        if remaining.len() < 11 {
+            return Err(CaptureError::Truncated);
+        }
+
+        let mut timestamp = [0x0; 8];
+        timestamp.copy_from_slice(&remaining[0..8]);
+
+        let direction = match remaining[8] {
+            0x0 => Direction::Sent,
+            0x1 => Direction::Received,
+            value => return Err(CaptureError::InvalidDirection(value)),
+        };
+
+        let length = u16::from_le_bytes([remaining[9], remaining[10]]) as usize;
+        if remaining.len() < 11 + length {
+            return Err(CaptureError::Truncated);
+        }
+
+        records.push(CaptureRecord {
+            timestamp: u64::from_le_bytes(timestamp),
+            direction,
+            frame: remaining[11..11 + length].to_vec(),
+        });
+
+        remaining = &remaining[11 + length..];
+    }
+
+    Ok(records)
+}
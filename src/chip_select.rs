@@ -0,0 +1,115 @@
+//! CS timing and inter-command dwell
+//!
+//! Fast MCUs may violate isoSPI timing requirements, e.g. when starting to clock data immediately after CS is
+//! asserted on an idle link (t_READY) or when CS is asserted again right after the previous command. The
+//! [TimedChipSelect] wraps the CS pin and inserts the configured delays using the given [DelayUs] implementation:
+//!
+//! * **Ready time**: Delay after CS is pulled low, before data is transferred
+//! * **Dwell time**: Minimum CS high time, delayed after CS is released
+//!
+//! The wrapper is applied to the client by [with_cs_timing](crate::monitor::LTC681X::with_cs_timing):
+//! ````
+//! use ltc681x::chip_select::CSTiming;
+//! use ltc681x::conversion::ConversionRequest;
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//! use ltc681x::timing::T_READY_US;
+//!
+//! let timing = CSTiming::new().ready_time(T_READY_US).dwell_time(5);
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{})
+//!     .with_cs_timing(timing, ExampleDelay::default());
+//!
+//! let request = ConversionRequest::cells(CellSelection::All).mode(ADCMode::Normal).discharge_permitted(true);
+//! client.start_conversion(request).unwrap();
+//! ````
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::OutputPin;
+
+/// CS timing parameters
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct CSTiming {
+    /// Delay after CS is pulled low in microseconds
+    ready_time: u32,
+
+    /// Delay after CS is released in microseconds
+    dwell_time: u32,
+}
+
+impl CSTiming {
+    /// Creates new timing parameters without any delays
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the delay (us) after CS is pulled low, before data is transferred
+    pub fn ready_time(mut self, us: u32) -> Self {
+        self.ready_time = us;
+        self
+    }
+
+    /// Sets the minimum CS high time (us), which is delayed after CS is released
+    pub fn dwell_time(mut self, us: u32) -> Self {
+        self.dwell_time = us;
+        self
+    }
+
+    /// Returns the delay after CS is pulled low in microseconds
+    pub fn get_ready_time(&self) -> u32 {
+        self.ready_time
+    }
+
+    /// Returns the delay after CS is released in microseconds
+    pub fn get_dwell_time(&self) -> u32 {
+        self.dwell_time
+    }
+}
+
+/// CS pin wrapper inserting the configured delays
+pub struct TimedChipSelect<CS: OutputPin, D: DelayUs<u32>> {
+    cs: CS,
+    delay: D,
+    timing: CSTiming,
+}
+
+impl<CS: OutputPin, D: DelayUs<u32>> TimedChipSelect<CS, D> {
+    /// Wraps the given CS pin
+    pub fn new(cs: CS, delay: D, timing: CSTiming) -> Self {
+        Self { cs, delay, timing }
+    }
+
+    /// Returns the timing parameters
+    pub fn get_timing(&self) -> CSTiming {
+        self.timing
+    }
+
+    /// Releases the CS pin and delay
+    pub fn release(self) -> (CS, D) {
+        (self.cs, self.delay)
+    }
+}
+
+impl<CS: OutputPin, D: DelayUs<u32>> OutputPin for TimedChipSelect<CS, D> {
+    type Error = CS::Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.cs.set_low()?;
+
+        if self.timing.ready_time > 0 {
+            self.delay.delay_us(self.timing.ready_time);
+        }
+
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.cs.set_high()?;
+
+        if self.timing.dwell_time > 0 {
+            self.delay.delay_us(self.timing.dwell_time);
+        }
+
+        Ok(())
+    }
+}
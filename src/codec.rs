@@ -0,0 +1,301 @@
+//! Transport-independent frame encoding and decoding
+//!
+//! Pure functions over byte slices for encoding command/data frames, checking PEC checksums and
+//! splitting daisy chain responses. Allows host-side tools to decode logic-analyzer captures and fuzzers to
+//! exercise parsing without an SPI bus.
+//!
+//! ````
+//! use ltc681x::codec::{decode_chain, decode_command, decode_register_words, encode_command};
+//!
+//! // Read command for cell voltage register A (RDCVA)
+//! let frame = encode_command(0x0004);
+//! assert_eq!([0x00, 0x04, 0x07, 0xC2], frame);
+//! assert_eq!(Ok(0x0004), decode_command(&frame));
+//!
+//! // Response of two devices in daisy chain
+//! let response = [
+//!     0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C,
+//!     0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94,
+//! ];
+//!
+//! let data: [[u8; 6]; 2] = decode_chain(&response).unwrap();
+//! assert_eq!([24979, 7867, 8878], decode_register_words(&data[0]));
+//! assert_eq!([26333, 7538, 7330], decode_register_words(&data[1]));
+//! ````
+//!
+//! # Frame parsing
+//!
+//! Daisy chain responses may be split into [DataFrame] items. Register words are decoded explicitly as little
+//! endian, so parsing is independent of the target endianness.
+//!
+//! [DataFrameRef] is a borrowed view of a frame within the receive buffer. PEC validation and word decoding work
+//! on the borrowed bytes, so no data is copied. The client parses received frames this way.
+//!
+//! ````
+//! use ltc681x::codec::{frame_refs, frames};
+//!
+//! let response = [
+//!     0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C,
+//!     0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94,
+//! ];
+//!
+//! let mut frames = frames(&response).unwrap();
+//! assert!(frames.next().unwrap().is_valid());
+//! assert_eq!([26333, 7538, 7330], frames.next().unwrap().words());
+//!
+//! let mut frames = frame_refs(&response).unwrap();
+//! assert_eq!([24979, 7867, 8878], frames.next().unwrap().words());
+//! assert_eq!(&response[8..14], frames.next().unwrap().data());
+//! ````
+use crate::pec15::PEC15;
+use core::fmt::{Display, Formatter};
+
+/// Number of bytes of a command frame (command + PEC)
+pub const COMMAND_FRAME_LEN: usize = 4;
+
+/// Number of bytes of a data frame (6 data bytes + PEC)
+pub const DATA_FRAME_LEN: usize = 8;
+
+/// Error while encoding or decoding a frame
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FrameError {
+    /// Given slice does not match the expected frame length
+    InvalidLength,
+
+    /// PEC checksum of the frame is invalid
+    ChecksumMismatch,
+}
+
+impl Display for FrameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FrameError::InvalidLength => write!(f, "Invalid frame length"),
+            FrameError::ChecksumMismatch => write!(f, "PEC checksum mismatch"),
+        }
+    }
+}
+
+/// Data frame of a single device as transmitted on the bus
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct DataFrame {
+    /// Register data
+    pub data: [u8; 6],
+
+    /// PEC checksum of the register data
+    pub pec: [u8; 2],
+}
+
+impl DataFrame {
+    /// Splits the given bytes into register data and PEC checksum. PEC is not validated.
+    pub fn from_bytes(frame: &[u8]) -> Result<DataFrame, FrameError> {
+        if frame.len() != DATA_FRAME_LEN {
+            return Err(FrameError::InvalidLength);
+        }
+
+        Ok(DataFrameRef::new(frame)?.to_owned())
+    }
+
+    /// Returns true if the PEC checksum matches the data
+    pub fn is_valid(&self) -> bool {
+        PEC15::calc(&self.data) == self.pec
+    }
+
+    /// Returns the three 16-bit register words (little endian)
+    pub fn words(&self) -> [u16; 3] {
+        decode_register_words(&self.data)
+    }
+}
+
+/// Borrowed data frame of a single device, e.g. within the receive buffer of a transfer
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DataFrameRef<'a> {
+    frame: &'a [u8; DATA_FRAME_LEN],
+}
+
+impl<'a> DataFrameRef<'a> {
+    /// Borrows the given bytes as data frame. PEC is not validated.
+    pub fn new(frame: &'a [u8]) -> Result<Self, FrameError> {
+        Ok(Self {
+            frame: frame.try_into().map_err(|_| FrameError::InvalidLength)?,
+        })
+    }
+
+    /// Returns the register data
+    pub fn data(&self) -> &'a [u8] {
+        &self.frame[..6]
+    }
+
+    /// Returns the PEC checksum of the register data
+    pub fn pec(&self) -> &'a [u8] {
+        &self.frame[6..]
+    }
+
+    /// Returns true if the PEC checksum matches the data
+    pub fn is_valid(&self) -> bool {
+        PEC15::calc(self.data()) == self.pec()
+    }
+
+    /// Returns the three 16-bit register words (little endian), decoded from the borrowed bytes
+    pub fn words(&self) -> [u16; 3] {
+        let mut words = [0x0; 3];
+        for (word, bytes) in words.iter_mut().zip(self.data().chunks_exact(2)) {
+            *word = u16::from_le_bytes([bytes[0], bytes[1]]);
+        }
+
+        words
+    }
+
+    /// Copies the frame
+    pub fn to_owned(&self) -> DataFrame {
+        let mut result = DataFrame::default();
+        result.data.copy_from_slice(self.data());
+        result.pec.copy_from_slice(self.pec());
+        result
+    }
+}
+
+impl Default for DataFrameRef<'_> {
+    /// Frame of zero bytes (invalid PEC)
+    fn default() -> Self {
+        Self {
+            frame: &[0x0; DATA_FRAME_LEN],
+        }
+    }
+}
+
+/// Splits the response of a daisy chain read (without the command frame) into data frames
+///
+/// The frames are returned in transmission order. PEC checksums are not validated.
+pub fn frames(response: &[u8]) -> Result<impl ExactSizeIterator<Item = DataFrame> + '_, FrameError> {
+    let chunks = response.chunks_exact(DATA_FRAME_LEN);
+    if !chunks.remainder().is_empty() {
+        return Err(FrameError::InvalidLength);
+    }
+
+    Ok(chunks.map(|frame| DataFrame::from_bytes(frame).unwrap_or_default()))
+}
+
+/// Splits the response of a daisy chain read (without the command frame) into borrowed data frames
+///
+/// Same as [frames], but without copying the frames. PEC checksums are not validated.
+pub fn frame_refs(response: &[u8]) -> Result<impl ExactSizeIterator<Item = DataFrameRef<'_>> + '_, FrameError> {
+    let chunks = response.chunks_exact(DATA_FRAME_LEN);
+    if !chunks.remainder().is_empty() {
+        return Err(FrameError::InvalidLength);
+    }
+
+    Ok(chunks.map(|frame| DataFrameRef::new(frame).unwrap_or_default()))
+}
+
+/// Calculates the PEC15 checksum of the given data
+pub fn pec15(data: &[u8]) -> [u8; 2] {
+    PEC15::calc(data)
+}
+
+/// Encodes the given 11-bit command code to a full command frame, including PEC
+pub fn encode_command(command: u16) -> [u8; COMMAND_FRAME_LEN] {
+    let mut frame = [(command >> 8) as u8, command as u8, 0x0, 0x0];
+    let pec = PEC15::calc(&frame[0..2]);
+
+    frame[2] = pec[0];
+    frame[3] = pec[1];
+    frame
+}
+
+/// Decodes a command frame and returns the command code
+pub fn decode_command(frame: &[u8]) -> Result<u16, FrameError> {
+    if frame.len() != COMMAND_FRAME_LEN {
+        return Err(FrameError::InvalidLength);
+    }
+
+    if PEC15::calc(&frame[0..2]) != frame[2..4] {
+        return Err(FrameError::ChecksumMismatch);
+    }
+
+    Ok((frame[0] as u16) << 8 | frame[1] as u16)
+}
+
+/// Encodes the register data of a single device to a data frame, including PEC
+pub fn encode_data_frame(data: &[u8; 6]) -> [u8; DATA_FRAME_LEN] {
+    let mut frame = [0x0; DATA_FRAME_LEN];
+    frame[..6].copy_from_slice(data);
+
+    let pec = PEC15::calc(data);
+    frame[6] = pec[0];
+    frame[7] = pec[1];
+    frame
+}
+
+/// Decodes the data frame of a single device. Returns the register data in case the PEC is valid.
+pub fn decode_data_frame(frame: &[u8]) -> Result<[u8; 6], FrameError> {
+    Ok(decode_frame(frame)?.to_owned().data)
+}
+
+/// Decodes the data frame of a single device into the three 16-bit register words, in case the PEC is valid
+pub fn decode_frame_words(frame: &[u8]) -> Result<[u16; 3], FrameError> {
+    Ok(decode_frame(frame)?.words())
+}
+
+/// Borrows the given bytes as data frame and validates the PEC
+fn decode_frame(frame: &[u8]) -> Result<DataFrameRef<'_>, FrameError> {
+    let frame = DataFrameRef::new(frame)?;
+
+    if !frame.is_valid() {
+        return Err(FrameError::ChecksumMismatch);
+    }
+
+    Ok(frame)
+}
+
+/// Converts the register data to the three 16-bit words (little endian)
+pub fn decode_register_words(data: &[u8; 6]) -> [u16; 3] {
+    [
+        u16::from_le_bytes([data[0], data[1]]),
+        u16::from_le_bytes([data[2], data[3]]),
+        u16::from_le_bytes([data[4], data[5]]),
+    ]
+}
+
+/// Converts the three 16-bit words to register data (little endian)
+pub fn encode_register_words(words: &[u16; 3]) -> [u8; 6] {
+    let (a, b, c) = (words[0].to_le_bytes(), words[1].to_le_bytes(), words[2].to_le_bytes());
+    [a[0], a[1], b[0], b[1], c[0], c[1]]
+}
+
+/// Splits the response of a daisy chain read (without the command frame) into the data of each device
+///
+/// The frames are returned in transmission order, so the first item is the data of the first device
+/// in daisy chain.
+pub fn decode_chain<const L: usize>(response: &[u8]) -> Result<[[u8; 6]; L], FrameError> {
+    if response.len() != L * DATA_FRAME_LEN {
+        return Err(FrameError::InvalidLength);
+    }
+
+    let mut result = [[0x0; 6]; L];
+    for (item, frame) in result.iter_mut().zip(frames(response)?) {
+        if !frame.is_valid() {
+            return Err(FrameError::ChecksumMismatch);
+        }
+
+        *item = frame.data;
+    }
+
+    Ok(result)
+}
+
+/// Encodes the data frames of a daisy chain write (without the command frame)
+///
+/// The first item is the data of the first device in daisy chain (same as [decode_chain]). As the first frame sent
+/// is shifted to the last device, the frames are encoded in reverse order.
+pub fn encode_chain<const L: usize>(data: &[[u8; 6]; L], output: &mut [u8]) -> Result<usize, FrameError> {
+    let length = L * DATA_FRAME_LEN;
+    if output.len() < length {
+        return Err(FrameError::InvalidLength);
+    }
+
+    for (item, frame) in data.iter().rev().zip(output.chunks_exact_mut(DATA_FRAME_LEN)) {
+        frame.copy_from_slice(&encode_data_frame(item));
+    }
+
+    Ok(length)
+}
@@ -0,0 +1,194 @@
+//! Write-only client for command-only operations
+//!
+//! Conversion starts, clear and mute commands don't return any data, so the MISO line is not needed. The
+//! [CommandClient] only requires the [embedded-hal SPI Write trait](<https://docs.rs/embedded-hal/latest/embedded_hal/blocking/spi/trait.Write.html>),
+//! which allows using HALs without full-duplex transfer support (e.g. half-duplex 3-wire buses).
+//!
+//! Reading registers still requires the regular [client](crate::monitor::LTC681X) based on `Transfer`.
+//! As commands are broadcast to all devices, the client is independent of the daisy chain length.
+//!
+//! CS is released immediately after each command, so the end of a conversion can not be polled via SDO.
+//!
+//! ````
+//! use ltc681x::command::CommandClient;
+//! use ltc681x::example::{ExampleCSPin, ExampleWriteBus};
+//! use ltc681x::ltc6813::{CellSelection, GPIOSelection, LTC6813};
+//! use ltc681x::monitor::ADCMode;
+//!
+//! let mut client: CommandClient<_, _, LTC6813> = CommandClient::new(ExampleWriteBus::default(), ExampleCSPin{});
+//!
+//! client.clear_cells().unwrap();
+//! let timing = client.start_conv_cells(ADCMode::Normal, CellSelection::All, true).unwrap();
+//! assert_eq!(2343, timing.regular);
+//!
+//! client.start_conv_gpio(ADCMode::Normal, GPIOSelection::All).unwrap();
+//! ````
+use crate::codec;
+use crate::conversion::ConversionRequest;
+use crate::monitor::{
+    gpio_conversion_command, gpio_redundancy_conversion_command, overlap_conversion_command, status_conversion_command,
+    status_redundancy_conversion_command, ADCMode, CommandTime, DeviceTypes, StatusGroup, ToCommandTiming,
+    CMD_CLEAR_AUX, CMD_CLEAR_CELLS, CMD_CLEAR_STATUS,
+};
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use embedded_hal::blocking::spi::Write;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Mute discharge (MUTE)
+const CMD_MUTE: u16 = 0b0000_0000_0010_1000;
+
+/// Unmute discharge (UNMUTE)
+const CMD_UNMUTE: u16 = 0b0000_0000_0010_1001;
+
+/// Error enum of [CommandClient]
+#[derive(PartialEq)]
+pub enum CommandError<B: Write<u8>, CS: OutputPin> {
+    /// SPI write error
+    TransferError(B::Error),
+
+    /// Error while changing state of CS pin
+    CSPinError(CS::Error),
+}
+
+/// Client for command-only operations on a write-only SPI bus
+pub struct CommandClient<B, CS, T>
+where
+    B: Write<u8>,
+    CS: OutputPin,
+    T: DeviceTypes,
+{
+    /// SPI bus
+    bus: B,
+
+    /// SPI CS pin
+    cs: CS,
+
+    device_types: PhantomData<T>,
+}
+
+impl<B, CS, T> CommandClient<B, CS, T>
+where
+    B: Write<u8>,
+    CS: OutputPin,
+    T: DeviceTypes,
+{
+    /// Creates a new client for the given device type
+    pub fn new(bus: B, cs: CS) -> Self {
+        Self {
+            bus,
+            cs,
+            device_types: PhantomData,
+        }
+    }
+
+    /// See [LTC681XClient::start_conv_cells](crate::monitor::LTC681XClient::start_conv_cells)
+    pub fn start_conv_cells(
+        &mut self,
+        mode: ADCMode,
+        cells: T::CellSelection,
+        dcp: bool,
+    ) -> Result<CommandTime, CommandError<B, CS>> {
+        let request = ConversionRequest::cells(cells).mode(mode).discharge_permitted(dcp);
+        self.start_conversion(request)
+    }
+
+    /// See [LTC681XClient::start_conversion](crate::monitor::LTC681XClient::start_conversion)
+    pub fn start_conversion(&mut self, request: ConversionRequest<T>) -> Result<CommandTime, CommandError<B, CS>> {
+        self.send_command(request.to_command())?;
+        Ok(request.expected_duration())
+    }
+
+    /// See [LTC681XClient::start_conv_gpio](crate::monitor::LTC681XClient::start_conv_gpio)
+    pub fn start_conv_gpio(
+        &mut self,
+        mode: ADCMode,
+        channels: T::GPIOSelection,
+    ) -> Result<CommandTime, CommandError<B, CS>> {
+        self.send_command(gpio_conversion_command(mode, &channels))?;
+        Ok(channels.to_conv_command_timing(mode))
+    }
+
+    /// See [LTC681XClient::start_conv_gpio_redundant](crate::monitor::LTC681XClient::start_conv_gpio_redundant)
+    pub fn start_conv_gpio_redundant(
+        &mut self,
+        mode: ADCMode,
+        channels: T::GPIOSelection,
+    ) -> Result<CommandTime, CommandError<B, CS>> {
+        self.send_command(gpio_redundancy_conversion_command(mode, &channels))?;
+        Ok(channels.to_conv_command_timing(mode))
+    }
+
+    /// See [LTC681XClient::start_overlap_measurement](crate::monitor::LTC681XClient::start_overlap_measurement)
+    pub fn start_overlap_measurement(&mut self, mode: ADCMode, dcp: bool) -> Result<(), CommandError<B, CS>> {
+        self.send_command(overlap_conversion_command(mode, dcp))
+    }
+
+    /// See [LTC681XClient::measure_internal_parameters](crate::monitor::LTC681XClient::measure_internal_parameters)
+    pub fn measure_internal_parameters(
+        &mut self,
+        mode: ADCMode,
+        group: StatusGroup,
+    ) -> Result<CommandTime, CommandError<B, CS>> {
+        self.send_command(status_conversion_command(mode, group))?;
+        Ok(group.to_conv_command_timing(mode))
+    }
+
+    /// See [LTC681XClient::measure_internal_parameters_redundant](crate::monitor::LTC681XClient::measure_internal_parameters_redundant)
+    pub fn measure_internal_parameters_redundant(
+        &mut self,
+        mode: ADCMode,
+        group: StatusGroup,
+    ) -> Result<CommandTime, CommandError<B, CS>> {
+        self.send_command(status_redundancy_conversion_command(mode, group))?;
+        Ok(group.to_conv_command_timing(mode))
+    }
+
+    /// Clears all cell voltage registers (CLRCELL command)
+    pub fn clear_cells(&mut self) -> Result<(), CommandError<B, CS>> {
+        self.send_command(CMD_CLEAR_CELLS)
+    }
+
+    /// Clears all auxiliary registers (CLRAUX command)
+    pub fn clear_aux(&mut self) -> Result<(), CommandError<B, CS>> {
+        self.send_command(CMD_CLEAR_AUX)
+    }
+
+    /// Clears the status registers (CLRSTAT command)
+    pub fn clear_status(&mut self) -> Result<(), CommandError<B, CS>> {
+        self.send_command(CMD_CLEAR_STATUS)
+    }
+
+    /// Temporarily disables all discharge switches (MUTE command)
+    pub fn mute(&mut self) -> Result<(), CommandError<B, CS>> {
+        self.send_command(CMD_MUTE)
+    }
+
+    /// Re-enables the discharge switches after [mute](Self::mute) (UNMUTE command)
+    pub fn unmute(&mut self) -> Result<(), CommandError<B, CS>> {
+        self.send_command(CMD_UNMUTE)
+    }
+
+    /// Returns the SPI bus and CS pin
+    pub fn release(self) -> (B, CS) {
+        (self.bus, self.cs)
+    }
+
+    /// Sends the given command as single transaction. Calculates and attaches the PEC checksum
+    fn send_command(&mut self, command: u16) -> Result<(), CommandError<B, CS>> {
+        self.cs.set_low().map_err(CommandError::CSPinError)?;
+        self.bus
+            .write(&codec::encode_command(command))
+            .map_err(CommandError::TransferError)?;
+        self.cs.set_high().map_err(CommandError::CSPinError)
+    }
+}
+
+impl<B: Write<u8>, CS: OutputPin> Debug for CommandError<B, CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CommandError::TransferError(_) => f.debug_struct("TransferError").finish(),
+            CommandError::CSPinError(_) => f.debug_struct("CSPinError").finish(),
+        }
+    }
+}
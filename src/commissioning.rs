@@ -0,0 +1,423 @@
+//! Commissioning routines
+//!
+//! Routines for verifying the hardware before a pack enters service. They take several conversion cycles and
+//! are not intended for regular operation.
+//!
+//! # Discharge switch test
+//!
+//! Enables each discharge switch (DCC bit) one at a time and measures the voltage dip of the corresponding cell.
+//! Switches producing no dip indicate unpopulated or failed balancing FETs/resistors. The same cell is tested on
+//! all devices of the daisy chain in parallel.
+//!
+//! ````
+//! use ltc681x::commissioning::SwitchTestConfig;
+//! use ltc681x::config::Configuration;
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // Expecting a dip of at least 10 mV after 100 ms
+//! let test = SwitchTestConfig::new(100).settle_time(100_000);
+//! let report = client.test_discharge_switches([Configuration::default()], &test, &mut ExampleDelay::default()).unwrap();
+//!
+//! // The example bus always returns the same voltages
+//! assert!(!report.passed());
+//! assert_eq!(18, report.failures().count());
+//! ````
+//!
+//! # Wiring-order check
+//!
+//! Discharges one cell input at a time and verifies that the largest voltage dip appears at the expected
+//! (device, cell) position of the daisy chain. Catches swapped sense wires and mis-ordered connectors before a
+//! pack enters service. As each cell of each device is perturbed individually, the check takes one conversion
+//! cycle per cell of the whole chain.
+//!
+//! ````
+//! use ltc681x::commissioning::SwitchTestConfig;
+//! use ltc681x::config::Configuration;
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let test = SwitchTestConfig::new(100).settle_time(100_000);
+//! let report = client.check_wiring_order([Configuration::default()], &test, &mut ExampleDelay::default()).unwrap();
+//!
+//! // The example bus always returns the same voltages, so no dip is observed
+//! assert!(!report.passed());
+//! assert_eq!(None, report.get(0, 0).unwrap().observed);
+//! ````
+use crate::config::{Cell, ConfigurationRegisters, DischargeControl};
+use crate::conversion::ConversionRequest;
+use crate::monitor::{ADCMode, DeviceTypes, Error, LTC681XClient, PollMethod, TransferHook, LTC681X};
+use crate::snapshot::{Snapshot, MAX_CELLS};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Parameters of the discharge switch test and wiring check
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SwitchTestConfig {
+    /// Minimum expected voltage dip, raw value (100 uV per LSB)
+    min_dip: u16,
+
+    /// Time between enabling the switch and starting the conversion in microseconds
+    settle_time: u32,
+
+    /// ADC mode used for conversion
+    mode: ADCMode,
+}
+
+impl SwitchTestConfig {
+    /// Creates a new test config for the given minimum voltage dip (raw value, 100 uV per LSB)
+    /// Default settle time: 10 ms, ADC mode: Normal
+    pub fn new(min_dip: u16) -> Self {
+        Self {
+            min_dip,
+            settle_time: 10_000,
+            mode: ADCMode::Normal,
+        }
+    }
+
+    /// Sets the time between enabling the switch and starting the conversion in microseconds
+    pub fn settle_time(mut self, settle_time: u32) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// Sets the ADC mode used for conversion
+    pub fn mode(mut self, mode: ADCMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the minimum expected voltage dip (raw value, 100 uV per LSB)
+    pub fn get_min_dip(&self) -> u16 {
+        self.min_dip
+    }
+
+    /// Returns the settle time in microseconds
+    pub fn get_settle_time(&self) -> u32 {
+        self.settle_time
+    }
+
+    /// Returns the ADC mode used for conversion
+    pub fn get_mode(&self) -> ADCMode {
+        self.mode
+    }
+}
+
+/// Result of testing a single discharge switch
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SwitchTest {
+    /// Cell voltage with all switches turned off, raw value (100 uV per LSB)
+    pub baseline: u16,
+
+    /// Cell voltage with the switch turned on, raw value (100 uV per LSB)
+    pub voltage: u16,
+
+    /// Voltage dip of the cell, raw value (100 uV per LSB). Negative in case the voltage raised.
+    pub dip: i32,
+
+    /// Largest voltage dip of the neighbor cells, raw value (100 uV per LSB)
+    /// A neighbor dip larger than the cell dip indicates swapped sense wires.
+    pub neighbor_dip: i32,
+
+    /// True if the dip reached the expected minimum
+    pub passed: bool,
+}
+
+/// Results of the discharge switch test
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SwitchTestReport<const L: usize> {
+    /// Results per device and cell (index 0 = cell 1), None if the cell was not tested
+    results: [[Option<SwitchTest>; MAX_CELLS]; L],
+}
+
+impl<const L: usize> SwitchTestReport<L> {
+    /// Returns the result of the given device and cell (index 0 = cell 1), None if the cell was not tested
+    pub fn get(&self, device: usize, cell: usize) -> Option<SwitchTest> {
+        *self.results.get(device)?.get(cell)?
+    }
+
+    /// Returns true if all tested switches produced the expected dip
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Returns the (device, cell index) of all switches which didn't produce the expected dip
+    pub fn failures(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.results.iter().enumerate().flat_map(|(device, cells)| {
+            cells.iter().enumerate().filter_map(move |(cell, result)| match result {
+                Some(result) if !result.passed => Some((device, cell)),
+                _ => None,
+            })
+        })
+    }
+}
+
+/// Result of perturbing a single cell input during the wiring check
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct WiringCheck {
+    /// Position (device, cell index) where the largest dip was observed
+    /// None if no dip reached the expected minimum, e.g. due to an open sense wire or a failed switch.
+    pub observed: Option<(usize, usize)>,
+
+    /// Largest observed voltage dip, raw value (100 uV per LSB)
+    pub dip: i32,
+}
+
+/// Results of the wiring check
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct WiringReport<const L: usize> {
+    /// Results per perturbed device and cell (index 0 = cell 1), None if the cell was not checked
+    results: [[Option<WiringCheck>; MAX_CELLS]; L],
+}
+
+impl<const L: usize> WiringReport<L> {
+    /// Returns the result of the given perturbed device and cell (index 0 = cell 1)
+    /// None if the cell was not checked.
+    pub fn get(&self, device: usize, cell: usize) -> Option<WiringCheck> {
+        *self.results.get(device)?.get(cell)?
+    }
+
+    /// Returns true if each perturbation was observed at the expected position
+    pub fn passed(&self) -> bool {
+        self.mismatches().next().is_none()
+    }
+
+    /// Returns the perturbed (device, cell index) of all inputs, which were not observed at the expected position
+    pub fn mismatches(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.results.iter().enumerate().flat_map(|(device, cells)| {
+            cells.iter().enumerate().filter_map(move |(cell, result)| match result {
+                Some(result) if result.observed != Some((device, cell)) => Some((device, cell)),
+                _ => None,
+            })
+        })
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Enables each discharge switch one at a time and measures the voltage dip of the corresponding cell
+    ///
+    /// The given configuration is used as base, one item per device in daisy chain. After the test (and in case
+    /// of an error) the base configuration is written with all discharge switches turned off (best-effort).
+    /// The delay is used for the settle time and for waiting for the conversions, see
+    /// [wait_conversion](LTC681X::wait_conversion).
+    pub fn test_discharge_switches<C, D>(
+        &mut self,
+        config: [C; L],
+        test: &SwitchTestConfig,
+        delay: &mut D,
+    ) -> Result<SwitchTestReport<L>, Error<B, CS>>
+    where
+        C: ConfigurationRegisters + DischargeControl + Clone,
+        D: DelayUs<u32>,
+    {
+        with_switches_off(self, config, |client, config| {
+            test_switches(client, config, test, delay)
+        })
+    }
+
+    /// Discharges each cell of each device one at a time and verifies that the voltage dip appears at the
+    /// expected position
+    ///
+    /// The given configuration is used as base, one item per device in daisy chain. After the check (and in case
+    /// of an error) the base configuration is written with all discharge switches turned off (best-effort).
+    /// The delay is used for the settle time and for waiting for the conversions, see
+    /// [wait_conversion](LTC681X::wait_conversion).
+    pub fn check_wiring_order<C, D>(
+        &mut self,
+        config: [C; L],
+        test: &SwitchTestConfig,
+        delay: &mut D,
+    ) -> Result<WiringReport<L>, Error<B, CS>>
+    where
+        C: ConfigurationRegisters + DischargeControl + Clone,
+        D: DelayUs<u32>,
+    {
+        with_switches_off(self, config, |client, config| check_wiring(client, config, test, delay))
+    }
+}
+
+/// Turns off all switches of the base configuration, runs the given routine and writes the base configuration
+/// afterwards (best-effort)
+fn with_switches_off<B, CS, P, T, C, R, const L: usize, H>(
+    client: &mut LTC681X<B, CS, P, T, L, H>,
+    mut config: [C; L],
+    routine: impl FnOnce(&mut LTC681X<B, CS, P, T, L, H>, &[C; L]) -> Result<R, Error<B, CS>>,
+) -> Result<R, Error<B, CS>>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    C: ConfigurationRegisters + DischargeControl + Clone,
+    H: TransferHook,
+{
+    for item in config.iter_mut() {
+        item.clear_discharge_cells();
+    }
+
+    let result = routine(client, &config);
+    let _ = client.write_configuration(config);
+
+    result
+}
+
+/// Writes the given configuration, waits the settle time and converts all cells (discharge permitted)
+fn measure<B, CS, P, T, C, D, const L: usize, H>(
+    client: &mut LTC681X<B, CS, P, T, L, H>,
+    config: [C; L],
+    test: &SwitchTestConfig,
+    delay: &mut D,
+) -> Result<Snapshot<L>, Error<B, CS>>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    C: ConfigurationRegisters + DischargeControl + Clone,
+    D: DelayUs<u32>,
+    H: TransferHook,
+{
+    client.write_configuration(config)?;
+    delay.delay_us(test.settle_time);
+
+    let request = ConversionRequest::cells(T::ALL_CELLS).mode(test.mode).discharge_permitted(true);
+    let voltages = client.begin_conversion(request)?.wait(delay)?;
+
+    let mut snapshot: Snapshot<L> = Snapshot::new();
+    snapshot.update(&voltages);
+    Ok(snapshot)
+}
+
+/// Measures the baseline and the voltages of each enabled switch
+fn test_switches<B, CS, P, T, C, D, const L: usize, H>(
+    client: &mut LTC681X<B, CS, P, T, L, H>,
+    config: &[C; L],
+    test: &SwitchTestConfig,
+    delay: &mut D,
+) -> Result<SwitchTestReport<L>, Error<B, CS>>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    C: ConfigurationRegisters + DischargeControl + Clone,
+    D: DelayUs<u32>,
+    H: TransferHook,
+{
+    let mut report = SwitchTestReport {
+        results: [[None; MAX_CELLS]; L],
+    };
+
+    let baseline = measure(client, config.clone(), test, delay)?;
+
+    for cell in 0..T::CELL_COUNT.min(MAX_CELLS) {
+        let mut step = config.clone();
+        for item in step.iter_mut() {
+            item.discharge_cell(Cell::from(cell));
+        }
+
+        let measured = measure(client, step, test, delay)?;
+
+        for device in 0..L {
+            let before = &baseline.device(device).cell_voltages;
+            let after = &measured.device(device).cell_voltages;
+
+            let (baseline, voltage) = match (before[cell], after[cell]) {
+                (Some(baseline), Some(voltage)) => (baseline, voltage),
+                _ => continue,
+            };
+
+            let dip = baseline as i32 - voltage as i32;
+            let neighbor_dip = [cell.checked_sub(1), Some(cell + 1)]
+                .iter()
+                .flatten()
+                .filter_map(|index| match (before.get(*index), after.get(*index)) {
+                    (Some(Some(before)), Some(Some(after))) => Some(*before as i32 - *after as i32),
+                    _ => None,
+                })
+                .max()
+                .unwrap_or(0);
+
+            report.results[device][cell] = Some(SwitchTest {
+                baseline,
+                voltage,
+                dip,
+                neighbor_dip,
+                passed: dip >= test.min_dip as i32,
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// Discharges each cell of each device one at a time and locates the largest voltage dip
+fn check_wiring<B, CS, P, T, C, D, const L: usize, H>(
+    client: &mut LTC681X<B, CS, P, T, L, H>,
+    config: &[C; L],
+    test: &SwitchTestConfig,
+    delay: &mut D,
+) -> Result<WiringReport<L>, Error<B, CS>>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    C: ConfigurationRegisters + DischargeControl + Clone,
+    D: DelayUs<u32>,
+    H: TransferHook,
+{
+    let mut report = WiringReport {
+        results: [[None; MAX_CELLS]; L],
+    };
+
+    let baseline = measure(client, config.clone(), test, delay)?;
+
+    for device in 0..L {
+        for cell in 0..T::CELL_COUNT.min(MAX_CELLS) {
+            let mut step = config.clone();
+            step[device].discharge_cell(Cell::from(cell));
+
+            let measured = measure(client, step, test, delay)?;
+
+            // Position of the largest dip across the whole daisy chain
+            let mut largest: Option<((usize, usize), i32)> = None;
+            for (index, (before, after)) in baseline.devices().iter().zip(measured.devices().iter()).enumerate() {
+                for (slot, (before, after)) in before.cell_voltages.iter().zip(after.cell_voltages.iter()).enumerate() {
+                    if let (Some(before), Some(after)) = (before, after) {
+                        let dip = *before as i32 - *after as i32;
+
+                        if largest.is_none_or(|(_, largest)| dip > largest) {
+                            largest = Some(((index, slot), dip));
+                        }
+                    }
+                }
+            }
+
+            report.results[device][cell] = largest.map(|(position, dip)| WiringCheck {
+                observed: if dip >= test.min_dip as i32 {
+                    Some(position)
+                } else {
+                    None
+                },
+                dip,
+            });
+        }
+    }
+
+    Ok(report)
+}
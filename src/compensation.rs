@@ -0,0 +1,134 @@
+//! IR-drop compensation
+//!
+//! When cells are converted with discharge permitted (DCP=1) or under PWM balancing, the balancing current flows
+//! through the resistance of the measurement path (e.g. input filter resistors). Thus the measured voltage
+//! of a discharged cell is lower than the real cell voltage:
+//!
+//! `measured = real * (1 - duty * R_path / (R_bleed + R_path))`
+//!
+//! [IrDropCompensation] reverses this drop based on the known bleed resistance, path resistance and duty
+//! cycle, so that balancing decisions are not chasing their own measurement artifact. Effects on neighbouring
+//! cells sharing a filter resistor are not considered.
+//!
+//! ````
+//! use ltc681x::compensation::IrDropCompensation;
+//! use ltc681x::config::{Cell, Configuration};
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, Channel, LTC6813};
+//! use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let mut config = Configuration::default();
+//! config.discharge_cell(Cell::Cell1);
+//!
+//! // 33 Ohm bleed resistor, 2 x 1 Ohm path resistance (e.g. harness and connector)
+//! let compensation = IrDropCompensation::new(33_000, 2_000);
+//!
+//! let mut voltages = client.read_voltages(CellSelection::Group1).unwrap();
+//! compensation.compensate_discharging(&mut voltages, &[config]);
+//!
+//! // Cell 1 is discharging: measured 2.4979 V * 35 / 33
+//! assert_eq!(Channel::Cell1, voltages[0][0].channel);
+//! assert_eq!(26492, voltages[0][0].voltage);
+//! // Cell 7 and 13 are not discharging, measured values are kept
+//! assert_eq!(25441, voltages[0][1].voltage);
+//! assert_eq!(25822, voltages[0][2].voltage);
+//!
+//! // PWM balancing of cell 13 with 50 % duty cycle
+//! let mut voltages = client.read_voltages(CellSelection::Group1).unwrap();
+//! compensation.compensate_with(&mut voltages, |_, cell| if cell == 12 { 500 } else { 0 });
+//!
+//! // Measured 2.5822 V * 35 / 34
+//! assert_eq!(24979, voltages[0][0].voltage);
+//! assert_eq!(26581, voltages[0][2].voltage);
+//! ````
+use crate::config::{Cell, DischargeControl};
+use crate::monitor::{ChannelIndex, DeviceTypes, Voltage};
+use heapless::Vec;
+
+/// Full duty cycle in per mille
+const FULL_DUTY: u32 = 1000;
+
+/// Parameters of the balancing path
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct IrDropCompensation {
+    /// Resistance of the bleed resistor in mOhm
+    bleed_resistance: u32,
+
+    /// Resistance of the measurement path carrying the balancing current in mOhm
+    path_resistance: u32,
+}
+
+impl IrDropCompensation {
+    /// Creates a new compensation based on the bleed resistance (mOhm) and the resistance of the measurement
+    /// path carrying the balancing current (mOhm)
+    pub fn new(bleed_resistance: u32, path_resistance: u32) -> Self {
+        Self {
+            bleed_resistance,
+            path_resistance,
+        }
+    }
+
+    /// Returns the bleed resistance in mOhm
+    pub fn get_bleed_resistance(&self) -> u32 {
+        self.bleed_resistance
+    }
+
+    /// Returns the resistance of the measurement path in mOhm
+    pub fn get_path_resistance(&self) -> u32 {
+        self.path_resistance
+    }
+
+    /// Returns the compensated raw voltage of a cell discharged with the given duty cycle (per mille)
+    /// The result saturates at u16::MAX.
+    pub fn compensate(&self, voltage: u16, duty: u32) -> u16 {
+        let duty = duty.min(FULL_DUTY) as u64;
+        let total = (self.bleed_resistance as u64 + self.path_resistance as u64) * FULL_DUTY as u64;
+        let divisor = total - duty * self.path_resistance as u64;
+
+        if divisor == 0 {
+            return u16::MAX;
+        }
+
+        (voltage as u64 * total / divisor).min(u16::MAX as u64) as u16
+    }
+
+    /// Compensates all cell voltages using the duty cycle (per mille) returned by the given closure for each
+    /// device and cell index (index 0 = cell 1). Other channels are not changed.
+    pub fn compensate_with<T, F, const N: usize, const L: usize>(
+        &self,
+        voltages: &mut Vec<Vec<Voltage<T>, N>, L>,
+        mut duty: F,
+    ) where
+        T: DeviceTypes,
+        F: FnMut(usize, usize) -> u32,
+    {
+        for (device, voltages) in voltages.iter_mut().enumerate() {
+            for voltage in voltages.iter_mut() {
+                if let Some(index) = voltage.channel.to_cell_index() {
+                    voltage.voltage = self.compensate(voltage.voltage, duty(device, index));
+                }
+            }
+        }
+    }
+
+    /// Compensates the voltages of all cells, which are discharging (full duty) according to the given
+    /// configuration. One configuration item per device in daisy chain.
+    pub fn compensate_discharging<T, C, const N: usize, const L: usize>(
+        &self,
+        voltages: &mut Vec<Vec<Voltage<T>, N>, L>,
+        config: &[C; L],
+    ) where
+        T: DeviceTypes,
+        C: DischargeControl,
+    {
+        self.compensate_with(voltages, |device, index| {
+            if config[device].is_cell_discharging(Cell::from(index)) {
+                FULL_DUTY
+            } else {
+                0
+            }
+        });
+    }
+}
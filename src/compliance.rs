@@ -0,0 +1,371 @@
+//! Datasheet timing enforcement (requires `timing-compliance` feature)
+//!
+//! The [CompliantClient] wraps the client and enforces the minimum timings of the datasheet based on the
+//! given [Clock] and [DelayUs] implementation:
+//!
+//! * **t_WAKE**: After more than [T_SLEEP_US] without activity (or before the first operation), the core of
+//!   each device in daisy chain is woken up, delaying [T_WAKE_US](crate::timing::T_WAKE_US) per device
+//! * **t_IDLE**: After more than [T_IDLE_US] without activity, the isoSPI port of each device is woken up,
+//!   delaying [T_READY_US](crate::timing::T_READY_US) per device
+//! * **t_REFUP**: The reference is powered up at the start of the first conversion after waking up the core,
+//!   so the remaining part of [T_REFUP_US] is added to the expected duration of this conversion
+//! * **Conversion times**: Registers are not read and no further conversion is started until the expected
+//!   duration of the last conversion (maximum of regular and alternative timing) has elapsed
+//!
+//! Waking up is performed by [LTC681X::wake_up] and [LTC681X::wake_up_idle], so chains behind an isoSPI bridge
+//! (e.g. LTC6820) are supported by configuring the [WakeProfile](crate::monitor::WakeProfile) of the client.
+//!
+//! Depending on the [TimingPolicy], operations violating conversion times are either delayed until
+//! the timing is met (default) or refused with [ComplianceError::Violation]. Rewaking is always performed, as
+//! it's the required action and not a violation.
+//!
+//! ````
+//! use ltc681x::compliance::{ComplianceError, CompliantClient, TimingPolicy, TimingViolation};
+//! use ltc681x::example::{ExampleClock, ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, Register, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//!
+//! let client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut client = CompliantClient::new(client, ExampleClock::default(), ExampleDelay::default())
+//!     .policy(TimingPolicy::Refuse);
+//!
+//! // First conversion after the initial wakeup includes the reference power-up
+//! let timing = client.start_conv_cells(ADCMode::Normal, CellSelection::All, true).unwrap();
+//! assert!(timing.regular > 2_343);
+//!
+//! // Conversion is still ongoing
+//! let result = client.read_register(Register::CellVoltageA);
+//! assert!(matches!(result, Err(ComplianceError::Violation(TimingViolation::ConversionPending { .. }))));
+//! ````
+//!
+//! # isoSPI bridge
+//!
+//! ````
+//! use ltc681x::compliance::CompliantClient;
+//! use ltc681x::example::{ExampleClock, ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{LTC6813, Register};
+//! use ltc681x::monitor::{WakeProfile, LTC681X};
+//!
+//! let client: LTC681X<_, _, _, LTC6813, 2> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{})
+//!     .with_wake_profile(WakeProfile::ltc6820());
+//! let mut client = CompliantClient::new(client, ExampleClock::default(), ExampleDelay::default());
+//!
+//! // Bridge is woken up before the devices
+//! client.read_register(Register::CellVoltageA).unwrap();
+//! ````
+use crate::config::ConfigurationRegisters;
+use crate::conversion::ConversionRequest;
+use crate::health::Clock;
+use crate::monitor::{
+    ADCMode, CommandTime, DeviceTypes, Error, InternalDeviceParameters, LTC681XClient, NoHook, PollMethod,
+    RegisterLocator, StatusGroup, TransferHook, Voltage, LTC681X,
+};
+use crate::pwm::PwmRegisters;
+use crate::timing::{T_IDLE_US, T_REFUP_US, T_SLEEP_US};
+use core::fmt::{Debug, Formatter};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Handling of operations violating the datasheet timing
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum TimingPolicy {
+    /// Operation is delayed until the timing is met
+    #[default]
+    Delay,
+
+    /// Operation is refused with [ComplianceError::Violation]
+    Refuse,
+}
+
+/// Violated timing requirement
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TimingViolation {
+    /// Last conversion is still ongoing
+    ConversionPending {
+        /// Remaining time of conversion in microseconds
+        remaining_us: u32,
+    },
+}
+
+/// Error enum of [CompliantClient]
+pub enum ComplianceError<B: Transfer<u8>, CS: OutputPin> {
+    /// Error of the wrapped client
+    Client(Error<B, CS>),
+
+    /// Operation was refused, as it would violate the datasheet timing
+    Violation(TimingViolation),
+}
+
+impl<B: Transfer<u8>, CS: OutputPin> From<Error<B, CS>> for ComplianceError<B, CS> {
+    fn from(error: Error<B, CS>) -> Self {
+        ComplianceError::Client(error)
+    }
+}
+
+/// Client wrapper enforcing the datasheet timing
+pub struct CompliantClient<B, CS, P, T, C, D, const L: usize, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    C: Clock,
+    D: DelayUs<u32>,
+    H: TransferHook,
+{
+    client: LTC681X<B, CS, P, T, L, H>,
+    clock: C,
+    delay: D,
+    policy: TimingPolicy,
+
+    /// Timestamp of the last operation, None if no operation was executed yet
+    last_activity: Option<u32>,
+
+    /// Timestamp at which the reference is powered up
+    reference_ready: Option<u32>,
+
+    /// Timestamp at which the last conversion is done
+    conversion_done: Option<u32>,
+}
+
+impl<B, CS, P, T, C, D, const L: usize, H> CompliantClient<B, CS, P, T, C, D, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    C: Clock,
+    D: DelayUs<u32>,
+    H: TransferHook,
+{
+    /// Wraps the given client. Devices are assumed to be in sleep state.
+    pub fn new(client: LTC681X<B, CS, P, T, L, H>, clock: C, delay: D) -> Self {
+        Self {
+            client,
+            clock,
+            delay,
+            policy: TimingPolicy::default(),
+            last_activity: None,
+            reference_ready: None,
+            conversion_done: None,
+        }
+    }
+
+    /// Sets the handling of operations violating the timing
+    pub fn policy(mut self, policy: TimingPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// See [LTC681XClient::start_conv_cells]
+    #[allow(deprecated)]
+    pub fn start_conv_cells(
+        &mut self,
+        mode: ADCMode,
+        cells: T::CellSelection,
+        dcp: bool,
+    ) -> Result<CommandTime, ComplianceError<B, CS>> {
+        self.before_conversion()?;
+        let timing = self.client.start_conv_cells(mode, cells, dcp)?;
+        Ok(self.after_conversion(timing))
+    }
+
+    /// See [LTC681XClient::start_conversion]
+    pub fn start_conversion(&mut self, request: ConversionRequest<T>) -> Result<CommandTime, ComplianceError<B, CS>> {
+        self.before_conversion()?;
+        let timing = self.client.start_conversion(request)?;
+        Ok(self.after_conversion(timing))
+    }
+
+    /// See [LTC681XClient::start_conv_gpio]
+    #[allow(deprecated)]
+    pub fn start_conv_gpio(
+        &mut self,
+        mode: ADCMode,
+        pins: T::GPIOSelection,
+    ) -> Result<CommandTime, ComplianceError<B, CS>> {
+        self.before_conversion()?;
+        let timing = self.client.start_conv_gpio(mode, pins)?;
+        Ok(self.after_conversion(timing))
+    }
+
+    /// See [LTC681XClient::measure_internal_parameters]
+    #[allow(deprecated)]
+    pub fn measure_internal_parameters(
+        &mut self,
+        mode: ADCMode,
+        group: StatusGroup,
+    ) -> Result<CommandTime, ComplianceError<B, CS>> {
+        self.before_conversion()?;
+        let timing = self.client.measure_internal_parameters(mode, group)?;
+        Ok(self.after_conversion(timing))
+    }
+
+    /// See [LTC681XClient::read_register]
+    pub fn read_register(&mut self, register: T::Register) -> Result<[[u16; 3]; L], ComplianceError<B, CS>> {
+        self.before_read()?;
+        let result = self.client.read_register(register);
+        self.after_operation(result)
+    }
+
+    /// See [LTC681XClient::read_voltages]
+    pub fn read_voltages<R: RegisterLocator<T> + 'static>(
+        &mut self,
+        locator: R,
+    ) -> Result<Vec<Vec<Voltage<T>, 18>, L>, ComplianceError<B, CS>> {
+        self.before_read()?;
+        let result = self.client.read_voltages(locator);
+        self.after_operation(result)
+    }
+
+    /// See [LTC681XClient::read_internal_device_parameters]
+    pub fn read_internal_device_parameters(
+        &mut self,
+    ) -> Result<Vec<InternalDeviceParameters, L>, ComplianceError<B, CS>> {
+        self.before_read()?;
+        let result = self.client.read_internal_device_parameters();
+        self.after_operation(result)
+    }
+
+    /// See [LTC681XClient::write_configuration]
+    pub fn write_configuration<R: ConfigurationRegisters>(
+        &mut self,
+        config: [R; L],
+    ) -> Result<(), ComplianceError<B, CS>> {
+        self.wake_up()?;
+        let result = self.client.write_configuration(config);
+        self.after_operation(result)
+    }
+
+    /// See [LTC681XClient::write_pwm]
+    pub fn write_pwm<R: PwmRegisters>(&mut self, pwm: [R; L]) -> Result<(), ComplianceError<B, CS>> {
+        self.wake_up()?;
+        let result = self.client.write_pwm(pwm);
+        self.after_operation(result)
+    }
+
+    /// Returns the wrapped client
+    /// Operations executed directly on the client are not tracked.
+    pub fn client(&mut self) -> &mut LTC681X<B, CS, P, T, L, H> {
+        &mut self.client
+    }
+
+    /// Consumes the wrapper and returns the client, clock and delay
+    pub fn release(self) -> (LTC681X<B, CS, P, T, L, H>, C, D) {
+        (self.client, self.clock, self.delay)
+    }
+
+    /// Wakes up the daisy chain if required and awaits the end of the last conversion
+    fn before_conversion(&mut self) -> Result<(), ComplianceError<B, CS>> {
+        self.wake_up()?;
+        self.await_conversion()?;
+
+        // isoSPI port may have entered idle state while delaying
+        self.wake_up()
+    }
+
+    /// Wakes up the daisy chain if required and awaits the end of the last conversion
+    fn before_read(&mut self) -> Result<(), ComplianceError<B, CS>> {
+        self.wake_up()?;
+        self.await_conversion()?;
+        self.wake_up()
+    }
+
+    /// Remembers the expected end of the started conversion
+    /// The device powers up the reference before converting, so the remaining power-up time is added to the
+    /// returned timing of the first conversion after wakeup.
+    fn after_conversion(&mut self, timing: CommandTime) -> CommandTime {
+        let now = self.clock.now_us();
+        let power_up = self.reference_ready.take().and_then(|ready| remaining(ready, now)).unwrap_or(0);
+
+        let timing = CommandTime {
+            regular: timing.regular + power_up,
+            alternative: timing.alternative + power_up,
+        };
+
+        self.conversion_done = Some(now.wrapping_add(timing.regular.max(timing.alternative)));
+        self.last_activity = Some(now);
+        timing
+    }
+
+    /// Updates the timestamp of the last activity
+    fn after_operation<V>(&mut self, result: Result<V, Error<B, CS>>) -> Result<V, ComplianceError<B, CS>> {
+        self.last_activity = Some(self.clock.now_us());
+        Ok(result?)
+    }
+
+    /// Checks if the last conversion is done
+    fn await_conversion(&mut self) -> Result<(), ComplianceError<B, CS>> {
+        let now = self.clock.now_us();
+
+        if let Some(remaining_us) = self.conversion_done.and_then(|done| remaining(done, now)) {
+            self.enforce(TimingViolation::ConversionPending { remaining_us })?;
+        }
+
+        self.conversion_done = None;
+        Ok(())
+    }
+
+    /// Delays or refuses the operation depending on the policy
+    fn enforce(&mut self, violation: TimingViolation) -> Result<(), ComplianceError<B, CS>> {
+        match (self.policy, violation) {
+            (TimingPolicy::Refuse, _) => Err(ComplianceError::Violation(violation)),
+            (TimingPolicy::Delay, TimingViolation::ConversionPending { remaining_us }) => {
+                self.delay.delay_us(remaining_us);
+                Ok(())
+            }
+        }
+    }
+
+    /// Wakes up the core (sleep state) or isoSPI port (idle state) of all devices in daisy chain
+    fn wake_up(&mut self) -> Result<(), ComplianceError<B, CS>> {
+        let now = self.clock.now_us();
+        let inactive = self.last_activity.map(|last| now.wrapping_sub(last));
+
+        let sleeping = match inactive {
+            None => true,
+            Some(duration) if duration >= T_SLEEP_US => true,
+            Some(duration) if duration >= T_IDLE_US => false,
+            Some(_) => return Ok(()),
+        };
+
+        if sleeping {
+            self.client.wake_up(&mut self.delay)?;
+        } else {
+            self.client.wake_up_idle(&mut self.delay)?;
+        }
+
+        let now = self.clock.now_us();
+        self.last_activity = Some(now);
+
+        if sleeping {
+            // Results of a conversion are lost in sleep state
+            self.conversion_done = None;
+            self.reference_ready = Some(now.wrapping_add(T_REFUP_US));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the remaining time until the given deadline, None if the deadline has passed
+fn remaining(deadline: u32, now: u32) -> Option<u32> {
+    let remaining = deadline.wrapping_sub(now) as i32;
+
+    if remaining > 0 {
+        Some(remaining as u32)
+    } else {
+        None
+    }
+}
+
+impl<B: Transfer<u8>, CS: OutputPin> Debug for ComplianceError<B, CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ComplianceError::Client(error) => f.debug_tuple("Client").field(error).finish(),
+            ComplianceError::Violation(violation) => f.debug_tuple("Violation").field(violation).finish(),
+        }
+    }
+}
@@ -32,6 +32,52 @@
 //! client.write_configuration([config]).unwrap();
 //! ````
 //!
+//! ## Device channels
+//!
+//! [Configuration] accepts all 18 cells and 9 GPIO pins (LTC6813 and ADBMS1818). Devices with less channels use
+//! their own alias of [SharedConfiguration] with device-specific `Cell` and `GPIO` enums, e.g.
+//! [ltc6811::Configuration](crate::ltc6811::Configuration):
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6811::{Cell, Configuration, LTC6811};
+//! use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6811, 1> = LTC681X::ltc6811(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let mut config = Configuration::default();
+//! config.discharge_cell(Cell::Cell12);
+//!
+//! client.write_configuration([config]).unwrap();
+//! ````
+//!
+//! Unavailable channels fail at compile time:
+//! ````compile_fail
+//! use ltc681x::ltc6811::{Cell, Configuration};
+//!
+//! let mut config = Configuration::default();
+//! config.discharge_cell(Cell::Cell13);
+//! ````
+//!
+//! ## Presets
+//!
+//! Instead of the raw power-on defaults, new applications may start from a typical Li-ion configuration. For
+//! details s. [Configuration::bms_defaults].
+//! ````
+//!# use ltc681x::config::{Cell, Configuration};
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::LTC6813;
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(spi_bus, cs_pin);
+//!
+//! let mut config = Configuration::bms_defaults();
+//! config.set_ov_comp_voltage(4_150_000).unwrap();
+//!
+//! client.write_configuration([config]).unwrap();
+//! ````
+//!
 //! ## Multiple devices in daisy-chain
 //!
 //! Writing to multiple devices in daisy-chain is supported, by providing an array item per device:
@@ -60,7 +106,67 @@
 //! client.write_configuration(config).unwrap();
 //! ````
 //!
-use core::fmt::{Display, Formatter};
+//! Alternatively, the configuration of each device can be created by a closure receiving the device index:
+//! ````
+//!# use ltc681x::config::{Cell, Configuration};
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::LTC6813;
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! let mut client: LTC681X<_, _, _, LTC6813, 3> = LTC681X::ltc6813(spi_bus, cs_pin);
+//!
+//! client.write_configuration_with(|index| {
+//!     let mut config = Configuration::default();
+//!     config.set_ov_comp_voltage(4_100_000 + index as u32 * 100_000).unwrap();
+//!     config
+//! }).unwrap();
+//! ````
+//!
+//! ## Read-modify-write
+//!
+//! When multiple subsystems share the configuration registers (e.g. balancing and GPIO control), the current
+//! configuration can be read, updated by a closure and written back. Only changed registers are written:
+//! ````
+//!# use ltc681x::config::{Cell, Configuration};
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::LTC6813;
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(spi_bus, cs_pin);
+//!
+//! let written = client.update_configuration(|config: &mut [Configuration; 1]| {
+//!     config[0].discharge_cell(Cell::Cell6);
+//! }).unwrap();
+//!
+//! assert!(written);
+//! ````
+//!
+//! ## Read-back
+//!
+//! [read_configuration](crate::monitor::LTC681XClient::read_configuration) reads the configuration registers and
+//! parses them back into a configuration per device, e.g. for verifying the written configuration:
+//! ````
+//!# use ltc681x::config::{Cell, Configuration, ConfigurationRegisters};
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::LTC6813;
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!#
+//!# let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let config: [Configuration; 1] = client.read_configuration().unwrap();
+//!
+//! assert_eq!([0xF8, 0x0, 0x0, 0x0, 0x0, 0x0], config[0].register_a());
+//! assert!(!config[0].is_cell_discharging(Cell::Cell1));
+//!
+//! let intended = Configuration::default();
+//! assert_eq!(intended.register_a(), config[0].register_a());
+//! ````
+//!
+use core::fmt::{Debug, Display, Formatter};
+use core::marker::PhantomData;
 
 pub trait ConfigurationRegisters {
     fn register_a(&self) -> [u8; 6];
@@ -68,17 +174,91 @@ pub trait ConfigurationRegisters {
     fn register_b(&self) -> Option<[u8; 6]>;
 }
 
-/// Abstracted configuration of configuration register(s)
-#[derive(Debug, Clone)]
-pub struct Configuration {
+/// Creates a configuration from register values read back from the device
+pub trait FromConfigurationRegisters: Sized {
+    /// Parses the raw values of register A and (if available on the device) register B
+    fn from_registers(register_a: [u8; 6], register_b: Option<[u8; 6]>) -> Self;
+}
+
+/// Device independent access to the discharge switches (DCC bits) of a configuration
+pub trait DischargeControl {
+    /// Turn ON Shorting Switch for Cell x
+    fn discharge_cell(&mut self, cell: Cell);
+
+    /// Turn OFF all shorting switches
+    fn clear_discharge_cells(&mut self);
+
+    /// Returns true if the shorting switch of the given cell is turned ON
+    fn is_cell_discharging(&self, cell: Cell) -> bool;
+
+    /// Enables the discharge timer for discharge switches
+    fn enable_discharge_timer(&mut self);
+
+    /// Sets the discharge timeout
+    fn set_discharge_timeout(&mut self, timeout: DischargeTimeout);
+}
+
+/// Cells and GPIO pins available on a device, bounding the channels accepted by a [Configuration]
+///
+/// Implemented by the device types using the shared configuration, e.g. [LTC6811](crate::ltc6811::LTC6811).
+/// Selecting a channel which is not available on the device fails at compile time.
+pub trait ConfigurationChannels {
+    /// Cells of the device
+    type Cell: Copy + Into<Cell> + TryFrom<Cell>;
+
+    /// GPIO pins of the device
+    type GPIO: Copy + Into<GPIO> + TryFrom<GPIO>;
+}
+
+/// All cells and GPIO pins of the device independent [Cell] and [GPIO] enums (LTC6813 and ADBMS1818)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AllChannels {}
+
+impl ConfigurationChannels for AllChannels {
+    type Cell = Cell;
+    type GPIO = GPIO;
+}
+
+/// Configuration of all cells and GPIO pins (LTC6813 and ADBMS1818)
+///
+/// For devices with less channels, use the device-specific alias, e.g.
+/// [ltc6811::Configuration](crate::ltc6811::Configuration).
+pub type Configuration = SharedConfiguration<AllChannels>;
+
+/// Abstracted configuration of configuration register(s), shared by all devices except the LTC6810
+///
+/// The type parameter restricts the cells and GPIO pins to the ones of the device. Bits of register B are ignored
+/// by devices without this register.
+pub struct SharedConfiguration<C: ConfigurationChannels> {
     /// Computed value of register A
     pub(crate) register_a: [u8; 6],
 
     /// Computed value of register B,
     pub(crate) register_b: [u8; 6],
+
+    channels: PhantomData<C>,
+}
+
+impl<C: ConfigurationChannels> Debug for SharedConfiguration<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SharedConfiguration")
+            .field("register_a", &self.register_a)
+            .field("register_b", &self.register_b)
+            .finish()
+    }
+}
+
+impl<C: ConfigurationChannels> Clone for SharedConfiguration<C> {
+    fn clone(&self) -> Self {
+        Self {
+            register_a: self.register_a,
+            register_b: self.register_b,
+            channels: PhantomData,
+        }
+    }
 }
 
-impl Default for Configuration {
+impl<C: ConfigurationChannels> Default for SharedConfiguration<C> {
     fn default() -> Self {
         Self {
             register_a: [
@@ -97,13 +277,25 @@ impl Default for Configuration {
                 0b0000_0000,
                 0b0000_0000,
             ],
+            channels: PhantomData,
+        }
+    }
+}
+
+impl<C: ConfigurationChannels> FromConfigurationRegisters for SharedConfiguration<C> {
+    /// Missing register B is replaced by its default value
+    fn from_registers(register_a: [u8; 6], register_b: Option<[u8; 6]>) -> Self {
+        Self {
+            register_a,
+            register_b: register_b.unwrap_or(Self::default().register_b),
+            channels: PhantomData,
         }
     }
 }
 
 /// GPIO pins of LTC681X device.
-/// Depending on the device type, not all pins may be available.
-/// Configuring a pin that is not physically available has no effect.
+/// Depending on the device type, not all pins may be available. The configuration of devices with less pins only
+/// accepts the pins of the device, s. [ConfigurationChannels].
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum GPIO {
     GPIO1,
@@ -118,8 +310,8 @@ pub enum GPIO {
 }
 
 /// Cell indexes of the LTC681X device.
-/// Depending on the device type, not all cells may be available.
-/// Configuring a cell that is not physically available has no effect.
+/// Depending on the device type, not all cells may be available. The configuration of devices with less cells only
+/// accepts the cells of the device, s. [ConfigurationChannels].
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum Cell {
     Cell1,
@@ -163,6 +355,61 @@ pub enum DischargeTimeout {
     TwoHours = 0xF,
 }
 
+impl DischargeTimeout {
+    /// All enabled timeouts in ascending order
+    const ENABLED: [DischargeTimeout; 15] = [
+        DischargeTimeout::HalfMinute,
+        DischargeTimeout::OneMinute,
+        DischargeTimeout::TwoMinutes,
+        DischargeTimeout::ThreeMinutes,
+        DischargeTimeout::FourMinutes,
+        DischargeTimeout::FiveMinutes,
+        DischargeTimeout::TenMinutes,
+        DischargeTimeout::FifteenMinutes,
+        DischargeTimeout::TwentyMinutes,
+        DischargeTimeout::ThirtyMinutes,
+        DischargeTimeout::FortyMinutes,
+        DischargeTimeout::SixtyMinutes,
+        DischargeTimeout::SeventyFiveMinutes,
+        DischargeTimeout::NinetyMinutes,
+        DischargeTimeout::TwoHours,
+    ];
+
+    /// Returns the timeout duration in milliseconds. Zero if disabled.
+    pub fn to_millis(&self) -> u32 {
+        match self {
+            DischargeTimeout::Disabled => 0,
+            DischargeTimeout::HalfMinute => 30_000,
+            DischargeTimeout::OneMinute => 60_000,
+            DischargeTimeout::TwoMinutes => 2 * 60_000,
+            DischargeTimeout::ThreeMinutes => 3 * 60_000,
+            DischargeTimeout::FourMinutes => 4 * 60_000,
+            DischargeTimeout::FiveMinutes => 5 * 60_000,
+            DischargeTimeout::TenMinutes => 10 * 60_000,
+            DischargeTimeout::FifteenMinutes => 15 * 60_000,
+            DischargeTimeout::TwentyMinutes => 20 * 60_000,
+            DischargeTimeout::ThirtyMinutes => 30 * 60_000,
+            DischargeTimeout::FortyMinutes => 40 * 60_000,
+            DischargeTimeout::SixtyMinutes => 60 * 60_000,
+            DischargeTimeout::SeventyFiveMinutes => 75 * 60_000,
+            DischargeTimeout::NinetyMinutes => 90 * 60_000,
+            DischargeTimeout::TwoHours => 120 * 60_000,
+        }
+    }
+
+    /// Returns the shortest timeout which is longer than the given control period in milliseconds
+    ///
+    /// Returns an error if no timeout exceeds the period (two hours or longer), as a shorter timeout would turn
+    /// off discharge before the next refresh.
+    pub fn exceeding(period_ms: u32) -> Result<Self, TimeoutOutOfRangeError> {
+        Self::ENABLED
+            .iter()
+            .find(|timeout| timeout.to_millis() > period_ms)
+            .copied()
+            .ok_or(TimeoutOutOfRangeError {})
+    }
+}
+
 /// Digital Redundancy Path Selection
 #[derive(Copy, Clone, Debug)]
 pub enum DigitalRedundancyPath {
@@ -187,10 +434,42 @@ impl Display for VoltageOutOfRangeError {
     }
 }
 
-impl Configuration {
+/// Given period is out-of-range for the discharge timer
+#[derive(Debug)]
+pub struct TimeoutOutOfRangeError {}
+
+impl Display for TimeoutOutOfRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Period exceeds the longest discharge timeout")
+    }
+}
+
+impl<C: ConfigurationChannels> SharedConfiguration<C> {
+    /// Typical configuration for Li-ion battery management, as known-good baseline instead of the power-on
+    /// defaults:
+    ///
+    /// * References remain powered up (REFON), avoiding the reference wake-up time before each conversion
+    /// * Under-voltage comparison at 3.0 V and over-voltage comparison at 4.2 V
+    /// * GPIO pull-downs off on all pins, so that the pins can be used as analog inputs
+    /// * Discharge timer enabled with a timeout of 30 minutes
+    ///
+    /// As the configuration abstraction is shared by all supported devices, the same preset applies to all of
+    /// them. Bits of unavailable pins or registers are ignored by the device.
+    pub fn bms_defaults() -> Self {
+        let mut config = Self::default();
+
+        config.enable_reference_power();
+        let _ = config.set_uv_comp_voltage(3_000_000);
+        let _ = config.set_ov_comp_voltage(4_200_000);
+        config.enable_discharge_timer();
+        config.set_discharge_timeout(DischargeTimeout::ThirtyMinutes);
+
+        config
+    }
+
     /// Enables pull-down of the given GPIO pin
-    pub fn enable_gpio_pull_down(&mut self, pin: GPIO) {
-        match pin {
+    pub fn enable_gpio_pull_down(&mut self, pin: C::GPIO) {
+        match pin.into() {
             GPIO::GPIO1 => self.register_a[0] &= 0b1111_0111,
             GPIO::GPIO2 => self.register_a[0] &= 0b1110_1111,
             GPIO::GPIO3 => self.register_a[0] &= 0b1101_1111,
@@ -204,8 +483,8 @@ impl Configuration {
     }
 
     /// Enables pull-down of the given GPIO pin
-    pub fn disable_gpio_pull_down(&mut self, pin: GPIO) {
-        match pin {
+    pub fn disable_gpio_pull_down(&mut self, pin: C::GPIO) {
+        match pin.into() {
             GPIO::GPIO1 => self.register_a[0] |= 0b0000_1000,
             GPIO::GPIO2 => self.register_a[0] |= 0b0001_0000,
             GPIO::GPIO3 => self.register_a[0] |= 0b0010_0000,
@@ -281,8 +560,8 @@ impl Configuration {
     }
 
     /// Turn ON Shorting Switch for Cell x
-    pub fn discharge_cell(&mut self, cell: Cell) {
-        match cell {
+    pub fn discharge_cell(&mut self, cell: C::Cell) {
+        match cell.into() {
             Cell::Cell1 => self.register_a[4] |= 0b0000_0001,
             Cell::Cell2 => self.register_a[4] |= 0b0000_0010,
             Cell::Cell3 => self.register_a[4] |= 0b0000_0100,
@@ -304,6 +583,38 @@ impl Configuration {
         }
     }
 
+    /// Turn OFF all shorting switches
+    pub fn clear_discharge_cells(&mut self) {
+        self.register_a[4] = 0x0;
+        self.register_a[5] &= 0b1111_0000;
+        self.register_b[0] &= 0b0000_1111;
+        self.register_b[1] &= 0b1111_1100;
+    }
+
+    /// Returns true if the shorting switch of the given cell is turned ON
+    pub fn is_cell_discharging(&self, cell: C::Cell) -> bool {
+        match cell.into() {
+            Cell::Cell1 => self.register_a[4] & 0b0000_0001 != 0,
+            Cell::Cell2 => self.register_a[4] & 0b0000_0010 != 0,
+            Cell::Cell3 => self.register_a[4] & 0b0000_0100 != 0,
+            Cell::Cell4 => self.register_a[4] & 0b0000_1000 != 0,
+            Cell::Cell5 => self.register_a[4] & 0b0001_0000 != 0,
+            Cell::Cell6 => self.register_a[4] & 0b0010_0000 != 0,
+            Cell::Cell7 => self.register_a[4] & 0b0100_0000 != 0,
+            Cell::Cell8 => self.register_a[4] & 0b1000_0000 != 0,
+            Cell::Cell9 => self.register_a[5] & 0b0000_0001 != 0,
+            Cell::Cell10 => self.register_a[5] & 0b0000_0010 != 0,
+            Cell::Cell11 => self.register_a[5] & 0b0000_0100 != 0,
+            Cell::Cell12 => self.register_a[5] & 0b0000_1000 != 0,
+            Cell::Cell13 => self.register_b[0] & 0b0001_0000 != 0,
+            Cell::Cell14 => self.register_b[0] & 0b0010_0000 != 0,
+            Cell::Cell15 => self.register_b[0] & 0b0100_0000 != 0,
+            Cell::Cell16 => self.register_b[0] & 0b1000_0000 != 0,
+            Cell::Cell17 => self.register_b[1] & 0b0000_0001 != 0,
+            Cell::Cell18 => self.register_b[1] & 0b0000_0010 != 0,
+        }
+    }
+
     /// Sets the discharge timeout
     pub fn set_discharge_timeout(&mut self, timeout: DischargeTimeout) {
         self.register_a[5] &= 0b0000_1111;
@@ -339,7 +650,7 @@ impl Configuration {
     }
 }
 
-impl ConfigurationRegisters for Configuration {
+impl<C: ConfigurationChannels> ConfigurationRegisters for SharedConfiguration<C> {
     fn register_a(&self) -> [u8; 6] {
         self.register_a
     }
@@ -349,13 +660,41 @@ impl ConfigurationRegisters for Configuration {
     }
 }
 
-impl PartialEq<Self> for Configuration {
+/// Cells not available on the device are ignored
+impl<C: ConfigurationChannels> DischargeControl for SharedConfiguration<C> {
+    fn discharge_cell(&mut self, cell: Cell) {
+        if let Ok(cell) = C::Cell::try_from(cell) {
+            SharedConfiguration::discharge_cell(self, cell)
+        }
+    }
+
+    fn clear_discharge_cells(&mut self) {
+        SharedConfiguration::clear_discharge_cells(self)
+    }
+
+    fn is_cell_discharging(&self, cell: Cell) -> bool {
+        match C::Cell::try_from(cell) {
+            Ok(cell) => SharedConfiguration::is_cell_discharging(self, cell),
+            Err(_) => false,
+        }
+    }
+
+    fn enable_discharge_timer(&mut self) {
+        SharedConfiguration::enable_discharge_timer(self)
+    }
+
+    fn set_discharge_timeout(&mut self, timeout: DischargeTimeout) {
+        SharedConfiguration::set_discharge_timeout(self, timeout)
+    }
+}
+
+impl<C: ConfigurationChannels> PartialEq<Self> for SharedConfiguration<C> {
     fn eq(&self, other: &Self) -> bool {
         self.register_a == other.register_a && self.register_b == other.register_b
     }
 }
 
-impl Eq for Configuration {}
+impl<C: ConfigurationChannels> Eq for SharedConfiguration<C> {}
 
 impl From<usize> for Cell {
     fn from(index: usize) -> Self {
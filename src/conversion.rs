@@ -0,0 +1,597 @@
+//! Fluent builder for cell conversion commands
+//!
+//! Instead of choosing between several positional-argument methods, a [ConversionRequest] describes
+//! the cell conversion to be started. The request renders the matching opcode (ADCV, ADCVAX, ADCVSC or ADOW)
+//! and knows its own expected execution time.
+//!
+//! ````
+//! use ltc681x::conversion::{ConversionRequest, PullDirection};
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // Regular cell conversion (ADCV)
+//! let request = ConversionRequest::cells(CellSelection::Group1).mode(ADCMode::Normal);
+//! let timing = client.start_conversion(request).unwrap();
+//! assert_eq!(407, timing.regular);
+//!
+//! // All cells + GPIO1 and GPIO2 (ADCVAX)
+//! let request = ConversionRequest::cells(CellSelection::All).mode(ADCMode::Fast).with_gpio12();
+//! client.start_conversion(request).unwrap();
+//!
+//! // Open wire conversion with pull-up current (ADOW)
+//! let request = ConversionRequest::cells(CellSelection::All).discharge_permitted(true).open_wire(PullDirection::PullUp);
+//! client.start_conversion(request).unwrap();
+//! ````
+//!
+//! # Selecting cells
+//!
+//! Instead of mapping cell numbers to cell groups manually, [select_cells] returns the minimal set of cell
+//! groups covering the given cells.
+//!
+//! ````
+//! use ltc681x::conversion::select_cells;
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//!
+//! // Cells 1, 7 and 8 are converted by group 1 and group 2
+//! let groups = select_cells::<LTC6813>(0b1100_0001);
+//! assert_eq!(&[CellSelection::Group1, CellSelection::Group2], groups.as_slice());
+//!
+//! // All groups required
+//! let groups = select_cells::<LTC6813>(0b11_1111);
+//! assert_eq!(&[CellSelection::All], groups.as_slice());
+//! ````
+//!
+//! # Conversion handle
+//!
+//! [LTC681X::begin_conversion] starts the conversion and returns a [Conversion] handle, which borrows the client
+//! until the conversion is completed, so the client can't be used for reading the registers in the meantime.
+//! [Conversion::wait] waits for completion and returns the voltages. Alternatively, [Conversion::complete] returns a
+//! [ConversionToken], which is consumed by [read_cell_voltages](LTC681X::read_cell_voltages). The token can't be
+//! created otherwise, so reading stale results fails to compile. Both fail with [Error::Timeout] in case SDO line
+//! polling doesn't report completion.
+//!
+//! The register based methods (e.g. [LTC681XClient::read_voltages]) remain available for low-level access and don't
+//! offer this protection.
+//!
+//! ````
+//! use ltc681x::conversion::ConversionRequest;
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, Channel, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // Waits the expected conversion time using the given delay
+//! let conversion = client.begin_conversion(ConversionRequest::cells(CellSelection::Group1)).unwrap();
+//! let voltages = conversion.wait(&mut ExampleDelay::default()).unwrap();
+//! assert_eq!(Channel::Cell1, voltages[0][0].channel);
+//!
+//! // Completed conversion token
+//! let conversion = client.begin_conversion(ConversionRequest::cells(CellSelection::Group1)).unwrap();
+//! let token = conversion.complete(&mut ExampleDelay::default()).unwrap();
+//! let voltages = client.read_cell_voltages(token).unwrap();
+//! assert_eq!(24979, voltages[0][0].voltage);
+//!
+//! // Polls the ADC status, in case SDO line polling is enabled (fails with Error::Timeout after twice the
+//! // expected conversion time)
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{})
+//!     .enable_sdo_polling();
+//! let conversion = client.begin_conversion(ConversionRequest::cells(CellSelection::Group1)).unwrap();
+//! let voltages = conversion.wait(&mut ExampleDelay::default()).unwrap();
+//! assert_eq!(24979, voltages[0][0].voltage);
+//! ````
+//!
+//! Reading without a completed conversion doesn't compile:
+//!
+//! ````compile_fail
+//! use ltc681x::conversion::{ConversionRequest, ConversionToken};
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let token = ConversionToken::<LTC6813> { cells: CellSelection::Group1 };
+//! client.read_cell_voltages(token).unwrap();
+//! ````
+//!
+//! Neither does reading while the conversion is running, as the handle borrows the client:
+//!
+//! ````compile_fail
+//! use ltc681x::conversion::ConversionRequest;
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//! use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let conversion = client.begin_conversion(ConversionRequest::cells(CellSelection::Group1)).unwrap();
+//! client.read_voltages(CellSelection::Group1).unwrap();
+//! let token = conversion.complete(&mut ExampleDelay::default()).unwrap();
+//! ````
+//!
+//! # One-shot measurement
+//!
+//! [measure_cell_voltages](LTC681X::measure_cell_voltages) starts the cell conversion (ADCV), waits for completion
+//! and returns the voltages in a single call. Without polling, the expected conversion time is awaited. In case of
+//! SDO line polling, the ADC status is polled every [POLL_INTERVAL] until the conversion is completed or the timeout
+//! of twice the expected conversion time is exceeded ([Error::Timeout]).
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, Channel, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let voltages = client
+//!     .measure_cell_voltages(ADCMode::Normal, CellSelection::Group1, &mut ExampleDelay::default())
+//!     .unwrap();
+//! assert_eq!(Channel::Cell1, voltages[0][0].channel);
+//! ````
+use crate::monitor::{
+    ADCMode, ChannelIndex, CommandTime, DeviceTypes, Error, InternalDeviceParameters, LTC681XClient, NoHook,
+    PollClient, PollMethod, RegisterLocator, SDOLinePolling, StatusGroup, ToCommandBitmap, ToCommandTiming,
+    TransferHook, Voltage, LTC681X,
+};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Interval between two ADC status polls of [LTC681X::wait_conversion] in microseconds
+pub const POLL_INTERVAL: u32 = 100;
+
+/// Direction of the current source used for open wire conversion (ADOW command)
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PullDirection {
+    /// Pull-down current
+    PullDown = 0x0,
+    /// Pull-up current
+    PullUp = 0x1,
+}
+
+/// Kind of the cell conversion, which determines the opcode
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ConversionKind {
+    /// Cell voltage conversion (ADCV command)
+    Cells,
+    /// Cell voltages and GPIO1/GPIO2 (ADCVAX command)
+    CellsAndGpio12,
+    /// Cell voltages and sum of cells (ADCVSC command)
+    CellsAndSum,
+    /// Open wire conversion (ADOW command)
+    OpenWire(PullDirection),
+}
+
+/// Description of a cell conversion to be started
+pub struct ConversionRequest<T: DeviceTypes> {
+    cells: T::CellSelection,
+    mode: ADCMode,
+    dcp: bool,
+    kind: ConversionKind,
+}
+
+impl<T: DeviceTypes> Copy for ConversionRequest<T> {}
+
+impl<T: DeviceTypes> Clone for ConversionRequest<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: DeviceTypes> ConversionRequest<T> {
+    /// Creates a regular cell conversion (ADCV) of the given cell group
+    ///
+    /// Defaults to [ADCMode::Normal] and discharge not permitted.
+    pub fn cells(cells: T::CellSelection) -> Self {
+        Self {
+            cells,
+            mode: ADCMode::Normal,
+            dcp: false,
+            kind: ConversionKind::Cells,
+        }
+    }
+
+    /// Sets the ADC mode
+    pub fn mode(mut self, mode: ADCMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// True if discharge is permitted during conversion
+    pub fn discharge_permitted(mut self, dcp: bool) -> Self {
+        self.dcp = dcp;
+        self
+    }
+
+    /// Converts GPIO1 and GPIO2 in the same conversion window (ADCVAX command)
+    /// ADCVAX always converts all cells, the cell selection is ignored.
+    pub fn with_gpio12(mut self) -> Self {
+        self.kind = ConversionKind::CellsAndGpio12;
+        self.cells = T::ALL_CELLS;
+        self
+    }
+
+    /// Converts the sum of cells in the same conversion window (ADCVSC command)
+    /// ADCVSC always converts all cells, the cell selection is ignored.
+    pub fn with_sum_of_cells(mut self) -> Self {
+        self.kind = ConversionKind::CellsAndSum;
+        self.cells = T::ALL_CELLS;
+        self
+    }
+
+    /// Runs an open wire conversion (ADOW command) using the given current direction
+    pub fn open_wire(mut self, pull: PullDirection) -> Self {
+        self.kind = ConversionKind::OpenWire(pull);
+        self
+    }
+
+    /// Returns the selected ADC mode
+    pub fn get_mode(&self) -> ADCMode {
+        self.mode
+    }
+
+    /// Returns the kind of the conversion
+    pub fn get_kind(&self) -> ConversionKind {
+        self.kind
+    }
+
+    /// Returns the selected cell group
+    pub fn get_cells(&self) -> T::CellSelection {
+        self.cells
+    }
+
+    /// Returns the 11-bit command code (without PEC)
+    pub fn to_command(&self) -> u16 {
+        let mut command: u16 = match self.kind {
+            ConversionKind::Cells => 0b0000_0010_0110_0000 | self.cells.to_bitmap(),
+            ConversionKind::CellsAndGpio12 => 0b0000_0100_0110_1111,
+            ConversionKind::CellsAndSum => 0b0000_0100_0110_0111,
+            ConversionKind::OpenWire(pull) => 0b0000_0010_0010_1000 | (pull as u16) << 6 | self.cells.to_bitmap(),
+        };
+
+        command |= (self.mode as u16) << 7;
+
+        if self.dcp {
+            command |= 0b0001_0000;
+        }
+
+        command
+    }
+
+    /// Returns the expected execution time
+    ///
+    /// ADCVAX and ADCVSC timings are estimated by adding one conversion step per additional channel to
+    /// the timing of an all-cell conversion.
+    pub fn expected_duration(&self) -> CommandTime {
+        let base = self.cells.to_conv_command_timing(self.mode);
+        let step = StatusGroup::CellSum.to_conv_command_timing(self.mode);
+
+        match self.kind {
+            ConversionKind::Cells | ConversionKind::OpenWire(_) => base,
+            ConversionKind::CellsAndGpio12 => {
+                CommandTime::new(base.regular + 2 * step.regular, base.alternative + 2 * step.alternative)
+            }
+            ConversionKind::CellsAndSum => {
+                CommandTime::new(base.regular + step.regular, base.alternative + step.alternative)
+            }
+        }
+    }
+}
+
+/// Returns the minimal set of cell groups covering the given cells
+///
+/// `cells` is a bitmask of the cells to be converted, bit 0 corresponds to cell 1. Bits beyond the cell count of
+/// the device are ignored. In case all cell groups are required, [ALL_CELLS](DeviceTypes::ALL_CELLS) is returned,
+/// as a single conversion of all cells is faster than converting each group separately.
+pub fn select_cells<T: DeviceTypes>(cells: u32) -> Vec<T::CellSelection, 6> {
+    let groups: Vec<T::CellSelection, 6> = T::CELL_GROUPS
+        .iter()
+        .filter(|group| {
+            group
+                .get_locations()
+                .filter_map(|address| address.channel.to_cell_index())
+                .any(|index| cells & (1 << index) != 0)
+        })
+        .copied()
+        .collect();
+
+    if groups.len() == T::CELL_GROUPS.len() {
+        return Vec::from_slice(&[T::ALL_CELLS]).unwrap();
+    }
+
+    groups
+}
+
+/// Handle of a running cell conversion
+///
+/// Borrows the client until the results are fetched by [Conversion::wait]. Without polling, the results are fetched
+/// after waiting the expected conversion time. In case of SDO line polling, the ADC status is polled before reading.
+pub struct Conversion<'a, B, CS, P, T, const L: usize, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    client: &'a mut LTC681X<B, CS, P, T, L, H>,
+    request: ConversionRequest<T>,
+    timing: CommandTime,
+}
+
+impl<'a, B, CS, P, T, const L: usize, H> Conversion<'a, B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Returns the request which started the conversion
+    pub fn request(&self) -> ConversionRequest<T> {
+        self.request
+    }
+
+    /// Returns the expected execution time
+    pub fn timing(&self) -> CommandTime {
+        self.timing
+    }
+
+    /// Waits until the conversion is completed and returns the voltages of the converted cells
+    /// See [LTC681X::wait_conversion] for the timeout.
+    pub fn wait<D: DelayUs<u32>>(self, delay: &mut D) -> Result<Vec<Vec<Voltage<T>, 18>, L>, Error<B, CS>> {
+        let client = &mut *self.client;
+        client.wait_conversion(self.timing, delay)?;
+        client.read_cell_voltages(ConversionToken {
+            cells: self.request.get_cells(),
+        })
+    }
+
+    /// Waits until the conversion is completed and returns the token for reading the results
+    /// See [LTC681X::wait_conversion] for the timeout.
+    pub fn complete<D: DelayUs<u32>>(self, delay: &mut D) -> Result<ConversionToken<T>, Error<B, CS>> {
+        self.client.wait_conversion(self.timing, delay)?;
+
+        Ok(ConversionToken {
+            cells: self.request.get_cells(),
+        })
+    }
+}
+
+/// Proof of a completed cell conversion, consumed by [read_cell_voltages](LTC681X::read_cell_voltages)
+///
+/// The token is only created by [Conversion::complete], so reading the results of a conversion which was not
+/// started or is still running fails to compile.
+#[must_use]
+pub struct ConversionToken<T: DeviceTypes> {
+    cells: T::CellSelection,
+}
+
+impl<T: DeviceTypes> ConversionToken<T> {
+    /// Returns the converted cell group
+    pub fn cells(&self) -> T::CellSelection {
+        self.cells
+    }
+}
+
+impl<'a, B, CS, T, const L: usize, H> Conversion<'a, B, CS, SDOLinePolling, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Returns true if the conversion is completed
+    pub fn is_ready(&mut self) -> Result<bool, Error<B, CS>> {
+        self.client.adc_ready()
+    }
+}
+
+/// Handle of a running GPIO conversion (ADAX command)
+///
+/// Borrows the client until the results are fetched by [GpioConversion::wait], see [Conversion].
+pub struct GpioConversion<'a, B, CS, P, T, const L: usize, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    client: &'a mut LTC681X<B, CS, P, T, L, H>,
+    pins: T::GPIOSelection,
+    timing: CommandTime,
+}
+
+impl<'a, B, CS, P, T, const L: usize, H> GpioConversion<'a, B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Returns the converted GPIO group
+    pub fn pins(&self) -> T::GPIOSelection {
+        self.pins
+    }
+
+    /// Returns the expected execution time
+    pub fn timing(&self) -> CommandTime {
+        self.timing
+    }
+
+    /// Waits until the conversion is completed and returns the voltages of the converted GPIOs
+    /// See [LTC681X::wait_conversion] for the timeout.
+    pub fn wait<D: DelayUs<u32>>(self, delay: &mut D) -> Result<Vec<Vec<Voltage<T>, 18>, L>, Error<B, CS>> {
+        self.client.wait_conversion(self.timing, delay)?;
+        self.client.read_voltages(self.pins)
+    }
+}
+
+impl<'a, B, CS, T, const L: usize, H> GpioConversion<'a, B, CS, SDOLinePolling, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Returns true if the conversion is completed
+    pub fn is_ready(&mut self) -> Result<bool, Error<B, CS>> {
+        self.client.adc_ready()
+    }
+}
+
+/// Handle of a running measurement of internal device parameters (ADSTAT command)
+///
+/// Borrows the client until the results are fetched by [StatusConversion::wait], see [Conversion].
+pub struct StatusConversion<'a, B, CS, P, T, const L: usize, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    client: &'a mut LTC681X<B, CS, P, T, L, H>,
+    group: StatusGroup,
+    timing: CommandTime,
+}
+
+impl<'a, B, CS, P, T, const L: usize, H> StatusConversion<'a, B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Returns the measured status group
+    pub fn group(&self) -> StatusGroup {
+        self.group
+    }
+
+    /// Returns the expected execution time
+    pub fn timing(&self) -> CommandTime {
+        self.timing
+    }
+
+    /// Waits until the measurement is completed and returns the internal device parameters
+    /// Parameters not covered by the measured group keep their previous value. See [LTC681X::wait_conversion]
+    /// for the timeout.
+    pub fn wait<D: DelayUs<u32>>(self, delay: &mut D) -> Result<Vec<InternalDeviceParameters, L>, Error<B, CS>> {
+        self.client.wait_conversion(self.timing, delay)?;
+        self.client.read_internal_device_parameters()
+    }
+}
+
+impl<'a, B, CS, T, const L: usize, H> StatusConversion<'a, B, CS, SDOLinePolling, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Returns true if the measurement is completed
+    pub fn is_ready(&mut self) -> Result<bool, Error<B, CS>> {
+        self.client.adc_ready()
+    }
+}
+
+/// Result of [begin_conversion](LTC681X::begin_conversion)
+pub type BeginResult<'a, B, CS, P, T, const L: usize, H> = Result<Conversion<'a, B, CS, P, T, L, H>, Error<B, CS>>;
+
+/// Result of [begin_gpio_conversion](LTC681X::begin_gpio_conversion)
+pub type GpioBeginResult<'a, B, CS, P, T, const L: usize, H> =
+    Result<GpioConversion<'a, B, CS, P, T, L, H>, Error<B, CS>>;
+
+/// Result of [begin_status_conversion](LTC681X::begin_status_conversion)
+pub type StatusBeginResult<'a, B, CS, P, T, const L: usize, H> =
+    Result<StatusConversion<'a, B, CS, P, T, L, H>, Error<B, CS>>;
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Starts the given cell conversion and returns a handle for fetching the results
+    pub fn begin_conversion(&mut self, request: ConversionRequest<T>) -> BeginResult<'_, B, CS, P, T, L, H> {
+        let timing = self.start_conversion(request)?;
+
+        Ok(Conversion {
+            client: self,
+            request,
+            timing,
+        })
+    }
+
+    /// Starts the conversion of the given GPIO group (ADAX command) and returns a handle for fetching the results
+    #[allow(deprecated)]
+    pub fn begin_gpio_conversion(
+        &mut self,
+        mode: ADCMode,
+        pins: T::GPIOSelection,
+    ) -> GpioBeginResult<'_, B, CS, P, T, L, H> {
+        let timing = self.start_conv_gpio(mode, pins)?;
+
+        Ok(GpioConversion {
+            client: self,
+            pins,
+            timing,
+        })
+    }
+
+    /// Starts measuring the given internal device parameters (ADSTAT command) and returns a handle for fetching
+    /// the results
+    #[allow(deprecated)]
+    pub fn begin_status_conversion(
+        &mut self,
+        mode: ADCMode,
+        group: StatusGroup,
+    ) -> StatusBeginResult<'_, B, CS, P, T, L, H> {
+        let timing = self.measure_internal_parameters(mode, group)?;
+
+        Ok(StatusConversion {
+            client: self,
+            group,
+            timing,
+        })
+    }
+
+    /// Reads the voltages of the cells converted by the completed conversion
+    /// Returns one vector for each device in daisy chain
+    pub fn read_cell_voltages(
+        &mut self,
+        token: ConversionToken<T>,
+    ) -> Result<Vec<Vec<Voltage<T>, 18>, L>, Error<B, CS>> {
+        self.read_voltages(token.cells)
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Starts the cell conversion (ADCV command), waits for completion and returns the voltages
+    /// Discharge is not permitted during conversion. See [LTC681X::wait_conversion] for the timeout.
+    pub fn measure_cell_voltages<D: DelayUs<u32>>(
+        &mut self,
+        mode: ADCMode,
+        cells: T::CellSelection,
+        delay: &mut D,
+    ) -> Result<Vec<Vec<Voltage<T>, 18>, L>, Error<B, CS>> {
+        self.begin_conversion(ConversionRequest::cells(cells).mode(mode))?.wait(delay)
+    }
+}
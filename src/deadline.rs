@@ -0,0 +1,217 @@
+//! Acquisition cycle deadline and jitter monitoring
+//!
+//! Real-time integrators need to prove that the acquisition loop holds its timing, especially as daisy chains
+//! grow. The [DeadlineMonitor] records the start of each acquisition cycle relative to its fixed schedule
+//! (multiples of the cycle period since the first cycle) and flags:
+//!
+//! * **Excessive jitter**: The cycle started earlier or later than the configured jitter limit
+//! * **Skipped cycles**: The cycle started more than a full period late, so previous slots were skipped
+//! * **Missed deadlines**: The cycle was not finished within the deadline, relative to its scheduled start
+//!
+//! Timestamps are taken from a [Clock] and are allowed to wrap around.
+//!
+//! ````
+//! use ltc681x::deadline::DeadlineMonitor;
+//!
+//! // 10 ms period, 200 us jitter limit, cycle has to finish within 5 ms
+//! let mut monitor = DeadlineMonitor::new(10_000).with_jitter_limit(200).with_deadline(5_000);
+//!
+//! monitor.start(1_000);
+//! assert!(!monitor.finish(4_000));
+//!
+//! // Starts 300 us late
+//! let cycle = monitor.start(11_300);
+//! assert_eq!(300, cycle.jitter);
+//! assert!(cycle.excessive_jitter);
+//!
+//! // Finished 5.5 ms after scheduled start
+//! assert!(monitor.finish(16_500));
+//!
+//! assert_eq!(2, monitor.count());
+//! assert_eq!(1, monitor.jitter_violations());
+//! assert_eq!(1, monitor.missed_deadlines());
+//! assert_eq!(300, monitor.max_jitter());
+//! ````
+use crate::health::Clock;
+
+/// Timing of a single started cycle
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct CycleTiming {
+    /// Deviation of the start from schedule in microseconds. Negative if started early.
+    pub jitter: i32,
+
+    /// True if the absolute jitter exceeds the configured limit
+    pub excessive_jitter: bool,
+
+    /// Number of schedule slots skipped since the previous cycle
+    pub skipped: u32,
+}
+
+/// Statistics of the acquisition cycle timing
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct DeadlineMonitor {
+    /// Cycle period in microseconds
+    period: u32,
+
+    /// Accepted absolute jitter in microseconds
+    jitter_limit: u32,
+
+    /// Maximum duration from scheduled start until finish in microseconds
+    deadline: u32,
+
+    /// Scheduled start of the current cycle, None if no cycle was started yet
+    scheduled: Option<u32>,
+
+    /// Number of started cycles
+    count: u32,
+
+    /// Maximum absolute jitter in microseconds
+    max_jitter: u32,
+
+    /// Number of cycles exceeding the jitter limit
+    jitter_violations: u32,
+
+    /// Number of skipped schedule slots
+    skipped: u32,
+
+    /// Number of cycles finished after the deadline
+    missed_deadlines: u32,
+}
+
+impl DeadlineMonitor {
+    /// Creates a new monitor for the given cycle period (us)
+    /// Defaults to a jitter limit of 10% of the period and a deadline of one full period.
+    pub fn new(period: u32) -> Self {
+        let period = period.max(1);
+
+        Self {
+            period,
+            jitter_limit: period / 10,
+            deadline: period,
+            scheduled: None,
+            count: 0,
+            max_jitter: 0,
+            jitter_violations: 0,
+            skipped: 0,
+            missed_deadlines: 0,
+        }
+    }
+
+    /// Sets the accepted absolute jitter (us) of the cycle start
+    pub fn with_jitter_limit(mut self, us: u32) -> Self {
+        self.jitter_limit = us;
+        self
+    }
+
+    /// Sets the maximum duration (us) from the scheduled start until the cycle is finished
+    pub fn with_deadline(mut self, us: u32) -> Self {
+        self.deadline = us;
+        self
+    }
+
+    /// Records the start of a cycle at the given timestamp (us)
+    /// The first cycle defines the schedule and has no jitter.
+    pub fn start(&mut self, now: u32) -> CycleTiming {
+        let mut timing = CycleTiming {
+            jitter: 0,
+            excessive_jitter: false,
+            skipped: 0,
+        };
+
+        let scheduled = match self.scheduled {
+            None => now,
+            Some(previous) => {
+                let mut scheduled = previous.wrapping_add(self.period);
+                let mut jitter = now.wrapping_sub(scheduled) as i32;
+
+                // More than a full period late, the cycle is assigned to the closest preceding slot
+                if jitter > 0 && jitter as u32 >= self.period {
+                    timing.skipped = jitter as u32 / self.period;
+                    scheduled = scheduled.wrapping_add(timing.skipped * self.period);
+                    jitter = now.wrapping_sub(scheduled) as i32;
+                }
+
+                timing.jitter = jitter;
+                timing.excessive_jitter = jitter.unsigned_abs() > self.jitter_limit;
+                scheduled
+            }
+        };
+
+        self.scheduled = Some(scheduled);
+        self.count = self.count.saturating_add(1);
+        self.max_jitter = self.max_jitter.max(timing.jitter.unsigned_abs());
+        self.skipped = self.skipped.saturating_add(timing.skipped);
+
+        if timing.excessive_jitter {
+            self.jitter_violations = self.jitter_violations.saturating_add(1);
+        }
+
+        timing
+    }
+
+    /// Records the end of the current cycle at the given timestamp (us)
+    /// Returns true if the deadline was missed. Always false if no cycle was started yet.
+    pub fn finish(&mut self, now: u32) -> bool {
+        let scheduled = match self.scheduled {
+            None => return false,
+            Some(scheduled) => scheduled,
+        };
+
+        let missed = now.wrapping_sub(scheduled) > self.deadline;
+
+        if missed {
+            self.missed_deadlines = self.missed_deadlines.saturating_add(1);
+        }
+
+        missed
+    }
+
+    /// Same as [start](Self::start), using the current timestamp of the given clock
+    pub fn start_with<C: Clock>(&mut self, clock: &mut C) -> CycleTiming {
+        self.start(clock.now_us())
+    }
+
+    /// Same as [finish](Self::finish), using the current timestamp of the given clock
+    pub fn finish_with<C: Clock>(&mut self, clock: &mut C) -> bool {
+        self.finish(clock.now_us())
+    }
+
+    /// Returns the number of started cycles
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns the maximum absolute jitter in microseconds
+    pub fn max_jitter(&self) -> u32 {
+        self.max_jitter
+    }
+
+    /// Returns the number of cycles exceeding the jitter limit
+    pub fn jitter_violations(&self) -> u32 {
+        self.jitter_violations
+    }
+
+    /// Returns the number of skipped schedule slots
+    pub fn skipped_cycles(&self) -> u32 {
+        self.skipped
+    }
+
+    /// Returns the number of cycles finished after the deadline
+    pub fn missed_deadlines(&self) -> u32 {
+        self.missed_deadlines
+    }
+
+    /// Returns true if no deadline was missed, no cycle was skipped and the jitter stayed within the limit
+    pub fn is_holding(&self) -> bool {
+        self.jitter_violations == 0 && self.skipped == 0 && self.missed_deadlines == 0
+    }
+
+    /// Clears all recorded statistics and the schedule, the configuration is kept
+    pub fn reset(&mut self) {
+        *self = Self {
+            jitter_limit: self.jitter_limit,
+            deadline: self.deadline,
+            ..Self::new(self.period)
+        };
+    }
+}
@@ -0,0 +1,218 @@
+//! Degraded-mode operation
+//!
+//! Intermittent faults (e.g. PEC errors caused by EMI) usually affect single devices of a daisy chain. Instead
+//! of failing the whole read, [read_voltages_tolerant](LTC681X::read_voltages_tolerant) returns all valid
+//! values and marks the channels of corrupted frames as invalid.
+//!
+//! The [DegradedTracker] applies these readings to a [Snapshot]. Invalid channels are cleared in the snapshot
+//! and the tracker remembers since when each channel is degraded. Once a valid value is received again, the
+//! channel recovers. Timestamps are provided by the application in milliseconds, e.g. based on a system tick.
+//!
+//! ````
+//! use ltc681x::degraded::DegradedTracker;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut tracker: DegradedTracker<1> = DegradedTracker::new();
+//!
+//! match client.read_voltages_tolerant(CellSelection::Group1) {
+//!     Ok(readings) => tracker.update(&readings, 100),
+//!     // Bus errors affect all requested channels
+//!     Err(_) => tracker.mark_failed(CellSelection::Group1, 100),
+//! }
+//!
+//! assert!(!tracker.is_degraded(0));
+//! assert_eq!(Some(24979), tracker.snapshot().device(0).cell_voltages[0]);
+//!
+//! // Simulating a failed bus transaction
+//! tracker.mark_failed(CellSelection::Group1, 200);
+//!
+//! assert!(tracker.is_degraded(0));
+//! assert_eq!(None, tracker.snapshot().device(0).cell_voltages[0]);
+//! assert_eq!(Some(50), tracker.degraded_for(0, 250));
+//! ````
+use crate::codec;
+use crate::monitor::{
+    decode_voltages, ChannelIndex, DeviceTypes, Error, PollMethod, RegisterLocator, ToFullCommand, TransferHook,
+    LTC681X,
+};
+use crate::snapshot::{Snapshot, MAX_CELLS, MAX_GPIOS};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Reading of a single channel, which may be invalid
+#[derive(PartialEq, Debug)]
+pub struct Reading<T: DeviceTypes> {
+    /// Channel of the voltage
+    pub channel: T::Channel,
+
+    /// Raw register value, None if the frame of the device was corrupted (PEC mismatch)
+    /// Real voltage: voltage * 100 uV
+    pub voltage: Option<u16>,
+}
+
+impl<T: DeviceTypes> Copy for Reading<T> {}
+
+impl<T: DeviceTypes> Clone for Reading<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Reads the values of the given cell or GPIO group. Returns one vector for each device in daisy chain
+    ///
+    /// Same as [read_voltages](crate::monitor::LTC681XClient::read_voltages), but PEC mismatches don't abort the
+    /// read. Instead, the channels of the corrupted frame are returned as None. Bus and CS pin errors are still
+    /// returned as error.
+    pub fn read_voltages_tolerant<R: RegisterLocator<T> + 'static>(
+        &mut self,
+        locator: R,
+    ) -> Result<Vec<Vec<Reading<T>, 18>, L>, Error<B, CS>> {
+        decode_voltages(
+            &locator,
+            |register| self.read_daisy_chain_tolerant(register.to_read_command()),
+            |data, address| Reading {
+                channel: address.channel,
+                voltage: data.map(|data| codec::decode_register_words(&data)[address.slot]),
+            },
+        )
+    }
+}
+
+/// Tracks the validity of all channels of the daisy chain
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct DegradedTracker<const L: usize> {
+    /// Latest valid values, invalid channels are None
+    snapshot: Snapshot<L>,
+
+    /// Timestamp (ms) since when the cell is degraded, None if valid
+    cells: [[Option<u32>; MAX_CELLS]; L],
+
+    /// Timestamp (ms) since when the GPIO is degraded, None if valid
+    gpios: [[Option<u32>; MAX_GPIOS]; L],
+}
+
+impl<const L: usize> Default for DegradedTracker<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const L: usize> DegradedTracker<L> {
+    /// Creates a new tracker with all channels valid but not measured yet
+    pub fn new() -> Self {
+        Self {
+            snapshot: Snapshot::new(),
+            cells: [[None; MAX_CELLS]; L],
+            gpios: [[None; MAX_GPIOS]; L],
+        }
+    }
+
+    /// Applies the given readings, as returned by [read_voltages_tolerant](LTC681X::read_voltages_tolerant)
+    ///
+    /// Valid channels are stored in the snapshot and recover from degraded state. Invalid channels are cleared and
+    /// marked as degraded since the given timestamp (ms), unless already degraded.
+    pub fn update<T: DeviceTypes, const N: usize>(&mut self, readings: &Vec<Vec<Reading<T>, N>, L>, now: u32) {
+        for (device, readings) in readings.iter().enumerate() {
+            for reading in readings {
+                self.apply(device, reading.channel, reading.voltage, now);
+            }
+        }
+    }
+
+    /// Marks all channels of the given group as degraded on all devices, e.g. in case of a bus error
+    pub fn mark_failed<T: DeviceTypes, R: RegisterLocator<T> + 'static>(&mut self, locator: R, now: u32) {
+        for device in 0..L {
+            for address in locator.get_locations() {
+                self.apply(device, address.channel, None, now);
+            }
+        }
+    }
+
+    /// Returns the latest valid values
+    pub fn snapshot(&self) -> &Snapshot<L> {
+        &self.snapshot
+    }
+
+    /// Returns true if any channel of the given device is degraded
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn is_degraded(&self, device: usize) -> bool {
+        self.cells[device]
+            .iter()
+            .chain(self.gpios[device].iter())
+            .any(|since| since.is_some())
+    }
+
+    /// Returns true if any channel of any device is degraded
+    pub fn any_degraded(&self) -> bool {
+        (0..L).any(|device| self.is_degraded(device))
+    }
+
+    /// Returns how long (ms) the given device has been degraded, based on the longest degraded channel
+    /// None if all channels are valid.
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn degraded_for(&self, device: usize, now: u32) -> Option<u32> {
+        self.cells[device]
+            .iter()
+            .chain(self.gpios[device].iter())
+            .filter_map(|since| since.map(|since| now.wrapping_sub(since)))
+            .max()
+    }
+
+    /// Returns how long (ms) the given cell (index 0 = cell 1) has been degraded, None if valid
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn cell_degraded_for(&self, device: usize, index: usize, now: u32) -> Option<u32> {
+        self.cells[device].get(index)?.map(|since| now.wrapping_sub(since))
+    }
+
+    /// Returns how long (ms) the given GPIO (index 0 = GPIO 1) has been degraded, None if valid
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn gpio_degraded_for(&self, device: usize, index: usize, now: u32) -> Option<u32> {
+        self.gpios[device].get(index)?.map(|since| now.wrapping_sub(since))
+    }
+
+    /// Applies the value of a single channel
+    fn apply<C: ChannelIndex>(&mut self, device: usize, channel: C, voltage: Option<u16>, now: u32) {
+        let values = self.snapshot.device_mut(device);
+
+        let (slot, since) = if let Some(index) = channel.to_cell_index() {
+            match (values.cell_voltages.get_mut(index), self.cells[device].get_mut(index)) {
+                (Some(slot), Some(since)) => (slot, since),
+                _ => return,
+            }
+        } else if let Some(index) = channel.to_gpio_index() {
+            match (values.gpio_voltages.get_mut(index), self.gpios[device].get_mut(index)) {
+                (Some(slot), Some(since)) => (slot, since),
+                _ => return,
+            }
+        } else {
+            return;
+        };
+
+        *slot = voltage;
+
+        match voltage {
+            Some(_) => *since = None,
+            None => {
+                if since.is_none() {
+                    *since = Some(now)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Runtime device detection
+//!
+//! [detect_devices](LTC681X::detect_devices) reads status register B of all chain members and returns the
+//! revision code (REV bits of STBR5) of each responding device. Positions without a valid response (e.g. missing
+//! device or broken isoSPI link) are returned as None, which allows validating the assembly of the chain.
+//!
+//! The revision code identifies the silicon revision only. The chip variant is not encoded and has to be known
+//! by the application, e.g. by comparing the revisions with the expected ones of the assembly.
+//!
+//! ````
+//! use ltc681x::detect::DetectedDevice;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 2> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let devices = client.detect_devices().unwrap();
+//!
+//! assert_eq!(Some(DetectedDevice { revision: 1 }), devices[0]);
+//! assert_eq!(Some(DetectedDevice { revision: 1 }), devices[1]);
+//! ````
+use crate::monitor::{DeviceTypes, Error, PollMethod, ToFullCommand, TransferHook, LTC681X};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Device responding to the status register read
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct DetectedDevice {
+    /// Revision code (REV)
+    pub revision: u8,
+}
+
+impl DetectedDevice {
+    /// Parses the raw status register B data
+    pub fn from_status_b(data: &[u8; 6]) -> Self {
+        Self { revision: data[5] >> 4 }
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Reads the revision code of all devices in daisy chain
+    /// One item per chain position, None if the position did not return a PEC-valid frame.
+    pub fn detect_devices(&mut self) -> Result<[Option<DetectedDevice>; L], Error<B, CS>> {
+        let frames = self.read_daisy_chain_tolerant(T::REG_STATUS_B.to_read_command())?;
+        Ok(frames.map(|frame| frame.map(|data| DetectedDevice::from_status_b(&data))))
+    }
+}
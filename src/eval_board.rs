@@ -0,0 +1,140 @@
+//! Profiles of evaluation boards (requires `eval-boards` feature)
+//!
+//! Presets for the demo boards of the LTC681X family, bundling the connected cells and the GPIO roles, so the
+//! converted temperatures and mappings are correct with a single profile selection:
+//!
+//! * [DC2259A](BoardProfile::dc2259a): LTC6811-1, 12 cells
+//! * [DC2350A](BoardProfile::dc2350a): LTC6813-1, 18 cells
+//!
+//! The profiles assume the default board population: 10 kΩ NTC thermistors (B = 3380 K) with a 10 kΩ pull-up
+//! resistor to VREF2 on GPIO1 - GPIO5, measured ratiometrically. If jumpers or the population were changed, the
+//! [AuxChannelMap] may be adjusted after creation. GPIO multiplexers are not covered, as the client has no mux
+//! support.
+//!
+//! ````
+//! use ltc681x::auxiliary::AuxValue;
+//! use ltc681x::eval_board::BoardProfile;
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let profile = BoardProfile::dc2350a();
+//! assert_eq!(18, profile.connected_cell_count());
+//!
+//! let values = client.read_aux_channels(&profile.aux_maps(), ADCMode::Normal, &mut ExampleDelay::default()).unwrap();
+//! assert_eq!(5, values[0].len());
+//! assert!(matches!(values[0][0].value, AuxValue::Temperature(_)));
+//! ````
+use crate::auxiliary::{AuxChannel, AuxChannelMap};
+use crate::config::GPIO;
+use crate::ltc6811::LTC6811;
+use crate::ltc6813::LTC6813;
+use crate::monitor::DeviceTypes;
+use crate::temperature::{Thermistor, ThermistorModel, VoltageDivider};
+use core::marker::PhantomData;
+
+/// Thermistor populated on the demo boards
+pub const DEMO_BOARD_THERMISTOR: Thermistor = Thermistor::new(
+    ThermistorModel::Beta {
+        nominal_resistance: 10_000,
+        beta: 3380,
+    },
+    VoltageDivider {
+        supply_voltage: 3_000_000,
+        series_resistance: 10_000,
+    },
+);
+
+/// GPIOs connected to thermistors on the demo boards
+const THERMISTOR_GPIOS: [GPIO; 5] = [GPIO::GPIO1, GPIO::GPIO2, GPIO::GPIO3, GPIO::GPIO4, GPIO::GPIO5];
+
+/// Wiring of a single evaluation board
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BoardProfile<T: DeviceTypes> {
+    /// Board name
+    name: &'static str,
+
+    /// Bit mask of connected cells, bit 0 = cell 1
+    connected_cells: u32,
+
+    /// Roles of the GPIOs
+    aux_map: AuxChannelMap,
+
+    device_type: PhantomData<T>,
+}
+
+impl BoardProfile<LTC6811> {
+    /// Profile of the DC2259A demo board (LTC6811-1)
+    pub fn dc2259a() -> Self {
+        BoardProfile::new("DC2259A", 12)
+    }
+}
+
+impl BoardProfile<LTC6813> {
+    /// Profile of the DC2350A demo board (LTC6813-1)
+    pub fn dc2350a() -> Self {
+        BoardProfile::new("DC2350A", 18)
+    }
+}
+
+impl<T: DeviceTypes> BoardProfile<T> {
+    /// Creates a profile with the given number of connected cells and thermistors on GPIO1 - GPIO5
+    fn new(name: &'static str, cell_count: usize) -> Self {
+        let mut aux_map = AuxChannelMap::new();
+
+        for pin in THERMISTOR_GPIOS {
+            aux_map.set(
+                pin,
+                AuxChannel::Thermistor {
+                    thermistor: DEMO_BOARD_THERMISTOR,
+                    ratiometric: true,
+                },
+            );
+        }
+
+        Self {
+            name,
+            connected_cells: (1 << cell_count) - 1,
+            aux_map,
+            device_type: PhantomData,
+        }
+    }
+
+    /// Overrides the role of the given GPIO, e.g. after changing the board population
+    pub fn with_aux_channel(mut self, pin: GPIO, role: AuxChannel) -> Self {
+        self.aux_map.set(pin, role);
+        self
+    }
+
+    /// Returns the board name
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the bit mask of connected cells, bit 0 = cell 1
+    pub fn connected_cells(&self) -> u32 {
+        self.connected_cells
+    }
+
+    /// Returns the number of connected cells
+    pub fn connected_cell_count(&self) -> usize {
+        self.connected_cells.count_ones() as usize
+    }
+
+    /// Returns true if the cell with the given index (0 = cell 1) is connected
+    pub fn is_cell_connected(&self, index: usize) -> bool {
+        index < 32 && self.connected_cells & (1 << index) != 0
+    }
+
+    /// Returns the GPIO roles of a single board
+    pub fn aux_map(&self) -> AuxChannelMap {
+        self.aux_map
+    }
+
+    /// Returns the GPIO roles for a daisy chain of identical boards
+    pub fn aux_maps<const L: usize>(&self) -> [AuxChannelMap; L] {
+        [self.aux_map; L]
+    }
+}
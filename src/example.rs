@@ -1,6 +1,8 @@
 //! SPI bus mock for doc examples
+use crate::health::Clock;
 use core::convert::Infallible;
-use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::{Transfer, Write};
 use embedded_hal::digital::v2::OutputPin;
 
 #[derive(Default)]
@@ -32,12 +34,26 @@ impl Transfer<u8> for ExampleSPIBus {
                 0b0000_0110 => Ok(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94]),
                 // Cell voltage register C
                 0b0000_1000 => Ok(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42]),
+                // Cell voltage register D
+                0b0000_1010 => Ok(&[0x8A, 0x61, 0x61, 0x1F, 0xCF, 0x21, 0x01, 0xEE]),
                 // Cell voltage register E
                 0b0000_1001 => Ok(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA]),
+                // Cell voltage register F
+                0b0000_1011 => Ok(&[0x00, 0x63, 0x2F, 0x1F, 0x8B, 0x1F, 0xC1, 0x68]),
                 // Aux voltage register A
                 0b0000_1100 => Ok(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C]),
+                // Aux voltage register B
+                0b0000_1110 => Ok(&[0x98, 0x3A, 0x98, 0x3A, 0x30, 0x75, 0x88, 0xAC]),
                 // Aux voltage register C
                 0b0000_1101 => Ok(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42]),
+                // Aux voltage register D
+                0b0000_1111 => Ok(&[0x98, 0x3A, 0x00, 0x00, 0x00, 0x00, 0xEB, 0xB4]),
+                // Configuration register A
+                0b0000_0010 => Ok(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2]),
+                // Configuration register B
+                0b0010_0110 => Ok(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68]),
+                // PWM register
+                0b0010_0010 => Ok(&[0x88, 0x88, 0x88, 0x00, 0x00, 0x00, 0x57, 0x36]),
                 _ => Ok(&[0x0; 8]),
             };
         }
@@ -48,6 +64,18 @@ impl Transfer<u8> for ExampleSPIBus {
     }
 }
 
+/// Write-only SPI bus, discarding all data
+#[derive(Default)]
+pub struct ExampleWriteBus {}
+
+impl Write<u8> for ExampleWriteBus {
+    type Error = Infallible;
+
+    fn write(&mut self, _words: &[u8]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
 pub struct ExampleCSPin {}
 
 impl OutputPin for ExampleCSPin {
@@ -61,3 +89,23 @@ impl OutputPin for ExampleCSPin {
         Ok(())
     }
 }
+
+#[derive(Default)]
+pub struct ExampleDelay {}
+
+impl DelayUs<u32> for ExampleDelay {
+    fn delay_us(&mut self, _us: u32) {}
+}
+
+/// Simulated clock advancing 200 us on every call
+#[derive(Default)]
+pub struct ExampleClock {
+    now: u32,
+}
+
+impl Clock for ExampleClock {
+    fn now_us(&mut self) -> u32 {
+        self.now = self.now.wrapping_add(200);
+        self.now
+    }
+}
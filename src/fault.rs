@@ -0,0 +1,269 @@
+//! Latched fault flags
+//!
+//! Faults are often transient: A PEC error caused by EMI or an over-voltage flag may be gone by the time the
+//! application polls the state. The [FaultLatch] records every observed [Fault] together with the timestamp of
+//! its first occurrence. Latched faults remain set until explicitly [cleared](FaultLatch::clear_faults).
+//! Timestamps are provided by the application in milliseconds, e.g. based on a system tick.
+//!
+//! Faults may be latched directly or derived from driver results:
+//!
+//! * [record_error](FaultLatch::record_error): PEC errors, latched for all devices as the corrupted frame is unknown
+//! * [record_cell_flags](FaultLatch::record_cell_flags): OV/UV flags of all cells, as returned by
+//!   [read_cell_flags](crate::monitor::LTC681X::read_cell_flags)
+//! * [record_status_flags](FaultLatch::record_status_flags): Thermal shutdown (THSD) of the
+//!   [decoded](crate::status) status register B
+//! * [record_self_test](FaultLatch::record_self_test): Result of the [self-test](crate::battery::BatteryMonitor::self_test)
+//!
+//! ````
+//! use ltc681x::config::Cell;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::fault::{Fault, FaultLatch};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut faults: FaultLatch<1> = FaultLatch::new();
+//!
+//! match client.read_cell_flags() {
+//!     Ok(flags) => faults.record_cell_flags(&flags, 100),
+//!     Err(error) => faults.record_error(&error, 100),
+//! }
+//!
+//! match client.read_status_b() {
+//!     Ok(status) => faults.record_status_flags(&status, 100),
+//!     Err(error) => faults.record_error(&error, 100),
+//! }
+//!
+//! assert!(faults.is_latched(0, Fault::OverVoltage(Cell::Cell5)));
+//! assert!(faults.is_latched(0, Fault::ThermalShutdown));
+//!
+//! // Open wire detected by the application
+//! faults.latch(0, Fault::OpenWire(Cell::Cell4), 200);
+//! faults.latch(0, Fault::OpenWire(Cell::Cell4), 300);
+//!
+//! assert!(faults.has_faults(0));
+//! assert_eq!(Some(200), faults.first_occurrence(0, Fault::OpenWire(Cell::Cell4)));
+//!
+//! faults.clear_faults();
+//! assert!(!faults.any_fault());
+//! ````
+use crate::config::Cell;
+use crate::monitor::Error;
+use crate::snapshot::MAX_CELLS;
+use crate::status::{CellFlags, StatusB};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Fault observed on a single device
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Fault {
+    /// PEC checksum of returned data was invalid
+    ChecksumMismatch,
+
+    /// Open wire of the given cell input
+    OpenWire(Cell),
+
+    /// Over-voltage flag of the given cell
+    OverVoltage(Cell),
+
+    /// Under-voltage flag of the given cell
+    UnderVoltage(Cell),
+
+    /// Device failed the self-test
+    SelfTestFailed,
+
+    /// Thermal shutdown (THSD) occurred
+    ThermalShutdown,
+}
+
+/// Latched faults of a single device
+/// Each item stores the timestamp (ms) of the first occurrence, None if not latched.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct DeviceFaults {
+    /// PEC checksum mismatch
+    pub checksum_mismatch: Option<u32>,
+
+    /// Open wire per cell, index 0 = cell 1
+    pub open_wire: [Option<u32>; MAX_CELLS],
+
+    /// Over-voltage per cell, index 0 = cell 1
+    pub over_voltage: [Option<u32>; MAX_CELLS],
+
+    /// Under-voltage per cell, index 0 = cell 1
+    pub under_voltage: [Option<u32>; MAX_CELLS],
+
+    /// Failed self-test
+    pub self_test_failed: Option<u32>,
+
+    /// Thermal shutdown
+    pub thermal_shutdown: Option<u32>,
+}
+
+impl DeviceFaults {
+    /// Returns the timestamp (ms) of the first occurrence of the given fault, None if not latched
+    pub fn first_occurrence(&self, fault: Fault) -> Option<u32> {
+        match fault {
+            Fault::ChecksumMismatch => self.checksum_mismatch,
+            Fault::OpenWire(cell) => self.open_wire[cell as usize],
+            Fault::OverVoltage(cell) => self.over_voltage[cell as usize],
+            Fault::UnderVoltage(cell) => self.under_voltage[cell as usize],
+            Fault::SelfTestFailed => self.self_test_failed,
+            Fault::ThermalShutdown => self.thermal_shutdown,
+        }
+    }
+
+    /// Returns true if any fault is latched
+    pub fn has_faults(&self) -> bool {
+        self.checksum_mismatch.is_some()
+            || self.self_test_failed.is_some()
+            || self.thermal_shutdown.is_some()
+            || self
+                .open_wire
+                .iter()
+                .chain(self.over_voltage.iter())
+                .chain(self.under_voltage.iter())
+                .any(|since| since.is_some())
+    }
+
+    /// Latches the given fault, unless already latched. Returns true if the fault was newly latched.
+    fn latch(&mut self, fault: Fault, now: u32) -> bool {
+        let slot = match fault {
+            Fault::ChecksumMismatch => &mut self.checksum_mismatch,
+            Fault::OpenWire(cell) => &mut self.open_wire[cell as usize],
+            Fault::OverVoltage(cell) => &mut self.over_voltage[cell as usize],
+            Fault::UnderVoltage(cell) => &mut self.under_voltage[cell as usize],
+            Fault::SelfTestFailed => &mut self.self_test_failed,
+            Fault::ThermalShutdown => &mut self.thermal_shutdown,
+        };
+
+        if slot.is_some() {
+            return false;
+        }
+
+        *slot = Some(now);
+        true
+    }
+}
+
+/// Latched faults of all devices in daisy chain
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct FaultLatch<const L: usize> {
+    devices: [DeviceFaults; L],
+}
+
+impl<const L: usize> Default for FaultLatch<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const L: usize> FaultLatch<L> {
+    /// Creates a new latch without any faults
+    pub fn new() -> Self {
+        Self {
+            devices: [DeviceFaults::default(); L],
+        }
+    }
+
+    /// Latches the given fault of the given device at the given timestamp (ms)
+    /// Returns true if the fault was newly latched, false if it was already latched before.
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn latch(&mut self, device: usize, fault: Fault, now: u32) -> bool {
+        self.devices[device].latch(fault, now)
+    }
+
+    /// Latches the given fault on all devices. Returns true if the fault was newly latched on any device.
+    pub fn latch_all(&mut self, fault: Fault, now: u32) -> bool {
+        self.devices
+            .iter_mut()
+            .fold(false, |latched, device| device.latch(fault, now) | latched)
+    }
+
+    /// Latches faults derived from the given client error
+    /// PEC errors are latched on all devices, other errors are not considered faults of the devices.
+    pub fn record_error<B: Transfer<u8>, CS: OutputPin>(&mut self, error: &Error<B, CS>, now: u32) {
+        if let Error::ChecksumMismatch = error {
+            self.latch_all(Fault::ChecksumMismatch, now);
+        }
+    }
+
+    /// Latches the OV/UV flags of all cells, as returned by [read_cell_flags](crate::monitor::LTC681X::read_cell_flags)
+    /// The flags cover all cells of the device type, including cells 13 - 18 of LTC6812/LTC6813/ADBMS1818.
+    pub fn record_cell_flags(&mut self, flags: &[CellFlags; L], now: u32) {
+        for (device, flags) in self.devices.iter_mut().zip(flags.iter()) {
+            for index in 0..MAX_CELLS {
+                let cell = Cell::from(index);
+
+                if flags.is_under_voltage(cell) {
+                    device.latch(Fault::UnderVoltage(cell), now);
+                }
+
+                if flags.is_over_voltage(cell) {
+                    device.latch(Fault::OverVoltage(cell), now);
+                }
+            }
+        }
+    }
+
+    /// Latches the thermal shutdown flag of the given status register B, as returned by
+    /// [read_status_b](crate::monitor::LTC681X::read_status_b)
+    ///
+    /// OV/UV flags are not considered, as status register B covers cells 1 - 12 only. See
+    /// [record_cell_flags](Self::record_cell_flags).
+    pub fn record_status_flags(&mut self, status: &[StatusB; L], now: u32) {
+        for (device, status) in self.devices.iter_mut().zip(status.iter()) {
+            if status.thermal_shutdown {
+                device.latch(Fault::ThermalShutdown, now);
+            }
+        }
+    }
+
+    /// Latches a self-test failure for each device which did not pass the test
+    /// One item per device in daisy chain, true if the device passed.
+    pub fn record_self_test(&mut self, passed: &[bool; L], now: u32) {
+        for (device, passed) in self.devices.iter_mut().zip(passed.iter()) {
+            if !passed {
+                device.latch(Fault::SelfTestFailed, now);
+            }
+        }
+    }
+
+    /// Returns the latched faults of the given device
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn device(&self, index: usize) -> &DeviceFaults {
+        &self.devices[index]
+    }
+
+    /// Returns the timestamp (ms) of the first occurrence of the given fault, None if not latched
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn first_occurrence(&self, device: usize, fault: Fault) -> Option<u32> {
+        self.devices[device].first_occurrence(fault)
+    }
+
+    /// Returns true if the given fault is latched on the given device
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn is_latched(&self, device: usize, fault: Fault) -> bool {
+        self.first_occurrence(device, fault).is_some()
+    }
+
+    /// Returns true if any fault is latched on the given device
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn has_faults(&self, device: usize) -> bool {
+        self.devices[device].has_faults()
+    }
+
+    /// Returns true if any fault is latched on any device
+    pub fn any_fault(&self) -> bool {
+        self.devices.iter().any(|device| device.has_faults())
+    }
+
+    /// Clears all latched faults of all devices
+    pub fn clear_faults(&mut self) {
+        *self = Self::new();
+    }
+}
@@ -0,0 +1,224 @@
+//! Conversion timing health statistics
+//!
+//! In case a clock is available, the actual time from conversion start until the ADC status (SDO line) reports
+//! completion is measured and compared against the expected duration of the conversion. Conversions which
+//! consistently run longer than expected can indicate problems of the device clock or supply.
+//!
+//! As the SDO line reports the status of the whole daisy chain, the slowest device determines the measured time.
+//!
+//! ````
+//! use ltc681x::conversion::ConversionRequest;
+//! use ltc681x::example::{ExampleClock, ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::health::ConversionHealth;
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{})
+//!     .enable_sdo_polling();
+//!
+//! let mut clock = ExampleClock::default();
+//! let mut health = ConversionHealth::new();
+//!
+//! let request = ConversionRequest::cells(CellSelection::Group1);
+//! let voltages = client
+//!     .timed_conversion(request, &mut clock, &mut ExampleDelay::default(), &mut health)
+//!     .unwrap();
+//! assert_eq!(24979, voltages[0][0].voltage);
+//!
+//! assert_eq!(1, health.count());
+//! assert_eq!(Some(200), health.mean());
+//! assert!(!health.is_running_long());
+//! ````
+use crate::conversion::ConversionRequest;
+use crate::monitor::{CommandTime, DeviceTypes, Error, LTC681XClient, SDOLinePolling, TransferHook, Voltage, LTC681X};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Monotonic clock with microsecond resolution. The counter is allowed to wrap around.
+pub trait Clock {
+    /// Returns the current timestamp in microseconds
+    fn now_us(&mut self) -> u32;
+}
+
+/// Statistics of the measured conversion times
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ConversionHealth {
+    /// Use the alternative timing (CFGAR0=1) as expected duration
+    alternative: bool,
+
+    /// Accepted deviation from the expected duration in percent
+    tolerance: u32,
+
+    /// Number of consecutive long conversions, after which the chain is flagged
+    limit: u32,
+
+    /// Number of recorded conversions
+    count: u32,
+
+    /// Minimum measured time in microseconds
+    min: u32,
+
+    /// Maximum measured time in microseconds
+    max: u32,
+
+    /// Sum of all measured times in microseconds
+    total: u64,
+
+    /// Number of conversions exceeding the expected duration
+    long_count: u32,
+
+    /// Number of consecutive conversions exceeding the expected duration
+    consecutive_long: u32,
+}
+
+impl Default for ConversionHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConversionHealth {
+    /// Creates new statistics using the regular timing, a tolerance of 10% and a limit of three consecutive long
+    /// conversions
+    pub fn new() -> Self {
+        Self {
+            alternative: false,
+            tolerance: 10,
+            limit: 3,
+            count: 0,
+            min: u32::MAX,
+            max: 0,
+            total: 0,
+            long_count: 0,
+            consecutive_long: 0,
+        }
+    }
+
+    /// Uses the alternative timing (CFGAR0=1) as expected duration
+    pub fn with_alternative_timing(mut self) -> Self {
+        self.alternative = true;
+        self
+    }
+
+    /// Sets the accepted deviation from the expected duration in percent
+    pub fn with_tolerance(mut self, percent: u32) -> Self {
+        self.tolerance = percent;
+        self
+    }
+
+    /// Sets the number of consecutive long conversions, after which the chain is considered as running long
+    pub fn with_consecutive_limit(mut self, limit: u32) -> Self {
+        self.limit = limit.max(1);
+        self
+    }
+
+    /// Records the measured time (us) of a conversion with the given expected timing
+    /// Returns true if the conversion took longer than expected (including tolerance).
+    pub fn record(&mut self, actual: u32, expected: CommandTime) -> bool {
+        let expected = if self.alternative {
+            expected.alternative
+        } else {
+            expected.regular
+        };
+
+        self.count = self.count.saturating_add(1);
+        self.min = self.min.min(actual);
+        self.max = self.max.max(actual);
+        self.total += actual as u64;
+
+        let limit = expected as u64 * (100 + self.tolerance as u64) / 100;
+        let long = actual as u64 > limit;
+
+        if long {
+            self.long_count = self.long_count.saturating_add(1);
+            self.consecutive_long = self.consecutive_long.saturating_add(1);
+        } else {
+            self.consecutive_long = 0;
+        }
+
+        long
+    }
+
+    /// Returns the number of recorded conversions
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns the minimum measured time in microseconds, None if no conversion was recorded yet
+    pub fn min(&self) -> Option<u32> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(self.min)
+    }
+
+    /// Returns the maximum measured time in microseconds, None if no conversion was recorded yet
+    pub fn max(&self) -> Option<u32> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(self.max)
+    }
+
+    /// Returns the mean measured time in microseconds, None if no conversion was recorded yet
+    pub fn mean(&self) -> Option<u32> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some((self.total / self.count as u64) as u32)
+    }
+
+    /// Returns the number of conversions exceeding the expected duration
+    pub fn long_count(&self) -> u32 {
+        self.long_count
+    }
+
+    /// Returns true if the configured number of consecutive conversions exceeded the expected duration
+    pub fn is_running_long(&self) -> bool {
+        self.consecutive_long >= self.limit
+    }
+
+    /// Clears all recorded statistics, the configuration is kept
+    pub fn reset(&mut self) {
+        *self = Self {
+            alternative: self.alternative,
+            tolerance: self.tolerance,
+            limit: self.limit,
+            ..Self::new()
+        };
+    }
+}
+
+impl<B, CS, T, const L: usize, H> LTC681X<B, CS, SDOLinePolling, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Starts the given cell conversion, polls the ADC status until done and returns the voltages of the converted
+    /// cells. The time from conversion start until completion is recorded in the given statistics.
+    ///
+    /// See [wait_conversion](LTC681X::wait_conversion) for the poll interval and the timeout. Timed out conversions
+    /// are not recorded.
+    pub fn timed_conversion<C: Clock, D: DelayUs<u32>>(
+        &mut self,
+        request: ConversionRequest<T>,
+        clock: &mut C,
+        delay: &mut D,
+        health: &mut ConversionHealth,
+    ) -> Result<Vec<Vec<Voltage<T>, 18>, L>, Error<B, CS>> {
+        let timing = self.start_conversion(request)?;
+        let start = clock.now_us();
+
+        self.wait_conversion(timing, delay)?;
+        health.record(clock.now_us().wrapping_sub(start), timing);
+
+        self.read_voltages(request.get_cells())
+    }
+}
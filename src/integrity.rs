@@ -0,0 +1,334 @@
+//! Configuration integrity checks
+//!
+//! In long-running systems, the device configuration may be corrupted silently (e.g. by a brown-out reset of a
+//! single device) or changed unexpectedly. [ConfigurationAudit] keeps a copy of the intended configuration
+//! (configuration registers A/B and PWM register) of all devices in daisy chain and computes a CRC-32 digest,
+//! which the application may log on every change.
+//!
+//! [audit_configuration](LTC681X::audit_configuration) reads back all tracked registers and reports the registers
+//! deviating from the intended values, including the digest of the read-back configuration.
+//!
+//! Some bits are read back differently than written, e.g. the GPIO bits of configuration register A return the
+//! pin state instead of the pull-down state. These bits may be excluded by a [bit mask](ConfigurationAudit::with_mask).
+//! S-control registers are not supported by the client, thus not covered.
+//!
+//! ````
+//! use ltc681x::config::Configuration;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::integrity::ConfigurationAudit;
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let config = [Configuration::default()];
+//! let mut audit: ConfigurationAudit<LTC6813, 1> = ConfigurationAudit::new();
+//! audit.set_configuration(&config);
+//! client.write_configuration(config).unwrap();
+//!
+//! // Periodic read-back
+//! let report = client.audit_configuration(&audit).unwrap();
+//! assert!(report.is_consistent());
+//! assert_eq!(audit.digest(), report.digest());
+//! ````
+use crate::config::ConfigurationRegisters;
+use crate::monitor::{DeviceTypes, Error, PollMethod, ToFullCommand, TransferHook, LTC681X};
+use crate::pwm::PwmRegisters;
+use core::fmt::{Display, Formatter};
+use core::marker::PhantomData;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Number of tracked registers per device
+const TRACKED_REGISTERS: usize = 3;
+
+/// Registers covered by the audit
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum AuditedRegister {
+    ConfigurationA = 0,
+    ConfigurationB = 1,
+    Pwm = 2,
+}
+
+impl AuditedRegister {
+    /// All audited registers in digest order
+    pub const ALL: [AuditedRegister; TRACKED_REGISTERS] = [
+        AuditedRegister::ConfigurationA,
+        AuditedRegister::ConfigurationB,
+        AuditedRegister::Pwm,
+    ];
+
+    /// Returns the register of the given device type, None if not available (e.g. PWM register of LTC6804)
+    fn device_register<T: DeviceTypes>(&self) -> Option<T::Register> {
+        match self {
+            AuditedRegister::ConfigurationA => Some(T::REG_CONF_A),
+            AuditedRegister::ConfigurationB => T::REG_CONF_B,
+            AuditedRegister::Pwm => T::REG_PWM,
+        }
+    }
+}
+
+/// Error in case the register is not available on the device type and therefore can't be audited
+#[derive(Debug, Eq, PartialEq)]
+pub struct UnavailableRegisterError {}
+
+impl Display for UnavailableRegisterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Register not available on the device type")
+    }
+}
+
+/// Intended configuration of all devices in daisy chain
+#[derive(PartialEq, Debug)]
+pub struct ConfigurationAudit<T: DeviceTypes, const L: usize> {
+    /// Intended register values, None = not tracked
+    registers: [[Option<[u8; 6]>; TRACKED_REGISTERS]; L],
+
+    /// Bits compared and included in the digest, per register
+    masks: [[u8; 6]; TRACKED_REGISTERS],
+
+    device_type: PhantomData<T>,
+}
+
+impl<T: DeviceTypes, const L: usize> Copy for ConfigurationAudit<T, L> {}
+
+impl<T: DeviceTypes, const L: usize> Clone for ConfigurationAudit<T, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: DeviceTypes, const L: usize> Default for ConfigurationAudit<T, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DeviceTypes, const L: usize> ConfigurationAudit<T, L> {
+    /// Creates a new audit without any tracked registers, all bits are compared
+    pub fn new() -> Self {
+        Self {
+            registers: [[None; TRACKED_REGISTERS]; L],
+            masks: [[0xff; 6]; TRACKED_REGISTERS],
+            device_type: PhantomData,
+        }
+    }
+
+    /// Sets the bits of the given register, which are compared and included in the digest
+    pub fn with_mask(mut self, register: AuditedRegister, mask: [u8; 6]) -> Self {
+        self.masks[register as usize] = mask;
+        self
+    }
+
+    /// Tracks the given configuration, one array item per device in daisy chain
+    /// Register B is only tracked if available on the device type.
+    pub fn set_configuration<C: ConfigurationRegisters>(&mut self, config: &[C; L]) {
+        for (registers, item) in self.registers.iter_mut().zip(config.iter()) {
+            registers[AuditedRegister::ConfigurationA as usize] = Some(item.register_a());
+
+            if T::REG_CONF_B.is_some() {
+                registers[AuditedRegister::ConfigurationB as usize] = item.register_b();
+            }
+        }
+    }
+
+    /// Tracks the given PWM duty cycles, one array item per device in daisy chain
+    /// Fails if the device type has no PWM register, as its value could never be read back.
+    pub fn set_pwm<P: PwmRegisters>(&mut self, pwm: &[P; L]) -> Result<(), UnavailableRegisterError> {
+        Self::check_available(AuditedRegister::Pwm)?;
+
+        for (registers, item) in self.registers.iter_mut().zip(pwm.iter()) {
+            registers[AuditedRegister::Pwm as usize] = Some(item.register_a());
+        }
+
+        Ok(())
+    }
+
+    /// Tracks the given raw register value of a single device
+    /// Fails if the register is not available on the device type, as its value could never be read back.
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn set_register(
+        &mut self,
+        device: usize,
+        register: AuditedRegister,
+        data: [u8; 6],
+    ) -> Result<(), UnavailableRegisterError> {
+        Self::check_available(register)?;
+        self.registers[device][register as usize] = Some(data);
+        Ok(())
+    }
+
+    /// Returns the intended value of the given register, None if not tracked
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn get_register(&self, device: usize, register: AuditedRegister) -> Option<[u8; 6]> {
+        self.registers[device][register as usize]
+    }
+
+    /// Returns the CRC-32 digest of the complete intended configuration, considering the bit masks
+    pub fn digest(&self) -> u32 {
+        !(0..L).fold(!0, |crc, device| self.update_digest(crc, device))
+    }
+
+    /// Returns the CRC-32 digest of the given device, considering the bit masks
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn device_digest(&self, device: usize) -> u32 {
+        !self.update_digest(!0, device)
+    }
+
+    /// Returns true if the given value matches the intended one, considering the bit mask
+    /// Untracked registers always match.
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn matches(&self, device: usize, register: AuditedRegister, data: &[u8; 6]) -> bool {
+        let mask = &self.masks[register as usize];
+
+        match &self.registers[device][register as usize] {
+            None => true,
+            Some(intended) => (0..6).all(|i| intended[i] & mask[i] == data[i] & mask[i]),
+        }
+    }
+
+    /// Returns an error if the given register is not available on the device type
+    fn check_available(register: AuditedRegister) -> Result<(), UnavailableRegisterError> {
+        match register.device_register::<T>() {
+            Some(_) => Ok(()),
+            None => Err(UnavailableRegisterError {}),
+        }
+    }
+
+    /// Feeds the masked registers of the given device into CRC
+    /// Each register is prefixed by a marker byte, so that untracked registers change the digest.
+    fn update_digest(&self, mut crc: u32, device: usize) -> u32 {
+        for register in AuditedRegister::ALL {
+            match &self.registers[device][register as usize] {
+                None => crc = crc32_update(crc, 0x00),
+                Some(data) => {
+                    crc = crc32_update(crc, 0x01);
+
+                    for (byte, mask) in data.iter().zip(self.masks[register as usize].iter()) {
+                        crc = crc32_update(crc, byte & mask);
+                    }
+                }
+            }
+        }
+
+        crc
+    }
+}
+
+/// Result of comparing the read-back configuration against the intended one
+#[derive(PartialEq, Debug)]
+pub struct AuditReport<T: DeviceTypes, const L: usize> {
+    /// Read-back configuration, using the masks of the intended one
+    actual: ConfigurationAudit<T, L>,
+
+    /// True if the register deviates from the intended value
+    mismatches: [[bool; TRACKED_REGISTERS]; L],
+}
+
+impl<T: DeviceTypes, const L: usize> Copy for AuditReport<T, L> {}
+
+impl<T: DeviceTypes, const L: usize> Clone for AuditReport<T, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: DeviceTypes, const L: usize> AuditReport<T, L> {
+    /// Returns true if all tracked registers of all devices match the intended values
+    pub fn is_consistent(&self) -> bool {
+        !self.mismatches.iter().flatten().any(|mismatch| *mismatch)
+    }
+
+    /// Returns true if the given register deviates from the intended value
+    ///
+    /// Panics if the device index is out of bounds.
+    pub fn is_mismatch(&self, device: usize, register: AuditedRegister) -> bool {
+        self.mismatches[device][register as usize]
+    }
+
+    /// Returns the indices of all devices with at least one deviating register
+    pub fn mismatched_devices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.mismatches
+            .iter()
+            .enumerate()
+            .filter(|(_, device)| device.iter().any(|mismatch| *mismatch))
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the read-back configuration
+    pub fn actual(&self) -> &ConfigurationAudit<T, L> {
+        &self.actual
+    }
+
+    /// Returns the CRC-32 digest of the read-back configuration, comparable to [ConfigurationAudit::digest]
+    pub fn digest(&self) -> u32 {
+        self.actual.digest()
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Reads back all registers tracked by the given audit and compares them against the intended values
+    /// Registers, which are not tracked for any device, are not read.
+    pub fn audit_configuration(
+        &mut self,
+        intended: &ConfigurationAudit<T, L>,
+    ) -> Result<AuditReport<T, L>, Error<B, CS>> {
+        let mut report = AuditReport {
+            actual: ConfigurationAudit {
+                registers: [[None; TRACKED_REGISTERS]; L],
+                ..*intended
+            },
+            mismatches: [[false; TRACKED_REGISTERS]; L],
+        };
+
+        for register in AuditedRegister::ALL {
+            let device_register = match register.device_register::<T>() {
+                Some(device_register) => device_register,
+                None => continue,
+            };
+
+            if intended.registers.iter().all(|item| item[register as usize].is_none()) {
+                continue;
+            }
+
+            let data = self.read_daisy_chain_raw(device_register.to_read_command())?;
+
+            for (device, data) in data.iter().enumerate() {
+                if intended.registers[device][register as usize].is_none() {
+                    continue;
+                }
+
+                report.actual.registers[device][register as usize] = Some(*data);
+                report.mismatches[device][register as usize] = !intended.matches(device, register, data);
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Feeds a single byte into the CRC-32 (IEEE 802.3, reflected) calculation
+fn crc32_update(mut crc: u32, byte: u8) -> u32 {
+    crc ^= byte as u32;
+
+    for _ in 0..8 {
+        crc = if crc & 1 == 1 {
+            (crc >> 1) ^ 0xEDB8_8320
+        } else {
+            crc >> 1
+        };
+    }
+
+    crc
+}
@@ -1,25 +1,66 @@
 //! # Generic client for LTC681X battery stack monitors
 //!
 //! Supports all devices of LTC681X family: [LTC6813](crate::ltc6813::LTC6813), [LTC6812](crate::ltc6812::LTC6812), [LTC6811](crate::ltc6811::LTC6811) and [LTC6810](crate::ltc6810::LTC6810).
+//! The legacy [LTC6804](crate::ltc6804::LTC6804) and the register compatible [ADBMS1818](crate::adbms1818::ADBMS1818)
+//! are supported as well.
 //!
 //! Currently the following features are implemented:
 //! * [Cell and GPIO conversion](crate::monitor#conversion)
+//! * [Fluent conversion requests (ADCV, ADCVAX, ADCVSC, ADOW)](crate::conversion)
+//! * [Conversion sequences of cells, GPIOs and status](crate::sequence)
+//! * [Write-only client for command-only operations](crate::command)
 //! * [Reading cell and GPIO voltage registers](crate::monitor#reading-registers)
+//! * [Raw register data alongside parsed values](crate::raw)
+//! * [Generic register group access](crate::register_group)
 //! * [Multiple devices in daisy chain](crate::monitor#multiple-devices-in-daisy-chain)
+//! * [Addressed (parallel) topology](crate::addressed)
+//! * [Mixed-device daisy chains](crate::mixed)
+//! * [Dual-port isoSPI ring topology](crate::ring)
 //! * [ADC status polling (SDO line method)](crate::monitor#polling)
+//! * [Wake-up of the daisy chain, including isoSPI bridges (LTC6820)](crate::monitor#waking-up)
+//! * [Configurable CS timing and inter-command dwell](crate::chip_select)
 //! * [Mapping voltages to GPIO and cell groups](crate::monitor#mapping-voltages)
+//! * [Runtime capability introspection](crate::monitor#capabilities)
+//! * [Runtime device detection via revision code](crate::detect)
 //! * [Abstracted device configuration](crate::config)
+//! * [Configuration integrity digest and read-back auditing](crate::integrity)
 //! * [Overlapping ADC measurement](crate::monitor#overlap-measurement-adol-command)
 //! * [Internal device parameters measurement](crate::monitor#internal-device-parameters-adstat-command)
+//! * [Reference power-up before GPIO and status conversions](crate::reference)
+//! * [ADC self-tests and multiplexer diagnosis (CVST, AXST, STATST, DIAGN)](crate::self_test)
+//! * [Open-wire detection of cell and GPIO inputs](crate::open_wire)
+//! * [Decoded status register groups](crate::status)
+//! * [Thermistor temperature measurement](crate::temperature)
+//! * [Temperature-compensated OV/UV thresholds](crate::threshold)
+//! * [Mapping GPIOs to sensor roles](crate::auxiliary)
+//! * [Measurement snapshots](crate::snapshot) and [CAN frame packing](crate::can)
+//! * [Conversion timing health statistics](crate::health)
+//! * [Acquisition cycle deadline and jitter monitoring](crate::deadline)
+//! * [PEC-tolerant bring-up mode](crate::bringup)
+//! * [Strict wrapper panicking on errors for bench testing](crate::strict)
+//! * [Degraded-mode operation tracking](crate::degraded)
+//! * [Latched fault flags](crate::fault)
+//! * [Voting of redundant measurement paths](crate::voting)
+//! * [Chain transaction latency estimation](crate::timing)
+//! * Bus capture for offline analysis (`std` feature)
+//! * Datasheet timing enforcement (`timing-compliance` feature)
+//! * Evaluation board profiles (DC2259A, DC2350A) (`eval-boards` feature)
+//! * Device modules selectable per device for reducing code size (`all-devices` feature, enabled by default)
+//! * [Cell PWM duty cycles](crate::pwm)
+//! * [Fail-safe cell balancing guard](crate::balancing)
+//! * [IR-drop compensation of measurements during balancing](crate::compensation)
+//! * [Commissioning routines (discharge switch self-test, wiring-order check)](crate::commissioning)
+//! * [Generic battery monitor trait](crate::battery)
 //!
 //! # Example
 //!
-//! For all details see [monitor](crate::monitor) module.
+//! For all details see [monitor] module.
 //!
 //! ````
-//!use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
-//!use ltc681x::ltc6813::{CellSelection, Channel, GPIOSelection, LTC6813};
-//!use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient, PollClient};
+//!use ltc681x::conversion::ConversionRequest;
+//!use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//!use ltc681x::ltc6813::{CellSelection, Channel, LTC6813};
+//!use ltc681x::monitor::{ADCMode, LTC681X};
 //!
 //!let spi_bus = ExampleSPIBus::default();
 //!let cs_pin = ExampleCSPin{};
@@ -29,32 +70,98 @@
 //!     .enable_sdo_polling();
 //!
 //! // Starts conversion for cell group 1
-//! client.start_conv_cells(ADCMode::Normal, CellSelection::Group1, true);
+//! let request = ConversionRequest::cells(CellSelection::Group1).mode(ADCMode::Normal).discharge_permitted(true);
+//! let mut conversion = client.begin_conversion(request).unwrap();
 //!
 //! // Poll ADC status
-//! while !client.adc_ready().unwrap() {
+//! while !conversion.is_ready().unwrap() {
 //!     // Conversion is not done yet
 //! }
 //!
 //! // Returns the value of cell group A. In case of LTC613: cell 1, 7 and 13
-//! let voltages = client.read_voltages(CellSelection::Group1).unwrap();
+//! let voltages = conversion.wait(&mut ExampleDelay::default()).unwrap();
 //! assert_eq!(Channel::Cell1, voltages[0][0].channel);
 //! assert_eq!(24979, voltages[0][0].voltage);
 //! ````
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![cfg_attr(feature = "strict", deny(warnings))]
 
+#[cfg(not(any(
+    feature = "adbms1818",
+    feature = "ltc6804",
+    feature = "ltc6810",
+    feature = "ltc6811",
+    feature = "ltc6812",
+    feature = "ltc6813"
+)))]
+compile_error!("At least one device feature is required, e.g. `ltc6813` or `all-devices`");
+
 extern crate alloc;
 
+#[cfg(feature = "adbms1818")]
+pub mod adbms1818;
+pub mod addressed;
+pub mod auxiliary;
+pub mod balancing;
+pub mod battery;
+pub mod bringup;
+pub mod can;
+#[cfg(feature = "std")]
+pub mod capture;
+pub mod chip_select;
+pub mod codec;
+pub mod command;
+pub mod commissioning;
+pub mod compensation;
+#[cfg(feature = "timing-compliance")]
+pub mod compliance;
 pub mod config;
+pub mod conversion;
+pub mod deadline;
+pub mod degraded;
+pub mod detect;
+#[cfg(feature = "eval-boards")]
+pub mod eval_board;
 #[cfg(feature = "example")]
 pub mod example;
+pub mod fault;
+pub mod health;
+pub mod integrity;
+#[cfg(feature = "ltc6804")]
+pub mod ltc6804;
+#[cfg(feature = "ltc6810")]
 pub mod ltc6810;
+#[cfg(feature = "ltc6811")]
 pub mod ltc6811;
+#[cfg(feature = "ltc6812")]
 pub mod ltc6812;
+#[cfg(feature = "ltc6813")]
 pub mod ltc6813;
+#[cfg(any(
+    feature = "adbms1818",
+    feature = "ltc6804",
+    feature = "ltc6810",
+    feature = "ltc6811",
+    feature = "ltc6812",
+    feature = "ltc6813"
+))]
+pub mod mixed;
 pub mod monitor;
+pub mod open_wire;
 pub mod pwm;
+pub mod raw;
+pub mod reference;
+pub mod register_group;
+pub mod ring;
+pub mod self_test;
+pub mod sequence;
+pub mod snapshot;
+pub mod status;
+pub mod strict;
+pub mod temperature;
+pub mod threshold;
+pub mod timing;
+pub mod voting;
 
 pub(crate) mod commands;
 pub(crate) mod pec15;
@@ -0,0 +1,472 @@
+//! Device-specific types for [LTC6804](<https://www.analog.com/en/products/ltc6804-1.html>)
+//!
+//! Legacy predecessor of the [LTC6811](crate::ltc6811) with the same cell and GPIO register layout, but a reduced
+//! command set: There's just a single configuration register group without discharge timer enable bit (DTEN) and
+//! neither a PWM nor a S-control register group. Writing PWM duty cycles returns
+//! [Error::ReadOnlyRegister](crate::monitor::Error::ReadOnlyRegister).
+//!
+//! The following commands are not supported by the device and must not be sent to an LTC6804:
+//!
+//! * ADOL (overlap measurement), so [Capabilities::overlap_measurement](crate::monitor::Capabilities) is false and
+//!   no overlap registers are read
+//! * ADCVSC (cell conversion including the sum of cells)
+//! * ADAXD and ADSTATD (GPIO and status conversions with digital redundancy)
+//! * MUTE and UNMUTE
+//!
+//! The shared configuration abstraction is used as [Configuration], restricted to the cells and GPIO pins of the
+//! device. Bits of configuration register group B are ignored.
+//!
+//! The die temperature is scaled by 7.5 mV/°C with an offset of 273 °C (instead of 7.6 mV/°C and 276 °C).
+use crate::commands::{
+    CMD_R_AUX_V_REG_A, CMD_R_AUX_V_REG_B, CMD_R_CELL_V_REG_A, CMD_R_CELL_V_REG_B, CMD_R_CELL_V_REG_C,
+    CMD_R_CELL_V_REG_D, CMD_R_CONF_A, CMD_R_STATUS_A, CMD_R_STATUS_B, CMD_W_CONF_A,
+};
+use crate::config;
+use crate::config::ConfigurationChannels;
+use crate::monitor::{
+    ADCMode, ChannelIndex, ChannelType, CommandTime, DeviceTypes, GroupedRegisterIndex, NoPolling, NoWriteCommandError,
+    RegisterAddress, RegisterLocator, ToCommandBitmap, ToCommandTiming, ToFullCommand, LTC681X,
+};
+use core::slice::Iter;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Cell selection for ADC conversion
+///
+/// See table 5 of [datasheet](<https://www.analog.com/media/en/technical-documentation/data-sheets/680412fc.pdf>)
+/// for conversion times
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum CellSelection {
+    /// All cells
+    All = 0x0,
+    /// Cells 1 and 7
+    Pair1 = 0x1,
+    /// Cells 2 and 8
+    Pair2 = 0x2,
+    /// Cells 3 and 9
+    Pair3 = 0x3,
+    /// Cells 4 and 10
+    Pair4 = 0x4,
+    /// Cells 5 and 11
+    Pair5 = 0x5,
+    /// Cells 6 and 12
+    Pair6 = 0x6,
+}
+
+/// GPIO selection for ADC conversion,
+///
+/// See table 5 of [datasheet](<https://www.analog.com/media/en/technical-documentation/data-sheets/680412fc.pdf>)
+/// for conversion times
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GPIOSelection {
+    /// GPIO 1-5 and 2nd Reference
+    All = 0x0,
+    GPIO1 = 0x1,
+    GPIO2 = 0x2,
+    GPIO3 = 0x3,
+    GPIO4 = 0x4,
+    GPIO5 = 0x5,
+    SecondReference = 0x6,
+}
+
+/// Available registers
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Register {
+    CellVoltageA,
+    CellVoltageB,
+    CellVoltageC,
+    CellVoltageD,
+    AuxiliaryA,
+    AuxiliaryB,
+    StatusA,
+    StatusB,
+    Configuration,
+}
+
+/// All conversion channels
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Channel {
+    Cell1,
+    Cell2,
+    Cell3,
+    Cell4,
+    Cell5,
+    Cell6,
+    Cell7,
+    Cell8,
+    Cell9,
+    Cell10,
+    Cell11,
+    Cell12,
+    GPIO1,
+    GPIO2,
+    GPIO3,
+    GPIO4,
+    GPIO5,
+    SecondReference,
+}
+
+/// Cell indexes of the LTC6804, s. [ConfigurationChannels]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Cell {
+    Cell1,
+    Cell2,
+    Cell3,
+    Cell4,
+    Cell5,
+    Cell6,
+    Cell7,
+    Cell8,
+    Cell9,
+    Cell10,
+    Cell11,
+    Cell12,
+}
+
+/// GPIO pins of the LTC6804, s. [ConfigurationChannels]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GPIO {
+    GPIO1,
+    GPIO2,
+    GPIO3,
+    GPIO4,
+    GPIO5,
+}
+
+impl From<Cell> for config::Cell {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Cell1 => config::Cell::Cell1,
+            Cell::Cell2 => config::Cell::Cell2,
+            Cell::Cell3 => config::Cell::Cell3,
+            Cell::Cell4 => config::Cell::Cell4,
+            Cell::Cell5 => config::Cell::Cell5,
+            Cell::Cell6 => config::Cell::Cell6,
+            Cell::Cell7 => config::Cell::Cell7,
+            Cell::Cell8 => config::Cell::Cell8,
+            Cell::Cell9 => config::Cell::Cell9,
+            Cell::Cell10 => config::Cell::Cell10,
+            Cell::Cell11 => config::Cell::Cell11,
+            Cell::Cell12 => config::Cell::Cell12,
+        }
+    }
+}
+
+impl TryFrom<config::Cell> for Cell {
+    type Error = ();
+
+    /// Converts the device independent cell, fails for cells not available on the LTC6804
+    fn try_from(cell: config::Cell) -> Result<Self, Self::Error> {
+        match cell {
+            config::Cell::Cell1 => Ok(Cell::Cell1),
+            config::Cell::Cell2 => Ok(Cell::Cell2),
+            config::Cell::Cell3 => Ok(Cell::Cell3),
+            config::Cell::Cell4 => Ok(Cell::Cell4),
+            config::Cell::Cell5 => Ok(Cell::Cell5),
+            config::Cell::Cell6 => Ok(Cell::Cell6),
+            config::Cell::Cell7 => Ok(Cell::Cell7),
+            config::Cell::Cell8 => Ok(Cell::Cell8),
+            config::Cell::Cell9 => Ok(Cell::Cell9),
+            config::Cell::Cell10 => Ok(Cell::Cell10),
+            config::Cell::Cell11 => Ok(Cell::Cell11),
+            config::Cell::Cell12 => Ok(Cell::Cell12),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<GPIO> for config::GPIO {
+    fn from(pin: GPIO) -> Self {
+        match pin {
+            GPIO::GPIO1 => config::GPIO::GPIO1,
+            GPIO::GPIO2 => config::GPIO::GPIO2,
+            GPIO::GPIO3 => config::GPIO::GPIO3,
+            GPIO::GPIO4 => config::GPIO::GPIO4,
+            GPIO::GPIO5 => config::GPIO::GPIO5,
+        }
+    }
+}
+
+impl TryFrom<config::GPIO> for GPIO {
+    type Error = ();
+
+    /// Converts the device independent GPIO, fails for pins not available on the LTC6804
+    fn try_from(pin: config::GPIO) -> Result<Self, Self::Error> {
+        match pin {
+            config::GPIO::GPIO1 => Ok(GPIO::GPIO1),
+            config::GPIO::GPIO2 => Ok(GPIO::GPIO2),
+            config::GPIO::GPIO3 => Ok(GPIO::GPIO3),
+            config::GPIO::GPIO4 => Ok(GPIO::GPIO4),
+            config::GPIO::GPIO5 => Ok(GPIO::GPIO5),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Device type of LTC6804
+pub struct LTC6804 {}
+
+/// Configuration of the LTC6804, restricted to its cells and GPIO pins
+pub type Configuration = config::SharedConfiguration<LTC6804>;
+
+impl ConfigurationChannels for LTC6804 {
+    type Cell = Cell;
+    type GPIO = GPIO;
+}
+
+impl DeviceTypes for LTC6804 {
+    type CellSelection = CellSelection;
+    type GPIOSelection = GPIOSelection;
+    type Register = Register;
+    type Channel = Channel;
+    type Configuration = Configuration;
+
+    const CELL_COUNT: usize = 12;
+    const GPIO_COUNT: usize = 5;
+    const CELL_REGISTER_GROUPS: usize = 4;
+    const AUX_REGISTER_GROUPS: usize = 2;
+    const S_CONTROL: bool = false;
+    const PWM_B: bool = false;
+    const ALL_CELLS: Self::CellSelection = CellSelection::All;
+    const CELL_GROUPS: &'static [Self::CellSelection] = &[
+        CellSelection::Pair1,
+        CellSelection::Pair2,
+        CellSelection::Pair3,
+        CellSelection::Pair4,
+        CellSelection::Pair5,
+        CellSelection::Pair6,
+    ];
+    const ALL_GPIOS: Self::GPIOSelection = GPIOSelection::All;
+
+    const OVERLAP_TEST_REG_1: Option<Self::Register> = None;
+    const OVERLAP_TEST_REG_2: Option<Self::Register> = None;
+
+    const REG_STATUS_A: Self::Register = Register::StatusA;
+    const REG_STATUS_B: Self::Register = Register::StatusB;
+
+    const REG_CONF_A: Self::Register = Register::Configuration;
+    const REG_CONF_B: Option<Self::Register> = None;
+    const REG_PWM: Option<Self::Register> = None;
+    const REG_AUX_D: Option<Self::Register> = None;
+
+    const TEMPERATURE_SCALE_UV: u32 = 7_500;
+    const TEMPERATURE_OFFSET: i32 = 273;
+}
+
+impl<B, CS, const L: usize> LTC681X<B, CS, NoPolling, LTC6804, L>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+{
+    /// Creates a client instant for LTC6804 variant
+    pub fn ltc6804(bus: B, cs: CS) -> Self {
+        LTC681X::new(bus, cs)
+    }
+}
+
+impl ToCommandBitmap for CellSelection {
+    fn to_bitmap(&self) -> u16 {
+        *self as u16
+    }
+}
+
+impl ToCommandBitmap for GPIOSelection {
+    fn to_bitmap(&self) -> u16 {
+        *self as u16
+    }
+}
+
+impl ToFullCommand for Register {
+    /// Returns the precalculated full command
+    fn to_read_command(&self) -> [u8; 4] {
+        match self {
+            Register::CellVoltageA => CMD_R_CELL_V_REG_A,
+            Register::CellVoltageB => CMD_R_CELL_V_REG_B,
+            Register::CellVoltageC => CMD_R_CELL_V_REG_C,
+            Register::CellVoltageD => CMD_R_CELL_V_REG_D,
+            Register::AuxiliaryA => CMD_R_AUX_V_REG_A,
+            Register::AuxiliaryB => CMD_R_AUX_V_REG_B,
+            Register::StatusA => CMD_R_STATUS_A,
+            Register::StatusB => CMD_R_STATUS_B,
+            Register::Configuration => CMD_R_CONF_A,
+        }
+    }
+
+    fn to_write_command(&self) -> Result<[u8; 4], NoWriteCommandError> {
+        match self {
+            Register::Configuration => Ok(CMD_W_CONF_A),
+            _ => Err(NoWriteCommandError {}),
+        }
+    }
+}
+
+impl ToCommandTiming for CellSelection {
+    fn to_conv_command_timing(&self, mode: ADCMode) -> CommandTime {
+        match self {
+            CellSelection::All => match mode {
+                ADCMode::Fast => CommandTime::new(1113, 1288),
+                ADCMode::Normal => CommandTime::new(2335, 3033),
+                ADCMode::Filtered => CommandTime::new(201_317, 4430),
+                ADCMode::Other => CommandTime::new(12_807, 7222),
+            },
+            CellSelection::Pair1
+            | CellSelection::Pair2
+            | CellSelection::Pair3
+            | CellSelection::Pair4
+            | CellSelection::Pair5
+            | CellSelection::Pair6 => match mode {
+                ADCMode::Fast => CommandTime::new(201, 230),
+                ADCMode::Normal => CommandTime::new(405, 501),
+                ADCMode::Filtered => CommandTime::new(33_568, 754),
+                ADCMode::Other => CommandTime::new(2150, 1219),
+            },
+        }
+    }
+}
+
+impl ToCommandTiming for GPIOSelection {
+    fn to_conv_command_timing(&self, mode: ADCMode) -> CommandTime {
+        match self {
+            GPIOSelection::All => match mode {
+                ADCMode::Fast => CommandTime::new(1113, 1288),
+                ADCMode::Normal => CommandTime::new(2335, 3033),
+                ADCMode::Filtered => CommandTime::new(201_317, 4430),
+                ADCMode::Other => CommandTime::new(12_807, 7_222),
+            },
+            GPIOSelection::GPIO1
+            | GPIOSelection::GPIO2
+            | GPIOSelection::GPIO3
+            | GPIOSelection::GPIO4
+            | GPIOSelection::GPIO5
+            | GPIOSelection::SecondReference => match mode {
+                ADCMode::Fast => CommandTime::new(201, 230),
+                ADCMode::Normal => CommandTime::new(405, 501),
+                ADCMode::Filtered => CommandTime::new(34_000, 751),
+                ADCMode::Other => CommandTime::new(2_200, 1_200),
+            },
+        }
+    }
+}
+
+impl GroupedRegisterIndex for Register {
+    fn to_index(&self) -> usize {
+        match self {
+            Register::CellVoltageA => 0,
+            Register::CellVoltageB => 1,
+            Register::CellVoltageC => 2,
+            Register::CellVoltageD => 3,
+            Register::AuxiliaryA => 0,
+            Register::AuxiliaryB => 1,
+            Register::StatusA => 0,
+            Register::StatusB => 1,
+            Register::Configuration => 0,
+        }
+    }
+}
+
+impl ChannelIndex for Channel {
+    fn to_cell_index(&self) -> Option<usize> {
+        match self {
+            Channel::Cell1 => Some(0),
+            Channel::Cell2 => Some(1),
+            Channel::Cell3 => Some(2),
+            Channel::Cell4 => Some(3),
+            Channel::Cell5 => Some(4),
+            Channel::Cell6 => Some(5),
+            Channel::Cell7 => Some(6),
+            Channel::Cell8 => Some(7),
+            Channel::Cell9 => Some(8),
+            Channel::Cell10 => Some(9),
+            Channel::Cell11 => Some(10),
+            Channel::Cell12 => Some(11),
+            _ => None,
+        }
+    }
+
+    fn to_gpio_index(&self) -> Option<usize> {
+        match self {
+            Channel::GPIO1 => Some(0),
+            Channel::GPIO2 => Some(1),
+            Channel::GPIO3 => Some(2),
+            Channel::GPIO4 => Some(3),
+            Channel::GPIO5 => Some(4),
+            _ => None,
+        }
+    }
+}
+
+impl From<Channel> for ChannelType {
+    fn from(channel: Channel) -> Self {
+        match channel {
+            Channel::GPIO1 => ChannelType::GPIO,
+            Channel::GPIO2 => ChannelType::GPIO,
+            Channel::GPIO3 => ChannelType::GPIO,
+            Channel::GPIO4 => ChannelType::GPIO,
+            Channel::GPIO5 => ChannelType::GPIO,
+            Channel::SecondReference => ChannelType::Reference,
+            _ => ChannelType::Cell,
+        }
+    }
+}
+
+impl RegisterAddress<LTC6804> {
+    pub const fn ltc6804(channel: Channel, register: Register, slot: usize) -> Self {
+        RegisterAddress {
+            channel,
+            register,
+            slot,
+        }
+    }
+}
+
+const CELL_REGISTER_LOCATIONS: [RegisterAddress<LTC6804>; 12] = [
+    RegisterAddress::ltc6804(Channel::Cell1, Register::CellVoltageA, 0),
+    RegisterAddress::ltc6804(Channel::Cell7, Register::CellVoltageC, 0),
+    RegisterAddress::ltc6804(Channel::Cell2, Register::CellVoltageA, 1),
+    RegisterAddress::ltc6804(Channel::Cell8, Register::CellVoltageC, 1),
+    RegisterAddress::ltc6804(Channel::Cell3, Register::CellVoltageA, 2),
+    RegisterAddress::ltc6804(Channel::Cell9, Register::CellVoltageC, 2),
+    RegisterAddress::ltc6804(Channel::Cell4, Register::CellVoltageB, 0),
+    RegisterAddress::ltc6804(Channel::Cell10, Register::CellVoltageD, 0),
+    RegisterAddress::ltc6804(Channel::Cell5, Register::CellVoltageB, 1),
+    RegisterAddress::ltc6804(Channel::Cell11, Register::CellVoltageD, 1),
+    RegisterAddress::ltc6804(Channel::Cell6, Register::CellVoltageB, 2),
+    RegisterAddress::ltc6804(Channel::Cell12, Register::CellVoltageD, 2),
+];
+
+impl RegisterLocator<LTC6804> for CellSelection {
+    fn get_locations(&self) -> Iter<'static, RegisterAddress<LTC6804>> {
+        match self {
+            CellSelection::All => CELL_REGISTER_LOCATIONS.iter(),
+            CellSelection::Pair1 => CELL_REGISTER_LOCATIONS[0..2].iter(),
+            CellSelection::Pair2 => CELL_REGISTER_LOCATIONS[2..4].iter(),
+            CellSelection::Pair3 => CELL_REGISTER_LOCATIONS[4..6].iter(),
+            CellSelection::Pair4 => CELL_REGISTER_LOCATIONS[6..8].iter(),
+            CellSelection::Pair5 => CELL_REGISTER_LOCATIONS[8..10].iter(),
+            CellSelection::Pair6 => CELL_REGISTER_LOCATIONS[10..12].iter(),
+        }
+    }
+}
+
+const GPIO_REGISTER_LOCATIONS: [RegisterAddress<LTC6804>; 6] = [
+    RegisterAddress::ltc6804(Channel::GPIO1, Register::AuxiliaryA, 0),
+    RegisterAddress::ltc6804(Channel::GPIO2, Register::AuxiliaryA, 1),
+    RegisterAddress::ltc6804(Channel::GPIO3, Register::AuxiliaryA, 2),
+    RegisterAddress::ltc6804(Channel::GPIO4, Register::AuxiliaryB, 0),
+    RegisterAddress::ltc6804(Channel::GPIO5, Register::AuxiliaryB, 1),
+    RegisterAddress::ltc6804(Channel::SecondReference, Register::AuxiliaryB, 2),
+];
+
+impl RegisterLocator<LTC6804> for GPIOSelection {
+    fn get_locations(&self) -> Iter<'static, RegisterAddress<LTC6804>> {
+        match self {
+            GPIOSelection::All => GPIO_REGISTER_LOCATIONS.iter(),
+            GPIOSelection::GPIO1 => GPIO_REGISTER_LOCATIONS[0..1].iter(),
+            GPIOSelection::GPIO2 => GPIO_REGISTER_LOCATIONS[1..2].iter(),
+            GPIOSelection::GPIO3 => GPIO_REGISTER_LOCATIONS[2..3].iter(),
+            GPIOSelection::GPIO4 => GPIO_REGISTER_LOCATIONS[3..4].iter(),
+            GPIOSelection::GPIO5 => GPIO_REGISTER_LOCATIONS[4..5].iter(),
+            GPIOSelection::SecondReference => GPIO_REGISTER_LOCATIONS[5..6].iter(),
+        }
+    }
+}
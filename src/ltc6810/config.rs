@@ -1,4 +1,63 @@
-use crate::config::{Cell, ConfigurationRegisters, DischargeTimeout, VoltageOutOfRangeError, GPIO};
+//! Configuration of the LTC6810
+//!
+//! The configuration uses device-specific [Cell] and [GPIO] enums, so selecting a channel which is not available
+//! on the LTC6810 fails at compile time.
+use crate::config;
+use crate::config::{
+    ConfigurationRegisters, DischargeControl, DischargeTimeout, FromConfigurationRegisters, VoltageOutOfRangeError,
+};
+
+/// Cell indexes of the LTC6810
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Cell {
+    Cell1,
+    Cell2,
+    Cell3,
+    Cell4,
+    Cell5,
+    Cell6,
+}
+
+/// GPIO pins of the LTC6810
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GPIO {
+    GPIO1,
+    GPIO2,
+    GPIO3,
+    GPIO4,
+}
+
+impl TryFrom<config::Cell> for Cell {
+    type Error = ();
+
+    /// Converts the device independent cell, fails for cells not available on the LTC6810
+    fn try_from(cell: config::Cell) -> Result<Self, Self::Error> {
+        match cell {
+            config::Cell::Cell1 => Ok(Cell::Cell1),
+            config::Cell::Cell2 => Ok(Cell::Cell2),
+            config::Cell::Cell3 => Ok(Cell::Cell3),
+            config::Cell::Cell4 => Ok(Cell::Cell4),
+            config::Cell::Cell5 => Ok(Cell::Cell5),
+            config::Cell::Cell6 => Ok(Cell::Cell6),
+            _ => Err(()),
+        }
+    }
+}
+
+impl TryFrom<config::GPIO> for GPIO {
+    type Error = ();
+
+    /// Converts the device independent GPIO, fails for pins not available on the LTC6810
+    fn try_from(pin: config::GPIO) -> Result<Self, Self::Error> {
+        match pin {
+            config::GPIO::GPIO1 => Ok(GPIO::GPIO1),
+            config::GPIO::GPIO2 => Ok(GPIO::GPIO2),
+            config::GPIO::GPIO3 => Ok(GPIO::GPIO3),
+            config::GPIO::GPIO4 => Ok(GPIO::GPIO4),
+            _ => Err(()),
+        }
+    }
+}
 
 /// Abstracted configuration of configuration register(s)
 #[derive(Debug, Clone)]
@@ -17,6 +76,12 @@ impl ConfigurationRegisters for Configuration {
     }
 }
 
+impl FromConfigurationRegisters for Configuration {
+    fn from_registers(register_a: [u8; 6], _register_b: Option<[u8; 6]>) -> Self {
+        Self { register_a }
+    }
+}
+
 impl Default for Configuration {
     fn default() -> Self {
         Self {
@@ -39,7 +104,7 @@ impl Configuration {
             GPIO::GPIO1 => self.register_a[0] &= 0b1111_0111,
             GPIO::GPIO2 => self.register_a[0] &= 0b1110_1111,
             GPIO::GPIO3 => self.register_a[0] &= 0b1101_1111,
-            _ => unimplemented!("unsupported GPIO"),
+            GPIO::GPIO4 => self.register_a[0] &= 0b1011_1111,
         }
     }
 
@@ -49,7 +114,7 @@ impl Configuration {
             GPIO::GPIO1 => self.register_a[0] |= 0b0000_1000,
             GPIO::GPIO2 => self.register_a[0] |= 0b0001_0000,
             GPIO::GPIO3 => self.register_a[0] |= 0b0010_0000,
-            _ => unimplemented!("unsupported GPIO"),
+            GPIO::GPIO4 => self.register_a[0] |= 0b0100_0000,
         }
     }
 
@@ -124,7 +189,23 @@ impl Configuration {
             Cell::Cell4 => self.register_a[4] |= 0b0000_1000,
             Cell::Cell5 => self.register_a[4] |= 0b0001_0000,
             Cell::Cell6 => self.register_a[4] |= 0b0010_0000,
-            _ => unimplemented!("Unsupported cell"),
+        }
+    }
+
+    /// Turn OFF all shorting switches
+    pub fn clear_discharge_cells(&mut self) {
+        self.register_a[4] &= 0b1100_0000;
+    }
+
+    /// Returns true if the shorting switch of the given cell is turned ON
+    pub fn is_cell_discharging(&self, cell: Cell) -> bool {
+        match cell {
+            Cell::Cell1 => self.register_a[4] & 0b0000_0001 != 0,
+            Cell::Cell2 => self.register_a[4] & 0b0000_0010 != 0,
+            Cell::Cell3 => self.register_a[4] & 0b0000_0100 != 0,
+            Cell::Cell4 => self.register_a[4] & 0b0000_1000 != 0,
+            Cell::Cell5 => self.register_a[4] & 0b0001_0000 != 0,
+            Cell::Cell6 => self.register_a[4] & 0b0010_0000 != 0,
         }
     }
 
@@ -157,6 +238,34 @@ impl Configuration {
     }
 }
 
+/// Cells not available on the LTC6810 are ignored
+impl DischargeControl for Configuration {
+    fn discharge_cell(&mut self, cell: config::Cell) {
+        if let Ok(cell) = Cell::try_from(cell) {
+            Configuration::discharge_cell(self, cell)
+        }
+    }
+
+    fn clear_discharge_cells(&mut self) {
+        Configuration::clear_discharge_cells(self)
+    }
+
+    fn is_cell_discharging(&self, cell: config::Cell) -> bool {
+        match Cell::try_from(cell) {
+            Ok(cell) => Configuration::is_cell_discharging(self, cell),
+            Err(_) => false,
+        }
+    }
+
+    fn enable_discharge_timer(&mut self) {
+        Configuration::enable_discharge_timer(self)
+    }
+
+    fn set_discharge_timeout(&mut self, timeout: DischargeTimeout) {
+        Configuration::set_discharge_timeout(self, timeout)
+    }
+}
+
 impl PartialEq<Self> for Configuration {
     fn eq(&self, other: &Self) -> bool {
         self.register_a == other.register_a
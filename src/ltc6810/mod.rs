@@ -86,9 +86,24 @@ impl DeviceTypes for LTC6810 {
     type GPIOSelection = GPIOSelection;
     type Register = Register;
     type Channel = Channel;
+    type Configuration = config::Configuration;
 
     const CELL_COUNT: usize = 6;
     const GPIO_COUNT: usize = 4;
+    const CELL_REGISTER_GROUPS: usize = 2;
+    const AUX_REGISTER_GROUPS: usize = 2;
+    const S_CONTROL: bool = true;
+    const PWM_B: bool = false;
+    const ALL_CELLS: Self::CellSelection = CellSelection::All;
+    const CELL_GROUPS: &'static [Self::CellSelection] = &[
+        CellSelection::Cell1,
+        CellSelection::Cell2,
+        CellSelection::Cell3,
+        CellSelection::Cell4,
+        CellSelection::Cell5,
+        CellSelection::Cell6,
+    ];
+    const ALL_GPIOS: Self::GPIOSelection = GPIOSelection::All;
 
     const OVERLAP_TEST_REG_1: Option<Self::Register> = None;
     const OVERLAP_TEST_REG_2: Option<Self::Register> = None;
@@ -99,7 +114,8 @@ impl DeviceTypes for LTC6810 {
     const REG_CONF_A: Self::Register = Register::Configuration;
     const REG_CONF_B: Option<Self::Register> = None;
 
-    const REG_PWM: Self::Register = Register::Pwm;
+    const REG_PWM: Option<Self::Register> = Some(Register::Pwm);
+    const REG_AUX_D: Option<Self::Register> = None;
 }
 
 impl<B, CS, const L: usize> LTC681X<B, CS, NoPolling, LTC6810, L>
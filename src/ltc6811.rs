@@ -4,6 +4,8 @@ use crate::commands::{
     CMD_R_CELL_V_REG_D, CMD_R_CONF_A, CMD_R_CONF_B, CMD_R_PWM, CMD_R_STATUS_A, CMD_R_STATUS_B, CMD_W_CONF_A,
     CMD_W_CONF_B, CMD_W_PWM,
 };
+use crate::config;
+use crate::config::ConfigurationChannels;
 use crate::monitor::{
     ADCMode, ChannelIndex, ChannelType, CommandTime, DeviceTypes, GroupedRegisterIndex, NoPolling, NoWriteCommandError,
     RegisterAddress, RegisterLocator, ToCommandBitmap, ToCommandTiming, ToFullCommand, LTC681X,
@@ -89,17 +91,137 @@ pub enum Channel {
     SecondReference,
 }
 
+/// Cell indexes of the LTC6811, s. [ConfigurationChannels]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Cell {
+    Cell1,
+    Cell2,
+    Cell3,
+    Cell4,
+    Cell5,
+    Cell6,
+    Cell7,
+    Cell8,
+    Cell9,
+    Cell10,
+    Cell11,
+    Cell12,
+}
+
+/// GPIO pins of the LTC6811, s. [ConfigurationChannels]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GPIO {
+    GPIO1,
+    GPIO2,
+    GPIO3,
+    GPIO4,
+    GPIO5,
+}
+
+impl From<Cell> for config::Cell {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Cell1 => config::Cell::Cell1,
+            Cell::Cell2 => config::Cell::Cell2,
+            Cell::Cell3 => config::Cell::Cell3,
+            Cell::Cell4 => config::Cell::Cell4,
+            Cell::Cell5 => config::Cell::Cell5,
+            Cell::Cell6 => config::Cell::Cell6,
+            Cell::Cell7 => config::Cell::Cell7,
+            Cell::Cell8 => config::Cell::Cell8,
+            Cell::Cell9 => config::Cell::Cell9,
+            Cell::Cell10 => config::Cell::Cell10,
+            Cell::Cell11 => config::Cell::Cell11,
+            Cell::Cell12 => config::Cell::Cell12,
+        }
+    }
+}
+
+impl TryFrom<config::Cell> for Cell {
+    type Error = ();
+
+    /// Converts the device independent cell, fails for cells not available on the LTC6811
+    fn try_from(cell: config::Cell) -> Result<Self, Self::Error> {
+        match cell {
+            config::Cell::Cell1 => Ok(Cell::Cell1),
+            config::Cell::Cell2 => Ok(Cell::Cell2),
+            config::Cell::Cell3 => Ok(Cell::Cell3),
+            config::Cell::Cell4 => Ok(Cell::Cell4),
+            config::Cell::Cell5 => Ok(Cell::Cell5),
+            config::Cell::Cell6 => Ok(Cell::Cell6),
+            config::Cell::Cell7 => Ok(Cell::Cell7),
+            config::Cell::Cell8 => Ok(Cell::Cell8),
+            config::Cell::Cell9 => Ok(Cell::Cell9),
+            config::Cell::Cell10 => Ok(Cell::Cell10),
+            config::Cell::Cell11 => Ok(Cell::Cell11),
+            config::Cell::Cell12 => Ok(Cell::Cell12),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<GPIO> for config::GPIO {
+    fn from(pin: GPIO) -> Self {
+        match pin {
+            GPIO::GPIO1 => config::GPIO::GPIO1,
+            GPIO::GPIO2 => config::GPIO::GPIO2,
+            GPIO::GPIO3 => config::GPIO::GPIO3,
+            GPIO::GPIO4 => config::GPIO::GPIO4,
+            GPIO::GPIO5 => config::GPIO::GPIO5,
+        }
+    }
+}
+
+impl TryFrom<config::GPIO> for GPIO {
+    type Error = ();
+
+    /// Converts the device independent GPIO, fails for pins not available on the LTC6811
+    fn try_from(pin: config::GPIO) -> Result<Self, Self::Error> {
+        match pin {
+            config::GPIO::GPIO1 => Ok(GPIO::GPIO1),
+            config::GPIO::GPIO2 => Ok(GPIO::GPIO2),
+            config::GPIO::GPIO3 => Ok(GPIO::GPIO3),
+            config::GPIO::GPIO4 => Ok(GPIO::GPIO4),
+            config::GPIO::GPIO5 => Ok(GPIO::GPIO5),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Device type of LTC6813
 pub struct LTC6811 {}
 
+/// Configuration of the LTC6811, restricted to its cells and GPIO pins
+pub type Configuration = config::SharedConfiguration<LTC6811>;
+
+impl ConfigurationChannels for LTC6811 {
+    type Cell = Cell;
+    type GPIO = GPIO;
+}
+
 impl DeviceTypes for LTC6811 {
     type CellSelection = CellSelection;
     type GPIOSelection = GPIOSelection;
     type Register = Register;
     type Channel = Channel;
+    type Configuration = Configuration;
 
     const CELL_COUNT: usize = 12;
     const GPIO_COUNT: usize = 5;
+    const CELL_REGISTER_GROUPS: usize = 4;
+    const AUX_REGISTER_GROUPS: usize = 2;
+    const S_CONTROL: bool = true;
+    const PWM_B: bool = false;
+    const ALL_CELLS: Self::CellSelection = CellSelection::All;
+    const CELL_GROUPS: &'static [Self::CellSelection] = &[
+        CellSelection::Pair1,
+        CellSelection::Pair2,
+        CellSelection::Pair3,
+        CellSelection::Pair4,
+        CellSelection::Pair5,
+        CellSelection::Pair6,
+    ];
+    const ALL_GPIOS: Self::GPIOSelection = GPIOSelection::All;
 
     const OVERLAP_TEST_REG_1: Option<Self::Register> = Some(Register::CellVoltageC);
     const OVERLAP_TEST_REG_2: Option<Self::Register> = None;
@@ -109,7 +231,8 @@ impl DeviceTypes for LTC6811 {
 
     const REG_CONF_A: Self::Register = Register::ConfigurationA;
     const REG_CONF_B: Option<Self::Register> = Some(Register::ConfigurationB);
-    const REG_PWM: Self::Register = Register::Pwm;
+    const REG_PWM: Option<Self::Register> = Some(Register::Pwm);
+    const REG_AUX_D: Option<Self::Register> = None;
 }
 
 impl<B, CS, const L: usize> LTC681X<B, CS, NoPolling, LTC6811, L>
@@ -4,6 +4,8 @@ use crate::commands::{
     CMD_R_CELL_V_REG_C, CMD_R_CELL_V_REG_D, CMD_R_CELL_V_REG_E, CMD_R_CONF_A, CMD_R_CONF_B, CMD_R_PWM, CMD_R_STATUS_A,
     CMD_R_STATUS_B, CMD_W_CONF_A, CMD_W_CONF_B, CMD_W_PWM,
 };
+use crate::config;
+use crate::config::ConfigurationChannels;
 use crate::monitor::{
     ADCMode, ChannelIndex, ChannelType, CommandTime, DeviceTypes, GroupedRegisterIndex, NoPolling, NoWriteCommandError,
     RegisterAddress, RegisterLocator, ToCommandBitmap, ToCommandTiming, ToFullCommand, LTC681X,
@@ -103,17 +105,107 @@ pub enum Channel {
     SecondReference,
 }
 
+/// Cell indexes of the LTC6812, s. [ConfigurationChannels]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Cell {
+    Cell1,
+    Cell2,
+    Cell3,
+    Cell4,
+    Cell5,
+    Cell6,
+    Cell7,
+    Cell8,
+    Cell9,
+    Cell10,
+    Cell11,
+    Cell12,
+    Cell13,
+    Cell14,
+    Cell15,
+}
+
+impl From<Cell> for config::Cell {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Cell1 => config::Cell::Cell1,
+            Cell::Cell2 => config::Cell::Cell2,
+            Cell::Cell3 => config::Cell::Cell3,
+            Cell::Cell4 => config::Cell::Cell4,
+            Cell::Cell5 => config::Cell::Cell5,
+            Cell::Cell6 => config::Cell::Cell6,
+            Cell::Cell7 => config::Cell::Cell7,
+            Cell::Cell8 => config::Cell::Cell8,
+            Cell::Cell9 => config::Cell::Cell9,
+            Cell::Cell10 => config::Cell::Cell10,
+            Cell::Cell11 => config::Cell::Cell11,
+            Cell::Cell12 => config::Cell::Cell12,
+            Cell::Cell13 => config::Cell::Cell13,
+            Cell::Cell14 => config::Cell::Cell14,
+            Cell::Cell15 => config::Cell::Cell15,
+        }
+    }
+}
+
+impl TryFrom<config::Cell> for Cell {
+    type Error = ();
+
+    /// Converts the device independent cell, fails for cells not available on the LTC6812
+    fn try_from(cell: config::Cell) -> Result<Self, Self::Error> {
+        match cell {
+            config::Cell::Cell1 => Ok(Cell::Cell1),
+            config::Cell::Cell2 => Ok(Cell::Cell2),
+            config::Cell::Cell3 => Ok(Cell::Cell3),
+            config::Cell::Cell4 => Ok(Cell::Cell4),
+            config::Cell::Cell5 => Ok(Cell::Cell5),
+            config::Cell::Cell6 => Ok(Cell::Cell6),
+            config::Cell::Cell7 => Ok(Cell::Cell7),
+            config::Cell::Cell8 => Ok(Cell::Cell8),
+            config::Cell::Cell9 => Ok(Cell::Cell9),
+            config::Cell::Cell10 => Ok(Cell::Cell10),
+            config::Cell::Cell11 => Ok(Cell::Cell11),
+            config::Cell::Cell12 => Ok(Cell::Cell12),
+            config::Cell::Cell13 => Ok(Cell::Cell13),
+            config::Cell::Cell14 => Ok(Cell::Cell14),
+            config::Cell::Cell15 => Ok(Cell::Cell15),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Device type of LTC6813
 pub struct LTC6812 {}
 
+/// Configuration of the LTC6812, restricted to its cells and GPIO pins
+pub type Configuration = config::SharedConfiguration<LTC6812>;
+
+impl ConfigurationChannels for LTC6812 {
+    type Cell = Cell;
+    type GPIO = config::GPIO;
+}
+
 impl DeviceTypes for LTC6812 {
     type CellSelection = CellSelection;
     type GPIOSelection = GPIOSelection;
     type Register = Register;
     type Channel = Channel;
+    type Configuration = Configuration;
 
     const CELL_COUNT: usize = 15;
     const GPIO_COUNT: usize = 9;
+    const CELL_REGISTER_GROUPS: usize = 5;
+    const AUX_REGISTER_GROUPS: usize = 4;
+    const S_CONTROL: bool = true;
+    const PWM_B: bool = true;
+    const ALL_CELLS: Self::CellSelection = CellSelection::All;
+    const CELL_GROUPS: &'static [Self::CellSelection] = &[
+        CellSelection::Group1,
+        CellSelection::Group2,
+        CellSelection::Group3,
+        CellSelection::Group4,
+        CellSelection::Group5,
+    ];
+    const ALL_GPIOS: Self::GPIOSelection = GPIOSelection::All;
 
     const OVERLAP_TEST_REG_1: Option<Self::Register> = Some(Register::CellVoltageC);
     const OVERLAP_TEST_REG_2: Option<Self::Register> = Some(Register::CellVoltageE);
@@ -123,7 +215,8 @@ impl DeviceTypes for LTC6812 {
 
     const REG_CONF_A: Self::Register = Register::ConfigurationA;
     const REG_CONF_B: Option<Self::Register> = Some(Register::ConfigurationB);
-    const REG_PWM: Self::Register = Register::Pwm;
+    const REG_PWM: Option<Self::Register> = Some(Register::Pwm);
+    const REG_AUX_D: Option<Self::Register> = Some(Register::AuxiliaryD);
 }
 
 impl<B, CS, const L: usize> LTC681X<B, CS, NoPolling, LTC6812, L>
@@ -114,9 +114,24 @@ impl DeviceTypes for LTC6813 {
     type GPIOSelection = GPIOSelection;
     type Register = Register;
     type Channel = Channel;
+    type Configuration = crate::config::Configuration;
 
     const CELL_COUNT: usize = 18;
     const GPIO_COUNT: usize = 9;
+    const CELL_REGISTER_GROUPS: usize = 6;
+    const AUX_REGISTER_GROUPS: usize = 4;
+    const S_CONTROL: bool = true;
+    const PWM_B: bool = true;
+    const ALL_CELLS: Self::CellSelection = CellSelection::All;
+    const CELL_GROUPS: &'static [Self::CellSelection] = &[
+        CellSelection::Group1,
+        CellSelection::Group2,
+        CellSelection::Group3,
+        CellSelection::Group4,
+        CellSelection::Group5,
+        CellSelection::Group6,
+    ];
+    const ALL_GPIOS: Self::GPIOSelection = GPIOSelection::All;
 
     const OVERLAP_TEST_REG_1: Option<Self::Register> = Some(Register::CellVoltageC);
     const OVERLAP_TEST_REG_2: Option<Self::Register> = Some(Register::CellVoltageE);
@@ -127,7 +142,8 @@ impl DeviceTypes for LTC6813 {
     const REG_CONF_A: Self::Register = Register::ConfigurationA;
     const REG_CONF_B: Option<Self::Register> = Some(Register::ConfigurationB);
 
-    const REG_PWM: Self::Register = Register::Pwm;
+    const REG_PWM: Option<Self::Register> = Some(Register::Pwm);
+    const REG_AUX_D: Option<Self::Register> = Some(Register::AuxiliaryD);
 }
 
 impl<B, CS, const L: usize> LTC681X<B, CS, NoPolling, LTC6813, L>
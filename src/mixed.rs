@@ -0,0 +1,326 @@
+//! Mixed-device daisy chains
+//!
+//! Devices of the LTC681X family share the same command set, so different device types may be combined in a
+//! single daisy chain, e.g. LTC6811 devices with a LTC6810 at the top of the stack. The [MixedChain] wraps the
+//! regular client and decodes the payload of each chain position based on its [DeviceKind].
+//!
+//! The device type of the wrapped client is used for encoding the commands and determines the register groups
+//! which are read. It should be the type with the most register groups in the chain. Frames of register groups
+//! not available on a device are discarded, including their PEC.
+//!
+//! Cell voltages are located at the same position on all devices. In contrast, the auxiliary layout differs:
+//! the LTC6810 stores the S0 voltage in front of GPIO 1.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6811::LTC6811;
+//! use ltc681x::mixed::{DeviceKind, MixedChain};
+//! use ltc681x::monitor::LTC681X;
+//! use ltc681x::snapshot::Snapshot;
+//!
+//! let client: LTC681X<_, _, _, LTC6811, 2> = LTC681X::ltc6811(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut chain = MixedChain::new(client, [DeviceKind::LTC6811, DeviceKind::LTC6810]);
+//!
+//! let mut snapshot: Snapshot<2> = Snapshot::new();
+//! chain.read_cell_voltages(&mut snapshot).unwrap();
+//! chain.read_gpio_voltages(&mut snapshot).unwrap();
+//!
+//! // Cell 12 of LTC6811
+//! assert_eq!(Some(8655), snapshot.device(0).cell_voltages[11]);
+//! // LTC6810 has just six cells
+//! assert_eq!(None, snapshot.device(1).cell_voltages[6]);
+//!
+//! // GPIO 1 of LTC6811 and LTC6810
+//! assert_eq!(Some(24979), snapshot.device(0).gpio_voltages[0]);
+//! assert_eq!(Some(7867), snapshot.device(1).gpio_voltages[0]);
+//! ````
+//!
+//! # Configuration
+//!
+//! As the configuration layout differs as well, each device is configured by a [DeviceConfiguration]:
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6811::LTC6811;
+//!# use ltc681x::mixed::{DeviceConfiguration, DeviceKind, MixedChain};
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient};
+//! use ltc681x::{config, ltc6810};
+//!
+//!# let client: LTC681X<_, _, _, LTC6811, 2> = LTC681X::ltc6811(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut chain = MixedChain::new(client, [DeviceKind::LTC6811, DeviceKind::LTC6810]);
+//!
+//! let mut top = ltc6810::Configuration::default();
+//! top.discharge_cell(ltc6810::config::Cell::Cell6);
+//!
+//! chain.client().write_configuration([
+//!     DeviceConfiguration::Standard(config::Configuration::default()),
+//!     DeviceConfiguration::LTC6810(top),
+//! ]).unwrap();
+//! ````
+#[cfg(feature = "adbms1818")]
+use crate::adbms1818::ADBMS1818;
+use crate::codec::DataFrame;
+use crate::config;
+use crate::config::ConfigurationRegisters;
+#[cfg(feature = "ltc6804")]
+use crate::ltc6804::LTC6804;
+#[cfg(feature = "ltc6810")]
+use crate::ltc6810;
+#[cfg(feature = "ltc6810")]
+use crate::ltc6810::LTC6810;
+#[cfg(feature = "ltc6811")]
+use crate::ltc6811::LTC6811;
+#[cfg(feature = "ltc6812")]
+use crate::ltc6812::LTC6812;
+#[cfg(feature = "ltc6813")]
+use crate::ltc6813::LTC6813;
+use crate::monitor::{
+    DeviceTypes, Error, GroupedRegisterIndex, NoHook, PollMethod, RegisterLocator, ToFullCommand, TransferHook, LTC681X,
+};
+use crate::snapshot::Snapshot;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Maximum number of register groups of a single register type
+const MAX_GROUPS: usize = 6;
+
+/// Device type of a single chain position
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DeviceKind {
+    #[cfg(feature = "adbms1818")]
+    ADBMS1818,
+    #[cfg(feature = "ltc6804")]
+    LTC6804,
+    #[cfg(feature = "ltc6810")]
+    LTC6810,
+    #[cfg(feature = "ltc6811")]
+    LTC6811,
+    #[cfg(feature = "ltc6812")]
+    LTC6812,
+    #[cfg(feature = "ltc6813")]
+    LTC6813,
+}
+
+impl DeviceKind {
+    /// Number of battery cells supported by the device
+    pub fn cell_count(&self) -> usize {
+        match self {
+            #[cfg(feature = "adbms1818")]
+            DeviceKind::ADBMS1818 => ADBMS1818::CELL_COUNT,
+            #[cfg(feature = "ltc6804")]
+            DeviceKind::LTC6804 => LTC6804::CELL_COUNT,
+            #[cfg(feature = "ltc6810")]
+            DeviceKind::LTC6810 => LTC6810::CELL_COUNT,
+            #[cfg(feature = "ltc6811")]
+            DeviceKind::LTC6811 => LTC6811::CELL_COUNT,
+            #[cfg(feature = "ltc6812")]
+            DeviceKind::LTC6812 => LTC6812::CELL_COUNT,
+            #[cfg(feature = "ltc6813")]
+            DeviceKind::LTC6813 => LTC6813::CELL_COUNT,
+        }
+    }
+
+    /// Number of GPIO channels
+    pub fn gpio_count(&self) -> usize {
+        match self {
+            #[cfg(feature = "adbms1818")]
+            DeviceKind::ADBMS1818 => ADBMS1818::GPIO_COUNT,
+            #[cfg(feature = "ltc6804")]
+            DeviceKind::LTC6804 => LTC6804::GPIO_COUNT,
+            #[cfg(feature = "ltc6810")]
+            DeviceKind::LTC6810 => LTC6810::GPIO_COUNT,
+            #[cfg(feature = "ltc6811")]
+            DeviceKind::LTC6811 => LTC6811::GPIO_COUNT,
+            #[cfg(feature = "ltc6812")]
+            DeviceKind::LTC6812 => LTC6812::GPIO_COUNT,
+            #[cfg(feature = "ltc6813")]
+            DeviceKind::LTC6813 => LTC6813::GPIO_COUNT,
+        }
+    }
+
+    /// Returns the position of the given cell (index 0 = cell 1) within the cell voltage registers
+    /// Position 0 = first slot of register group A. None if the cell is not available on the device.
+    pub fn cell_position(&self, cell: usize) -> Option<usize> {
+        if cell >= self.cell_count() {
+            return None;
+        }
+
+        Some(cell)
+    }
+
+    /// Returns the position of the given GPIO (index 0 = GPIO 1) within the auxiliary registers
+    /// Position 0 = first slot of register group A. None if the GPIO is not available on the device.
+    pub fn gpio_position(&self, gpio: usize) -> Option<usize> {
+        if gpio >= self.gpio_count() {
+            return None;
+        }
+
+        // Second reference follows GPIO 5
+        let shifted = gpio >= 5;
+
+        // S0 voltage precedes GPIO 1
+        #[cfg(feature = "ltc6810")]
+        let shifted = shifted || *self == DeviceKind::LTC6810;
+
+        if shifted {
+            Some(gpio + 1)
+        } else {
+            Some(gpio)
+        }
+    }
+}
+
+/// Configuration of a single chain position
+#[derive(Clone, Debug)]
+pub enum DeviceConfiguration {
+    /// Shared configuration of LTC6804, LTC6811, LTC6812, LTC6813 and ADBMS1818
+    Standard(config::Configuration),
+
+    /// LTC6810 specific configuration
+    #[cfg(feature = "ltc6810")]
+    LTC6810(ltc6810::Configuration),
+}
+
+impl ConfigurationRegisters for DeviceConfiguration {
+    fn register_a(&self) -> [u8; 6] {
+        match self {
+            DeviceConfiguration::Standard(config) => config.register_a(),
+            #[cfg(feature = "ltc6810")]
+            DeviceConfiguration::LTC6810(config) => config.register_a(),
+        }
+    }
+
+    fn register_b(&self) -> Option<[u8; 6]> {
+        match self {
+            DeviceConfiguration::Standard(config) => config.register_b(),
+            #[cfg(feature = "ltc6810")]
+            DeviceConfiguration::LTC6810(config) => config.register_b(),
+        }
+    }
+}
+
+/// Client for daisy chains of different device types
+pub struct MixedChain<B, CS, P, T, const L: usize, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    client: LTC681X<B, CS, P, T, L, H>,
+
+    /// Device type of each chain position
+    devices: [DeviceKind; L],
+}
+
+impl<B, CS, P, T, const L: usize, H> MixedChain<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Wraps the given client. One item per device in daisy chain.
+    pub fn new(client: LTC681X<B, CS, P, T, L, H>, devices: [DeviceKind; L]) -> Self {
+        Self { client, devices }
+    }
+
+    /// Returns the device types of all chain positions
+    pub fn devices(&self) -> &[DeviceKind; L] {
+        &self.devices
+    }
+
+    /// Returns the wrapped client, e.g. for starting conversions or writing the configuration
+    pub fn client(&mut self) -> &mut LTC681X<B, CS, P, T, L, H> {
+        &mut self.client
+    }
+
+    /// Releases the wrapped client
+    pub fn release(self) -> LTC681X<B, CS, P, T, L, H> {
+        self.client
+    }
+
+    /// Reads all cell voltage registers and stores the voltages of the available cells in the given snapshot
+    pub fn read_cell_voltages(&mut self, snapshot: &mut Snapshot<L>) -> Result<(), Error<B, CS>> {
+        let groups = self.read_groups(T::ALL_CELLS, |kind, position| {
+            (0..kind.cell_count()).any(|cell| kind.cell_position(cell) == Some(position))
+        })?;
+
+        for (index, kind) in self.devices.iter().enumerate() {
+            let device = snapshot.device_mut(index);
+
+            for (cell, slot) in device.cell_voltages.iter_mut().enumerate() {
+                if let Some(voltage) = kind.cell_position(cell).and_then(|position| word(&groups, index, position)) {
+                    *slot = Some(voltage);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads all auxiliary registers and stores the voltages of the available GPIOs in the given snapshot
+    pub fn read_gpio_voltages(&mut self, snapshot: &mut Snapshot<L>) -> Result<(), Error<B, CS>> {
+        let groups = self.read_groups(T::ALL_GPIOS, |kind, position| {
+            (0..kind.gpio_count()).any(|gpio| kind.gpio_position(gpio) == Some(position))
+        })?;
+
+        for (index, kind) in self.devices.iter().enumerate() {
+            let device = snapshot.device_mut(index);
+
+            for (gpio, slot) in device.gpio_voltages.iter_mut().enumerate() {
+                if let Some(voltage) = kind.gpio_position(gpio).and_then(|position| word(&groups, index, position)) {
+                    *slot = Some(voltage);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads all register groups of the given locator
+    ///
+    /// The PEC of a frame is only validated if the device uses any slot of the register group, as decided by the
+    /// given function (device type, position). Frames of unused groups are returned as None.
+    fn read_groups<R, F>(&mut self, locator: R, used: F) -> Result<[Option<[DataFrame; L]>; MAX_GROUPS], Error<B, CS>>
+    where
+        R: RegisterLocator<T>,
+        F: Fn(&DeviceKind, usize) -> bool,
+    {
+        let mut registers: [Option<T::Register>; MAX_GROUPS] = [None; MAX_GROUPS];
+        for address in locator.get_locations() {
+            registers[address.register.to_index()] = Some(address.register);
+        }
+
+        let mut groups = [None; MAX_GROUPS];
+
+        for (group, register) in registers.iter().enumerate() {
+            let register = match register {
+                Some(register) => register,
+                None => continue,
+            };
+
+            let frames = self.client.read_daisy_chain_frames(register.to_read_command())?;
+
+            for (kind, frame) in self.devices.iter().zip(frames.iter()) {
+                let in_use = (group * 3..group * 3 + 3).any(|position| used(kind, position));
+
+                if in_use && !frame.is_valid() {
+                    return Err(Error::ChecksumMismatch);
+                }
+            }
+
+            groups[group] = Some(frames);
+        }
+
+        Ok(groups)
+    }
+}
+
+/// Returns the register word at the given position of the given device
+fn word<const L: usize>(groups: &[Option<[DataFrame; L]>; MAX_GROUPS], device: usize, position: usize) -> Option<u16> {
+    let frames = groups.get(position / 3)?.as_ref()?;
+    Some(frames[device].words()[position % 3])
+}
@@ -1,4 +1,4 @@
-use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::blocking::spi::{Transfer, Write};
 use embedded_hal::digital::v2::OutputPin;
 use mockall::mock;
 
@@ -17,6 +17,16 @@ mock! {
     }
 }
 
+mock! {
+    pub WriteBus {}
+
+    impl Write<u8> for WriteBus {
+        type Error = BusError;
+
+        fn write(&mut self, words: &[u8]) -> Result<(), BusError>;
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum PinError {
     Error1,
@@ -74,7 +84,39 @@ impl BusMockBuilder {
         self
     }
 
+    pub fn expect_dummy_bytes(mut self, count: usize) -> Self {
+        self.bus.expect_transfer().times(1).returning(move |data| {
+            assert_eq!(count, data.len());
+            assert!(data.iter().all(|byte| *byte == 0xff));
+            Ok(&[0xff; 8])
+        });
+
+        self
+    }
+
+    pub fn expect_poll(mut self, ready: bool) -> Self {
+        self.bus.expect_transfer().times(1).returning(move |data| {
+            assert_eq!([0xff], data);
+            if ready {
+                Ok(&[0xff])
+            } else {
+                Ok(&[0x00])
+            }
+        });
+
+        self
+    }
+
     pub fn into_mock(self) -> MockSPIBus {
         self.bus
     }
 }
+
+/// Returns a CS pin expecting the given number of low/high cycles
+pub fn get_cs(call_count: usize) -> MockPin {
+    let mut cs = MockPin::new();
+    cs.expect_set_high().times(call_count).returning(move || Ok(()));
+    cs.expect_set_low().times(call_count).returning(move || Ok(()));
+
+    cs
+}
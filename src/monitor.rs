@@ -32,41 +32,50 @@
 //!
 //! ## Cell conversion
 //!
-//! A cell conversion is started using the [LTC681XClient::start_conv_cells](LTC681XClient#tymethod.start_conv_cells) method.
-//! The method takes three arguments:
+//! A cell conversion is started using the [LTC681X::begin_conversion] method, which returns a
+//! [Conversion](crate::conversion::Conversion) handle for fetching the results once the conversion is completed.
+//! The [ConversionRequest] describes the conversion:
 //! * **mode**: ADC frequency and filter settings, s. [ADCMode]
 //! * **cells**: Group of cells to be converted, e.g. [LTC6813::CellSelection](crate::ltc6813::CellSelection)
 //! * **dcp**: Allow discharging during conversion?
 //!
 //! ````
-//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::conversion::ConversionRequest;
+//!# use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
 //!# use ltc681x::ltc6813::{CellSelection, LTC6813};
-//!# use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//!# use ltc681x::monitor::{ADCMode, LTC681X};
 //!#
 //!# let mut  client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
 //!#
 //!#
 //! // Converting first cell group using normal ADC mode
-//! client.start_conv_cells(ADCMode::Normal, CellSelection::Group1, true);
+//! let request = ConversionRequest::cells(CellSelection::Group1).mode(ADCMode::Normal).discharge_permitted(true);
+//! let voltages = client.begin_conversion(request).unwrap().wait(&mut ExampleDelay::default()).unwrap();
 //!
 //! // Converting all cells using fast ADC mode
-//! client.start_conv_cells(ADCMode::Fast, CellSelection::All, true);
+//! let request = ConversionRequest::cells(CellSelection::All).mode(ADCMode::Fast).discharge_permitted(true);
+//! let voltages = client.begin_conversion(request).unwrap().wait(&mut ExampleDelay::default()).unwrap();
 //! ````
 //!
+//! The positional [LTC681XClient::start_conv_cells](LTC681XClient#tymethod.start_conv_cells) method is
+//! deprecated, as it allows reading the registers before the conversion completed.
+//!
 //! ### Conversion time
 //!
 //! Execution time of cell conversions is deterministic. The expected timing is returned as [CommandTime].
 //!
 //! ````
+//!# use ltc681x::conversion::ConversionRequest;
 //!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
 //!# use ltc681x::ltc6813::{CellSelection, LTC6813};
-//!# use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//!# use ltc681x::monitor::{ADCMode, LTC681X};
 //!#
 //!# let mut  client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
 //!#
 //!#
 //! // Converting first cell group using normal ADC mode
-//! let timing = client.start_conv_cells(ADCMode::Normal, CellSelection::Group1, true).unwrap();
+//! let conversion = client.begin_conversion(ConversionRequest::cells(CellSelection::Group1)).unwrap();
+//! let timing = conversion.timing();
 //!
 //! // 407 us in 7kHz mode (CFGAR0=0)
 //! assert_eq!(407, timing.regular);
@@ -75,27 +84,42 @@
 //! assert_eq!(523, timing.alternative);
 //! ````
 //!
+//! ### Clearing registers
+//!
+//! [LTC681XClient::clear_cells](LTC681XClient#tymethod.clear_cells), [clear_aux](LTC681XClient#tymethod.clear_aux)
+//! and [clear_status](LTC681XClient#tymethod.clear_status) set the result registers to 0xFF. Clearing the
+//! registers before starting a new measurement cycle invalidates stale data, so results read before the conversion
+//! finished are detected.
+//!
 //! ## GPIO conversion
 //!
-//! A GPIO conversion is started using the [LTC681XClient::start_conv_gpio](LTC681XClient#tymethod.start_conv_gpio) method.
-//! The method takes three arguments:
+//! A GPIO conversion is started using the [LTC681X::begin_gpio_conversion] method, which returns a
+//! [GpioConversion](crate::conversion::GpioConversion) handle. The method takes two arguments:
 //! * **mode**: ADC frequency and filter settings, s. [ADCMode]
 //! * **pins**: Group of GPIO channels to be converted, e.g. [LTC6813::GPIOSelection](crate::ltc6813::GPIOSelection)
 //!
 //! ````
-//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
 //!# use ltc681x::ltc6813::{GPIOSelection, LTC6813};
-//!# use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//!# use ltc681x::monitor::{ADCMode, LTC681X};
 //!#
 //!# let mut  client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
 //!#
 //! // Converting second GPIO group using normal ADC mode
-//! client.start_conv_gpio(ADCMode::Normal, GPIOSelection::Group2);
+//! let conversion = client.begin_gpio_conversion(ADCMode::Normal, GPIOSelection::Group2).unwrap();
+//! let voltages = conversion.wait(&mut ExampleDelay::default()).unwrap();
 //!
 //! // Converting all GPIOs using fast ADC mode
-//! client.start_conv_gpio(ADCMode::Fast, GPIOSelection::All);
+//! let conversion = client.begin_gpio_conversion(ADCMode::Fast, GPIOSelection::All).unwrap();
+//! let voltages = conversion.wait(&mut ExampleDelay::default()).unwrap();
 //! ````
 //!
+//! The unguarded [LTC681XClient::start_conv_gpio](LTC681XClient#tymethod.start_conv_gpio) method is deprecated.
+//!
+//! For safety-critical applications, [LTC681XClient::start_conv_gpio_redundant](LTC681XClient#tymethod.start_conv_gpio_redundant)
+//! starts the conversion with digital redundancy (ADAXD command). Measurements failing the redundancy check
+//! are stored as 0xFF0X.
+//!
 //! ### Conversion time
 //!
 //! Execution time of GPIO conversions is deterministic. The expected timing is returned as [CommandTime].
@@ -103,12 +127,12 @@
 //! ````
 //!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
 //!# use ltc681x::ltc6813::{GPIOSelection, LTC6813};
-//!# use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//!# use ltc681x::monitor::{ADCMode, LTC681X};
 //!#
 //!# let mut  client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
 //!#
 //! // Converting second GPIO group using normal ADC mode
-//! let timing = client.start_conv_gpio(ADCMode::Normal, GPIOSelection::Group2).unwrap();
+//! let timing = client.begin_gpio_conversion(ADCMode::Normal, GPIOSelection::Group2).unwrap().timing();
 //!
 //! // 788 us in 7kHz mode (CFGAR0=0)
 //! assert_eq!(788, timing.regular);
@@ -142,6 +166,27 @@
 //! }
 //! ````
 //!
+//! ### Waiting for conversions
+//!
+//! [wait_conversion](LTC681X::wait_conversion) waits for the completion of a conversion independent of the poll
+//! method. Without polling, the expected conversion time is awaited. With SDO line polling, the ADC status is polled
+//! until the conversion is completed or the timeout is exceeded ([Error::Timeout]).
+//!
+//! ````
+//!# use ltc681x::conversion::ConversionRequest;
+//!# use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//!# use ltc681x::ltc6813::{CellSelection, LTC6813};
+//!# use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(spi_bus, cs_pin)
+//!     .enable_sdo_polling();
+//!
+//! let timing = client.start_conversion(ConversionRequest::cells(CellSelection::All)).unwrap();
+//! client.wait_conversion(timing, &mut ExampleDelay::default()).unwrap();
+//! ````
+//!
 //! ## Reading registers
 //!
 //! The content of registers may be directly read. The client returns an array containing three u16,
@@ -192,6 +237,172 @@
 //! assert_eq!(8878, cell_voltages[1][2]);
 //! ````
 //!
+//! The daisy chain length is validated at compile time. It must be at least one and must not exceed
+//! [MAX_CHAIN_LENGTH]:
+//!
+//! ````compile_fail
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::LTC6813;
+//!# use ltc681x::monitor::LTC681X;
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! // Fails to compile
+//! let client: LTC681X<_, _, _, LTC6813, 0> = LTC681X::ltc6813(spi_bus, cs_pin);
+//! ````
+//!
+//! ### Long daisy chains
+//!
+//! Very long chains may need additional clock cycles for flushing data through every isoSPI hop.
+//! The number of trailing dummy bytes of reads and the pause between two device frames are configurable:
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//!# use ltc681x::ltc6813::{LTC6813, Register};
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! let mut client: LTC681X<_, _, _, LTC6813, 12, _> = LTC681X::ltc6813(spi_bus, cs_pin)
+//!     .with_trailing_dummy_bytes(4)
+//!     .with_frame_gap(10, ExampleDelay::default());
+//!
+//! let cell_voltages = client.read_register(Register::CellVoltageA).unwrap();
+//! ````
+//!
+//! The trailing dummy bytes are not sent after writes, as the daisy chain acts as shift register: extra bytes
+//! would shift the written frames and corrupt the data of every device. For the same reason, the gap between two
+//! frames is a pause of the clock (see [FrameGapDelay]) instead of dummy bytes, which would shift out and discard
+//! the data of the next device.
+//!
+//! ### Write coalescing
+//!
+//! Control loops often rewrite the configuration or PWM registers every cycle, even when nothing changed.
+//! If enabled, register writes are skipped when the data is byte-identical to the last value sent to the same
+//! register. [force_write_register](LTC681X::force_write_register) writes regardless of the cache.
+//!
+//! The skip-duplicate cache holds the sent data, which is not verified by reading back. It is cleared
+//! automatically on any PEC error and in case a write fails. As the devices lose their register contents in sleep
+//! state, the cache must be [invalidated](LTC681X::invalidate_write_cache) after the devices may have been reset.
+//!
+//! ````
+//!# use ltc681x::config::Configuration;
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::LTC6813;
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(spi_bus, cs_pin)
+//!     .with_write_coalescing();
+//!
+//! client.write_configuration([Configuration::default()]).unwrap();
+//!
+//! // Skipped, as nothing changed
+//! client.write_configuration([Configuration::default()]).unwrap();
+//!
+//! // Written again after the devices woke up from sleep state
+//! client.invalidate_write_cache();
+//! client.write_configuration([Configuration::default()]).unwrap();
+//! ````
+//!
+//! ### Double-read validation
+//!
+//! For reducing the chance of acting on a single corrupted frame with a coincidentally valid PEC, safety-relevant
+//! registers (configuration and status registers) may be read twice back-to-back. The data is only accepted if
+//! both reads are PEC-valid and identical, otherwise [Error::InconsistentRead] is returned.
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::{LTC6813, Register};
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(spi_bus, cs_pin)
+//!     .with_double_read();
+//!
+//! // Read twice
+//! let configuration = client.read_register(Register::ConfigurationA).unwrap();
+//!
+//! // Cell voltage registers are read once
+//! let cell_voltages = client.read_register(Register::CellVoltageA).unwrap();
+//! ````
+//!
+//! ## Transfer hooks
+//!
+//! Board-specific actions (e.g. pulsing a wake line, toggling an isolation enable or time-stamping)
+//! may be injected before and after each SPI transaction by a [TransferHook]:
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::{LTC6813, Register};
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient, Operation, TransferHook};
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! #[derive(Default)]
+//! struct TransferCounter {
+//!     reads: usize,
+//! }
+//!
+//! impl TransferHook for TransferCounter {
+//!     fn pre_transfer(&mut self, operation: Operation) {
+//!         // e.g. pulse the wake line
+//!         if operation == Operation::Read {
+//!             self.reads += 1;
+//!         }
+//!     }
+//! }
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1, _> = LTC681X::ltc6813(spi_bus, cs_pin)
+//!     .with_transfer_hook(TransferCounter::default());
+//!
+//! let cell_voltages = client.read_register(Register::CellVoltageA).unwrap();
+//! assert_eq!(1, client.transfer_hook().reads);
+//! ````
+//!
+//! ## Waking up
+//!
+//! The devices enter sleep state (core) and idle state (isoSPI port) after periods without activity.
+//! [wake_up](LTC681X::wake_up) and [wake_up_idle](LTC681X::wake_up_idle) send one wake pulse per device in daisy
+//! chain. Chains behind an isoSPI bridge (e.g. LTC6820) need additional wake pulses for the bridge itself, which
+//! are configured by the [WakeProfile]:
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//!# use ltc681x::ltc6813::{LTC6813, Register};
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient, WakeProfile};
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! let mut client: LTC681X<_, _, _, LTC6813, 2> = LTC681X::ltc6813(spi_bus, cs_pin)
+//!     .with_wake_profile(WakeProfile::ltc6820());
+//!
+//! // Bridge is woken up before the devices
+//! client.wake_up(&mut ExampleDelay::default()).unwrap();
+//! ````
+//!
+//! ## Capabilities
+//!
+//! Generic application layers and host tools may query the properties of the device type at runtime:
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::LTC6813;
+//!# use ltc681x::monitor::LTC681X;
+//!#
+//!# let spi_bus = ExampleSPIBus::default();
+//!# let cs_pin = ExampleCSPin{};
+//! let client: LTC681X<_, _, _, LTC6813, 3> = LTC681X::ltc6813(spi_bus, cs_pin);
+//!
+//! let capabilities = client.capabilities();
+//! assert_eq!(18, capabilities.cell_count);
+//! assert_eq!(6, capabilities.cell_register_groups);
+//! assert!(capabilities.pwm_b);
+//! assert_eq!(3, capabilities.chain_length);
+//! ````
+//!
 //! # Mapping voltages
 //!
 //! Instead of manually reading voltage registers, the client offers a convenient method for mapping
@@ -230,6 +441,39 @@
 //! assert_eq!(7869, voltages[0][1].voltage);
 //! ````
 //!
+//! [LTC681XClient::read_register_voltages](LTC681XClient#method.read_register_voltages) maps the voltages of a
+//! single cell or auxiliary register group instead, e.g. when polling registers in a fixed sequence.
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::{Channel, LTC6813, Register};
+//!# use ltc681x::monitor::{LTC681X, LTC681XClient};
+//!#
+//!# let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let voltages = client.read_register_voltages(Register::AuxiliaryB).unwrap();
+//!
+//! assert_eq!(Channel::GPIO4, voltages[0][0].channel);
+//! assert_eq!(Channel::GPIO5, voltages[0][1].channel);
+//! assert_eq!(Channel::SecondReference, voltages[0][2].channel);
+//! assert_eq!(30000, voltages[0][2].voltage);
+//! ````
+//!
+//! [read_all_cell_voltages](LTC681X::read_all_cell_voltages) reads all cell voltage registers of the device type
+//! and returns the voltages in cell order, so the register-to-cell mapping is not required.
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::LTC6813;
+//!# use ltc681x::monitor::LTC681X;
+//!#
+//!# let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let voltages = client.read_all_cell_voltages().unwrap();
+//! // Cell 1
+//! assert_eq!(Some(24979), voltages[0][0]);
+//! // Cell 2
+//! assert_eq!(Some(7867), voltages[0][1]);
+//! ````
+//!
 //! # Self-tests
 //!
 //! The LTC681X family supports a number of verification and fault-tests.
@@ -259,22 +503,23 @@
 //! assert_eq!(8591, data[0][3]);
 //! ````
 //!
+//! Both results may be compared against a tolerance using [read_overlap_measurement](LTC681X::read_overlap_measurement).
+//!
 //! ## Internal device parameters (ADSTAT command)
 //!
 //! Measuring internal device parameters and reading the results.
 //!
 //! The expected execution time is returned as [CommandTime], see [command timing of cell conversion](#conversion-time) as example.
 //! ````
-//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
 //!# use ltc681x::ltc6813::{CellSelection, LTC6813};
-//!# use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient, StatusGroup};
+//!# use ltc681x::monitor::{ADCMode, LTC681X, StatusGroup};
 //!#
 //!# let mut  client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
 //!#
 //!#
-//! client.measure_internal_parameters(ADCMode::Normal, StatusGroup::All);
-//! // [...] waiting until conversion finished
-//! let data = client.read_internal_device_parameters().unwrap();
+//! let conversion = client.begin_status_conversion(ADCMode::Normal, StatusGroup::All).unwrap();
+//! let data = conversion.wait(&mut ExampleDelay::default()).unwrap();
 //!
 //! // Sum of all voltages in uV => 75.318 V
 //! assert_eq!(75_318_000, data[0].total_voltage);
@@ -285,22 +530,44 @@
 //! // Digital power supply voltage in uV => 5.12 V
 //! assert_eq!(5_120_000, data[0].digital_power);
 //! ````
-use crate::config::ConfigurationRegisters;
+//!
+//! [LTC681XClient::measure_internal_parameters_redundant](LTC681XClient#tymethod.measure_internal_parameters_redundant)
+//! measures the internal parameters with digital redundancy (ADSTATD command) for applications requiring
+//! diagnostic coverage.
+use crate::addressed;
+use crate::chip_select::{CSTiming, TimedChipSelect};
+use crate::codec;
+use crate::codec::{DataFrame, DataFrameRef};
+use crate::config::{ConfigurationRegisters, DischargeControl, FromConfigurationRegisters};
+use crate::conversion::{ConversionRequest, POLL_INTERVAL};
 use crate::monitor::Error::TransferError;
-use crate::pec15::PEC15;
 use crate::pwm::PwmRegisters;
+use crate::snapshot::MAX_CELLS;
+use crate::timing::{T_BRIDGE_READY_US, T_READY_US, T_WAKE_US};
 use core::fmt::{Debug, Display, Formatter};
 use core::marker::PhantomData;
 use core::slice::Iter;
+use embedded_hal::blocking::delay::DelayUs;
 use embedded_hal::blocking::spi::Transfer;
 use embedded_hal::digital::v2::OutputPin;
 use fixed::types::I16F16;
 use heapless::Vec;
 
+/// Maximum number of devices in daisy chain
+///
+/// isoSPI itself has no hard limit, but every hop adds wake-up and propagation time. Beyond this length, command
+/// and polling timings are no longer practical.
+pub const MAX_CHAIN_LENGTH: usize = 64;
+
 /// Poll Strategy
 pub trait PollMethod<CS: OutputPin> {
     /// Handles the CS pin state after command has been sent
     fn end_command(&self, cs: &mut CS) -> Result<(), CS::Error>;
+
+    /// Returns true if the end of conversions is polled on the SDO line
+    fn is_polling(&self) -> bool {
+        false
+    }
 }
 
 /// Leaves CS Low and waits until SDO goes high
@@ -310,6 +577,10 @@ impl<CS: OutputPin> PollMethod<CS> for SDOLinePolling {
     fn end_command(&self, _cs: &mut CS) -> Result<(), CS::Error> {
         Ok(())
     }
+
+    fn is_polling(&self) -> bool {
+        true
+    }
 }
 
 /// No ADC polling is used
@@ -321,6 +592,103 @@ impl<CS: OutputPin> PollMethod<CS> for NoPolling {
     }
 }
 
+/// Kind of SPI transaction, passed to transfer hooks
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Operation {
+    /// Command without data (e.g. conversion start)
+    Command,
+    /// Register read
+    Read,
+    /// Register write
+    Write,
+    /// ADC status poll
+    Poll,
+    /// Wake-up pulse (CS toggle with a single dummy byte)
+    Wake,
+}
+
+/// Hook invoked before and after each SPI transaction
+///
+/// All methods default to no-ops. Implementors may carry state, e.g. counters or a wake line pin.
+/// Each pre-transfer call is followed by exactly one post-transfer call, also in case the transaction failed.
+pub trait TransferHook {
+    /// Invoked immediately before each SPI transaction (before CS is pulled low)
+    fn pre_transfer(&mut self, _operation: Operation) {}
+
+    /// Invoked immediately after each SPI transaction (after CS is released)
+    ///
+    /// Also invoked if the transaction failed. CS is released beforehand, unless pulling CS low failed.
+    ///
+    /// In case of SDO polling, conversion commands leave CS low. The hook is invoked after the command was sent.
+    fn post_transfer(&mut self, _operation: Operation) {}
+
+    /// Invoked between the frames of two devices of a daisy chain read, while CS is low
+    ///
+    /// No bytes may be transferred, as each clocked byte shifts out the data of the next device. The gap is
+    /// therefore a pause of the clock, e.g. see [FrameGapDelay].
+    fn frame_gap(&mut self, _operation: Operation) {}
+}
+
+/// Transfer hook doing nothing (default)
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NoHook {}
+
+impl TransferHook for NoHook {}
+
+/// Transfer hook pausing between the frames of two devices of a daisy chain read, see [LTC681X::with_frame_gap]
+pub struct FrameGapDelay<D: DelayUs<u32>> {
+    delay: D,
+
+    /// Pause in microseconds
+    gap_us: u32,
+}
+
+impl<D: DelayUs<u32>> FrameGapDelay<D> {
+    /// Creates a new hook pausing the given time (in microseconds) between two frames
+    pub fn new(delay: D, gap_us: u32) -> Self {
+        Self { delay, gap_us }
+    }
+
+    /// Returns the pause between two frames in microseconds
+    pub fn gap(&self) -> u32 {
+        self.gap_us
+    }
+}
+
+impl<D: DelayUs<u32>> TransferHook for FrameGapDelay<D> {
+    fn frame_gap(&mut self, _operation: Operation) {
+        self.delay.delay_us(self.gap_us);
+    }
+}
+
+/// Wake-up sequence of the transport between MCU and daisy chain
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct WakeProfile {
+    /// Number of wake pulses sent before waking up the devices, e.g. for waking up an isoSPI bridge
+    pub bridge_pulses: usize,
+
+    /// Delay after each bridge wake pulse in microseconds
+    pub bridge_ready_us: u32,
+}
+
+impl WakeProfile {
+    /// Direct connection without bridge (default)
+    pub const fn direct() -> Self {
+        Self {
+            bridge_pulses: 0,
+            bridge_ready_us: 0,
+        }
+    }
+
+    /// Connection via LTC6820 isoSPI bridge: single wake pulse followed by [T_BRIDGE_READY_US]
+    pub const fn ltc6820() -> Self {
+        Self {
+            bridge_pulses: 1,
+            bridge_ready_us: T_BRIDGE_READY_US,
+        }
+    }
+}
+
 /// ADC frequency and filtering settings
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum ADCMode {
@@ -410,15 +778,15 @@ impl<T: DeviceTypes> Copy for Voltage<T> {}
 
 impl<T: DeviceTypes> Clone for Voltage<T> {
     fn clone(&self) -> Self {
-        Self {
-            channel: self.channel,
-            voltage: self.voltage,
-        }
+        *self
     }
 }
 
 /// Error enum of LTC681X
+///
+/// New variants may be added in future versions. For stable matching, use the [kind](Error::kind) accessor.
 #[derive(PartialEq)]
+#[non_exhaustive]
 pub enum Error<B: Transfer<u8>, CS: OutputPin> {
     /// SPI transfer error
     TransferError(B::Error),
@@ -431,6 +799,35 @@ pub enum Error<B: Transfer<u8>, CS: OutputPin> {
 
     /// Writing to to the given register is not supported
     ReadOnlyRegister,
+
+    /// Both reads of a double-read register returned different data
+    InconsistentRead,
+
+    /// Conversion was not completed within the timeout
+    Timeout,
+}
+
+/// Stable classification of [Error] variants
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// SPI transfer error
+    Transfer,
+
+    /// Error while changing state of CS pin
+    CSPin,
+
+    /// PEC checksum of returned data was invalid
+    ChecksumMismatch,
+
+    /// Writing to to the given register is not supported
+    ReadOnlyRegister,
+
+    /// Both reads of a double-read register returned different data
+    InconsistentRead,
+
+    /// Conversion was not completed within the timeout
+    Timeout,
 }
 
 /// Trait for casting command options to command bitmaps
@@ -520,6 +917,40 @@ pub struct InternalDeviceParameters {
     pub temperature: I16F16,
 }
 
+/// Properties of a device type and daisy chain, see [LTC681X::capabilities]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Capabilities {
+    /// Number of battery cells per device
+    pub cell_count: usize,
+
+    /// Number of GPIO channels per device
+    pub gpio_count: usize,
+
+    /// Number of cell voltage register groups
+    pub cell_register_groups: usize,
+
+    /// Number of auxiliary register groups
+    pub aux_register_groups: usize,
+
+    /// Number of configuration register groups
+    pub configuration_register_groups: usize,
+
+    /// True if the overlap measurement (ADOL command) is supported
+    pub overlap_measurement: bool,
+
+    /// True if the device has a PWM register group
+    pub pwm: bool,
+
+    /// True if the device has a S-control register group (SCTRL)
+    pub s_control: bool,
+
+    /// True if the device has a PWM/S-control register group B (PSB) for cells 13 - 18
+    pub pwm_b: bool,
+
+    /// Configured number of devices in daisy chain
+    pub chain_length: usize,
+}
+
 /// Device specific types
 pub trait DeviceTypes: Send + Sync + Sized + 'static {
     /// Argument for the identification of cell groups, which depends on the exact device type.
@@ -534,12 +965,41 @@ pub trait DeviceTypes: Send + Sync + Sized + 'static {
     /// Available cells and GPIOs
     type Channel: ChannelIndex + Into<ChannelType> + Copy + Clone + Send + Sync;
 
+    /// Configuration abstraction matching the configuration register layout of the device
+    type Configuration: ConfigurationRegisters + FromConfigurationRegisters + DischargeControl + Default + Clone;
+
     /// Number of battery cells supported by the device
     const CELL_COUNT: usize;
 
     /// Number of GPIO channels
     const GPIO_COUNT: usize;
 
+    /// Number of cell voltage register groups
+    /// Default: Three cells per register group
+    const CELL_REGISTER_GROUPS: usize = Self::CELL_COUNT.div_ceil(3);
+
+    /// Number of auxiliary register groups
+    /// Default: Three channels (GPIOs and second reference) per register group
+    const AUX_REGISTER_GROUPS: usize = (Self::GPIO_COUNT + 1).div_ceil(3);
+
+    /// True if the device has a S-control register group (SCTRL)
+    /// Default: false
+    const S_CONTROL: bool = false;
+
+    /// True if the device has a PWM/S-control register group B (PSB)
+    /// Default: false
+    const PWM_B: bool = false;
+
+    /// Cell selection converting all cells
+    const ALL_CELLS: Self::CellSelection;
+
+    /// Cell selections of the individual cell groups, excluding [ALL_CELLS](DeviceTypes::ALL_CELLS)
+    /// Default: No individual groups, so [select_cells](crate::conversion::select_cells) always selects all cells
+    const CELL_GROUPS: &'static [Self::CellSelection] = &[];
+
+    /// GPIO selection converting all GPIOs and the second reference
+    const ALL_GPIOS: Self::GPIOSelection;
+
     /// Defines the first register storing the results of overlap measurement.
     /// None in case overlap test is not supported.
     const OVERLAP_TEST_REG_1: Option<Self::Register>;
@@ -560,8 +1020,21 @@ pub trait DeviceTypes: Send + Sync + Sized + 'static {
     /// Configuration register B, None in case device type has no second configuration register
     const REG_CONF_B: Option<Self::Register>;
 
-    /// Cell pwm discharge register
-    const REG_PWM: Self::Register;
+    /// Cell pwm discharge register, None in case device type has no PWM register
+    /// Default: None
+    const REG_PWM: Option<Self::Register> = None;
+
+    /// Auxiliary register D storing the OV/UV flags of cells 13 - 18, None in case device type has no such register
+    /// Default: None
+    const REG_AUX_D: Option<Self::Register> = None;
+
+    /// Scale of the die temperature (ITMP) in uV per °C
+    /// Default: 7.6 mV/°C (LTC6810, LTC6811, LTC6812, LTC6813, ADBMS1818)
+    const TEMPERATURE_SCALE_UV: u32 = DEFAULT_TEMPERATURE_SCALE_UV;
+
+    /// Offset of the die temperature (ITMP) in °C, which is subtracted from the scaled value
+    /// Default: 276 °C (LTC6810, LTC6811, LTC6812, LTC6813, ADBMS1818)
+    const TEMPERATURE_OFFSET: i32 = DEFAULT_TEMPERATURE_OFFSET;
 }
 
 /// Public LTC681X client interface
@@ -577,6 +1050,13 @@ pub trait LTC681XClient<T: DeviceTypes, const L: usize> {
     /// * `mode`: ADC mode
     /// * `cells`: Measures the given cell group
     /// * `dcp`: True if discharge is permitted during conversion
+    ///
+    /// The registers may be read before the conversion completed. [LTC681X::begin_conversion] returns a
+    /// [Conversion](crate::conversion::Conversion) handle preventing this.
+    #[deprecated(
+        since = "0.5.0",
+        note = "use `LTC681X::begin_conversion`, which guards the results until completion"
+    )]
     fn start_conv_cells(
         &mut self,
         mode: ADCMode,
@@ -584,14 +1064,34 @@ pub trait LTC681XClient<T: DeviceTypes, const L: usize> {
         dcp: bool,
     ) -> Result<CommandTime, Self::Error>;
 
+    /// Starts the cell conversion described by the given request (ADCV, ADCVAX, ADCVSC or ADOW command)
+    /// See [ConversionRequest] for details.
+    fn start_conversion(&mut self, request: ConversionRequest<T>) -> Result<CommandTime, Self::Error>;
+
     /// Starts GPIOs ADC conversion
     ///
     /// # Arguments
     ///
     /// * `mode`: ADC mode
-    /// * `channels`: Measures t:he given GPIO group
+    /// * `channels`: Measures the given GPIO group
+    ///
+    /// The registers may be read before the conversion completed. [LTC681X::begin_gpio_conversion] returns a
+    /// [GpioConversion](crate::conversion::GpioConversion) handle preventing this.
+    #[deprecated(
+        since = "0.5.0",
+        note = "use `LTC681X::begin_gpio_conversion`, which guards the results until completion"
+    )]
     fn start_conv_gpio(&mut self, mode: ADCMode, pins: T::GPIOSelection) -> Result<CommandTime, Self::Error>;
 
+    /// Starts GPIOs ADC conversion with digital redundancy (ADAXD command)
+    /// In case the redundancy check of a measurement fails, the device stores 0xFF0X as result.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode`: ADC mode
+    /// * `channels`: Measures the given GPIO group
+    fn start_conv_gpio_redundant(&mut self, mode: ADCMode, pins: T::GPIOSelection) -> Result<CommandTime, Self::Error>;
+
     /// Start the  Overlap Measurements (ADOL command)
     /// Note: This command is not available on LTC6810, as this device only includes one ADC
     ///
@@ -607,20 +1107,112 @@ pub trait LTC681XClient<T: DeviceTypes, const L: usize> {
     ///
     /// * `mode`: ADC mode
     /// * `group`: Selection of status parameter to measure
+    ///
+    /// The registers may be read before the measurement completed. [LTC681X::begin_status_conversion] returns a
+    /// [StatusConversion](crate::conversion::StatusConversion) handle preventing this.
+    #[deprecated(
+        since = "0.5.0",
+        note = "use `LTC681X::begin_status_conversion`, which guards the results until completion"
+    )]
     fn measure_internal_parameters(&mut self, mode: ADCMode, group: StatusGroup) -> Result<CommandTime, Self::Error>;
 
+    /// Starts measuring internal device parameters with digital redundancy (ADSTATD command)
+    /// In case the redundancy check of a measurement fails, the device stores 0xFF0X as result.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode`: ADC mode
+    /// * `group`: Selection of status parameter to measure
+    fn measure_internal_parameters_redundant(
+        &mut self,
+        mode: ADCMode,
+        group: StatusGroup,
+    ) -> Result<CommandTime, Self::Error>;
+
+    /// Clears all cell voltage registers (CLRCELL command)
+    /// The registers are set to 0xFF, which allows detecting reads before the conversion finished.
+    fn clear_cells(&mut self) -> Result<(), Self::Error>;
+
+    /// Clears all auxiliary registers (CLRAUX command)
+    /// The registers are set to 0xFF, which allows detecting reads before the conversion finished.
+    fn clear_aux(&mut self) -> Result<(), Self::Error>;
+
+    /// Clears the status registers (CLRSTAT command)
+    /// The registers are set to 0xFF, except for the flags and revision code of status register B.
+    fn clear_status(&mut self) -> Result<(), Self::Error>;
+
     /// Reads the values of the given register
     /// Returns one array for each device in daisy chain
     fn read_register(&mut self, register: T::Register) -> Result<[[u16; 3]; L], Self::Error>;
 
     /// Writes the values of the given register
-    /// One 3-bytes array per device in daisy chain
+    /// One 3-bytes array per device in daisy chain, index 0 = first device in daisy chain (as for reads)
     fn write_register(&mut self, register: T::Register, data: [[u8; 6]; L]) -> Result<(), Self::Error>;
 
     /// Writes the configuration, one array item per device in daisy chain
     fn write_configuration<C: ConfigurationRegisters>(&mut self, config: [C; L]) -> Result<(), Self::Error>;
 
+    /// Writes the configuration returned by the given closure, which is called once per device index in daisy
+    /// chain (starting with 0 for the first device)
+    fn write_configuration_with<C, F>(&mut self, f: F) -> Result<(), Self::Error>
+    where
+        C: ConfigurationRegisters,
+        F: FnMut(usize) -> C,
+    {
+        self.write_configuration(core::array::from_fn(f))
+    }
+
+    /// Reads configuration register A and (if available) B and parses the configuration of all devices
+    /// Returns one item per device in daisy chain
+    ///
+    /// Note: Some configuration bits read back the device state instead of the written value (e.g. GPIO pin
+    /// levels).
+    fn read_configuration<C: FromConfigurationRegisters>(&mut self) -> Result<[C; L], Self::Error> {
+        let (current_a, current_b) = read_configuration_words(self)?;
+
+        Ok(core::array::from_fn(|index| {
+            C::from_registers(current_a[index], current_b.map(|data| data[index]))
+        }))
+    }
+
+    /// Reads the current configuration, applies the given closure and writes back the changed register(s)
+    ///
+    /// Only register groups which differ from the read values are written. Returns true if at least one
+    /// register has been written.
+    ///
+    /// Note: Some configuration bits read back the device state instead of the written value (e.g. GPIO pin
+    /// levels). These are written back as read.
+    fn update_configuration<C, F>(&mut self, f: F) -> Result<bool, Self::Error>
+    where
+        C: ConfigurationRegisters + FromConfigurationRegisters,
+        F: FnOnce(&mut [C; L]),
+    {
+        let (current_a, current_b) = read_configuration_words(self)?;
+
+        let mut config: [C; L] =
+            core::array::from_fn(|index| C::from_registers(current_a[index], current_b.map(|data| data[index])));
+        f(&mut config);
+
+        let (register_a, register_b) = encode_configuration(&config);
+        let mut written = false;
+
+        if register_a != current_a {
+            self.write_register(T::REG_CONF_A, register_a)?;
+            written = true;
+        }
+
+        if let (Some(register), Some(current_b)) = (T::REG_CONF_B, current_b) {
+            if register_b != current_b {
+                self.write_register(register, register_b)?;
+                written = true;
+            }
+        }
+
+        Ok(written)
+    }
+
     /// Writes the pwm dutycycles, one array item per device in daisy chain
+    /// Fails in case the device type has no PWM register (LTC6804).
     fn write_pwm<P: PwmRegisters>(&mut self, pwm: [P; L]) -> Result<(), Self::Error>;
 
     /// Reads and returns the conversion result (voltages) of Cell or GPIO group
@@ -634,6 +1226,39 @@ pub trait LTC681XClient<T: DeviceTypes, const L: usize> {
     where
         T: 'static;
 
+    /// Reads the given cell voltage or auxiliary register and returns the voltages of its channels in slot order
+    /// Returns one vector for each device in daisy chain. Registers without voltage channels (e.g. configuration)
+    /// return empty vectors.
+    fn read_register_voltages(&mut self, register: T::Register) -> Result<Vec<Vec<Voltage<T>, 3>, L>, Self::Error> {
+        let command = register.to_read_command();
+        let mut addresses: Vec<&RegisterAddress<T>, 3> = T::ALL_CELLS
+            .get_locations()
+            .chain(T::ALL_GPIOS.get_locations())
+            .filter(|address| address.register.to_read_command() == command)
+            .take(3)
+            .collect();
+        addresses.sort_unstable_by_key(|address| address.slot);
+
+        let data = if addresses.is_empty() {
+            [[0; 3]; L]
+        } else {
+            self.read_register(register)?
+        };
+
+        Ok(data
+            .iter()
+            .map(|slots| {
+                addresses
+                    .iter()
+                    .map(|address| Voltage {
+                        channel: address.channel,
+                        voltage: slots[address.slot],
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
     /// Reads and returns the results of the overlap measurement
     ///
     /// Index 0: Result of ADC A of first cell*
@@ -658,12 +1283,13 @@ pub trait PollClient {
 }
 
 /// Client for LTC681X IC
-pub struct LTC681X<B, CS, P, T, const L: usize>
+pub struct LTC681X<B, CS, P, T, const L: usize, H = NoHook>
 where
     B: Transfer<u8>,
     CS: OutputPin,
     P: PollMethod<CS>,
     T: DeviceTypes,
+    H: TransferHook,
 {
     /// SPI bus
     bus: B,
@@ -674,6 +1300,24 @@ where
     /// Poll method used for type state
     poll_method: P,
 
+    /// Number of dummy bytes sent after the last frame of a daisy chain transaction
+    trailing_dummy_bytes: usize,
+
+    /// Hook invoked before and after each SPI transaction
+    transfer_hook: H,
+
+    /// Wake-up sequence of the transport
+    wake_profile: WakeProfile,
+
+    /// Last sent (unverified) register values, None if write coalescing is disabled
+    duplicate_cache: Option<SkipDuplicateCache<L>>,
+
+    /// Configuration and status registers are read twice
+    double_read: bool,
+
+    /// Address of the targeted device in addressed (parallel) topology, None for broadcasting
+    address: Option<u8>,
+
     device_types: PhantomData<T>,
 }
 
@@ -684,21 +1328,30 @@ where
     T: DeviceTypes,
 {
     pub(crate) fn new(bus: B, cs: CS) -> Self {
+        validate_chain_length::<L>();
+
         LTC681X {
             bus,
             cs,
             poll_method: NoPolling {},
+            trailing_dummy_bytes: 0,
+            transfer_hook: NoHook {},
+            wake_profile: WakeProfile::direct(),
+            duplicate_cache: None,
+            double_read: false,
+            address: None,
             device_types: PhantomData,
         }
     }
 }
 
-impl<B, CS, P, T, const L: usize> LTC681XClient<T, L> for LTC681X<B, CS, P, T, L>
+impl<B, CS, P, T, const L: usize, H> LTC681XClient<T, L> for LTC681X<B, CS, P, T, L, H>
 where
     B: Transfer<u8>,
     CS: OutputPin,
     P: PollMethod<CS>,
     T: DeviceTypes,
+    H: TransferHook,
 {
     type Error = Error<B, CS>;
 
@@ -709,65 +1362,72 @@ where
         cells: T::CellSelection,
         dcp: bool,
     ) -> Result<CommandTime, Error<B, CS>> {
-        self.cs.set_low().map_err(Error::CSPinError)?;
-        let mut command: u16 = 0b0000_0010_0110_0000;
-
-        command |= (mode as u16) << 7;
-        command |= cells.to_bitmap();
-
-        if dcp {
-            command |= 0b0001_0000;
-        }
-
-        self.send_command(command).map_err(Error::TransferError)?;
-        self.poll_method.end_command(&mut self.cs).map_err(Error::CSPinError)?;
+        let request = ConversionRequest::cells(cells).mode(mode).discharge_permitted(dcp);
+        self.start_conversion(request)
+    }
 
-        Ok(cells.to_conv_command_timing(mode))
+    /// See [LTC681XClient::start_conversion](LTC681XClient#tymethod.start_conversion)
+    fn start_conversion(&mut self, request: ConversionRequest<T>) -> Result<CommandTime, Error<B, CS>> {
+        self.command_transaction(request.to_command())?;
+        Ok(request.expected_duration())
     }
 
     /// See [LTC681XClient::start_conv_gpio](LTC681XClient#tymethod.start_conv_gpio)
     fn start_conv_gpio(&mut self, mode: ADCMode, channels: T::GPIOSelection) -> Result<CommandTime, Error<B, CS>> {
-        self.cs.set_low().map_err(Error::CSPinError)?;
-        let mut command: u16 = 0b0000_0100_0110_0000;
+        self.command_transaction(gpio_conversion_command(mode, &channels))?;
 
-        command |= (mode as u16) << 7;
-        command |= channels.to_bitmap();
+        Ok(channels.to_conv_command_timing(mode))
+    }
 
-        self.send_command(command).map_err(Error::TransferError)?;
-        self.poll_method.end_command(&mut self.cs).map_err(Error::CSPinError)?;
+    /// See [LTC681XClient::start_conv_gpio_redundant](LTC681XClient#tymethod.start_conv_gpio_redundant)
+    fn start_conv_gpio_redundant(
+        &mut self,
+        mode: ADCMode,
+        channels: T::GPIOSelection,
+    ) -> Result<CommandTime, Error<B, CS>> {
+        self.command_transaction(gpio_redundancy_conversion_command(mode, &channels))?;
 
         Ok(channels.to_conv_command_timing(mode))
     }
 
     /// See [LTC681XClient::start_conv_gpio](LTC681XClient#tymethod.start_overlap_measurement)
     fn start_overlap_measurement(&mut self, mode: ADCMode, dcp: bool) -> Result<(), Error<B, CS>> {
-        self.cs.set_low().map_err(Error::CSPinError)?;
-        let mut command: u16 = 0b0000_0010_0000_0001;
-
-        command |= (mode as u16) << 7;
-
-        if dcp {
-            command |= 0b0001_0000;
-        }
-
-        self.send_command(command).map_err(Error::TransferError)?;
-        self.poll_method.end_command(&mut self.cs).map_err(Error::CSPinError)
+        self.command_transaction(overlap_conversion_command(mode, dcp))
     }
 
     /// See [LTC681XClient::start_conv_gpio](LTC681XClient#tymethod.measure_internal_parameters)
     fn measure_internal_parameters(&mut self, mode: ADCMode, group: StatusGroup) -> Result<CommandTime, Error<B, CS>> {
-        self.cs.set_low().map_err(Error::CSPinError)?;
-        let mut command: u16 = 0b0000_0100_0110_1000;
+        self.command_transaction(status_conversion_command(mode, group))?;
 
-        command |= (mode as u16) << 7;
-        command |= group.to_bitmap();
+        Ok(group.to_conv_command_timing(mode))
+    }
 
-        self.send_command(command).map_err(Error::TransferError)?;
-        self.poll_method.end_command(&mut self.cs).map_err(Error::CSPinError)?;
+    /// See [LTC681XClient::measure_internal_parameters_redundant](LTC681XClient#tymethod.measure_internal_parameters_redundant)
+    fn measure_internal_parameters_redundant(
+        &mut self,
+        mode: ADCMode,
+        group: StatusGroup,
+    ) -> Result<CommandTime, Error<B, CS>> {
+        self.command_transaction(status_redundancy_conversion_command(mode, group))?;
 
         Ok(group.to_conv_command_timing(mode))
     }
 
+    /// See [LTC681XClient::clear_cells](LTC681XClient#tymethod.clear_cells)
+    fn clear_cells(&mut self) -> Result<(), Error<B, CS>> {
+        self.command_transaction(CMD_CLEAR_CELLS)
+    }
+
+    /// See [LTC681XClient::clear_aux](LTC681XClient#tymethod.clear_aux)
+    fn clear_aux(&mut self) -> Result<(), Error<B, CS>> {
+        self.command_transaction(CMD_CLEAR_AUX)
+    }
+
+    /// See [LTC681XClient::clear_status](LTC681XClient#tymethod.clear_status)
+    fn clear_status(&mut self) -> Result<(), Error<B, CS>> {
+        self.command_transaction(CMD_CLEAR_STATUS)
+    }
+
     /// See [LTC681XClient::read_cell_voltages](LTC681XClient#tymethod.read_register)
     fn read_register(&mut self, register: T::Register) -> Result<[[u16; 3]; L], Error<B, CS>> {
         self.read_daisy_chain(register.to_read_command())
@@ -775,27 +1435,18 @@ where
 
     /// See [LTC681XClient::read_cell_voltages](LTC681XClient#tymethod.write_register)
     fn write_register(&mut self, register: T::Register, data: [[u8; 6]; L]) -> Result<(), Error<B, CS>> {
-        let mut pre_command = match register.to_write_command() {
+        let command = match register.to_write_command() {
             Ok(command) => command,
             Err(_) => return Err(Error::ReadOnlyRegister),
         };
 
-        self.cs.set_low().map_err(Error::CSPinError)?;
-        self.bus.transfer(&mut pre_command).map_err(Error::TransferError)?;
-
-        for item in &data {
-            let mut full_command: [u8; 8] = [0x0; 8];
-            full_command[..6].clone_from_slice(item);
-
-            let pec = PEC15::calc(item);
-            full_command[6] = pec[0];
-            full_command[7] = pec[1];
-
-            self.bus.transfer(&mut full_command).map_err(Error::TransferError)?;
+        if let Some(cache) = &self.duplicate_cache {
+            if cache.matches(&self.addressed(command), &data) {
+                return Ok(());
+            }
         }
 
-        self.cs.set_high().map_err(Error::CSPinError)?;
-        Ok(())
+        self.write_daisy_chain(command, data)
     }
 
     /// See [LTC681XClient::read_cell_voltages](LTC681XClient#tymethod.write_configuration)
@@ -810,57 +1461,65 @@ where
             }
         }
 
-        self.write_register(T::REG_CONF_A, register_a)?;
+        self.write_configuration_registers(register_a, register_b)
+    }
 
-        if let Some(register) = T::REG_CONF_B {
-            self.write_register(register, register_b)?;
-        }
+    /// See [LTC681XClient::read_configuration](LTC681XClient#method.read_configuration)
+    fn read_configuration<C: FromConfigurationRegisters>(&mut self) -> Result<[C; L], Self::Error> {
+        let (current_a, current_b) = self.read_configuration_registers()?;
 
-        Ok(())
+        Ok(core::array::from_fn(|index| {
+            C::from_registers(current_a[index], current_b.map(|data| data[index]))
+        }))
     }
 
-    /// See [LTC681XClient::read_cell_voltages](LTC681XClient#tymethod.read_voltages)
-    fn read_voltages<R: RegisterLocator<T> + 'static>(
-        &mut self,
-        locator: R,
-    ) -> Result<Vec<Vec<Voltage<T>, 18>, L>, Self::Error>
+    /// See [LTC681XClient::update_configuration](LTC681XClient#method.update_configuration)
+    fn update_configuration<C, F>(&mut self, f: F) -> Result<bool, Self::Error>
     where
-        T: 'static,
+        C: ConfigurationRegisters + FromConfigurationRegisters,
+        F: FnOnce(&mut [C; L]),
     {
-        let mut result: Vec<Vec<Voltage<T>, 18>, L> = Vec::new();
-
-        // One slot for each register
-        // 1. index: register index
-        // 2. index: device index
-        // 3. index: Slot within register
-        let mut register_data = [[[0u16; 3]; L]; 6];
-
-        // Array for flagging loaded registers, 0 = not loaded, 1 = loaded
-        let mut loaded_registers = [0; 6];
+        let (current_a, current_b) = self.read_configuration_registers()?;
 
-        // Map register data
-        for device_index in 0..L {
-            let _ = result.push(Vec::new());
-
-            for address in locator.get_locations() {
-                let register_index = address.register.to_index();
+        let mut config: [C; L] =
+            core::array::from_fn(|index| C::from_registers(current_a[index], current_b.map(|data| data[index])));
+        f(&mut config);
 
-                // Load register if not done yet
-                if loaded_registers[register_index] == 0 {
-                    register_data[register_index] = self.read_register(address.register)?;
-                    loaded_registers[register_index] = 1;
-                }
+        let (register_a, register_b) = encode_configuration(&config);
+        let mut written = false;
 
-                let voltage = Voltage {
-                    channel: address.channel,
-                    voltage: register_data[register_index][device_index][address.slot],
-                };
+        // Written regardless of the skip-duplicate cache, as the read values are deviating
+        if register_a != current_a {
+            self.force_write_register(T::REG_CONF_A, register_a)?;
+            written = true;
+        }
 
-                let _ = result[device_index].push(voltage);
+        if let (Some(register), Some(current_b)) = (T::REG_CONF_B, current_b) {
+            if register_b != current_b {
+                self.force_write_register(register, register_b)?;
+                written = true;
             }
         }
 
-        Ok(result)
+        Ok(written)
+    }
+
+    /// See [LTC681XClient::read_cell_voltages](LTC681XClient#tymethod.read_voltages)
+    fn read_voltages<R: RegisterLocator<T> + 'static>(
+        &mut self,
+        locator: R,
+    ) -> Result<Vec<Vec<Voltage<T>, 18>, L>, Self::Error>
+    where
+        T: 'static,
+    {
+        decode_voltages(
+            &locator,
+            |register| self.read_register(register),
+            |words, address| Voltage {
+                channel: address.channel,
+                voltage: words[address.slot],
+            },
+        )
     }
 
     /// See [LTC681XClient::read_cell_voltages](LTC681XClient#tymethod.read_overlap_result)
@@ -917,117 +1576,781 @@ where
             register_a[item.0] = item.1.register_a();
         }
 
-        self.write_register(T::REG_PWM, register_a)?;
-
-        Ok(())
+        match T::REG_PWM {
+            Some(register) => self.write_register(register, register_a),
+            None => Err(Error::ReadOnlyRegister),
+        }
     }
 }
 
-impl<B, CS, P, T, const L: usize> LTC681X<B, CS, P, T, L>
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
 where
     B: Transfer<u8>,
     CS: OutputPin,
     P: PollMethod<CS>,
     T: DeviceTypes,
+    H: TransferHook,
 {
-    /// Sends the given command. Calculates and attaches the PEC checksum
-    fn send_command(&mut self, command: u16) -> Result<(), B::Error> {
-        let mut data = [(command >> 8) as u8, command as u8, 0x0, 0x0];
-        let pec = PEC15::calc(&data[0..2]);
+    /// Invokes the pre-transfer hook and pulls CS low
+    /// If CS can't be pulled low, the transaction is aborted and the post-transfer hook is invoked.
+    fn begin_transaction(&mut self, operation: Operation) -> Result<(), Error<B, CS>> {
+        self.transfer_hook.pre_transfer(operation);
+
+        if let Err(error) = self.cs.set_low() {
+            self.transfer_hook.post_transfer(operation);
+            return Err(Error::CSPinError(error));
+        }
+
+        Ok(())
+    }
+
+    /// Runs the given transfers as single transaction
+    /// CS is released and the post-transfer hook is invoked on every exit path. In case both the transfers and the
+    /// CS release fail, the error of the transfers is returned.
+    fn transaction<R>(
+        &mut self,
+        operation: Operation,
+        transfers: impl FnOnce(&mut Self) -> Result<R, Error<B, CS>>,
+    ) -> Result<R, Error<B, CS>> {
+        self.begin_transaction(operation)?;
+
+        let result = transfers(self);
+        let released = self.end_transaction(operation);
+
+        let value = result?;
+        released?;
+        Ok(value)
+    }
+
+    /// Waits until the conversion of the given timing is completed
+    ///
+    /// Without polling, the expected conversion time is awaited. As the active ADC mode set (regular or alternative)
+    /// is not known by the client, the longer timing is used. In case of SDO line polling, the ADC status is polled
+    /// every [POLL_INTERVAL]. Fails with [Error::Timeout] in case the conversion is not completed within twice the
+    /// expected conversion time. CS is released in this case.
+    pub fn wait_conversion<D: DelayUs<u32>>(&mut self, timing: CommandTime, delay: &mut D) -> Result<(), Error<B, CS>> {
+        let expected = timing.regular.max(timing.alternative);
+
+        if !self.poll_method.is_polling() {
+            delay.delay_us(expected);
+            return Ok(());
+        }
 
-        data[2] = pec[0];
-        data[3] = pec[1];
+        let timeout = 2 * expected;
+        let mut elapsed = 0;
 
+        while !self.poll_adc()? {
+            if elapsed >= timeout {
+                self.end_transaction(Operation::Poll)?;
+                return Err(Error::Timeout);
+            }
+
+            delay.delay_us(POLL_INTERVAL);
+            elapsed += POLL_INTERVAL;
+        }
+
+        Ok(())
+    }
+
+    /// Reads all cell voltage registers and returns the raw voltages in cell order, index 0 = cell 1
+    /// Real voltage: voltage * 100 uV. Cells not available on the device type are None.
+    /// Returns one item per device in daisy chain
+    pub fn read_all_cell_voltages(&mut self) -> Result<[[Option<u16>; MAX_CELLS]; L], Error<B, CS>> {
+        let voltages = self.read_voltages(T::ALL_CELLS)?;
+        let mut result = [[None; MAX_CELLS]; L];
+
+        for (cells, voltages) in result.iter_mut().zip(voltages.iter()) {
+            for voltage in voltages {
+                if let Some(slot) = voltage.channel.to_cell_index().and_then(|index| cells.get_mut(index)) {
+                    *slot = Some(voltage.voltage);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Returns false if the ADC is busy
+    /// If ADC is ready or the transfer failed, CS line is pulled high
+    fn poll_adc(&mut self) -> Result<bool, Error<B, CS>> {
+        self.transfer_hook.pre_transfer(Operation::Poll);
+
+        let mut command = [0xff];
+        let ready = match self.bus.transfer(&mut command) {
+            Ok(result) => result[0] == 0xff,
+            Err(error) => {
+                // The transfer error takes precedence over a failing CS release
+                let _ = self.end_transaction(Operation::Poll);
+                return Err(Error::TransferError(error));
+            }
+        };
+
+        if ready {
+            self.end_transaction(Operation::Poll)?;
+            return Ok(true);
+        }
+
+        self.transfer_hook.post_transfer(Operation::Poll);
+
+        Ok(false)
+    }
+
+    /// Pulls CS high and invokes the post-transfer hook
+    /// The hook is invoked even if CS can't be released.
+    pub(crate) fn end_transaction(&mut self, operation: Operation) -> Result<(), Error<B, CS>> {
+        let result = self.cs.set_high().map_err(Error::CSPinError);
+
+        self.transfer_hook.post_transfer(operation);
+
+        result
+    }
+
+    /// Sends the given command as single transaction. CS state is handled by the poll method.
+    /// In case the command can't be sent, CS is released regardless of the poll method.
+    pub(crate) fn command_transaction(&mut self, command: u16) -> Result<(), Error<B, CS>> {
+        self.begin_transaction(Operation::Command)?;
+
+        let result = self.send_command(command).map_err(Error::TransferError);
+        let released = match result {
+            Ok(_) => self.poll_method.end_command(&mut self.cs),
+            Err(_) => self.cs.set_high(),
+        };
+
+        self.transfer_hook.post_transfer(Operation::Command);
+
+        result?;
+        released.map_err(Error::CSPinError)
+    }
+
+    /// Writes the given data to all devices in daisy chain and updates the skip-duplicate cache
+    /// The cache is cleared in case of an error, as the register contents are unknown afterwards.
+    pub(crate) fn write_daisy_chain(&mut self, command: [u8; 4], data: [[u8; 6]; L]) -> Result<(), Error<B, CS>> {
+        let command = self.addressed(command);
+        let result = self.write_daisy_chain_frames(command, &data);
+
+        if let Some(cache) = &mut self.duplicate_cache {
+            match result {
+                Ok(_) => cache.store(command, data),
+                Err(_) => cache.clear(),
+            }
+        }
+
+        result
+    }
+
+    /// Single transaction of [write_daisy_chain](Self::write_daisy_chain)
+    fn write_daisy_chain_frames(&mut self, command: [u8; 4], data: &[[u8; 6]; L]) -> Result<(), Error<B, CS>> {
+        self.transaction(Operation::Write, |client| {
+            let mut pre_command = command;
+            client.bus.transfer(&mut pre_command).map_err(Error::TransferError)?;
+
+            // Each device shifts the received frames through to the next device until CS is released, so the first
+            // frame sent ends up in the farthest device (see daisy chain write of the LTC6811 datasheet). The data is
+            // sent in reverse order for keeping index 0 as the device closest to the host (same as for reads).
+            for item in data.iter().rev() {
+                let mut full_command = codec::encode_data_frame(item);
+                client.bus.transfer(&mut full_command).map_err(Error::TransferError)?;
+            }
+
+            // No trailing dummy bytes, as they would be shifted into the chain, displacing the frames sent before
+            Ok(())
+        })
+    }
+
+    /// Reads the raw values of configuration register A and (if available) B
+    fn read_configuration_registers(&mut self) -> Result<ConfigurationData<L>, Error<B, CS>> {
+        let register_a = self.read_daisy_chain_raw(T::REG_CONF_A.to_read_command())?;
+        let register_b = match T::REG_CONF_B {
+            Some(register) => Some(self.read_daisy_chain_raw(register.to_read_command())?),
+            None => None,
+        };
+
+        Ok((register_a, register_b))
+    }
+
+    /// Writes the raw values of configuration register A and (if available) B
+    fn write_configuration_registers(
+        &mut self,
+        register_a: [[u8; 6]; L],
+        register_b: [[u8; 6]; L],
+    ) -> Result<(), Error<B, CS>> {
+        self.write_register(T::REG_CONF_A, register_a)?;
+
+        if let Some(register) = T::REG_CONF_B {
+            self.write_register(register, register_b)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the given command. Calculates and attaches the PEC checksum
+    fn send_command(&mut self, command: u16) -> Result<(), B::Error> {
+        let mut data = self.addressed(codec::encode_command(command));
         self.bus.transfer(&mut data)?;
         Ok(())
     }
 
     /// Send the given read command and returns the response of all devices in daisy chain
-    fn read_daisy_chain(&mut self, mut command: [u8; 4]) -> Result<[[u16; 3]; L], Error<B, CS>> {
-        self.cs.set_low().map_err(Error::CSPinError)?;
-        self.bus.transfer(&mut command).map_err(Error::TransferError)?;
+    fn read_daisy_chain(&mut self, command: [u8; 4]) -> Result<[[u16; 3]; L], Error<B, CS>> {
+        let result = self.read_daisy_chain_words(command)?;
+
+        if self.requires_double_read(&command) && self.read_daisy_chain_words(command)? != result {
+            return Err(Error::InconsistentRead);
+        }
+
+        Ok(result)
+    }
+
+    /// Single transaction of [read_daisy_chain](Self::read_daisy_chain)
+    fn read_daisy_chain_words(&mut self, command: [u8; 4]) -> Result<[[u16; 3]; L], Error<B, CS>> {
+        self.read_frames(command, |frame| frame.is_valid().then(|| frame.words()))
+    }
+
+    /// Send the given read command and returns the raw register bytes of all devices in daisy chain
+    pub(crate) fn read_daisy_chain_raw(&mut self, command: [u8; 4]) -> Result<[[u8; 6]; L], Error<B, CS>> {
+        let result = self.read_daisy_chain_bytes(command)?;
+
+        if self.requires_double_read(&command) && self.read_daisy_chain_bytes(command)? != result {
+            return Err(Error::InconsistentRead);
+        }
+
+        Ok(result)
+    }
+
+    /// Returns true if the register of the given read command needs to be read twice
+    fn requires_double_read(&self, command: &[u8; 4]) -> bool {
+        if !self.double_read {
+            return false;
+        }
 
-        let mut result = [[0, 0, 0]; L];
-        for item in result.iter_mut().take(L) {
-            *item = self.read()?;
+        let registers = [
+            Some(T::REG_CONF_A),
+            T::REG_CONF_B,
+            Some(T::REG_STATUS_A),
+            Some(T::REG_STATUS_B),
+            T::REG_AUX_D,
+        ];
+        registers
+            .iter()
+            .flatten()
+            .any(|register| register.to_read_command() == *command)
+    }
+
+    /// Single transaction of [read_daisy_chain_raw](Self::read_daisy_chain_raw)
+    fn read_daisy_chain_bytes(&mut self, command: [u8; 4]) -> Result<[[u8; 6]; L], Error<B, CS>> {
+        self.read_frames(command, |frame| frame.is_valid().then(|| frame.to_owned().data))
+    }
+
+    /// Sends the wake pulses of the [WakeProfile] followed by one pulse per device in daisy chain
+    /// Each device pulse is followed by the given delay.
+    fn send_wake_sequence<D: DelayUs<u32>>(&mut self, device_ready_us: u32, delay: &mut D) -> Result<(), Error<B, CS>> {
+        for _ in 0..self.wake_profile.bridge_pulses {
+            self.send_wake_pulse()?;
+            delay.delay_us(self.wake_profile.bridge_ready_us);
+        }
+
+        for _ in 0..L {
+            self.send_wake_pulse()?;
+            delay.delay_us(device_ready_us);
+        }
+
+        Ok(())
+    }
+
+    /// Pulls CS low, sends a single dummy byte and releases CS again for waking up the next device in chain
+    fn send_wake_pulse(&mut self) -> Result<(), Error<B, CS>> {
+        self.transaction(Operation::Wake, |client| {
+            client.send_dummy_bytes(1).map_err(Error::TransferError)
+        })
+    }
+
+    /// Send the given read command and returns the raw register bytes of all devices in daisy chain
+    /// In contrast to [read_daisy_chain_raw](Self::read_daisy_chain_raw), frames with invalid PEC are
+    /// returned as None and don't abort the transaction.
+    pub(crate) fn read_daisy_chain_tolerant(&mut self, command: [u8; 4]) -> Result<[Option<[u8; 6]>; L], Error<B, CS>> {
+        let frames = self.read_daisy_chain_frames(command)?;
+        Ok(frames.map(|frame| if frame.is_valid() { Some(frame.data) } else { None }))
+    }
+
+    /// Send the given read command and returns the received data frames of all devices in daisy chain
+    /// PEC checksums are not validated.
+    pub(crate) fn read_daisy_chain_frames(&mut self, command: [u8; 4]) -> Result<[DataFrame; L], Error<B, CS>> {
+        let result = self.read_frames(command, |frame| Some(frame.to_owned()))?;
+
+        if result.iter().any(|frame| !frame.is_valid()) {
+            self.invalidate_write_cache();
         }
 
-        self.cs.set_high().map_err(Error::CSPinError)?;
         Ok(result)
     }
 
-    /// Reads a register
-    fn read(&mut self) -> Result<[u16; 3], Error<B, CS>> {
-        let mut command = [0xff_u8; 8];
-        let result = self.bus.transfer(&mut command).map_err(TransferError)?;
+    /// Sends the given read command and receives the data frames of all devices in daisy chain as single transaction
+    ///
+    /// Each frame is passed to `decode` as borrowed view of the receive buffer, so the data is parsed without copying.
+    /// The transaction is aborted if `decode` returns None, e.g. in case of an invalid PEC.
+    fn read_frames<R: Copy + Default>(
+        &mut self,
+        mut command: [u8; 4],
+        decode: impl Fn(DataFrameRef<'_>) -> Option<R>,
+    ) -> Result<[R; L], Error<B, CS>> {
+        command = self.addressed(command);
+
+        self.transaction(Operation::Read, |client| {
+            client.bus.transfer(&mut command).map_err(Error::TransferError)?;
+
+            let mut result = [R::default(); L];
+            for (index, item) in result.iter_mut().enumerate() {
+                if index > 0 {
+                    client.transfer_hook.frame_gap(Operation::Read);
+                }
+
+                let mut frame = [0xff_u8; 8];
+                let response = client.bus.transfer(&mut frame).map_err(TransferError)?;
+                let frame = DataFrameRef::new(response).map_err(|_| client.checksum_mismatch())?;
+
+                *item = match decode(frame) {
+                    Some(value) => value,
+                    None => return Err(client.checksum_mismatch()),
+                };
+            }
+
+            client
+                .send_dummy_bytes(client.trailing_dummy_bytes)
+                .map_err(Error::TransferError)?;
+            Ok(result)
+        })
+    }
+
+    /// Encodes the device address into the given command frame, unless broadcasting
+    fn addressed(&self, command: [u8; 4]) -> [u8; 4] {
+        match self.address {
+            Some(address) => {
+                let command = u16::from_be_bytes([command[0], command[1]]);
+                codec::encode_command(addressed::address_command(command, address))
+            }
+            None => command,
+        }
+    }
+
+    /// Sets the address of the targeted device, None for broadcasting
+    pub(crate) fn set_address(&mut self, address: Option<u8>) {
+        self.address = address;
+    }
+
+    /// Sends the given number of dummy bytes (0xFF) for flushing data through the isoSPI chain
+    fn send_dummy_bytes(&mut self, count: usize) -> Result<(), B::Error> {
+        let mut remaining = count;
+
+        while remaining > 0 {
+            let chunk = remaining.min(8);
+            let mut dummy = [0xff_u8; 8];
 
-        let pec = PEC15::calc(&result[0..6]);
-        if pec[0] != result[6] || pec[1] != result[7] {
-            return Err(Error::ChecksumMismatch);
+            self.bus.transfer(&mut dummy[..chunk])?;
+            remaining -= chunk;
         }
 
-        let mut registers = [result[0] as u16, result[2] as u16, result[4] as u16];
-        registers[0] |= (result[1] as u16) << 8;
-        registers[1] |= (result[3] as u16) << 8;
-        registers[2] |= (result[5] as u16) << 8;
+        Ok(())
+    }
 
-        Ok(registers)
+    /// Clears the skip-duplicate cache and returns [Error::ChecksumMismatch]
+    ///
+    /// A PEC error indicates a disturbed communication, so the register contents of the devices are no longer known.
+    fn checksum_mismatch(&mut self) -> Error<B, CS> {
+        self.invalidate_write_cache();
+        Error::ChecksumMismatch
     }
 
     /// Enables SDO ADC polling
     ///
     /// After entering a conversion command, the SDO line is driven low when the device is busy
     /// performing conversions. SDO is pulled high when the device completes conversions.
-    pub fn enable_sdo_polling(self) -> LTC681X<B, CS, SDOLinePolling, T, L> {
+    pub fn enable_sdo_polling(self) -> LTC681X<B, CS, SDOLinePolling, T, L, H> {
+        self.convert(|cs| cs, |_| SDOLinePolling {}, |hook| hook)
+    }
+
+    /// Sets the number of dummy bytes (0xFF) sent after the last frame of a daisy chain read
+    ///
+    /// Very long chains may need additional clock cycles for flushing data through every isoSPI hop. Writes don't
+    /// send trailing dummy bytes, as the daisy chain acts as shift register and the extra bytes would displace the
+    /// data written to the devices.
+    /// Default: 0
+    pub fn with_trailing_dummy_bytes(mut self, count: usize) -> Self {
+        self.trailing_dummy_bytes = count;
+        self
+    }
+
+    /// Pauses the given time (in microseconds) between the frames of two devices of a daisy chain read
+    ///
+    /// No bytes are sent during the gap, see [FrameGapDelay]. Replaces the current transfer hook.
+    pub fn with_frame_gap<D: DelayUs<u32>>(self, gap_us: u32, delay: D) -> LTC681X<B, CS, P, T, L, FrameGapDelay<D>> {
+        self.with_transfer_hook(FrameGapDelay::new(delay, gap_us))
+    }
+
+    /// Inserts the given CS timing delays using the given delay implementation
+    /// See [chip_select](crate::chip_select) module for details.
+    pub fn with_cs_timing<D: DelayUs<u32>>(
+        self,
+        timing: CSTiming,
+        delay: D,
+    ) -> LTC681X<B, TimedChipSelect<CS, D>, P, T, L, H>
+    where
+        P: PollMethod<TimedChipSelect<CS, D>>,
+    {
+        self.convert(
+            |cs| TimedChipSelect::new(cs, delay, timing),
+            |poll_method| poll_method,
+            |hook| hook,
+        )
+    }
+
+    /// Enables write coalescing: register writes are skipped if the data is byte-identical to the last value
+    /// sent to the same register. Sent values are not verified by reading back.
+    /// See [write coalescing](crate::monitor#write-coalescing) for details.
+    pub fn with_write_coalescing(mut self) -> Self {
+        self.duplicate_cache = Some(SkipDuplicateCache::new());
+        self
+    }
+
+    /// Writes the given register regardless of the skip-duplicate cache
+    pub fn force_write_register(&mut self, register: T::Register, data: [[u8; 6]; L]) -> Result<(), Error<B, CS>> {
+        match register.to_write_command() {
+            Ok(command) => self.write_daisy_chain(command, data),
+            Err(_) => Err(Error::ReadOnlyRegister),
+        }
+    }
+
+    /// Clears the skip-duplicate cache, so that the next write of each register is sent
+    /// Required after the devices may have lost their register contents (e.g. sleep state or power loss). The cache
+    /// is cleared automatically on PEC errors, failed writes and when waking up the devices from sleep state.
+    pub fn invalidate_write_cache(&mut self) {
+        if let Some(cache) = &mut self.duplicate_cache {
+            cache.clear();
+        }
+    }
+
+    /// Enables double-read validation: configuration and status registers are read twice back-to-back and only
+    /// accepted if both reads are PEC-valid and identical, otherwise [Error::InconsistentRead] is returned
+    ///
+    /// Auxiliary register group D (if available) is validated as well, as it holds the OV/UV flags of cell 13-18.
+    pub fn with_double_read(mut self) -> Self {
+        self.double_read = true;
+        self
+    }
+
+    /// Returns the properties of the device type and the configured daisy chain length
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            cell_count: T::CELL_COUNT,
+            gpio_count: T::GPIO_COUNT,
+            cell_register_groups: T::CELL_REGISTER_GROUPS,
+            aux_register_groups: T::AUX_REGISTER_GROUPS,
+            configuration_register_groups: if T::REG_CONF_B.is_some() { 2 } else { 1 },
+            overlap_measurement: T::OVERLAP_TEST_REG_1.is_some(),
+            pwm: T::REG_PWM.is_some(),
+            s_control: T::S_CONTROL,
+            pwm_b: T::PWM_B,
+            chain_length: L,
+        }
+    }
+
+    /// Releases the SPI bus and CS pin
+    pub fn release(self) -> (B, CS) {
+        (self.bus, self.cs)
+    }
+
+    /// Sets the hook, which is invoked before and after each SPI transaction
+    ///
+    /// Enables board-specific needs like pulsing a wake line or toggling an isolation enable.
+    pub fn with_transfer_hook<N: TransferHook>(self, hook: N) -> LTC681X<B, CS, P, T, L, N> {
+        self.convert(|cs| cs, |poll_method| poll_method, |_| hook)
+    }
+
+    /// Converts the client to different CS pin, poll method or transfer hook types, keeping all other settings
+    fn convert<CS2, P2, H2>(
+        self,
+        cs: impl FnOnce(CS) -> CS2,
+        poll_method: impl FnOnce(P) -> P2,
+        transfer_hook: impl FnOnce(H) -> H2,
+    ) -> LTC681X<B, CS2, P2, T, L, H2>
+    where
+        CS2: OutputPin,
+        P2: PollMethod<CS2>,
+        H2: TransferHook,
+    {
+        validate_chain_length::<L>();
+
         LTC681X {
             bus: self.bus,
-            cs: self.cs,
-            poll_method: SDOLinePolling {},
+            cs: cs(self.cs),
+            poll_method: poll_method(self.poll_method),
+            trailing_dummy_bytes: self.trailing_dummy_bytes,
+            transfer_hook: transfer_hook(self.transfer_hook),
+            wake_profile: self.wake_profile,
+            duplicate_cache: self.duplicate_cache,
+            double_read: self.double_read,
+            address: self.address,
             device_types: PhantomData,
         }
     }
 
-    /// Calculates the temperature in °C based on raw register value
-    fn calc_temperature(&self, value: u16) -> I16F16 {
-        if value >= 53744 {
-            return I16F16::MAX;
-        }
+    /// Returns the transfer hook, e.g. for reading its state
+    pub fn transfer_hook(&mut self) -> &mut H {
+        &mut self.transfer_hook
+    }
 
-        // Constant of 276 °C, which needs to be subtracted
-        const TEMP_SUB: i16 = 20976;
+    /// Sets the wake-up sequence of the transport, e.g. [WakeProfile::ltc6820] for chains behind an isoSPI bridge
+    /// Default: [WakeProfile::direct]
+    pub fn with_wake_profile(mut self, profile: WakeProfile) -> Self {
+        self.wake_profile = profile;
+        self
+    }
 
-        // Check if temperature is negative
-        let temp_i32: i16 = if value >= TEMP_SUB as u16 {
-            value as i16 - TEMP_SUB
-        } else {
-            0 - TEMP_SUB + value as i16
-        };
+    /// Wakes up the core of all devices in daisy chain from sleep state, delaying [T_WAKE_US] per device
+    /// Register contents are lost in sleep state, so the skip-duplicate cache is cleared.
+    pub fn wake_up<D: DelayUs<u32>>(&mut self, delay: &mut D) -> Result<(), Error<B, CS>> {
+        self.send_wake_sequence(T_WAKE_US, delay)?;
+        self.invalidate_write_cache();
+        Ok(())
+    }
+
+    /// Wakes up the isoSPI port of all devices in daisy chain from idle state, delaying [T_READY_US] per device
+    pub fn wake_up_idle<D: DelayUs<u32>>(&mut self, delay: &mut D) -> Result<(), Error<B, CS>> {
+        self.send_wake_sequence(T_READY_US, delay)
+    }
 
-        // Applying factor 100 uV/7.6 mV
-        I16F16::from_num(temp_i32) / 76
+    /// Calculates the temperature in °C based on raw register value and the scaling of the device type
+    pub(crate) fn calc_temperature(&self, value: u16) -> I16F16 {
+        calc_temperature(value, T::TEMPERATURE_SCALE_UV, T::TEMPERATURE_OFFSET)
     }
 }
 
-impl<B, CS, T, const L: usize> PollClient for LTC681X<B, CS, SDOLinePolling, T, L>
+impl<B, CS, T, const L: usize, H> PollClient for LTC681X<B, CS, SDOLinePolling, T, L, H>
 where
     B: Transfer<u8>,
     CS: OutputPin,
     T: DeviceTypes,
+    H: TransferHook,
 {
     type Error = Error<B, CS>;
 
     /// Returns false if the ADC is busy
     /// If ADC is ready, CS line is pulled high
     fn adc_ready(&mut self) -> Result<bool, Self::Error> {
-        let mut command = [0xff];
-        let result = self.bus.transfer(&mut command).map_err(Error::TransferError)?;
+        self.poll_adc()
+    }
+}
 
-        if result[0] == 0xff {
-            self.cs.set_high().map_err(Error::CSPinError)?;
-            return Ok(true);
+/// Number of registers tracked by the skip-duplicate cache
+const DUPLICATE_CACHE_SIZE: usize = 4;
+
+/// Last sent values of registers, identified by the write command
+///
+/// Used for skipping duplicate writes only. The values are not verified by reading back, so the cache does not
+/// necessarily reflect the register contents of the devices.
+struct SkipDuplicateCache<const L: usize> {
+    entries: Vec<([u8; 4], [[u8; 6]; L]), DUPLICATE_CACHE_SIZE>,
+}
+
+impl<const L: usize> SkipDuplicateCache<L> {
+    fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Returns true if the given data matches the last sent value
+    fn matches(&self, command: &[u8; 4], data: &[[u8; 6]; L]) -> bool {
+        self.entries
+            .iter()
+            .any(|(cached_command, cached_data)| cached_command == command && cached_data == data)
+    }
+
+    /// Stores the sent value. In case the cache is full, the oldest entry is dropped.
+    fn store(&mut self, command: [u8; 4], data: [[u8; 6]; L]) {
+        if let Some(index) = self.entries.iter().position(|(cached, _)| *cached == command) {
+            self.entries.remove(index);
+        } else if self.entries.is_full() {
+            self.entries.remove(0);
         }
 
-        Ok(false)
+        let _ = self.entries.push((command, data));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Clear cell voltage registers (CLRCELL)
+pub(crate) const CMD_CLEAR_CELLS: u16 = 0b0000_0111_0001_0001;
+
+/// Clear auxiliary registers (CLRAUX)
+pub(crate) const CMD_CLEAR_AUX: u16 = 0b0000_0111_0001_0010;
+
+/// Clear status registers (CLRSTAT)
+pub(crate) const CMD_CLEAR_STATUS: u16 = 0b0000_0111_0001_0011;
+
+/// Raw values of configuration register A and (if available) B of all devices
+type ConfigurationData<const L: usize> = ([[u8; 6]; L], Option<[[u8; 6]; L]>);
+
+/// Maps the channels of the given locator to the register data of all devices in daisy chain
+///
+/// Each register group is loaded at most once by `load`, which returns the data of all devices. `decode` returns
+/// the item of a single channel based on the loaded data of the device and the location of the channel.
+/// Returns one vector for each device in daisy chain.
+pub(crate) fn decode_voltages<T, R, D, V, E, const L: usize>(
+    locator: &R,
+    mut load: impl FnMut(T::Register) -> Result<[D; L], E>,
+    mut decode: impl FnMut(&D, &RegisterAddress<T>) -> V,
+) -> Result<Vec<Vec<V, 18>, L>, E>
+where
+    T: DeviceTypes + 'static,
+    R: RegisterLocator<T>,
+{
+    let mut result: Vec<Vec<V, 18>, L> = Vec::new();
+
+    // Loaded data per register index, None = not loaded yet
+    let mut register_data: [Option<[D; L]>; 6] = core::array::from_fn(|_| None);
+
+    for device_index in 0..L {
+        let _ = result.push(Vec::new());
+
+        for address in locator.get_locations() {
+            let register_index = address.register.to_index();
+
+            if register_data[register_index].is_none() {
+                register_data[register_index] = Some(load(address.register)?);
+            }
+
+            if let Some(data) = &register_data[register_index] {
+                let _ = result[device_index].push(decode(&data[device_index], address));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Validates the daisy chain length at compile time, called by every constructor of [LTC681X]
+const fn validate_chain_length<const L: usize>() {
+    const {
+        assert!(L >= 1, "Daisy chain requires at least one device");
+        assert!(L <= MAX_CHAIN_LENGTH, "Daisy chain exceeds MAX_CHAIN_LENGTH");
+    }
+}
+
+/// Reads configuration register A and (if available) B by the given client and converts the words to raw bytes
+fn read_configuration_words<C, T, const L: usize>(client: &mut C) -> Result<ConfigurationData<L>, C::Error>
+where
+    C: LTC681XClient<T, L> + ?Sized,
+    T: DeviceTypes,
+{
+    let register_a = client.read_register(T::REG_CONF_A)?;
+    let register_b = match T::REG_CONF_B {
+        Some(register) => Some(client.read_register(register)?),
+        None => None,
+    };
+
+    Ok((
+        register_a.map(|words| codec::encode_register_words(&words)),
+        register_b.map(|data| data.map(|words| codec::encode_register_words(&words))),
+    ))
+}
+
+/// Encodes configuration register A and B of all devices
+/// Register B is zeroed if not supported by the configuration type.
+fn encode_configuration<C: ConfigurationRegisters, const L: usize>(config: &[C; L]) -> ([[u8; 6]; L], [[u8; 6]; L]) {
+    let mut register_a = [[0x0u8; 6]; L];
+    let mut register_b = [[0x0u8; 6]; L];
+
+    for (index, item) in config.iter().enumerate() {
+        register_a[index] = item.register_a();
+        if let Some(reg) = item.register_b() {
+            register_b[index] = reg;
+        }
+    }
+
+    (register_a, register_b)
+}
+
+/// Default scale of the die temperature (ITMP) in uV per °C
+pub(crate) const DEFAULT_TEMPERATURE_SCALE_UV: u32 = 7_600;
+
+/// Default offset of the die temperature (ITMP) in °C
+pub(crate) const DEFAULT_TEMPERATURE_OFFSET: i32 = 276;
+
+/// Calculates the temperature in °C based on raw register value
+/// Datasheet: ITMP * 100 uV / scale - offset
+pub(crate) fn calc_temperature(value: u16, scale_uv: u32, offset: i32) -> I16F16 {
+    // Offset as register value, 100 uV per LSB
+    let raw_offset = offset * scale_uv as i32 / 100;
+    let difference = value as i32 - raw_offset;
+
+    if difference > i16::MAX as i32 {
+        return I16F16::MAX;
+    }
+
+    I16F16::from_num(difference) / (I16F16::from_num(scale_uv) / 100)
+}
+
+/// Returns the ADAX command for the given GPIO selection
+pub(crate) fn gpio_conversion_command<S: ToCommandBitmap>(mode: ADCMode, channels: &S) -> u16 {
+    let mut command: u16 = 0b0000_0100_0110_0000;
+
+    command |= (mode as u16) << 7;
+    command |= channels.to_bitmap();
+    command
+}
+
+/// Returns the ADAXD command for the given GPIO selection
+pub(crate) fn gpio_redundancy_conversion_command<S: ToCommandBitmap>(mode: ADCMode, channels: &S) -> u16 {
+    let mut command: u16 = 0b0000_0100_0000_0000;
+
+    command |= (mode as u16) << 7;
+    command |= channels.to_bitmap();
+    command
+}
+
+/// Returns the ADOL command
+pub(crate) fn overlap_conversion_command(mode: ADCMode, dcp: bool) -> u16 {
+    let mut command: u16 = 0b0000_0010_0000_0001;
+
+    command |= (mode as u16) << 7;
+
+    if dcp {
+        command |= 0b0001_0000;
+    }
+
+    command
+}
+
+/// Returns the ADSTAT command for the given status group
+pub(crate) fn status_conversion_command(mode: ADCMode, group: StatusGroup) -> u16 {
+    let mut command: u16 = 0b0000_0100_0110_1000;
+
+    command |= (mode as u16) << 7;
+    command |= group.to_bitmap();
+    command
+}
+
+/// Returns the ADSTATD command for the given status group
+pub(crate) fn status_redundancy_conversion_command(mode: ADCMode, group: StatusGroup) -> u16 {
+    let mut command: u16 = 0b0000_0100_0000_1000;
+
+    command |= (mode as u16) << 7;
+    command |= group.to_bitmap();
+    command
+}
+
+impl<B: Transfer<u8>, CS: OutputPin> Error<B, CS> {
+    /// Returns the kind of the error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::TransferError(_) => ErrorKind::Transfer,
+            Error::CSPinError(_) => ErrorKind::CSPin,
+            Error::ChecksumMismatch => ErrorKind::ChecksumMismatch,
+            Error::ReadOnlyRegister => ErrorKind::ReadOnlyRegister,
+            Error::InconsistentRead => ErrorKind::InconsistentRead,
+            Error::Timeout => ErrorKind::Timeout,
+        }
     }
 }
 
@@ -1038,6 +2361,8 @@ impl<B: Transfer<u8>, CS: OutputPin> Debug for Error<B, CS> {
             Error::CSPinError(_) => f.debug_struct("CSPinError").finish(),
             Error::ChecksumMismatch => f.debug_struct("ChecksumMismatch").finish(),
             Error::ReadOnlyRegister => f.debug_struct("ReadOnlyRegister").finish(),
+            Error::InconsistentRead => f.debug_struct("InconsistentRead").finish(),
+            Error::Timeout => f.debug_struct("Timeout").finish(),
         }
     }
 }
@@ -0,0 +1,252 @@
+//! Open-wire detection of cell and GPIO inputs
+//!
+//! Implements the datasheet open-wire algorithm based on the ADOW command:
+//!
+//! 1. ADOW with pull-up current is run twice, then the cell voltages (V_PU) are read
+//! 2. ADOW with pull-down current is run twice, then the cell voltages (V_PD) are read
+//! 3. For each cell n > 1: If V_PU(n) - V_PD(n) < -400 mV, input C(n-1) is open
+//! 4. If V_PU(1) = 0 V, input C0 is open. If V_PD(N) = 0 V of the top cell N, input C(N) is open
+//!
+//! [detect_open_wires](LTC681X::detect_open_wires) returns a bitmask of the open C-pins per device, bit 0 = C0.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let open_pins = client.detect_open_wires(ADCMode::Normal, &mut ExampleDelay::default()).unwrap();
+//!
+//! // The example bus returns the same voltages for both directions
+//! assert_eq!(0, open_pins[0]);
+//! ````
+//!
+//! In case of SDO line polling, the end of each conversion is polled and no delay is required.
+//!
+//! # Auxiliary inputs
+//!
+//! LTC6812, LTC6813 and ADBMS1818 support open-wire conversions of the GPIO inputs (AXOW command). The conversion
+//! is started by [start_conv_gpio_open_wire](LTC681X::start_conv_gpio_open_wire).
+//! [detect_open_gpios](LTC681X::detect_open_gpios) runs AXOW with both current directions and reports an input as
+//! open if V_PU - V_PD exceeds +400 mV. The bitmask per device uses bit 0 = GPIO1.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let open_pins = client.detect_open_gpios(ADCMode::Normal, &mut ExampleDelay::default()).unwrap();
+//! assert_eq!(0, open_pins[0]);
+//! ````
+use crate::conversion::{ConversionRequest, PullDirection};
+#[cfg(feature = "ltc6812")]
+use crate::ltc6812::LTC6812;
+#[cfg(feature = "ltc6813")]
+use crate::ltc6813::LTC6813;
+use crate::monitor::{
+    ADCMode, CommandTime, DeviceTypes, Error, LTC681XClient, PollMethod, ToCommandBitmap, ToCommandTiming,
+    TransferHook, Voltage, LTC681X,
+};
+use crate::snapshot::{Snapshot, MAX_CELLS, MAX_GPIOS};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Number of ADOW runs per pull direction
+pub const OPEN_WIRE_RUNS: usize = 2;
+
+/// Delta threshold (V_PU - V_PD) of open inputs, raw value (100 uV per LSB) => -400 mV
+pub const OPEN_WIRE_THRESHOLD: i32 = -4000;
+
+/// Delta threshold (V_PU - V_PD) of open GPIO inputs, raw value (100 uV per LSB) => +400 mV
+pub const AUX_OPEN_WIRE_THRESHOLD: i32 = 4000;
+
+/// Open-wire conversion of GPIOs (AXOW), ADC mode, PUP and channel bits cleared
+const CMD_AXOW: u16 = 0b0000_0100_0001_0000;
+
+/// Device types supporting the open-wire conversion of GPIO inputs (AXOW command)
+pub trait AuxOpenWire: DeviceTypes {}
+
+#[cfg(feature = "ltc6812")]
+impl AuxOpenWire for LTC6812 {}
+
+#[cfg(feature = "ltc6813")]
+impl AuxOpenWire for LTC6813 {}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Runs the open-wire algorithm and returns a bitmask of open C-pins per device (bit 0 = C0)
+    ///
+    /// See [wait_conversion](LTC681X::wait_conversion) for waiting and the timeout of each conversion.
+    pub fn detect_open_wires<D: DelayUs<u32>>(
+        &mut self,
+        mode: ADCMode,
+        delay: &mut D,
+    ) -> Result<[u32; L], Error<B, CS>> {
+        detect(self, |client, pull| {
+            for _ in 0..OPEN_WIRE_RUNS {
+                let timing = client.start_conversion(open_wire_request(mode, pull))?;
+                client.wait_conversion(timing, delay)?;
+            }
+
+            client.read_voltages(T::ALL_CELLS)
+        })
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: AuxOpenWire,
+    H: TransferHook,
+{
+    /// Starts the open-wire conversion of the given GPIOs (AXOW command)
+    ///
+    /// # Arguments
+    ///
+    /// * `mode`: ADC mode
+    /// * `pins`: Measures the given GPIO group
+    /// * `pull`: Direction of the current source
+    pub fn start_conv_gpio_open_wire(
+        &mut self,
+        mode: ADCMode,
+        pins: T::GPIOSelection,
+        pull: PullDirection,
+    ) -> Result<CommandTime, Error<B, CS>> {
+        self.command_transaction(aux_open_wire_command(mode, &pins, pull))?;
+
+        Ok(pins.to_conv_command_timing(mode))
+    }
+
+    /// Runs AXOW with both current directions and returns a bitmask of open GPIO inputs per device (bit 0 = GPIO1)
+    ///
+    /// See [wait_conversion](LTC681X::wait_conversion) for waiting and the timeout of each conversion.
+    pub fn detect_open_gpios<D: DelayUs<u32>>(
+        &mut self,
+        mode: ADCMode,
+        delay: &mut D,
+    ) -> Result<[u32; L], Error<B, CS>> {
+        detect_gpios(self, |client, pull| {
+            for _ in 0..OPEN_WIRE_RUNS {
+                let timing = client.start_conv_gpio_open_wire(mode, T::ALL_GPIOS, pull)?;
+                client.wait_conversion(timing, delay)?;
+            }
+
+            client.read_voltages(T::ALL_GPIOS)
+        })
+    }
+}
+
+/// Returns the AXOW command for the given GPIO selection and pull direction
+fn aux_open_wire_command<S: ToCommandBitmap>(mode: ADCMode, pins: &S, pull: PullDirection) -> u16 {
+    CMD_AXOW | (mode as u16) << 7 | (pull as u16) << 6 | pins.to_bitmap()
+}
+
+/// Returns the ADOW request of all cells for the given pull direction
+fn open_wire_request<T: DeviceTypes>(mode: ADCMode, pull: PullDirection) -> ConversionRequest<T> {
+    ConversionRequest::cells(T::ALL_CELLS).mode(mode).open_wire(pull)
+}
+
+/// Result of a measurement using a single pull direction
+type Measurement<B, CS, T, const L: usize> = Result<Vec<Vec<Voltage<T>, 18>, L>, Error<B, CS>>;
+
+/// Measures both pull directions and evaluates the open C-pins
+fn detect<B, CS, P, T, const L: usize, H>(
+    client: &mut LTC681X<B, CS, P, T, L, H>,
+    measure: impl FnMut(&mut LTC681X<B, CS, P, T, L, H>, PullDirection) -> Measurement<B, CS, T, L>,
+) -> Result<[u32; L], Error<B, CS>>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    let (pull_up, pull_down) = measure_both(client, measure)?;
+    let cell_count = T::CELL_COUNT.min(MAX_CELLS);
+
+    Ok(core::array::from_fn(|device| {
+        let pull_up = &pull_up.device(device).cell_voltages;
+        let pull_down = &pull_down.device(device).cell_voltages;
+        let mut open_pins = 0;
+
+        if pull_up[0] == Some(0) {
+            open_pins |= 1;
+        }
+
+        for cell in 1..cell_count {
+            if let (Some(up), Some(down)) = (pull_up[cell], pull_down[cell]) {
+                if (up as i32 - down as i32) < OPEN_WIRE_THRESHOLD {
+                    open_pins |= 1 << cell;
+                }
+            }
+        }
+
+        if cell_count > 0 && pull_down[cell_count - 1] == Some(0) {
+            open_pins |= 1 << cell_count;
+        }
+
+        open_pins
+    }))
+}
+
+/// Measures both pull directions and evaluates the open GPIO inputs
+fn detect_gpios<B, CS, P, T, const L: usize, H>(
+    client: &mut LTC681X<B, CS, P, T, L, H>,
+    measure: impl FnMut(&mut LTC681X<B, CS, P, T, L, H>, PullDirection) -> Measurement<B, CS, T, L>,
+) -> Result<[u32; L], Error<B, CS>>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    let (pull_up, pull_down) = measure_both(client, measure)?;
+    let gpio_count = T::GPIO_COUNT.min(MAX_GPIOS);
+
+    Ok(core::array::from_fn(|device| {
+        let pull_up = &pull_up.device(device).gpio_voltages;
+        let pull_down = &pull_down.device(device).gpio_voltages;
+
+        (0..gpio_count)
+            .filter(|gpio| match (pull_up[*gpio], pull_down[*gpio]) {
+                (Some(up), Some(down)) => (up as i32 - down as i32) > AUX_OPEN_WIRE_THRESHOLD,
+                _ => false,
+            })
+            .fold(0, |open_pins, gpio| open_pins | 1 << gpio)
+    }))
+}
+
+/// Returns the snapshots of the pull-up and pull-down measurement
+fn measure_both<B, CS, P, T, const L: usize, H>(
+    client: &mut LTC681X<B, CS, P, T, L, H>,
+    mut measure: impl FnMut(&mut LTC681X<B, CS, P, T, L, H>, PullDirection) -> Measurement<B, CS, T, L>,
+) -> Result<(Snapshot<L>, Snapshot<L>), Error<B, CS>>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    let mut pull_up: Snapshot<L> = Snapshot::new();
+    pull_up.update(&measure(client, PullDirection::PullUp)?);
+
+    let mut pull_down: Snapshot<L> = Snapshot::new();
+    pull_down.update(&measure(client, PullDirection::PullDown)?);
+
+    Ok((pull_up, pull_down))
+}
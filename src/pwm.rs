@@ -1,4 +1,31 @@
-#[derive(Copy, Clone)]
+//! Cell PWM duty cycles
+//!
+//! The PWM register group stores a 4-bit duty cycle per cell (cells 1 - 12), used for discharging cells when
+//! the discharge timer is active. The duty cycles are written by
+//! [write_pwm](crate::monitor::LTC681XClient::write_pwm) and read back by [read_pwm](LTC681X::read_pwm).
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//! use ltc681x::pwm::PwmDutyCycle;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let duty_cycles = client.read_pwm().unwrap().unwrap();
+//! // Cell 1
+//! assert_eq!(PwmDutyCycle::_30, duty_cycles[0][0]);
+//! // Cell 7
+//! assert_eq!(PwmDutyCycle::Off, duty_cycles[0][6]);
+//! ````
+use crate::monitor::{DeviceTypes, Error, PollMethod, ToFullCommand, TransferHook, LTC681X};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Number of cells covered by the PWM register group
+pub const PWM_CHANNELS: usize = 12;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum PwmDutyCycle {
     Off = 0b0000,
     /// 3.3
@@ -31,6 +58,55 @@ pub enum PwmDutyCycle {
     _50,
 }
 
+impl PwmDutyCycle {
+    /// Decodes the given 4-bit register value, upper bits are ignored
+    /// Values above [PwmDutyCycle::_50] are decoded as [PwmDutyCycle::_50].
+    pub fn from_bits(bits: u8) -> Self {
+        match bits & 0xF {
+            0b0000 => PwmDutyCycle::Off,
+            0b0001 => PwmDutyCycle::_3_3,
+            0b0010 => PwmDutyCycle::_6_7,
+            0b0011 => PwmDutyCycle::_10,
+            0b0100 => PwmDutyCycle::_16_7,
+            0b0101 => PwmDutyCycle::_20,
+            0b0110 => PwmDutyCycle::_23_3,
+            0b0111 => PwmDutyCycle::_26_7,
+            0b1000 => PwmDutyCycle::_30,
+            0b1001 => PwmDutyCycle::_33_3,
+            0b1010 => PwmDutyCycle::_36_7,
+            0b1011 => PwmDutyCycle::_40,
+            0b1100 => PwmDutyCycle::_43_3,
+            0b1101 => PwmDutyCycle::_46_7,
+            _ => PwmDutyCycle::_50,
+        }
+    }
+}
+
 pub trait PwmRegisters {
     fn register_a(&self) -> [u8; 6];
 }
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Reads and decodes the PWM duty cycles of cells 1 - 12, index 0 = cell 1
+    /// Returns one item per device in daisy chain. Returns None in case the device type has no PWM register
+    /// (LTC6804).
+    pub fn read_pwm(&mut self) -> Result<Option<[[PwmDutyCycle; PWM_CHANNELS]; L]>, Error<B, CS>> {
+        let register = match T::REG_PWM {
+            Some(register) => register,
+            None => return Ok(None),
+        };
+
+        let data = self.read_daisy_chain_raw(register.to_read_command())?;
+
+        Ok(Some(data.map(|data| {
+            core::array::from_fn(|cell| PwmDutyCycle::from_bits(data[cell / 2] >> (4 * (cell % 2))))
+        })))
+    }
+}
@@ -0,0 +1,139 @@
+//! Raw register data alongside parsed values
+//!
+//! For data-logging and certification evidence, it may be required to store exactly what was received from the
+//! devices. The following methods return the raw register contents (6 data bytes per device, PEC already
+//! validated) in addition to the parsed values:
+//!
+//! * [read_register_raw](LTC681X::read_register_raw): Raw bytes of a single register
+//! * [read_voltages_with_raw](LTC681X::read_voltages_with_raw): Voltages and all registers they were decoded from
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813, Register};
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let result = client.read_voltages_with_raw(CellSelection::Group1).unwrap();
+//! assert_eq!(24979, result.voltages[0][0].voltage);
+//!
+//! // Cell registers A, C and E
+//! assert_eq!(3, result.registers.len());
+//! assert_eq!(Register::CellVoltageA, result.registers[0].register);
+//! assert_eq!([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22], result.registers[0].data[0]);
+//! ````
+//!
+//! # Raw commands
+//!
+//! Registers or features not modeled by this crate may be accessed by their 11-bit command code. The PEC of the
+//! command and data frames is handled internally.
+//!
+//! * [send_raw_command](LTC681X::send_raw_command): Sends a command without data, e.g. a conversion command
+//! * [read_raw](LTC681X::read_raw): Sends a read command and returns the data bytes of all devices
+//! * [write_raw](LTC681X::write_raw): Sends a write command followed by the given data bytes
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // Start I2C/SPI communication (STCOMM)
+//! client.send_raw_command(0x0723).unwrap();
+//!
+//! // Write COMM register (WRCOMM)
+//! client.write_raw(0x0721, &[[0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9]]).unwrap();
+//!
+//! // Read status register B (RDSTATB)
+//! let data = client.read_raw(0x0012).unwrap();
+//! assert_eq!([0x00, 0xC8, 0x00, 0x66, 0x00, 0x1B], data[0]);
+//! ````
+use crate::codec;
+use crate::monitor::{
+    decode_voltages, DeviceTypes, Error, PollMethod, RegisterLocator, ToFullCommand, TransferHook, Voltage, LTC681X,
+};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Raw contents of a single register of all devices in daisy chain
+#[derive(PartialEq, Debug)]
+pub struct RawRegister<T: DeviceTypes, const L: usize> {
+    /// Register which was read
+    pub register: T::Register,
+
+    /// Received data bytes (without PEC), one item per device in daisy chain
+    pub data: [[u8; 6]; L],
+}
+
+impl<T: DeviceTypes, const L: usize> Copy for RawRegister<T, L> {}
+
+impl<T: DeviceTypes, const L: usize> Clone for RawRegister<T, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Voltages including the raw registers they were decoded from
+pub struct RawVoltages<T: DeviceTypes, const L: usize> {
+    /// Parsed voltages, one vector per device in daisy chain
+    pub voltages: Vec<Vec<Voltage<T>, 18>, L>,
+
+    /// Raw registers in order of reading
+    pub registers: Vec<RawRegister<T, L>, 6>,
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Reads the raw data bytes of the given register, one array item per device in daisy chain
+    pub fn read_register_raw(&mut self, register: T::Register) -> Result<[[u8; 6]; L], Error<B, CS>> {
+        self.read_daisy_chain_raw(register.to_read_command())
+    }
+
+    /// Sends the given 11-bit command code (without data)
+    pub fn send_raw_command(&mut self, command: u16) -> Result<(), Error<B, CS>> {
+        self.command_transaction(command)
+    }
+
+    /// Sends the given 11-bit read command code and returns the raw data bytes of all devices in daisy chain
+    pub fn read_raw(&mut self, command: u16) -> Result<[[u8; 6]; L], Error<B, CS>> {
+        self.read_daisy_chain_raw(codec::encode_command(command))
+    }
+
+    /// Sends the given 11-bit write command code followed by the data, one array item per device in daisy chain
+    /// The skip-duplicate cache is not checked, so the data is always sent.
+    pub fn write_raw(&mut self, command: u16, data: &[[u8; 6]; L]) -> Result<(), Error<B, CS>> {
+        self.write_daisy_chain(codec::encode_command(command), *data)
+    }
+
+    /// Same as [read_voltages](crate::monitor::LTC681XClient::read_voltages), but additionally returns the raw
+    /// contents of all registers read
+    pub fn read_voltages_with_raw<R: RegisterLocator<T> + 'static>(
+        &mut self,
+        locator: R,
+    ) -> Result<RawVoltages<T, L>, Error<B, CS>> {
+        let mut registers = Vec::new();
+
+        let voltages = decode_voltages(
+            &locator,
+            |register| {
+                let data = self.read_register_raw(register)?;
+                let _ = registers.push(RawRegister { register, data });
+                Ok(data)
+            },
+            |data, address| Voltage {
+                channel: address.channel,
+                voltage: codec::decode_register_words(data)[address.slot],
+            },
+        )?;
+
+        Ok(RawVoltages { voltages, registers })
+    }
+}
@@ -0,0 +1,83 @@
+//! Reference power-up before GPIO and status conversions
+//!
+//! GPIO and status conversions return invalid results if the reference has not settled yet. In case the
+//! references are not kept powered up (REFON bit of the [configuration](crate::config::Configuration)),
+//! [ensure_reference](LTC681X::ensure_reference) sets the REFON bit on all devices by read-modify-write and waits
+//! the reference power-up time ([T_REFUP_US]). If REFON is already set on all devices, neither a write nor a delay
+//! is performed.
+//!
+//! [start_conv_gpio_referenced](LTC681X::start_conv_gpio_referenced) and
+//! [measure_internal_parameters_referenced](LTC681X::measure_internal_parameters_referenced) ensure the reference
+//! before starting the conversion. Both return a handle for fetching the results, see
+//! [GpioConversion](crate::conversion::GpioConversion) and
+//! [StatusConversion](crate::conversion::StatusConversion).
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{GPIOSelection, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // REFON is not set in the configuration returned by the example bus
+//! assert!(client.ensure_reference(&mut ExampleDelay::default()).unwrap());
+//!
+//! let conversion = client
+//!     .start_conv_gpio_referenced(ADCMode::Normal, GPIOSelection::All, &mut ExampleDelay::default())
+//!     .unwrap();
+//! assert_eq!(3862, conversion.timing().regular);
+//! ````
+use crate::config::Configuration;
+use crate::conversion::{GpioBeginResult, StatusBeginResult};
+use crate::monitor::{ADCMode, DeviceTypes, Error, LTC681XClient, PollMethod, StatusGroup, TransferHook, LTC681X};
+use crate::timing::T_REFUP_US;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Sets the REFON bit on all devices and waits the reference power-up time, if not already set
+    /// Returns true if the reference had to be powered up.
+    pub fn ensure_reference<D: DelayUs<u32>>(&mut self, delay: &mut D) -> Result<bool, Error<B, CS>> {
+        let written = self.update_configuration(|config: &mut [Configuration; L]| {
+            config.iter_mut().for_each(|config| config.enable_reference_power());
+        })?;
+
+        if written {
+            delay.delay_us(T_REFUP_US);
+        }
+
+        Ok(written)
+    }
+
+    /// Ensures the reference is powered up and starts the GPIO conversion (ADAX command)
+    /// See [begin_gpio_conversion](LTC681X::begin_gpio_conversion) for the arguments.
+    pub fn start_conv_gpio_referenced<D: DelayUs<u32>>(
+        &mut self,
+        mode: ADCMode,
+        pins: T::GPIOSelection,
+        delay: &mut D,
+    ) -> GpioBeginResult<'_, B, CS, P, T, L, H> {
+        self.ensure_reference(delay)?;
+        self.begin_gpio_conversion(mode, pins)
+    }
+
+    /// Ensures the reference is powered up and starts measuring internal device parameters (ADSTAT command)
+    /// See [begin_status_conversion](LTC681X::begin_status_conversion) for the arguments.
+    pub fn measure_internal_parameters_referenced<D: DelayUs<u32>>(
+        &mut self,
+        mode: ADCMode,
+        group: StatusGroup,
+        delay: &mut D,
+    ) -> StatusBeginResult<'_, B, CS, P, T, L, H> {
+        self.ensure_reference(delay)?;
+        self.begin_status_conversion(mode, group)
+    }
+}
@@ -0,0 +1,107 @@
+//! Generic register group access
+//!
+//! A [RegisterGroup] describes a register group by its command code and the decoding of the register data, so
+//! [read_register_group](LTC681X::read_register_group) and [write_register_group](LTC681X::write_register_group)
+//! may be used for any register group, including ones without a dedicated read function. All register groups of
+//! the LTC681X family hold [REGISTER_GROUP_LEN] data bytes per device.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//! use ltc681x::register_group::{RegisterGroup, WritableRegisterGroup};
+//! use ltc681x::status::StatusB;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // Built-in register group
+//! let status: [StatusB; 1] = client.read_register_group().unwrap();
+//! assert_eq!(5_120_000, status[0].digital_power);
+//!
+//! // Custom register group (COMM register)
+//! struct Comm([u8; 6]);
+//!
+//! impl RegisterGroup for Comm {
+//!     const READ_COMMAND: u16 = 0x0722;
+//!
+//!     fn decode(data: &[u8; 6]) -> Self {
+//!         Comm(*data)
+//!     }
+//! }
+//!
+//! impl WritableRegisterGroup for Comm {
+//!     const WRITE_COMMAND: u16 = 0x0721;
+//!
+//!     fn encode(&self) -> [u8; 6] {
+//!         self.0
+//!     }
+//! }
+//!
+//! client.write_register_group(&[Comm([0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9])]).unwrap();
+//! ````
+use crate::codec;
+use crate::monitor::{DeviceTypes, Error, PollMethod, TransferHook, LTC681X};
+use crate::status::{StatusA, StatusB};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Number of data bytes (without PEC) of a register group per device
+pub const REGISTER_GROUP_LEN: usize = 6;
+
+/// Register group which may be read and decoded
+pub trait RegisterGroup: Sized {
+    /// 11-bit read command code (without PEC)
+    const READ_COMMAND: u16;
+
+    /// Decodes the register data of a single device
+    fn decode(data: &[u8; REGISTER_GROUP_LEN]) -> Self;
+}
+
+/// Register group which may be encoded and written
+pub trait WritableRegisterGroup: RegisterGroup {
+    /// 11-bit write command code (without PEC)
+    const WRITE_COMMAND: u16;
+
+    /// Encodes the register data of a single device
+    fn encode(&self) -> [u8; REGISTER_GROUP_LEN];
+}
+
+/// Die temperature is decoded with the default scaling, see [StatusA::from_register]
+impl RegisterGroup for StatusA {
+    const READ_COMMAND: u16 = 0x0010;
+
+    fn decode(data: &[u8; REGISTER_GROUP_LEN]) -> Self {
+        StatusA::from_register(data)
+    }
+}
+
+impl RegisterGroup for StatusB {
+    const READ_COMMAND: u16 = 0x0012;
+
+    fn decode(data: &[u8; REGISTER_GROUP_LEN]) -> Self {
+        StatusB::from_register(data)
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Reads and decodes the given register group of all devices
+    /// Returns one item per device in daisy chain
+    pub fn read_register_group<G: RegisterGroup>(&mut self) -> Result<[G; L], Error<B, CS>> {
+        let data = self.read_daisy_chain_raw(codec::encode_command(G::READ_COMMAND))?;
+        Ok(data.map(|data| G::decode(&data)))
+    }
+
+    /// Encodes and writes the given register group, one array item per device in daisy chain
+    /// The skip-duplicate cache is not checked, so the data is always sent.
+    pub fn write_register_group<G: WritableRegisterGroup>(&mut self, groups: &[G; L]) -> Result<(), Error<B, CS>> {
+        let data = core::array::from_fn(|index| groups[index].encode());
+        self.write_daisy_chain(codec::encode_command(G::WRITE_COMMAND), data)
+    }
+}
@@ -0,0 +1,228 @@
+//! Dual-port isoSPI ring topology
+//!
+//! In a ring topology, both ends of the daisy chain are connected to the MCU, each by its own SPI bus and CS
+//! pin. Commands are either sent via port A (bottom device first) or via port B, which reverses the order of the
+//! devices. The [RingClient] keeps the device indices stable independent of the active port: index 0 is always
+//! the device closest to port A.
+//!
+//! If a link breaks, the devices beyond the break are no longer reachable from one end, but still from the other
+//! one. [read_register_redundant](RingClient::read_register_redundant) reads from both ends and combines the
+//! PEC-valid frames, so operation continues with a single broken link.
+//!
+//! # Broadcasting conversion commands
+//!
+//! [start_conv_cells](RingClient::start_conv_cells) and [start_conv_gpio](RingClient::start_conv_gpio) are sent
+//! via both ports independent of the active port, so devices beyond a broken link start converting as well.
+//! Devices reachable from both ends receive the command twice, which just restarts the conversion. The command is
+//! sent via both ports even if sending via port A failed, the error of port A takes precedence.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813, Register};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//! use ltc681x::ring::{Port, RingClient};
+//!
+//! let port_a: LTC681X<_, _, _, LTC6813, 2> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let port_b: LTC681X<_, _, _, LTC6813, 2> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut client = RingClient::new(port_a, port_b);
+//!
+//! client.start_conv_cells(ADCMode::Normal, CellSelection::All, true).unwrap();
+//! let voltages = client.read_register(Register::CellVoltageA).unwrap();
+//!
+//! // Switching direction, e.g. after communication errors
+//! client.switch_to(Port::B);
+//! let voltages = client.read_register(Register::CellVoltageA).unwrap();
+//!
+//! // Combined data of both directions
+//! let frames = client.read_register_redundant(Register::CellVoltageA).unwrap();
+//! assert!(frames.iter().all(|frame| frame.is_some()));
+//! ````
+use crate::monitor::{
+    ADCMode, CommandTime, DeviceTypes, Error, LTC681XClient, NoHook, PollMethod, ToFullCommand, TransferHook, LTC681X,
+};
+use core::fmt::{Debug, Formatter};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+
+/// Port of the daisy chain used for communication
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Port {
+    /// Regular direction, device 0 receives the command first
+    #[default]
+    A,
+
+    /// Reverse direction, the last device receives the command first
+    B,
+}
+
+/// Error enum of [RingClient]
+pub enum RingError<BA: Transfer<u8>, CSA: OutputPin, BB: Transfer<u8>, CSB: OutputPin> {
+    /// Error of the client connected to port A
+    PortA(Error<BA, CSA>),
+
+    /// Error of the client connected to port B
+    PortB(Error<BB, CSB>),
+}
+
+/// Result of [RingClient] operations
+pub type RingResult<V, BA, CSA, BB, CSB> = Result<V, RingError<BA, CSA, BB, CSB>>;
+
+/// Client for daisy chains connected at both ends
+pub struct RingClient<BA, CSA, BB, CSB, P, T, const L: usize, H = NoHook>
+where
+    BA: Transfer<u8>,
+    CSA: OutputPin,
+    BB: Transfer<u8>,
+    CSB: OutputPin,
+    P: PollMethod<CSA> + PollMethod<CSB>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    port_a: LTC681X<BA, CSA, P, T, L, H>,
+    port_b: LTC681X<BB, CSB, P, T, L, H>,
+
+    /// Port used for communication
+    active: Port,
+}
+
+impl<BA, CSA, BB, CSB, P, T, const L: usize, H> RingClient<BA, CSA, BB, CSB, P, T, L, H>
+where
+    BA: Transfer<u8>,
+    CSA: OutputPin,
+    BB: Transfer<u8>,
+    CSB: OutputPin,
+    P: PollMethod<CSA> + PollMethod<CSB>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Creates a new ring client based on the clients of both ends. Port A is active.
+    pub fn new(port_a: LTC681X<BA, CSA, P, T, L, H>, port_b: LTC681X<BB, CSB, P, T, L, H>) -> Self {
+        Self {
+            port_a,
+            port_b,
+            active: Port::A,
+        }
+    }
+
+    /// Switches the communication direction to the given port
+    pub fn switch_to(&mut self, port: Port) {
+        self.active = port;
+    }
+
+    /// Returns the port used for communication
+    pub fn active_port(&self) -> Port {
+        self.active
+    }
+
+    /// See [LTC681XClient::start_conv_cells], broadcast via both ports
+    /// See [broadcasting](crate::ring#broadcasting-conversion-commands) for details.
+    #[allow(deprecated)]
+    pub fn start_conv_cells(
+        &mut self,
+        mode: ADCMode,
+        cells: T::CellSelection,
+        dcp: bool,
+    ) -> RingResult<CommandTime, BA, CSA, BB, CSB> {
+        let result_a = self.port_a.start_conv_cells(mode, cells, dcp);
+        let result_b = self.port_b.start_conv_cells(mode, cells, dcp);
+        Self::broadcast_result(result_a, result_b)
+    }
+
+    /// See [LTC681XClient::start_conv_gpio], broadcast via both ports
+    /// See [broadcasting](crate::ring#broadcasting-conversion-commands) for details.
+    #[allow(deprecated)]
+    pub fn start_conv_gpio(
+        &mut self,
+        mode: ADCMode,
+        pins: T::GPIOSelection,
+    ) -> RingResult<CommandTime, BA, CSA, BB, CSB> {
+        let result_a = self.port_a.start_conv_gpio(mode, pins);
+        let result_b = self.port_b.start_conv_gpio(mode, pins);
+        Self::broadcast_result(result_a, result_b)
+    }
+
+    /// See [LTC681XClient::read_register], read via the active port
+    /// Index 0 is the device closest to port A, independent of the active port.
+    pub fn read_register(&mut self, register: T::Register) -> RingResult<[[u16; 3]; L], BA, CSA, BB, CSB> {
+        match self.active {
+            Port::A => self.port_a.read_register(register).map_err(RingError::PortA),
+            Port::B => {
+                let mut data = self.port_b.read_register(register).map_err(RingError::PortB)?;
+                data.reverse();
+                Ok(data)
+            }
+        }
+    }
+
+    /// See [LTC681XClient::write_register], written via the active port
+    /// Index 0 is the device closest to port A, independent of the active port.
+    pub fn write_register(
+        &mut self,
+        register: T::Register,
+        mut data: [[u8; 6]; L],
+    ) -> RingResult<(), BA, CSA, BB, CSB> {
+        match self.active {
+            Port::A => self.port_a.write_register(register, data).map_err(RingError::PortA),
+            Port::B => {
+                data.reverse();
+                self.port_b.write_register(register, data).map_err(RingError::PortB)
+            }
+        }
+    }
+
+    /// Reads the given register from both ends and combines the PEC-valid frames
+    /// Frames of port A take precedence. Devices without a valid frame from any end are returned as None.
+    pub fn read_register_redundant(
+        &mut self,
+        register: T::Register,
+    ) -> RingResult<[Option<[u8; 6]>; L], BA, CSA, BB, CSB> {
+        let command = register.to_read_command();
+
+        let frames_a = self.port_a.read_daisy_chain_frames(command).map_err(RingError::PortA)?;
+        let mut frames_b = self.port_b.read_daisy_chain_frames(command).map_err(RingError::PortB)?;
+        frames_b.reverse();
+
+        Ok(core::array::from_fn(|index| {
+            [frames_a[index], frames_b[index]]
+                .iter()
+                .find(|frame| frame.is_valid())
+                .map(|frame| frame.data)
+        }))
+    }
+
+    /// Returns the client connected to port A
+    pub fn port_a(&mut self) -> &mut LTC681X<BA, CSA, P, T, L, H> {
+        &mut self.port_a
+    }
+
+    /// Returns the client connected to port B
+    /// Device order of this client is reversed.
+    pub fn port_b(&mut self) -> &mut LTC681X<BB, CSB, P, T, L, H> {
+        &mut self.port_b
+    }
+
+    /// Combines the results of a command sent via both ports, the error of port A takes precedence
+    fn broadcast_result(
+        result_a: Result<CommandTime, Error<BA, CSA>>,
+        result_b: Result<CommandTime, Error<BB, CSB>>,
+    ) -> RingResult<CommandTime, BA, CSA, BB, CSB> {
+        let timing = result_a.map_err(RingError::PortA)?;
+        result_b.map_err(RingError::PortB)?;
+        Ok(timing)
+    }
+}
+
+impl<BA, CSA, BB, CSB> Debug for RingError<BA, CSA, BB, CSB>
+where
+    BA: Transfer<u8>,
+    CSA: OutputPin,
+    BB: Transfer<u8>,
+    CSB: OutputPin,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RingError::PortA(error) => f.debug_tuple("PortA").field(error).finish(),
+            RingError::PortB(error) => f.debug_tuple("PortB").field(error).finish(),
+        }
+    }
+}
@@ -0,0 +1,472 @@
+//! ADC self-tests (CVST, AXST and STATST commands) and multiplexer diagnosis (DIAGN command)
+//!
+//! The self-test commands fill the result registers with a fixed test pattern instead of measured values. Reading
+//! back and comparing the registers verifies the digital filters, the result registers and the communication
+//! path of each device.
+//!
+//! [cell_voltage_self_test](LTC681X::cell_voltage_self_test) starts the self-test and returns the expected
+//! execution time. After the conversion is done, [verify_cell_voltage_self_test](LTC681X::verify_cell_voltage_self_test)
+//! reads all cell voltage registers and reports each channel not matching the expected pattern.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{Channel, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//! use ltc681x::self_test::SelfTestPattern;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let timing = client.cell_voltage_self_test(ADCMode::Normal, SelfTestPattern::Pattern1).unwrap();
+//! // [...] waiting until conversion finished
+//! let report = client.verify_cell_voltage_self_test(ADCMode::Normal, SelfTestPattern::Pattern1).unwrap();
+//!
+//! // The example bus returns measured voltages instead of the test pattern
+//! assert!(!report.passed());
+//! assert_eq!(Some((0, Channel::Cell1)), report.failures().next());
+//! ````
+//!
+//! The auxiliary ADC path (GPIOs and second reference) is tested in the same way by
+//! [aux_self_test](LTC681X::aux_self_test) and [verify_aux_self_test](LTC681X::verify_aux_self_test), the status
+//! ADC path (SC, ITMP, VA, VD) by [status_self_test](LTC681X::status_self_test) and
+//! [verify_status_self_test](LTC681X::verify_status_self_test).
+//!
+//! # Overlap measurement
+//!
+//! The [ADOL](crate::monitor::LTC681XClient::start_overlap_measurement) command measures cell 7 (and cell 13 on
+//! LTC6812/LTC6813/ADBMS1818) by two ADCs. [read_overlap_measurement](LTC681X::read_overlap_measurement) returns
+//! both raw ADC results of each overlap cell, which may be logged or compared against a tolerance. Comparing both
+//! results detects latent faults of a single ADC.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::{ADCMode, LTC681X, LTC681XClient};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! client.start_overlap_measurement(ADCMode::Normal, true).unwrap();
+//! // [...] waiting until conversion finished
+//!
+//! let result = client.read_overlap_measurement().unwrap();
+//!
+//! // Cell 7
+//! let cell_7 = result[0].first.unwrap();
+//! assert_eq!(25441, cell_7.first_adc);
+//! assert_eq!(7869, cell_7.second_adc);
+//! assert_eq!(1_757_200, cell_7.deviation_uv());
+//!
+//! // Tolerance of 5 mV
+//! assert!(!result[0].matches(5_000));
+//! ````
+//!
+//! # Multiplexer diagnosis
+//!
+//! The DIAGN command checks the multiplexer of each device and sets the MUXFAIL bit of status register B in case
+//! of a failure. [diagnose_mux](LTC681X::diagnose_mux) starts the diagnosis, waits for completion and returns the
+//! result per device.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let passed = client.diagnose_mux(&mut ExampleDelay::default()).unwrap();
+//!
+//! // The example bus reports a multiplexer failure
+//! assert!(!passed[0]);
+//! ````
+//!
+//! # ADC mode cross-check
+//!
+//! [compare_adc_modes](LTC681X::compare_adc_modes) converts the same cell group in two different ADC modes back
+//! to back and reports all channels whose results diverge beyond the given tolerance. As the modes use different
+//! filter settings, this is a cheap sanity check of the analog path.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // Tolerance of 5 mV
+//! let comparison = client
+//!     .compare_adc_modes(CellSelection::All, ADCMode::Normal, ADCMode::Filtered, 50, &mut ExampleDelay::default())
+//!     .unwrap();
+//! assert!(comparison.passed());
+//! ````
+use crate::conversion::ConversionRequest;
+use crate::monitor::{
+    ADCMode, CommandTime, DeviceTypes, Error, LTC681XClient, PollMethod, StatusGroup, ToCommandTiming, TransferHook,
+    Voltage, LTC681X,
+};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Maximum number of tested channels per device
+const MAX_CHANNELS: usize = 18;
+
+/// Cell voltage self-test (CVST), ADC mode and pattern bits cleared
+const CMD_CVST: u16 = 0b0000_0010_0000_0111;
+
+/// Auxiliary self-test (AXST), ADC mode and pattern bits cleared
+const CMD_AXST: u16 = 0b0000_0100_0000_0111;
+
+/// Status self-test (STATST), ADC mode and pattern bits cleared
+const CMD_STATST: u16 = 0b0000_0100_0000_1111;
+
+/// Diagnose MUX and poll status (DIAGN)
+const CMD_DIAGN: u16 = 0b0000_0111_0001_0101;
+
+/// Execution time of the multiplexer diagnosis (DIAGN) in microseconds
+pub const MUX_DIAGNOSIS_TIME: u32 = 400;
+
+/// Self-test pattern selection (ST bits)
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SelfTestPattern {
+    /// Self-test 1, 0x9555 in most ADC modes
+    Pattern1 = 0b01,
+
+    /// Self-test 2, 0x6AAA in most ADC modes
+    Pattern2 = 0b10,
+}
+
+impl SelfTestPattern {
+    /// Returns the expected register values (regular, alternative) of the given ADC mode
+    /// The alternative value applies in case of CFGAR0=1 configuration.
+    pub fn expected_values(&self, mode: ADCMode) -> (u16, u16) {
+        match (self, mode) {
+            (SelfTestPattern::Pattern1, ADCMode::Fast) => (0x9565, 0x9553),
+            (SelfTestPattern::Pattern2, ADCMode::Fast) => (0x6A9A, 0x6AAC),
+            (SelfTestPattern::Pattern1, _) => (0x9555, 0x9555),
+            (SelfTestPattern::Pattern2, _) => (0x6AAA, 0x6AAA),
+        }
+    }
+}
+
+/// Results of a self-test
+///
+/// C: Identifier of the tested channel, e.g. the device [Channel](crate::monitor::DeviceTypes::Channel)
+#[derive(Clone, Debug)]
+pub struct SelfTestReport<C, const L: usize> {
+    /// Register values per device and channel
+    values: Vec<Vec<(C, u16), MAX_CHANNELS>, L>,
+
+    /// Expected register values (regular, alternative)
+    expected: (u16, u16),
+}
+
+impl<C: Copy, const L: usize> SelfTestReport<C, L> {
+    /// Returns the tested channels and register values of the given device, None if out of bounds
+    pub fn get(&self, device: usize) -> Option<&[(C, u16)]> {
+        self.values.get(device).map(|values| values.as_slice())
+    }
+
+    /// Returns the expected register values (regular, alternative)
+    pub fn expected(&self) -> (u16, u16) {
+        self.expected
+    }
+
+    /// Returns true if all channels matched the expected pattern
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Returns the (device, channel) of all channels not matching the expected pattern
+    pub fn failures(&self) -> impl Iterator<Item = (usize, C)> + '_ {
+        self.values.iter().enumerate().flat_map(move |(device, values)| {
+            values.iter().filter_map(move |(channel, value)| {
+                if *value != self.expected.0 && *value != self.expected.1 {
+                    Some((device, *channel))
+                } else {
+                    None
+                }
+            })
+        })
+    }
+}
+
+/// Results of both ADCs measuring a single overlap cell
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct OverlapMeasurement {
+    /// Result of the first ADC (raw value, 100 uV per LSB)
+    pub first_adc: u16,
+
+    /// Result of the second ADC (raw value, 100 uV per LSB)
+    pub second_adc: u16,
+}
+
+impl OverlapMeasurement {
+    /// Returns the absolute deviation between both ADC results in uV
+    pub fn deviation_uv(&self) -> u32 {
+        self.first_adc.abs_diff(self.second_adc) as u32 * 100
+    }
+
+    /// Returns true if the deviation between both ADC results is within the given tolerance in uV
+    pub fn matches(&self, tolerance_uv: u32) -> bool {
+        self.deviation_uv() <= tolerance_uv
+    }
+}
+
+/// Overlap measurement results of a single device
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct OverlapComparison {
+    /// Results of the first overlap cell (cell 7)
+    /// None in case overlap measurement is not supported by the device
+    pub first: Option<OverlapMeasurement>,
+
+    /// Results of the second overlap cell (cell 13)
+    /// None in case the device has just one overlap cell
+    pub second: Option<OverlapMeasurement>,
+}
+
+impl OverlapComparison {
+    /// Returns true if the results of all overlap cells are within the given tolerance in uV
+    pub fn matches(&self, tolerance_uv: u32) -> bool {
+        self.first
+            .iter()
+            .chain(self.second.iter())
+            .all(|measurement| measurement.matches(tolerance_uv))
+    }
+}
+
+/// Results of a conversion in two different ADC modes
+///
+/// C: Identifier of the converted channel, e.g. the device [Channel](crate::monitor::DeviceTypes::Channel)
+#[derive(Clone, Debug)]
+pub struct ModeComparison<C, const L: usize> {
+    /// Register values (first mode, second mode) per device and channel
+    values: Vec<Vec<(C, u16, u16), MAX_CHANNELS>, L>,
+
+    /// Accepted absolute deviation (raw value, 100 uV per LSB)
+    tolerance: u16,
+}
+
+impl<C: Copy, const L: usize> ModeComparison<C, L> {
+    /// Returns the channels and register values (first mode, second mode) of the given device, None if out of bounds
+    pub fn get(&self, device: usize) -> Option<&[(C, u16, u16)]> {
+        self.values.get(device).map(|values| values.as_slice())
+    }
+
+    /// Returns the accepted absolute deviation (raw value, 100 uV per LSB)
+    pub fn tolerance(&self) -> u16 {
+        self.tolerance
+    }
+
+    /// Returns true if the results of all channels are within the tolerance
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Returns the (device, channel) of all channels diverging beyond the tolerance
+    pub fn failures(&self) -> impl Iterator<Item = (usize, C)> + '_ {
+        self.values.iter().enumerate().flat_map(move |(device, values)| {
+            values.iter().filter_map(move |(channel, first, second)| {
+                if first.abs_diff(*second) > self.tolerance {
+                    Some((device, *channel))
+                } else {
+                    None
+                }
+            })
+        })
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Starts the cell voltage self-test of all cells (CVST command)
+    /// The expected execution time equals the conversion time of all cells.
+    pub fn cell_voltage_self_test(
+        &mut self,
+        mode: ADCMode,
+        pattern: SelfTestPattern,
+    ) -> Result<CommandTime, Error<B, CS>> {
+        self.command_transaction(self_test_command(CMD_CVST, mode, pattern))?;
+        Ok(T::ALL_CELLS.to_conv_command_timing(mode))
+    }
+
+    /// Reads all cell voltage registers and compares them with the expected pattern of the given self-test
+    /// As the active ADC mode set (regular or alternative) is not known by the client, both values are accepted.
+    pub fn verify_cell_voltage_self_test(
+        &mut self,
+        mode: ADCMode,
+        pattern: SelfTestPattern,
+    ) -> Result<SelfTestReport<T::Channel, L>, Error<B, CS>> {
+        let voltages = self.read_voltages(T::ALL_CELLS)?;
+        Ok(voltage_report(voltages, pattern.expected_values(mode)))
+    }
+
+    /// Starts the auxiliary self-test of all GPIOs and the second reference (AXST command)
+    /// The expected execution time equals the conversion time of all GPIOs.
+    pub fn aux_self_test(&mut self, mode: ADCMode, pattern: SelfTestPattern) -> Result<CommandTime, Error<B, CS>> {
+        self.command_transaction(self_test_command(CMD_AXST, mode, pattern))?;
+        Ok(T::ALL_GPIOS.to_conv_command_timing(mode))
+    }
+
+    /// Reads all auxiliary registers and compares them with the expected pattern of the given self-test
+    /// As the active ADC mode set (regular or alternative) is not known by the client, both values are accepted.
+    pub fn verify_aux_self_test(
+        &mut self,
+        mode: ADCMode,
+        pattern: SelfTestPattern,
+    ) -> Result<SelfTestReport<T::Channel, L>, Error<B, CS>> {
+        let voltages = self.read_voltages(T::ALL_GPIOS)?;
+        Ok(voltage_report(voltages, pattern.expected_values(mode)))
+    }
+
+    /// Starts the status self-test of SC, ITMP, VA and VD (STATST command)
+    /// The expected execution time equals the conversion time of all status parameters.
+    pub fn status_self_test(&mut self, mode: ADCMode, pattern: SelfTestPattern) -> Result<CommandTime, Error<B, CS>> {
+        self.command_transaction(self_test_command(CMD_STATST, mode, pattern))?;
+        Ok(StatusGroup::All.to_conv_command_timing(mode))
+    }
+
+    /// Reads the status registers and compares SC, ITMP, VA and VD with the expected pattern of the given self-test
+    /// As the active ADC mode set (regular or alternative) is not known by the client, both values are accepted.
+    pub fn verify_status_self_test(
+        &mut self,
+        mode: ADCMode,
+        pattern: SelfTestPattern,
+    ) -> Result<SelfTestReport<StatusGroup, L>, Error<B, CS>> {
+        let status_a = self.read_register(T::REG_STATUS_A)?;
+        let status_b = self.read_register(T::REG_STATUS_B)?;
+
+        let values = status_a
+            .iter()
+            .zip(status_b.iter())
+            .map(|(status_a, status_b)| {
+                [
+                    (StatusGroup::CellSum, status_a[0]),
+                    (StatusGroup::Temperature, status_a[1]),
+                    (StatusGroup::AnalogVoltage, status_a[2]),
+                    (StatusGroup::DigitalVoltage, status_b[0]),
+                ]
+                .into_iter()
+                .collect()
+            })
+            .collect();
+
+        Ok(SelfTestReport {
+            values,
+            expected: pattern.expected_values(mode),
+        })
+    }
+
+    /// Reads the results of the overlap measurement (ADOL command)
+    /// Returns one item per device in daisy chain
+    pub fn read_overlap_measurement(&mut self) -> Result<[OverlapComparison; L], Error<B, CS>> {
+        let results = self.read_overlap_result()?;
+
+        Ok(results.map(|result| OverlapComparison {
+            first: T::OVERLAP_TEST_REG_1.map(|_| OverlapMeasurement {
+                first_adc: result[0],
+                second_adc: result[1],
+            }),
+            second: T::OVERLAP_TEST_REG_2.map(|_| OverlapMeasurement {
+                first_adc: result[2],
+                second_adc: result[3],
+            }),
+        }))
+    }
+
+    /// Starts the multiplexer diagnosis (DIAGN command)
+    /// The result is stored in the MUXFAIL bit of status register B.
+    pub fn start_mux_diagnosis(&mut self) -> Result<CommandTime, Error<B, CS>> {
+        self.command_transaction(CMD_DIAGN)?;
+        Ok(CommandTime::new(MUX_DIAGNOSIS_TIME, MUX_DIAGNOSIS_TIME))
+    }
+
+    /// Reads the MUXFAIL bits of status register B
+    /// Returns one item per device in daisy chain, true if multiplexer diagnosis passed
+    pub fn read_mux_diagnosis(&mut self) -> Result<[bool; L], Error<B, CS>> {
+        Ok(self.read_status_b()?.map(|status| !status.mux_fail))
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Runs the multiplexer diagnosis (DIAGN command) and reads the MUXFAIL bits after the end of execution
+    /// Returns one item per device in daisy chain, true if multiplexer diagnosis passed
+    ///
+    /// See [wait_conversion](LTC681X::wait_conversion) for waiting and the timeout.
+    pub fn diagnose_mux<D: DelayUs<u32>>(&mut self, delay: &mut D) -> Result<[bool; L], Error<B, CS>> {
+        let timing = self.start_mux_diagnosis()?;
+        self.wait_conversion(timing, delay)?;
+        self.read_mux_diagnosis()
+    }
+
+    /// Converts the given cells in both ADC modes back to back and compares the results
+    /// The tolerance is given as raw value (100 uV per LSB). Discharge is not permitted during conversion.
+    pub fn compare_adc_modes<D: DelayUs<u32>>(
+        &mut self,
+        cells: T::CellSelection,
+        first: ADCMode,
+        second: ADCMode,
+        tolerance: u16,
+        delay: &mut D,
+    ) -> Result<ModeComparison<T::Channel, L>, Error<B, CS>> {
+        let first = self
+            .begin_conversion(ConversionRequest::cells(cells).mode(first))?
+            .wait(delay)?;
+        let second = self
+            .begin_conversion(ConversionRequest::cells(cells).mode(second))?
+            .wait(delay)?;
+
+        Ok(mode_comparison(first, second, tolerance))
+    }
+}
+
+/// Returns the command of the given self-test base command (ADC mode and pattern bits cleared)
+fn self_test_command(base: u16, mode: ADCMode, pattern: SelfTestPattern) -> u16 {
+    base | (mode as u16) << 7 | (pattern as u16) << 5
+}
+
+/// Creates the report based on the read voltages
+fn voltage_report<T: DeviceTypes, const L: usize>(
+    voltages: Vec<Vec<Voltage<T>, MAX_CHANNELS>, L>,
+    expected: (u16, u16),
+) -> SelfTestReport<T::Channel, L> {
+    let values = voltages
+        .iter()
+        .map(|voltages| voltages.iter().map(|voltage| (voltage.channel, voltage.voltage)).collect())
+        .collect();
+
+    SelfTestReport { values, expected }
+}
+
+/// Creates the comparison of both conversion results
+fn mode_comparison<T: DeviceTypes, const L: usize>(
+    first: Vec<Vec<Voltage<T>, MAX_CHANNELS>, L>,
+    second: Vec<Vec<Voltage<T>, MAX_CHANNELS>, L>,
+    tolerance: u16,
+) -> ModeComparison<T::Channel, L> {
+    let values = first
+        .iter()
+        .zip(second.iter())
+        .map(|(first, second)| {
+            first
+                .iter()
+                .zip(second.iter())
+                .map(|(first, second)| (first.channel, first.voltage, second.voltage))
+                .collect()
+        })
+        .collect();
+
+    ModeComparison { values, tolerance }
+}
@@ -0,0 +1,105 @@
+//! Conversion sequences
+//!
+//! A sequence is a list of conversion [steps](Step), e.g. cells → GPIOs → status. [run_sequence](LTC681X::run_sequence)
+//! executes the steps in the given order, waits for each conversion to complete and reads the results before
+//! starting the next step. Cell and GPIO voltages are collected in a [Snapshot], the internal device parameters of
+//! the last status step are returned as well.
+//!
+//! Discharge is not permitted during cell conversions.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, GPIOSelection, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//! use ltc681x::sequence::Step;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let steps = [Step::Cells(CellSelection::Group1), Step::Gpio(GPIOSelection::Group2), Step::Status];
+//! let result = client.run_sequence(ADCMode::Normal, &steps, &mut ExampleDelay::default()).unwrap();
+//!
+//! // Cell 1
+//! assert_eq!(Some(24979), result.voltages.device(0).cell_voltages[0]);
+//! // GPIO 2
+//! assert_eq!(Some(7867), result.voltages.device(0).gpio_voltages[1]);
+//! // Internal device parameters
+//! assert!(result.internal.is_some());
+//! ````
+use crate::monitor::{
+    ADCMode, DeviceTypes, Error, InternalDeviceParameters, PollMethod, StatusGroup, TransferHook, LTC681X,
+};
+use crate::snapshot::Snapshot;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Single conversion step of a sequence
+pub enum Step<T: DeviceTypes> {
+    /// Cell conversion (ADCV command) of the given cell group
+    Cells(T::CellSelection),
+
+    /// GPIO conversion (ADAX command) of the given GPIO group
+    Gpio(T::GPIOSelection),
+
+    /// Measurement of all internal device parameters (ADSTAT command)
+    Status,
+}
+
+impl<T: DeviceTypes> Copy for Step<T> {}
+
+impl<T: DeviceTypes> Clone for Step<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Collected results of a conversion sequence
+#[derive(Debug)]
+pub struct SequenceResult<const L: usize> {
+    /// Cell and GPIO voltages of all cell and GPIO steps
+    pub voltages: Snapshot<L>,
+
+    /// Internal device parameters of the last status step, None if the sequence contains no status step
+    pub internal: Option<Vec<InternalDeviceParameters, L>>,
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Executes the given conversion steps in order and returns the collected results
+    ///
+    /// See [wait_conversion](LTC681X::wait_conversion) for waiting and the timeout of each step.
+    pub fn run_sequence<D: DelayUs<u32>>(
+        &mut self,
+        mode: ADCMode,
+        steps: &[Step<T>],
+        delay: &mut D,
+    ) -> Result<SequenceResult<L>, Error<B, CS>> {
+        let mut result = SequenceResult {
+            voltages: Snapshot::new(),
+            internal: None,
+        };
+
+        for step in steps {
+            match *step {
+                Step::Cells(cells) => {
+                    result.voltages.update(&self.measure_cell_voltages(mode, cells, delay)?);
+                }
+                Step::Gpio(pins) => {
+                    result.voltages.update(&self.begin_gpio_conversion(mode, pins)?.wait(delay)?);
+                }
+                Step::Status => {
+                    result.internal = Some(self.begin_status_conversion(mode, StatusGroup::All)?.wait(delay)?);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,159 @@
+//! Device independent measurement snapshot
+//!
+//! A [Snapshot] stores the latest cell and GPIO voltages of all devices in daisy chain, indexed by cell and GPIO
+//! number. Missing (not yet measured) channels are None.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, GPIOSelection, LTC6813};
+//! use ltc681x::monitor::{LTC681X, LTC681XClient};
+//! use ltc681x::snapshot::Snapshot;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut snapshot: Snapshot<1> = Snapshot::new();
+//!
+//! snapshot.update(&client.read_voltages(CellSelection::Group1).unwrap());
+//! snapshot.update(&client.read_voltages(GPIOSelection::Group2).unwrap());
+//!
+//! // Cell 1
+//! assert_eq!(Some(24979), snapshot.device(0).cell_voltages[0]);
+//! // Cell 2 was not measured
+//! assert_eq!(None, snapshot.device(0).cell_voltages[1]);
+//! // GPIO 2
+//! assert_eq!(Some(7867), snapshot.device(0).gpio_voltages[1]);
+//! ````
+//!
+//! # Single cells
+//!
+//! For spot checks, [read_cell](LTC681X::read_cell) reads just the register group storing the given cell.
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//!# use ltc681x::ltc6813::LTC6813;
+//!# use ltc681x::monitor::LTC681X;
+//!#
+//!# let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! // Cell 7 of first device
+//! assert_eq!(Some(25441), client.read_cell(0, 6).unwrap());
+//!
+//! // Out of bounds
+//! assert_eq!(None, client.read_cell(1, 0).unwrap());
+//! ````
+use crate::monitor::{
+    ChannelIndex, ChannelType, DeviceTypes, Error, LTC681XClient, PollMethod, RegisterLocator, TransferHook, Voltage,
+    LTC681X,
+};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Maximum number of cells per device
+pub const MAX_CELLS: usize = 18;
+
+/// Maximum number of GPIOs per device
+pub const MAX_GPIOS: usize = 9;
+
+/// Measurements of a single device
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct DeviceSnapshot {
+    /// Raw cell voltages, index 0 = cell 1
+    /// Real voltage: voltage * 100 uV
+    pub cell_voltages: [Option<u16>; MAX_CELLS],
+
+    /// Raw GPIO voltages, index 0 = GPIO 1
+    /// Real voltage: voltage * 100 uV
+    pub gpio_voltages: [Option<u16>; MAX_GPIOS],
+
+    /// Raw second reference voltage, used by ratiometric thermistors
+    /// Real voltage: voltage * 100 uV
+    pub reference_voltage: Option<u16>,
+}
+
+/// Measurements of all devices in daisy chain
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Snapshot<const L: usize> {
+    devices: [DeviceSnapshot; L],
+}
+
+impl<const L: usize> Default for Snapshot<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const L: usize> Snapshot<L> {
+    /// Creates an empty snapshot
+    pub fn new() -> Self {
+        Self {
+            devices: [DeviceSnapshot::default(); L],
+        }
+    }
+
+    /// Stores the given voltages, as returned by [read_voltages](crate::monitor::LTC681XClient::read_voltages)
+    pub fn update<T: DeviceTypes, const N: usize>(&mut self, voltages: &Vec<Vec<Voltage<T>, N>, L>) {
+        for (device, voltages) in self.devices.iter_mut().zip(voltages.iter()) {
+            for voltage in voltages {
+                if let Some(index) = voltage.channel.to_cell_index() {
+                    if let Some(slot) = device.cell_voltages.get_mut(index) {
+                        *slot = Some(voltage.voltage);
+                    }
+                }
+
+                if let Some(index) = voltage.channel.to_gpio_index() {
+                    if let Some(slot) = device.gpio_voltages.get_mut(index) {
+                        *slot = Some(voltage.voltage);
+                    }
+                }
+
+                if matches!(voltage.channel.into(), ChannelType::Reference) {
+                    device.reference_voltage = Some(voltage.voltage);
+                }
+            }
+        }
+    }
+
+    /// Returns the measurements of the given device
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn device(&self, index: usize) -> &DeviceSnapshot {
+        &self.devices[index]
+    }
+
+    /// Returns the mutable measurements of the given device
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn device_mut(&mut self, index: usize) -> &mut DeviceSnapshot {
+        &mut self.devices[index]
+    }
+
+    /// Returns the measurements of all devices
+    pub fn devices(&self) -> &[DeviceSnapshot; L] {
+        &self.devices
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Reads the raw voltage of a single cell, index 0 = cell 1
+    /// Just the register group storing the cell is read. Returns None if the device or cell index is out of bounds.
+    pub fn read_cell(&mut self, device_index: usize, cell_index: usize) -> Result<Option<u16>, Error<B, CS>> {
+        if device_index >= L {
+            return Ok(None);
+        }
+
+        let address = T::ALL_CELLS
+            .get_locations()
+            .find(|address| address.channel.to_cell_index() == Some(cell_index));
+
+        match address {
+            Some(address) => Ok(Some(self.read_register(address.register)?[device_index][address.slot])),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Decoded status register groups
+//!
+//! [read_status_a](LTC681X::read_status_a) and [read_status_b](LTC681X::read_status_b) read the status register
+//! groups of all devices and return the decoded values instead of raw register words. The status values are
+//! updated by the [ADSTAT](crate::monitor::LTC681XClient::measure_internal_parameters) command, the OV/UV flags are
+//! updated by each cell conversion.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let status = client.read_status_a().unwrap();
+//! // Sum of all cells in uV => 75.318 V
+//! assert_eq!(75_318_000, status[0].total_voltage);
+//! // Die temperature in °C
+//! assert_eq!("56.31578", status[0].temperature.to_string());
+//! // Raw ITMP register value
+//! assert_eq!(25256, status[0].temperature_raw);
+//!
+//! // Die temperature in tenths of °C
+//! let temperatures = client.read_internal_temperature().unwrap();
+//! assert_eq!(563, temperatures[0]);
+//!
+//! let status = client.read_status_b().unwrap();
+//! // Digital power supply voltage in uV => 5.12 V
+//! assert_eq!(5_120_000, status[0].digital_power);
+//! // Over-voltage flag of cell 5
+//! assert!(status[0].over_voltage[4]);
+//! assert!(status[0].thermal_shutdown);
+//! assert_eq!(1, status[0].revision);
+//! ````
+//!
+//! # Cell voltage flags
+//!
+//! The OV/UV flags are set by the hardware comparators, based on the thresholds of the
+//! [configuration](crate::config::Configuration). [read_cell_flags](LTC681X::read_cell_flags) returns the flags of
+//! all cells as bitfield, including cells 13 - 18 of LTC6812/LTC6813/ADBMS1818 located in auxiliary register D.
+//!
+//! ````
+//! use ltc681x::config::Cell;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! let flags = client.read_cell_flags().unwrap();
+//! assert!(flags[0].is_over_voltage(Cell::Cell5));
+//! assert!(flags[0].is_under_voltage(Cell::Cell6));
+//! assert!(!flags[0].is_over_voltage(Cell::Cell13));
+//! ````
+use crate::config::Cell;
+use crate::monitor::{
+    calc_temperature, DeviceTypes, Error, PollMethod, ToFullCommand, TransferHook, DEFAULT_TEMPERATURE_OFFSET,
+    DEFAULT_TEMPERATURE_SCALE_UV, LTC681X,
+};
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use fixed::types::I16F16;
+
+/// Number of cells covered by the OV/UV flags of status register B
+pub const STATUS_FLAG_CELLS: usize = 12;
+
+/// Decoded status register group A of a single device
+///
+/// Further decoded values may be added in future releases, so use [from_register](Self::from_register) for
+/// construction.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[non_exhaustive]
+pub struct StatusA {
+    /// Sum of all cells (SC) in uV
+    pub total_voltage: u32,
+
+    /// Die temperature (ITMP) in °C as fixed-point number
+    /// In case register value overflows 16-bit integer, this value is set to I16F16::MAX (32767.99998)
+    pub temperature: I16F16,
+
+    /// Voltage of analog power supply (VA) in uV
+    pub analog_power: u32,
+
+    /// Raw register value of the sum of all cells (SC), 3 mV per LSB
+    pub total_voltage_raw: u16,
+
+    /// Raw register value of the die temperature (ITMP), 100 uV per LSB
+    pub temperature_raw: u16,
+
+    /// Raw register value of the analog power supply voltage (VA), 100 uV per LSB
+    pub analog_power_raw: u16,
+}
+
+/// Status register group A, named after the register group of the datasheet
+pub type StatusGroupA = StatusA;
+
+impl StatusA {
+    /// Parses the raw status register A data
+    /// The die temperature is decoded with the default scaling of [DeviceTypes], which matches all devices except
+    /// LTC6804. See [from_register_of](Self::from_register_of) for device-specific decoding.
+    pub fn from_register(data: &[u8; 6]) -> Self {
+        Self::decode(data, DEFAULT_TEMPERATURE_SCALE_UV, DEFAULT_TEMPERATURE_OFFSET)
+    }
+
+    /// Parses the raw status register A data, decoding the die temperature with the scaling of the given device type
+    pub fn from_register_of<T: DeviceTypes>(data: &[u8; 6]) -> Self {
+        Self::decode(data, T::TEMPERATURE_SCALE_UV, T::TEMPERATURE_OFFSET)
+    }
+
+    fn decode(data: &[u8; 6], temperature_scale_uv: u32, temperature_offset: i32) -> Self {
+        let total_voltage_raw = u16::from_le_bytes([data[0], data[1]]);
+        let temperature_raw = u16::from_le_bytes([data[2], data[3]]);
+        let analog_power_raw = u16::from_le_bytes([data[4], data[5]]);
+
+        Self {
+            total_voltage: total_voltage_raw as u32 * 30 * 100,
+            temperature: calc_temperature(temperature_raw, temperature_scale_uv, temperature_offset),
+            analog_power: analog_power_raw as u32 * 100,
+            total_voltage_raw,
+            temperature_raw,
+            analog_power_raw,
+        }
+    }
+}
+
+/// Decoded status register group B of a single device
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct StatusB {
+    /// Voltage of digital power supply (VD) in uV
+    pub digital_power: u32,
+
+    /// Under-voltage flags, index 0 = cell 1
+    pub under_voltage: [bool; STATUS_FLAG_CELLS],
+
+    /// Over-voltage flags, index 0 = cell 1
+    pub over_voltage: [bool; STATUS_FLAG_CELLS],
+
+    /// Thermal shutdown occurred (THSD)
+    pub thermal_shutdown: bool,
+
+    /// Multiplexer self-test failed (MUXFAIL)
+    pub mux_fail: bool,
+
+    /// Revision code (REV)
+    pub revision: u8,
+}
+
+impl StatusB {
+    /// Parses the raw status register B data
+    pub fn from_register(data: &[u8; 6]) -> Self {
+        let mut under_voltage = [false; STATUS_FLAG_CELLS];
+        let mut over_voltage = [false; STATUS_FLAG_CELLS];
+
+        for index in 0..STATUS_FLAG_CELLS {
+            let flags = data[2 + index / 4] >> ((index % 4) * 2);
+            under_voltage[index] = flags & 0b01 != 0;
+            over_voltage[index] = flags & 0b10 != 0;
+        }
+
+        Self {
+            digital_power: u16::from_le_bytes([data[0], data[1]]) as u32 * 100,
+            under_voltage,
+            over_voltage,
+            thermal_shutdown: data[5] & 0b0000_0001 != 0,
+            mux_fail: data[5] & 0b0000_0010 != 0,
+            revision: data[5] >> 4,
+        }
+    }
+}
+
+/// Over/under-voltage comparator flags of all cells of a single device
+/// Bit 0 = cell 1, bits of cells not available on the device are always cleared.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct CellFlags {
+    /// Under-voltage flags
+    pub under_voltage: u32,
+
+    /// Over-voltage flags
+    pub over_voltage: u32,
+}
+
+impl CellFlags {
+    /// Returns true if the under-voltage flag of the given cell is set
+    pub fn is_under_voltage(&self, cell: Cell) -> bool {
+        self.under_voltage & (1 << cell as u32) != 0
+    }
+
+    /// Returns true if the over-voltage flag of the given cell is set
+    pub fn is_over_voltage(&self, cell: Cell) -> bool {
+        self.over_voltage & (1 << cell as u32) != 0
+    }
+
+    /// Returns true if any flag is set
+    pub fn any(&self) -> bool {
+        self.under_voltage != 0 || self.over_voltage != 0
+    }
+
+    /// Adds the interleaved flag pairs (UV = lower bit, OV = upper bit) of the given bytes, starting at the given cell
+    fn add_pairs(&mut self, data: &[u8], first_cell: usize) {
+        for (index, byte) in data.iter().enumerate() {
+            for pair in 0..4 {
+                let cell = first_cell + index * 4 + pair;
+                let flags = byte >> (pair * 2);
+
+                if flags & 0b01 != 0 {
+                    self.under_voltage |= 1 << cell;
+                }
+
+                if flags & 0b10 != 0 {
+                    self.over_voltage |= 1 << cell;
+                }
+            }
+        }
+    }
+
+    /// Clears all flags of cells beyond the given count
+    fn mask(&mut self, cell_count: usize) {
+        let mask = (1u32 << cell_count) - 1;
+        self.under_voltage &= mask;
+        self.over_voltage &= mask;
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Reads and decodes status register group A of all devices
+    /// Returns one item per device in daisy chain
+    pub fn read_status_a(&mut self) -> Result<[StatusA; L], Error<B, CS>> {
+        let data = self.read_daisy_chain_raw(T::REG_STATUS_A.to_read_command())?;
+
+        Ok(data.map(|data| StatusA::from_register_of::<T>(&data)))
+    }
+
+    /// Reads the OV/UV comparator flags of all cells
+    /// Flags of cells 1 - 12 are located in status register B, flags of cells 13 - 18 in auxiliary register D.
+    /// The latter is only read on devices with more than 12 cells. Returns one item per device in daisy chain
+    pub fn read_cell_flags(&mut self) -> Result<[CellFlags; L], Error<B, CS>> {
+        let status_b = self.read_daisy_chain_raw(T::REG_STATUS_B.to_read_command())?;
+
+        let aux_d = match T::REG_AUX_D {
+            Some(register) if T::CELL_COUNT > STATUS_FLAG_CELLS => {
+                Some(self.read_daisy_chain_raw(register.to_read_command())?)
+            }
+            _ => None,
+        };
+
+        Ok(core::array::from_fn(|device| {
+            let mut flags = CellFlags::default();
+            flags.add_pairs(&status_b[device][2..5], 0);
+
+            if let Some(aux_d) = &aux_d {
+                flags.add_pairs(&aux_d[device][4..6], STATUS_FLAG_CELLS);
+            }
+
+            flags.mask(T::CELL_COUNT);
+            flags
+        }))
+    }
+
+    /// Reads the internal die temperature (ITMP) of all devices in tenths of °C
+    /// Returns one item per device in daisy chain, e.g. 563 = 56.3 °C
+    pub fn read_internal_temperature(&mut self) -> Result<[i16; L], Error<B, CS>> {
+        let data = self.read_daisy_chain_raw(T::REG_STATUS_A.to_read_command())?;
+        Ok(data.map(|data| temperature_tenths::<T>(u16::from_le_bytes([data[2], data[3]]))))
+    }
+
+    /// Reads and decodes status register group B of all devices
+    /// Returns one item per device in daisy chain
+    pub fn read_status_b(&mut self) -> Result<[StatusB; L], Error<B, CS>> {
+        let data = self.read_daisy_chain_raw(T::REG_STATUS_B.to_read_command())?;
+        Ok(data.map(|data| StatusB::from_register(&data)))
+    }
+}
+
+/// Converts the raw ITMP value to tenths of °C
+/// Datasheet: ITMP * 100 uV / scale - offset
+fn temperature_tenths<T: DeviceTypes>(value: u16) -> i16 {
+    (value as i64 * 1_000 / T::TEMPERATURE_SCALE_UV as i64 - T::TEMPERATURE_OFFSET as i64 * 10) as i16
+}
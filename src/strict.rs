@@ -0,0 +1,198 @@
+//! Strict wrapper for bench testing
+//!
+//! The [StrictClient] wraps the client and converts every error into an immediate panic, including the failing
+//! operation. For register reads, the frames are validated by the wrapper itself, so the panic message contains
+//! the index of the device and the raw frame (data + PEC) which failed the PEC check.
+//!
+//! **Intended for development builds only**, as panics are not a valid error handling in production.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::{CellSelection, LTC6813};
+//! use ltc681x::monitor::{ADCMode, LTC681X};
+//! use ltc681x::strict::StrictClient;
+//!
+//! let client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//! let mut strict = StrictClient::new(client);
+//!
+//! // No unwrap needed, errors panic
+//! strict.start_conv_cells(ADCMode::Normal, CellSelection::All, true);
+//! let voltages = strict.read_voltages(CellSelection::Group1);
+//! assert_eq!(24979, voltages[0][0].voltage);
+//! ````
+use crate::config::ConfigurationRegisters;
+use crate::conversion::ConversionRequest;
+use crate::monitor::{
+    decode_voltages, ADCMode, CommandTime, DeviceTypes, Error, InternalDeviceParameters, LTC681XClient, NoHook,
+    PollClient, PollMethod, RegisterLocator, SDOLinePolling, StatusGroup, ToFullCommand, TransferHook, Voltage,
+    LTC681X,
+};
+use crate::pwm::PwmRegisters;
+use core::convert::Infallible;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use heapless::Vec;
+
+/// Wrapper panicking on every error
+pub struct StrictClient<B, CS, P, T, const L: usize, H = NoHook>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    client: LTC681X<B, CS, P, T, L, H>,
+}
+
+impl<B, CS, P, T, const L: usize, H> StrictClient<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Wraps the given client
+    pub fn new(client: LTC681X<B, CS, P, T, L, H>) -> Self {
+        Self { client }
+    }
+
+    /// See [LTC681XClient::start_conv_cells]
+    #[allow(deprecated)]
+    pub fn start_conv_cells(&mut self, mode: ADCMode, cells: T::CellSelection, dcp: bool) -> CommandTime {
+        let result = self.client.start_conv_cells(mode, cells, dcp);
+        unwrap("start_conv_cells", result)
+    }
+
+    /// See [LTC681XClient::start_conversion]
+    pub fn start_conversion(&mut self, request: ConversionRequest<T>) -> CommandTime {
+        let result = self.client.start_conversion(request);
+        unwrap("start_conversion", result)
+    }
+
+    /// See [LTC681XClient::start_conv_gpio]
+    #[allow(deprecated)]
+    pub fn start_conv_gpio(&mut self, mode: ADCMode, pins: T::GPIOSelection) -> CommandTime {
+        let result = self.client.start_conv_gpio(mode, pins);
+        unwrap("start_conv_gpio", result)
+    }
+
+    /// See [LTC681XClient::start_overlap_measurement]
+    pub fn start_overlap_measurement(&mut self, mode: ADCMode, dcp: bool) {
+        let result = self.client.start_overlap_measurement(mode, dcp);
+        unwrap("start_overlap_measurement", result)
+    }
+
+    /// See [LTC681XClient::measure_internal_parameters]
+    #[allow(deprecated)]
+    pub fn measure_internal_parameters(&mut self, mode: ADCMode, group: StatusGroup) -> CommandTime {
+        let result = self.client.measure_internal_parameters(mode, group);
+        unwrap("measure_internal_parameters", result)
+    }
+
+    /// See [LTC681XClient::read_register]
+    /// Panics with device index and raw frame if the PEC of any device is invalid.
+    pub fn read_register(&mut self, register: T::Register) -> [[u16; 3]; L] {
+        let command = register.to_read_command();
+        let result = self.client.read_daisy_chain_frames(command);
+        let frames = match result {
+            Ok(frames) => frames,
+            Err(error) => panic!("read_register (command {:02X?}) failed: {:?}", command, error),
+        };
+
+        let mut words = [[0x0u16; 3]; L];
+        for (device, frame) in frames.iter().enumerate() {
+            if !frame.is_valid() {
+                panic!(
+                    "read_register (command {:02X?}) failed: PEC mismatch of device {}, data {:02X?}, PEC {:02X?}",
+                    command, device, frame.data, frame.pec
+                );
+            }
+
+            words[device] = frame.words();
+        }
+
+        words
+    }
+
+    /// See [LTC681XClient::write_register]
+    pub fn write_register(&mut self, register: T::Register, data: [[u8; 6]; L]) {
+        let result = self.client.write_register(register, data);
+        unwrap("write_register", result)
+    }
+
+    /// See [LTC681XClient::write_configuration]
+    pub fn write_configuration<C: ConfigurationRegisters>(&mut self, config: [C; L]) {
+        let result = self.client.write_configuration(config);
+        unwrap("write_configuration", result)
+    }
+
+    /// See [LTC681XClient::write_pwm]
+    pub fn write_pwm<PWM: PwmRegisters>(&mut self, pwm: [PWM; L]) {
+        let result = self.client.write_pwm(pwm);
+        unwrap("write_pwm", result)
+    }
+
+    /// See [LTC681XClient::read_voltages]
+    /// Panics with device index and raw frame if the PEC of any device is invalid.
+    pub fn read_voltages<R: RegisterLocator<T> + 'static>(&mut self, locator: R) -> Vec<Vec<Voltage<T>, 18>, L> {
+        let result = decode_voltages(
+            &locator,
+            |register| Ok::<_, Infallible>(self.read_register(register)),
+            |words, address| Voltage {
+                channel: address.channel,
+                voltage: words[address.slot],
+            },
+        );
+
+        match result {
+            Ok(result) => result,
+            Err(never) => match never {},
+        }
+    }
+
+    /// See [LTC681XClient::read_overlap_result]
+    pub fn read_overlap_result(&mut self) -> [[u16; 4]; L] {
+        let result = self.client.read_overlap_result();
+        unwrap("read_overlap_result", result)
+    }
+
+    /// See [LTC681XClient::read_internal_device_parameters]
+    pub fn read_internal_device_parameters(&mut self) -> Vec<InternalDeviceParameters, L> {
+        let result = self.client.read_internal_device_parameters();
+        unwrap("read_internal_device_parameters", result)
+    }
+
+    /// Returns the wrapped client
+    pub fn client(&mut self) -> &mut LTC681X<B, CS, P, T, L, H> {
+        &mut self.client
+    }
+
+    /// Consumes the wrapper and returns the client
+    pub fn release(self) -> LTC681X<B, CS, P, T, L, H> {
+        self.client
+    }
+}
+
+impl<B, CS, T, const L: usize, H> StrictClient<B, CS, SDOLinePolling, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// See [PollClient::adc_ready]
+    pub fn adc_ready(&mut self) -> bool {
+        let result = self.client.adc_ready();
+        unwrap("adc_ready", result)
+    }
+}
+
+/// Returns the value or panics with the failed operation
+fn unwrap<V, B: Transfer<u8>, CS: OutputPin>(operation: &str, result: Result<V, Error<B, CS>>) -> V {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic!("{} failed: {:?}", operation, error),
+    }
+}
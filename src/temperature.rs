@@ -0,0 +1,382 @@
+//! Thermistor temperature measurement
+//!
+//! Thermistors are usually connected to the GPIO pins as lower part of a voltage divider, which is supplied by
+//! the second reference (VREF2). [LTC681X::read_temperatures] triggers the GPIO conversion, waits until the
+//! conversion is done, reads the voltages and converts them into temperatures (°C) based on the given
+//! [Thermistor] model and [VoltageDivider] parameters.
+//!
+//! ````
+//! use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//! use ltc681x::ltc6813::{Channel, GPIOSelection, LTC6813};
+//! use ltc681x::monitor::LTC681X;
+//! use ltc681x::temperature::{TemperatureConfig, Thermistor, ThermistorModel, VoltageDivider};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // 10k NTC (B = 3435) in series with a 10k resistor supplied by 3 V
+//! let thermistor = Thermistor::new(
+//!     ThermistorModel::Beta { nominal_resistance: 10_000, beta: 3435 },
+//!     VoltageDivider { supply_voltage: 3_000_000, series_resistance: 10_000 },
+//! );
+//!
+//! let config = TemperatureConfig::new(GPIOSelection::Group2, thermistor);
+//! let temperatures = client.read_temperatures(&config, &mut ExampleDelay::default()).unwrap();
+//!
+//! assert_eq!(Channel::GPIO2, temperatures[0][0].channel);
+//! assert_eq!(54, temperatures[0][0].temperature.unwrap().round().to_num::<i32>());
+//! ````
+//!
+//! ## Ratiometric measurement
+//!
+//! Instead of assuming a fixed excitation voltage, the second reference (VREF2) can be measured in the same
+//! conversion pass. The thermistor resistance is then calculated based on the ratio of both voltages, which
+//! cancels out drifts of the reference. In this mode, all GPIOs are converted while only the configured
+//! channels are returned. The configured supply voltage of the divider is ignored.
+//!
+//! ````
+//!# use ltc681x::example::{ExampleCSPin, ExampleDelay, ExampleSPIBus};
+//!# use ltc681x::ltc6813::{Channel, GPIOSelection, LTC6813};
+//!# use ltc681x::monitor::LTC681X;
+//!# use ltc681x::temperature::{TemperatureConfig, Thermistor, ThermistorModel, VoltageDivider};
+//!#
+//!# let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!# let thermistor = Thermistor::new(
+//!#     ThermistorModel::Beta { nominal_resistance: 10_000, beta: 3435 },
+//!#     VoltageDivider { supply_voltage: 3_000_000, series_resistance: 10_000 },
+//!# );
+//! let config = TemperatureConfig::new(GPIOSelection::Group2, thermistor).ratiometric();
+//! let temperatures = client.read_temperatures(&config, &mut ExampleDelay::default()).unwrap();
+//!
+//! assert_eq!(Channel::GPIO2, temperatures[0][0].channel);
+//! assert_eq!(Channel::GPIO7, temperatures[0][1].channel);
+//! ````
+use crate::monitor::{
+    ADCMode, ChannelIndex, ChannelType, DeviceTypes, Error, PollMethod, RegisterLocator, TransferHook, Voltage, LTC681X,
+};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi::Transfer;
+use embedded_hal::digital::v2::OutputPin;
+use fixed::types::I16F16;
+use heapless::Vec;
+
+/// Zero degree Celsius in Kelvin
+const ZERO_CELSIUS: f32 = 273.15;
+
+/// Nominal temperature (25 °C) of Beta model in Kelvin
+const NOMINAL_TEMPERATURE: f32 = ZERO_CELSIUS + 25.0;
+
+/// Resistance/temperature characteristic of a NTC thermistor
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ThermistorModel {
+    /// Beta parameter model
+    Beta {
+        /// Resistance in Ohm at 25 °C
+        nominal_resistance: u32,
+        /// Beta value in Kelvin
+        beta: u32,
+    },
+    /// Steinhart-Hart equation: 1/T = a + b * ln(R) + c * ln(R)^3
+    SteinhartHart { a: f32, b: f32, c: f32 },
+}
+
+impl ThermistorModel {
+    /// Returns the temperature in °C for the given resistance in Ohm
+    pub fn temperature(&self, resistance: f32) -> Option<f32> {
+        if resistance <= 0.0 {
+            return None;
+        }
+
+        let inverse_kelvin = match self {
+            ThermistorModel::Beta {
+                nominal_resistance,
+                beta,
+            } => {
+                if *nominal_resistance == 0 || *beta == 0 {
+                    return None;
+                }
+
+                1.0 / NOMINAL_TEMPERATURE + ln(resistance / *nominal_resistance as f32) / *beta as f32
+            }
+            ThermistorModel::SteinhartHart { a, b, c } => {
+                let ln_r = ln(resistance);
+                a + b * ln_r + c * ln_r * ln_r * ln_r
+            }
+        };
+
+        if inverse_kelvin <= 0.0 {
+            return None;
+        }
+
+        Some(1.0 / inverse_kelvin - ZERO_CELSIUS)
+    }
+}
+
+/// Voltage divider with the thermistor connected between GPIO and ground
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct VoltageDivider {
+    /// Excitation voltage in uV, e.g. 3 V of VREF2
+    pub supply_voltage: u32,
+
+    /// Resistance of the upper resistor in Ohm
+    pub series_resistance: u32,
+}
+
+impl VoltageDivider {
+    /// Returns the thermistor resistance in Ohm for the given GPIO voltage in uV
+    ///
+    /// Returns None in case of open (voltage >= supply voltage) or shorted (zero voltage) thermistor.
+    pub fn resistance(&self, voltage: u32) -> Option<f32> {
+        if voltage == 0 || voltage >= self.supply_voltage {
+            return None;
+        }
+
+        Some(self.series_resistance as f32 * voltage as f32 / (self.supply_voltage - voltage) as f32)
+    }
+
+    /// Returns the thermistor resistance in Ohm based on the ratio of GPIO voltage and the measured
+    /// excitation voltage (both in uV). The configured supply voltage is ignored.
+    pub fn ratiometric_resistance(&self, voltage: u32, reference: u32) -> Option<f32> {
+        VoltageDivider {
+            supply_voltage: reference,
+            series_resistance: self.series_resistance,
+        }
+        .resistance(voltage)
+    }
+}
+
+/// Thermistor model combined with the divider parameters
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Thermistor {
+    model: ThermistorModel,
+    divider: VoltageDivider,
+}
+
+impl Thermistor {
+    pub const fn new(model: ThermistorModel, divider: VoltageDivider) -> Self {
+        Self { model, divider }
+    }
+
+    /// Returns the temperature in °C for the given GPIO voltage in uV
+    /// Returns None if the voltage is out of the valid range (e.g. open or shorted thermistor).
+    pub fn temperature(&self, voltage: u32) -> Option<I16F16> {
+        let resistance = self.divider.resistance(voltage)?;
+        let temperature = self.model.temperature(resistance)?;
+
+        Some(I16F16::saturating_from_num(temperature))
+    }
+
+    /// Returns the temperature in °C based on the GPIO voltage and the measured excitation voltage (both in uV)
+    /// Returns None if the voltage is out of the valid range (e.g. open or shorted thermistor).
+    pub fn ratiometric_temperature(&self, voltage: u32, reference: u32) -> Option<I16F16> {
+        let resistance = self.divider.ratiometric_resistance(voltage, reference)?;
+        let temperature = self.model.temperature(resistance)?;
+
+        Some(I16F16::saturating_from_num(temperature))
+    }
+
+    /// Returns the thermistor model
+    pub fn get_model(&self) -> ThermistorModel {
+        self.model
+    }
+
+    /// Returns the divider parameters
+    pub fn get_divider(&self) -> VoltageDivider {
+        self.divider
+    }
+}
+
+/// Temperature measurement settings
+pub struct TemperatureConfig<T: DeviceTypes> {
+    channels: T::GPIOSelection,
+    mode: ADCMode,
+    thermistor: Thermistor,
+    ratiometric: bool,
+}
+
+impl<T: DeviceTypes> TemperatureConfig<T> {
+    /// Creates settings for converting the given GPIO group using the thermistor model
+    ///
+    /// Defaults to [ADCMode::Normal].
+    pub fn new(channels: T::GPIOSelection, thermistor: Thermistor) -> Self {
+        Self {
+            channels,
+            mode: ADCMode::Normal,
+            thermistor,
+            ratiometric: false,
+        }
+    }
+
+    /// Measures the second reference in the same conversion pass and uses it as excitation voltage
+    pub fn ratiometric(mut self) -> Self {
+        self.ratiometric = true;
+        self
+    }
+
+    /// Sets the ADC mode
+    pub fn mode(mut self, mode: ADCMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the selected GPIO group
+    pub fn get_channels(&self) -> T::GPIOSelection {
+        self.channels
+    }
+
+    /// Returns the selected ADC mode
+    pub fn get_mode(&self) -> ADCMode {
+        self.mode
+    }
+
+    /// Returns the thermistor model and divider parameters
+    pub fn get_thermistor(&self) -> Thermistor {
+        self.thermistor
+    }
+
+    /// Returns true if ratiometric measurement is enabled
+    pub fn is_ratiometric(&self) -> bool {
+        self.ratiometric
+    }
+
+    /// Returns the GPIO group to be converted
+    fn conversion_channels(&self) -> T::GPIOSelection {
+        if self.ratiometric {
+            T::ALL_GPIOS
+        } else {
+            self.channels
+        }
+    }
+}
+
+/// Temperature of a single GPIO channel
+#[derive(Debug)]
+pub struct Temperature<T: DeviceTypes> {
+    /// Corresponding GPIO channel
+    pub channel: T::Channel,
+
+    /// Temperature in °C, None if the voltage is out of the valid range (e.g. open or shorted thermistor)
+    pub temperature: Option<I16F16>,
+}
+
+impl<T: DeviceTypes> Copy for Temperature<T> {}
+
+impl<T: DeviceTypes> Clone for Temperature<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B, CS, P, T, const L: usize, H> LTC681X<B, CS, P, T, L, H>
+where
+    B: Transfer<u8>,
+    CS: OutputPin,
+    P: PollMethod<CS>,
+    T: DeviceTypes,
+    H: TransferHook,
+{
+    /// Converts the configured GPIOs, waits for completion and returns the temperatures
+    /// Returns one vector for each device in daisy chain
+    ///
+    /// See [wait_conversion](LTC681X::wait_conversion) for waiting and the timeout.
+    pub fn read_temperatures<D: DelayUs<u32>>(
+        &mut self,
+        config: &TemperatureConfig<T>,
+        delay: &mut D,
+    ) -> Result<Vec<Vec<Temperature<T>, 9>, L>, Error<B, CS>> {
+        let channels = config.conversion_channels();
+
+        let voltages = self.begin_gpio_conversion(config.mode, channels)?.wait(delay)?;
+        Ok(map_temperatures(&voltages, config))
+    }
+}
+
+/// Converts the GPIO voltages of all devices, other channels (e.g. second reference) are skipped
+fn map_temperatures<T: DeviceTypes, const L: usize>(
+    voltages: &Vec<Vec<Voltage<T>, 18>, L>,
+    config: &TemperatureConfig<T>,
+) -> Vec<Vec<Temperature<T>, 9>, L> {
+    let mut result = Vec::new();
+
+    for device in voltages {
+        let mut temperatures = Vec::new();
+
+        let reference = device
+            .iter()
+            .find(|voltage| matches!(voltage.channel.into(), ChannelType::Reference))
+            .map(|voltage| voltage.voltage as u32 * 100);
+
+        for voltage in device.iter().filter(|voltage| is_selected(config, voltage.channel)) {
+            let gpio_voltage = voltage.voltage as u32 * 100;
+
+            let temperature = if config.ratiometric {
+                reference.and_then(|reference| config.thermistor.ratiometric_temperature(gpio_voltage, reference))
+            } else {
+                config.thermistor.temperature(gpio_voltage)
+            };
+
+            let _ = temperatures.push(Temperature {
+                channel: voltage.channel,
+                temperature,
+            });
+        }
+
+        let _ = result.push(temperatures);
+    }
+
+    result
+}
+
+/// Returns true if the given channel is a GPIO, which is part of the configured group
+fn is_selected<T: DeviceTypes>(config: &TemperatureConfig<T>, channel: T::Channel) -> bool {
+    let index = match channel.to_gpio_index() {
+        None => return false,
+        Some(index) => index,
+    };
+
+    config
+        .channels
+        .get_locations()
+        .any(|location| location.channel.to_gpio_index() == Some(index))
+}
+
+/// Natural logarithm, as core offers no float math functions
+///
+/// Splits x into mantissa m (in range [1, 2)) and exponent e, so ln(x) = e * ln(2) + ln(m).
+/// ln(m) is approximated using the series of 2 * atanh((m - 1) / (m + 1)), which converges quickly for
+/// the given range of m. Absolute error is below 1e-6.
+pub(crate) fn ln(x: f32) -> f32 {
+    if x <= 0.0 || x.is_nan() {
+        return f32::NAN;
+    }
+
+    if x.is_infinite() {
+        return f32::INFINITY;
+    }
+
+    let mut bits = x.to_bits();
+    let mut exponent = ((bits >> 23) & 0xFF) as i32 - 127;
+
+    // Subnormal numbers
+    if exponent == -127 {
+        let normalized = x * (1u32 << 23) as f32;
+        bits = normalized.to_bits();
+        exponent = ((bits >> 23) & 0xFF) as i32 - 127 - 23;
+    }
+
+    let mantissa = f32::from_bits((bits & 0x007F_FFFF) | 0x3F80_0000);
+
+    let z = (mantissa - 1.0) / (mantissa + 1.0);
+    let z2 = z * z;
+
+    let mut term = z;
+    let mut sum = 0.0;
+    let mut divisor = 1.0;
+
+    for _ in 0..8 {
+        sum += term / divisor;
+        term *= z2;
+        divisor += 2.0;
+    }
+
+    exponent as f32 * core::f32::consts::LN_2 + 2.0 * sum
+}
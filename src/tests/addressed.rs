@@ -0,0 +1,75 @@
+//! Tests for addressed (parallel) topology
+use crate::addressed::{address_command, AddressedLTC681X};
+use crate::conversion::ConversionRequest;
+use crate::ltc6811::{CellSelection, Register};
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{LTC681XClient, LTC681X};
+
+#[test]
+fn test_address_command() {
+    assert_eq!(0x9804, address_command(0x0004, 3));
+    assert_eq!(0x8001, address_command(0x0001, 0));
+    assert_eq!(0xF804, address_command(0x0004, 15));
+}
+
+#[test]
+fn test_broadcast_and_addressed_conversion() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0000, 0xf4, 0x6c)
+        .expect_command(0b1001_1011, 0b0110_0000, 0x1B, 0x48)
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(2));
+    let mut devices = AddressedLTC681X::new(client);
+
+    devices
+        .broadcast()
+        .start_conversion(ConversionRequest::cells(CellSelection::All))
+        .unwrap();
+    devices
+        .device(3)
+        .start_conversion(ConversionRequest::cells(CellSelection::All))
+        .unwrap();
+}
+
+#[test]
+fn test_read_register_addressed() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b1001_1000, 0b0000_0100, 0xE8, 0xE6)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(1));
+    let mut devices = AddressedLTC681X::new(client);
+
+    let data = devices.device(3).read_register(Register::CellVoltageA).unwrap();
+    assert_eq!([[24979, 7867, 8878]], data);
+}
+
+#[test]
+fn test_write_coalescing_per_address() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b1000_1000, 0b0000_0001, 0xBE, 0x84)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b1001_0000, 0b0000_0001, 0x21, 0xB4)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(2)).with_write_coalescing();
+    let mut devices = AddressedLTC681X::new(client);
+
+    let data = [[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00]];
+    devices.device(1).write_register(Register::ConfigurationA, data).unwrap();
+    devices.device(2).write_register(Register::ConfigurationA, data).unwrap();
+
+    // Skipped, as already written to the same device
+    devices.device(1).write_register(Register::ConfigurationA, data).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Device address exceeds MAX_ADDRESS")]
+fn test_device_address_out_of_range() {
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(BusMockBuilder::new().into_mock(), get_cs(0));
+    let mut devices = AddressedLTC681X::new(client);
+    devices.device(16);
+}
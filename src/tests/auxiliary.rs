@@ -0,0 +1,267 @@
+//! Tests for mapping GPIOs to sensor roles
+use crate::auxiliary::{AuxChannel, AuxChannelMap, AuxScaling, AuxValue};
+use crate::config::GPIO;
+use crate::ltc6813::{Channel, GPIOSelection};
+use crate::mocks::{BusMockBuilder, MockPin};
+use crate::monitor::{ADCMode, LTC681XClient, LTC681X};
+use crate::snapshot::Snapshot;
+use crate::temperature::{Thermistor, ThermistorModel, VoltageDivider};
+use embedded_hal::blocking::delay::DelayUs;
+use fixed::types::I16F16;
+
+const THERMISTOR: AuxChannel = AuxChannel::Thermistor {
+    thermistor: Thermistor::new(
+        ThermistorModel::Beta {
+            nominal_resistance: 10_000,
+            beta: 3435,
+        },
+        VoltageDivider {
+            supply_voltage: 3_000_000,
+            series_resistance: 10_000,
+        },
+    ),
+    ratiometric: true,
+};
+
+#[test]
+fn test_aux_channel_map_default() {
+    let map = AuxChannelMap::default();
+
+    assert_eq!(AuxChannel::Unused, map.get(GPIO::GPIO1));
+    assert_eq!(AuxChannel::Unused, map.get(GPIO::GPIO9));
+    assert_eq!(AuxChannel::Unused, map.get_by_index(12));
+}
+
+#[test]
+fn test_aux_channel_map_set() {
+    let mut map = AuxChannelMap::new().with(GPIO::GPIO3, AuxChannel::Voltage);
+    map.set(GPIO::GPIO9, THERMISTOR);
+
+    assert_eq!(AuxChannel::Voltage, map.get(GPIO::GPIO3));
+    assert_eq!(AuxChannel::Voltage, map.get_by_index(2));
+    assert_eq!(THERMISTOR, map.get(GPIO::GPIO9));
+    assert_eq!(AuxChannel::Unused, map.get(GPIO::GPIO1));
+}
+
+#[test]
+fn test_aux_channel_convert() {
+    assert_eq!(None, AuxChannel::Unused.convert(1_000_000, None));
+    assert_eq!(
+        Some(AuxValue::Voltage(1_000_000)),
+        AuxChannel::Voltage.convert(1_000_000, None)
+    );
+
+    let current = AuxChannel::CurrentSense {
+        offset: 2_500_000,
+        sensitivity: 40_000,
+    };
+    assert_eq!(
+        Some(AuxValue::Current(I16F16::from_num(-12.5))),
+        current.convert(2_000_000, None)
+    );
+    assert_eq!(
+        Some(AuxValue::Current(I16F16::from_num(25))),
+        current.convert(3_500_000, None)
+    );
+
+    let humidity = AuxChannel::Humidity {
+        offset: 500_000,
+        sensitivity: 20_000,
+    };
+    assert_eq!(
+        Some(AuxValue::Humidity(I16F16::from_num(50))),
+        humidity.convert(1_500_000, None)
+    );
+
+    let invalid = AuxChannel::Humidity {
+        offset: 0,
+        sensitivity: 0,
+    };
+    assert_eq!(Some(AuxValue::Humidity(I16F16::ZERO)), invalid.convert(1_500_000, None));
+}
+
+#[test]
+fn test_aux_channel_convert_thermistor() {
+    match THERMISTOR.convert(1_450_000, Some(2_900_000)) {
+        Some(AuxValue::Temperature(Some(temperature))) => {
+            assert!((temperature - I16F16::from_num(25)).abs() < I16F16::from_num(0.01))
+        }
+        _ => panic!("Unexpected value"),
+    }
+
+    // Reference voltage missing
+    assert_eq!(Some(AuxValue::Temperature(None)), THERMISTOR.convert(1_450_000, None));
+}
+
+#[test]
+fn test_aux_scaling() {
+    assert_eq!(1_000_000, AuxScaling::default().apply(1_000_000));
+    assert_eq!(11_000_000, AuxScaling::divider(100_000, 10_000).apply(1_000_000));
+    assert_eq!(AuxScaling::IDENTITY, AuxScaling::divider(100_000, 0));
+
+    let scaling = AuxScaling::new(2, 1, -500_000);
+    assert_eq!(1_500_000, scaling.apply(1_000_000));
+
+    // Saturating at zero
+    assert_eq!(0, scaling.apply(100_000));
+
+    // Rounding down, no overflow of the intermediate product
+    assert_eq!(2_863_311_530, AuxScaling::new(2, 3, 0).apply(u32::MAX));
+    assert_eq!(u32::MAX, AuxScaling::new(3, 2, 0).apply(u32::MAX));
+
+    // Zero denominator is treated like one
+    assert_eq!(3_000_000, AuxScaling::new(3, 0, 0).apply(1_000_000));
+}
+
+#[test]
+fn test_aux_channel_map_scaling() {
+    let map = AuxChannelMap::new()
+        .with(GPIO::GPIO1, AuxChannel::Voltage)
+        .with(GPIO::GPIO2, AuxChannel::Voltage)
+        .with_scaling(GPIO::GPIO2, AuxScaling::divider(30_000, 10_000));
+
+    assert_eq!(AuxScaling::IDENTITY, map.get_scaling(GPIO::GPIO1));
+    assert_eq!(AuxScaling::divider(30_000, 10_000), map.get_scaling(GPIO::GPIO2));
+
+    assert_eq!(
+        Some(AuxValue::Voltage(1_000_000)),
+        map.convert_by_index(0, 1_000_000, None)
+    );
+    assert_eq!(
+        Some(AuxValue::Voltage(4_000_000)),
+        map.convert_by_index(1, 1_000_000, None)
+    );
+    assert_eq!(None, map.convert_by_index(12, 1_000_000, None));
+
+    // Scaling is applied before the role conversion
+    let mut map = AuxChannelMap::new().with(
+        GPIO::GPIO4,
+        AuxChannel::CurrentSense {
+            offset: 2_500_000,
+            sensitivity: 40_000,
+        },
+    );
+    map.set_scaling(GPIO::GPIO4, AuxScaling::divider(10_000, 10_000));
+    assert_eq!(
+        Some(AuxValue::Current(I16F16::from_num(25))),
+        map.convert_by_index(3, 1_750_000, None)
+    );
+}
+
+#[test]
+fn test_read_aux_channels_sdo_polling() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0110_0000, 0xD3, 0xA0)
+        .expect_poll(true)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1101, 0x64, 0xFE)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        // Register B
+        .expect_command(0b0000_0000, 0b0000_1110, 0x72, 0x9A)
+        .expect_register_read(&[0x00, 0x00, 0x00, 0x00, 0x48, 0x71, 0x88, 0x2C])
+        // Register D
+        .expect_command(0b0000_0000, 0b0000_1111, 0xF9, 0xA8)
+        .expect_register_read(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC2, 0x12])
+        .into_mock();
+
+    let mut cs = MockPin::new();
+    cs.expect_set_high().times(5).returning(move || Ok(()));
+    cs.expect_set_low().times(5).returning(move || Ok(()));
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs).enable_sdo_polling();
+
+    let map = AuxChannelMap::new()
+        .with(GPIO::GPIO1, AuxChannel::Voltage)
+        .with(GPIO::GPIO2, AuxChannel::Voltage)
+        .with_scaling(GPIO::GPIO2, AuxScaling::divider(90_000, 10_000))
+        .with(GPIO::GPIO7, THERMISTOR);
+
+    let mut delay = RecordingDelay::default();
+    let result = monitor.read_aux_channels(&[map], ADCMode::Normal, &mut delay).unwrap();
+    assert_eq!(3, result[0].len());
+    assert_eq!(0, delay.total);
+
+    assert_eq!(Channel::GPIO1, result[0][0].channel);
+    assert_eq!(24979, result[0][0].voltage);
+    assert_eq!(AuxValue::Voltage(2_497_900), result[0][0].value);
+
+    // Raw value is kept, converted value is scaled
+    assert_eq!(Channel::GPIO2, result[0][1].channel);
+    assert_eq!(7867, result[0][1].voltage);
+    assert_eq!(AuxValue::Voltage(7_867_000), result[0][1].value);
+
+    assert_eq!(Channel::GPIO7, result[0][2].channel);
+    assert_eq!(7869, result[0][2].voltage);
+    assert!(matches!(result[0][2].value, AuxValue::Temperature(Some(_))));
+}
+
+#[test]
+fn test_snapshot_aux_values() {
+    let mut snapshot: Snapshot<2> = Snapshot::new();
+    snapshot.device_mut(0).gpio_voltages[0] = Some(12_000);
+    snapshot.device_mut(0).gpio_voltages[1] = Some(12_000);
+    snapshot.device_mut(0).gpio_voltages[2] = Some(15_000);
+    snapshot.device_mut(1).gpio_voltages[2] = Some(15_000);
+    snapshot.device_mut(1).reference_voltage = Some(30_000);
+
+    let maps = [
+        AuxChannelMap::new()
+            .with(GPIO::GPIO1, AuxChannel::Voltage)
+            .with_scaling(GPIO::GPIO1, AuxScaling::divider(100_000, 10_000))
+            .with(GPIO::GPIO3, THERMISTOR),
+        AuxChannelMap::new().with(GPIO::GPIO3, THERMISTOR),
+    ];
+
+    let values = snapshot.aux_values(&maps);
+
+    // Scaled by the map
+    assert_eq!(Some(AuxValue::Voltage(13_200_000)), values[0][0]);
+    // Measured, but unused
+    assert_eq!(None, values[0][1]);
+    // Ratiometric thermistor without second reference
+    assert_eq!(Some(AuxValue::Temperature(None)), values[0][2]);
+    // Not measured
+    assert_eq!(None, values[0][3]);
+
+    // Half of the second reference: nominal resistance
+    match values[1][2] {
+        Some(AuxValue::Temperature(Some(temperature))) => assert!((temperature - I16F16::from_num(25)).abs() < 1),
+        value => panic!("Unexpected value {:?}", value),
+    }
+    assert_eq!(None, values[1][0]);
+}
+
+#[test]
+fn test_snapshot_stores_reference() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_1110, 0x72, 0x9A)
+        .expect_register_read(&[0x00, 0x00, 0x00, 0x00, 0x48, 0x71, 0x88, 0x2C])
+        .into_mock();
+
+    let mut cs = MockPin::new();
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+
+    let mut snapshot: Snapshot<1> = Snapshot::new();
+    snapshot.update(&monitor.read_voltages(GPIOSelection::Group6).unwrap());
+
+    assert_eq!(Some(29_000), snapshot.device(0).reference_voltage);
+    assert_eq!([None; 9], snapshot.device(0).gpio_voltages);
+}
+
+/// Delay mock summing up all delays
+#[derive(Default)]
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
@@ -0,0 +1,225 @@
+//! Tests for safe cell balancing
+use crate::balancing::{BalancingGuard, BalancingStatistics};
+use crate::config::{Cell, Configuration, DischargeTimeout};
+use crate::conversion::ConversionRequest;
+use crate::ltc6813::CellSelection;
+use crate::mocks::{get_cs, BusError, BusMockBuilder, MockPin, MockSPIBus, PinError};
+use crate::monitor::{Error, LTC681XClient, LTC681X};
+
+#[test]
+fn test_balancing_guard_clears_discharge_on_drop() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1F, 0x00, 0x00, 0x00, 0x00, 0x00, 0xCD, 0x8C])
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(4));
+    let mut guard = BalancingGuard::new(client, [Configuration::default()]);
+
+    let mut config = Configuration::default();
+    config.discharge_cell(Cell::Cell3);
+    config.discharge_cell(Cell::Cell13);
+    guard.write_configuration([config]).unwrap();
+
+    assert!(guard.configuration()[0].is_cell_discharging(Cell::Cell3));
+    drop(guard);
+}
+
+#[test]
+fn test_balancing_guard_release_keeps_discharge() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(2));
+    let mut guard = BalancingGuard::new(client, [Configuration::default()]);
+
+    let mut config = Configuration::default();
+    config.discharge_cell(Cell::Cell3);
+    guard.write_configuration([config]).unwrap();
+
+    let _client = guard.release();
+}
+
+#[test]
+fn test_balancing_guard_fail_safe_on_write_error() {
+    let mut bus = MockSPIBus::new();
+    bus.expect_transfer().times(2).returning(move |_| Err(BusError::Error1));
+
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(2).returning(move || Ok(()));
+    cs.expect_set_high().times(2).returning(move || Ok(()));
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+    let mut guard = BalancingGuard::new(client, [Configuration::default()]);
+
+    let mut config = Configuration::default();
+    config.discharge_cell(Cell::Cell3);
+
+    match guard.write_configuration([config]).unwrap_err() {
+        Error::TransferError(_) => {}
+        _ => panic!("Unexpected error type"),
+    }
+
+    assert!(!guard.configuration()[0].is_cell_discharging(Cell::Cell3));
+    let _client = guard.release();
+}
+
+#[test]
+fn test_balancing_guard_with_client_error() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .into_mock();
+
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Err(PinError::Error1));
+    cs.expect_set_low().times(2).returning(move || Ok(()));
+    cs.expect_set_high().times(2).returning(move || Ok(()));
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+    let mut guard = BalancingGuard::new(client, [Configuration::default()]);
+
+    let result = guard.with_client(|client| {
+        client.start_conversion(ConversionRequest::cells(CellSelection::All).discharge_permitted(true))
+    });
+    match result.unwrap_err() {
+        Error::CSPinError(_) => {}
+        _ => panic!("Unexpected error type"),
+    }
+
+    let _client = guard.release();
+}
+
+#[test]
+fn test_balancing_guard_auto_expire() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xFA, 0x00, 0x00, 0x00, 0x04, 0x10, 0x21, 0x1A])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xFA, 0x00, 0x00, 0x00, 0x00, 0x10, 0x9D, 0x7C])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(4));
+    let mut guard = BalancingGuard::new(client, [Configuration::default()]);
+    assert_eq!(None, guard.get_auto_expire());
+
+    guard.set_auto_expire(1_000).unwrap();
+    assert_eq!(Some(DischargeTimeout::HalfMinute), guard.get_auto_expire());
+
+    // Rejected period keeps the previous timeout
+    assert!(guard.set_auto_expire(7_200_000).is_err());
+    assert_eq!(Some(DischargeTimeout::HalfMinute), guard.get_auto_expire());
+
+    let mut config = Configuration::default();
+    config.discharge_cell(Cell::Cell3);
+    guard.write_configuration([config]).unwrap();
+}
+
+#[test]
+fn test_clear_discharge_cells() {
+    let mut config = Configuration::default();
+    config.discharge_cell(Cell::Cell1);
+    config.discharge_cell(Cell::Cell9);
+    config.discharge_cell(Cell::Cell12);
+    config.discharge_cell(Cell::Cell18);
+
+    assert!(config.is_cell_discharging(Cell::Cell1));
+    assert!(config.is_cell_discharging(Cell::Cell9));
+    assert!(config.is_cell_discharging(Cell::Cell12));
+    assert!(config.is_cell_discharging(Cell::Cell18));
+    assert!(!config.is_cell_discharging(Cell::Cell2));
+
+    config.clear_discharge_cells();
+
+    assert!(!config.is_cell_discharging(Cell::Cell1));
+    assert!(!config.is_cell_discharging(Cell::Cell9));
+    assert!(!config.is_cell_discharging(Cell::Cell12));
+    assert!(!config.is_cell_discharging(Cell::Cell18));
+}
+
+#[test]
+fn test_statistics_record() {
+    let mut statistics: BalancingStatistics<2> = BalancingStatistics::new();
+
+    let mut first = Configuration::default();
+    first.discharge_cell(Cell::Cell1);
+    first.discharge_cell(Cell::Cell18);
+
+    let mut second = Configuration::default();
+    second.discharge_cell(Cell::Cell5);
+
+    statistics.record(&[first.clone(), second], 1_000);
+    statistics.record(&[first, Configuration::default()], 500);
+
+    assert_eq!(1_500, statistics.total_time());
+    assert_eq!(1_500, statistics.on_time(0, Cell::Cell1));
+    assert_eq!(1_500, statistics.on_time(0, Cell::Cell18));
+    assert_eq!(0, statistics.on_time(0, Cell::Cell5));
+    assert_eq!(1_000, statistics.on_time(1, Cell::Cell5));
+    assert_eq!(0, statistics.on_time(1, Cell::Cell1));
+
+    assert_eq!(1000, statistics.duty(0, Cell::Cell1));
+    assert_eq!(666, statistics.duty(1, Cell::Cell5));
+    assert_eq!(0, statistics.duty(1, Cell::Cell6));
+}
+
+#[test]
+fn test_statistics_flagged_cells() {
+    let mut statistics: BalancingStatistics<2> = BalancingStatistics::new();
+    assert_eq!(0, statistics.duty(0, Cell::Cell1));
+    assert_eq!(None, statistics.flagged_cells(0).next());
+
+    let mut first = Configuration::default();
+    first.discharge_cell(Cell::Cell2);
+
+    let mut second = Configuration::default();
+    second.discharge_cell(Cell::Cell7);
+
+    statistics.record(&[first.clone(), second], 900);
+    statistics.record(&[first, Configuration::default()], 100);
+
+    let mut flagged = statistics.flagged_cells(900);
+    assert_eq!(Some((0, Cell::Cell2)), flagged.next());
+    assert_eq!(None, flagged.next());
+
+    let mut flagged = statistics.flagged_cells(500);
+    assert_eq!(Some((0, Cell::Cell2)), flagged.next());
+    assert_eq!(Some((1, Cell::Cell7)), flagged.next());
+    assert_eq!(None, flagged.next());
+}
+
+#[test]
+fn test_statistics_restore() {
+    let mut statistics: BalancingStatistics<1> = BalancingStatistics::new();
+
+    let mut config = Configuration::default();
+    config.discharge_cell(Cell::Cell4);
+    statistics.record(&[config.clone()], 2_000);
+
+    let mut restored = BalancingStatistics::restore(*statistics.on_times(), statistics.total_time());
+    assert_eq!(statistics, restored);
+
+    restored.record(&[config], 1_000);
+    assert_eq!(3_000, restored.on_time(0, Cell::Cell4));
+    assert_eq!(3_000, restored.total_time());
+
+    restored.reset();
+    assert_eq!(0, restored.on_time(0, Cell::Cell4));
+    assert_eq!(0, restored.total_time());
+}
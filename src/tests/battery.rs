@@ -0,0 +1,137 @@
+//! Tests for generic battery monitor interface
+use crate::battery::{BatteryMonitor, ChainMonitor};
+use crate::config::{Cell, DischargeControl};
+use crate::mocks::{get_cs, BusMockBuilder, MockPin};
+use crate::monitor::LTC681X;
+use crate::snapshot::{MAX_CELLS, MAX_GPIOS};
+use embedded_hal::blocking::delay::DelayUs;
+
+#[test]
+fn test_measure_cells_delayed() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0000, 0xF4, 0x6C)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register B
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6810(bus, get_cs(3));
+    let mut monitor = ChainMonitor::new(client, RecordingDelay::default());
+
+    let snapshot = monitor.measure_cells().unwrap();
+    assert_eq!(
+        [Some(24979), Some(7867), Some(8878), Some(26333), Some(7538), Some(7330)],
+        snapshot.device(0).cell_voltages[..6]
+    );
+    assert_eq!(None, snapshot.device(0).cell_voltages[6]);
+
+    // Longer (alternative) timing of LTC6810 all cells conversion
+    assert_eq!(3026, monitor.delay.total);
+}
+
+#[test]
+fn test_measure_cells_polled() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0000, 0xF4, 0x6C)
+        .expect_poll(false)
+        .expect_poll(true)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register B
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6810(bus, get_cs(3)).enable_sdo_polling();
+    let mut monitor = ChainMonitor::new(client, RecordingDelay::default());
+
+    let snapshot = monitor.measure_cells().unwrap();
+    assert_eq!(Some(24979), snapshot.device(0).cell_voltages[0]);
+    assert_eq!(Some(7330), snapshot.device(0).cell_voltages[5]);
+
+    // Single poll interval
+    assert_eq!(100, monitor.delay.total);
+}
+
+#[test]
+fn test_measure_temperatures_disabled() {
+    let bus = BusMockBuilder::new().into_mock();
+
+    let client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6810(bus, MockPin::new());
+    let mut monitor = ChainMonitor::new(client, RecordingDelay::default());
+
+    assert_eq!([[None; MAX_GPIOS]; 2], monitor.measure_temperatures().unwrap());
+}
+
+#[test]
+fn test_set_discharge() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6810(bus, get_cs(1));
+    let mut monitor = ChainMonitor::new(client, RecordingDelay::default());
+
+    let mut cells = [[false; MAX_CELLS]; 1];
+    cells[0][2] = true;
+    // Not available on LTC6810
+    cells[0][10] = true;
+
+    monitor.set_discharge(&cells).unwrap();
+    let config = &monitor.configuration()[0];
+    assert!(DischargeControl::is_cell_discharging(config, Cell::Cell3));
+    assert!(!DischargeControl::is_cell_discharging(config, Cell::Cell11));
+}
+
+#[test]
+fn test_self_test() {
+    let bus = BusMockBuilder::new()
+        // Cell voltage self-test
+        .expect_command(0b0000_0011, 0b0010_0111, 0xB4, 0x1C)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        // Auxiliary self-test
+        .expect_command(0b0000_0101, 0b0010_0111, 0x93, 0xD0)
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_command(0b0000_0000, 0b0000_1110, 0x72, 0x9A)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        // Status self-test: VD of device 2 does not match
+        .expect_command(0b0000_0101, 0b0010_1111, 0x7B, 0xDE)
+        .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x55, 0x95, 0x00, 0x00, 0x00, 0x10, 0x82, 0x5C])
+        .expect_register_read(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x99, 0x3C])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6810(bus, get_cs(9));
+    let mut monitor = ChainMonitor::new(client, RecordingDelay::default());
+
+    assert_eq!([true, false], monitor.self_test().unwrap());
+    // Longer (alternative) timing of each self-test
+    assert_eq!(3026 + 3023 + 2000, monitor.delay.total);
+}
+
+#[derive(Default)]
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
@@ -0,0 +1,58 @@
+//! Tests for PEC-tolerant bring-up mode
+use crate::ltc6813::{CellSelection, Channel, Register};
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::LTC681X;
+
+#[test]
+fn test_bring_up_read_register() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x95])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(1));
+    let frames = monitor.bring_up().read_register(Register::CellVoltageA).unwrap();
+
+    assert!(frames[0].pec_valid);
+    assert_eq!([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22], frames[0].data);
+    assert_eq!([0x9A, 0x1C], frames[0].pec);
+
+    // Data is returned despite PEC mismatch
+    assert!(!frames[1].pec_valid);
+    assert_eq!([0x11, 0x95], frames[1].pec);
+    assert_eq!([26333, 7538, 7330], frames[1].words());
+}
+
+#[test]
+fn test_bring_up_read_voltages() {
+    let bus = BusMockBuilder::new()
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x43])
+        // Register E
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(3));
+    let mut debug = monitor.bring_up();
+
+    let result = debug.read_voltages(CellSelection::Group1).unwrap();
+    assert_eq!(3, result[0].len());
+
+    assert_eq!(Channel::Cell1, result[0][0].channel);
+    assert_eq!(24979, result[0][0].voltage);
+    assert!(result[0][0].pec_valid);
+
+    assert_eq!(Channel::Cell7, result[0][1].channel);
+    assert_eq!(25441, result[0][1].voltage);
+    assert!(!result[0][1].pec_valid);
+
+    assert_eq!(Channel::Cell13, result[0][2].channel);
+    assert_eq!(25822, result[0][2].voltage);
+    assert!(result[0][2].pec_valid);
+}
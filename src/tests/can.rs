@@ -0,0 +1,109 @@
+//! Tests for CAN frame packing
+use crate::can::{decode_frame, CanFrameError, FrameEncoder, CLASSIC_FRAME_LEN, FD_FRAME_LEN};
+use crate::snapshot::Snapshot;
+use alloc::vec::Vec;
+
+#[test]
+fn test_encode_classic_frames() {
+    let mut snapshot: Snapshot<2> = Snapshot::new();
+    snapshot.device_mut(0).cell_voltages[0] = Some(0x1234);
+    snapshot.device_mut(0).cell_voltages[4] = Some(0xABCD);
+    snapshot.device_mut(1).gpio_voltages[8] = Some(0x0102);
+
+    let frames: Vec<[u8; 8]> = FrameEncoder::<2, CLASSIC_FRAME_LEN>::new(&snapshot).collect();
+    assert_eq!(3, frames.len());
+
+    assert_eq!([0x00, 0x00, 0x34, 0x12, 0xFF, 0xFF, 0xFF, 0xFF], frames[0]);
+    assert_eq!([0x00, 0x03, 0xFF, 0xFF, 0xCD, 0xAB, 0xFF, 0xFF], frames[1]);
+    assert_eq!([0x01, 0x46, 0xFF, 0xFF, 0xFF, 0xFF, 0x02, 0x01], frames[2]);
+}
+
+#[test]
+fn test_encode_fd_frames() {
+    let mut snapshot: Snapshot<1> = Snapshot::new();
+    for (index, voltage) in snapshot.device_mut(0).cell_voltages.iter_mut().enumerate() {
+        *voltage = Some(index as u16);
+    }
+    snapshot.device_mut(0).gpio_voltages[0] = Some(0x5555);
+
+    let frames: Vec<[u8; 64]> = FrameEncoder::<1, FD_FRAME_LEN>::new(&snapshot).collect();
+    assert_eq!(2, frames.len());
+
+    assert_eq!([0x00, 0x00, 0x00, 0x00, 0x01, 0x00], frames[0][0..6]);
+    assert_eq!([0x11, 0x00], frames[0][36..38]);
+    assert!(frames[0][38..].iter().all(|byte| *byte == 0xFF));
+
+    assert_eq!([0x00, 0x40, 0x55, 0x55, 0xFF, 0xFF], frames[1][0..6]);
+}
+
+#[test]
+fn test_encode_empty_snapshot() {
+    let snapshot: Snapshot<3> = Snapshot::new();
+    assert_eq!(0, FrameEncoder::<3, CLASSIC_FRAME_LEN>::new(&snapshot).count());
+}
+
+#[test]
+fn test_encode_decode_round_trip() {
+    let mut snapshot: Snapshot<4> = Snapshot::new();
+    snapshot.device_mut(0).cell_voltages[17] = Some(36_000);
+    snapshot.device_mut(2).cell_voltages[8] = Some(37_000);
+    snapshot.device_mut(3).gpio_voltages[3] = Some(15_000);
+
+    let mut classic: Snapshot<4> = Snapshot::new();
+    for frame in FrameEncoder::<4, CLASSIC_FRAME_LEN>::new(&snapshot) {
+        decode_frame(&frame, &mut classic).unwrap();
+    }
+    assert_eq!(snapshot, classic);
+
+    let mut fd: Snapshot<4> = Snapshot::new();
+    for frame in FrameEncoder::<4, FD_FRAME_LEN>::new(&snapshot) {
+        decode_frame(&frame, &mut fd).unwrap();
+    }
+    assert_eq!(snapshot, fd);
+}
+
+#[test]
+fn test_decode_not_available_clears_value() {
+    let mut snapshot: Snapshot<1> = Snapshot::new();
+    snapshot.device_mut(0).cell_voltages[1] = Some(36_000);
+
+    decode_frame(&[0x00, 0x00, 0x01, 0x00, 0xFF, 0xFF, 0x02, 0x00], &mut snapshot).unwrap();
+
+    assert_eq!(Some(1), snapshot.device(0).cell_voltages[0]);
+    assert_eq!(None, snapshot.device(0).cell_voltages[1]);
+    assert_eq!(Some(2), snapshot.device(0).cell_voltages[2]);
+}
+
+#[test]
+fn test_decode_errors() {
+    let mut snapshot: Snapshot<2> = Snapshot::new();
+
+    assert_eq!(
+        Err(CanFrameError::InvalidLength),
+        decode_frame(&[0x00, 0x00, 0x01], &mut snapshot)
+    );
+    assert_eq!(
+        Err(CanFrameError::InvalidLength),
+        decode_frame(&[0x00, 0x00, 0x01, 0x00, 0x02], &mut snapshot)
+    );
+    assert_eq!(
+        Err(CanFrameError::InvalidDevice(2)),
+        decode_frame(&[0x02, 0x00, 0x01, 0x00], &mut snapshot)
+    );
+    assert_eq!(
+        Err(CanFrameError::InvalidKind(2)),
+        decode_frame(&[0x00, 0x80, 0x01, 0x00], &mut snapshot)
+    );
+    assert_eq!(
+        Err(CanFrameError::InvalidIndex(9)),
+        decode_frame(&[0x00, 0x49, 0x01, 0x00], &mut snapshot)
+    );
+    assert_eq!(
+        Err(CanFrameError::InvalidIndex(9)),
+        decode_frame(&[0x00, 0x48, 0x01, 0x00, 0x02, 0x00], &mut snapshot)
+    );
+
+    // Padding beyond the available slots is accepted
+    decode_frame(&[0x00, 0x48, 0x01, 0x00, 0xFF, 0xFF], &mut snapshot).unwrap();
+    assert_eq!(Some(1), snapshot.device(0).gpio_voltages[8]);
+}
@@ -0,0 +1,81 @@
+//! Tests for bus capture
+use crate::capture::{decode_capture, CaptureBus, CaptureError, Direction, CAPTURE_MAGIC};
+use crate::codec;
+use crate::ltc6813::Register;
+use crate::mocks::{BusError, BusMockBuilder, MockPin, MockSPIBus};
+use crate::monitor::{LTC681XClient, LTC681X};
+use embedded_hal::blocking::spi::Transfer;
+
+#[test]
+fn test_capture_read_register() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(CaptureBus::new(bus), cs);
+    monitor.read_register(Register::CellVoltageA).unwrap();
+
+    let capture = monitor.release().0.take_capture();
+    let records = decode_capture(&capture).unwrap();
+    assert_eq!(4, records.len());
+
+    assert_eq!(Direction::Sent, records[0].direction);
+    assert_eq!(vec![0x00, 0x04, 0x07, 0xC2], records[0].frame);
+
+    assert_eq!(Direction::Received, records[1].direction);
+
+    assert_eq!(Direction::Sent, records[2].direction);
+    assert_eq!(vec![0xff; 8], records[2].frame);
+
+    assert_eq!(Direction::Received, records[3].direction);
+    assert_eq!(
+        Ok([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22]),
+        codec::decode_data_frame(&records[3].frame)
+    );
+
+    assert!(records[0].timestamp <= records[3].timestamp);
+}
+
+#[test]
+fn test_capture_transfer_error() {
+    let mut bus = MockSPIBus::new();
+    bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
+
+    let mut capture_bus = CaptureBus::new(bus);
+    assert_eq!(Err(BusError::Error1), capture_bus.transfer(&mut [0x1, 0x2]).map(|_| ()));
+
+    let records = decode_capture(capture_bus.capture()).unwrap();
+    assert_eq!(1, records.len());
+    assert_eq!(vec![0x1, 0x2], records[0].frame);
+}
+
+#[test]
+fn test_take_capture_restarts() {
+    let mut capture_bus = CaptureBus::new(BusMockBuilder::new().into_mock());
+    let capture = capture_bus.take_capture();
+
+    assert_eq!(CAPTURE_MAGIC.to_vec(), capture);
+    assert_eq!(CAPTURE_MAGIC, capture_bus.capture());
+    assert!(decode_capture(&capture).unwrap().is_empty());
+}
+
+#[test]
+fn test_decode_capture_errors() {
+    assert_eq!(Err(CaptureError::InvalidMagic), decode_capture(b"LTX"));
+    assert_eq!(Err(CaptureError::Truncated), decode_capture(b"LTCC\x01\x00"));
+
+    let mut capture = CAPTURE_MAGIC.to_vec();
+    capture.extend_from_slice(&[0x0; 8]);
+    capture.extend_from_slice(&[0x2, 0x0, 0x0]);
+    assert_eq!(Err(CaptureError::InvalidDirection(2)), decode_capture(&capture));
+
+    let mut capture = CAPTURE_MAGIC.to_vec();
+    capture.extend_from_slice(&[0x0; 8]);
+    capture.extend_from_slice(&[0x0, 0x4, 0x0, 0x1]);
+    assert_eq!(Err(CaptureError::Truncated), decode_capture(&capture));
+}
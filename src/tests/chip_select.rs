@@ -0,0 +1,95 @@
+//! Tests for CS timing wrapper
+use crate::chip_select::{CSTiming, TimedChipSelect};
+use crate::conversion::ConversionRequest;
+use crate::ltc6813::CellSelection;
+use crate::mocks::{get_cs, BusMockBuilder, MockPin, PinError};
+use crate::monitor::{LTC681XClient, LTC681X};
+use core::cell::RefCell;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::OutputPin;
+
+#[test]
+fn test_cs_timing_defaults() {
+    let timing = CSTiming::new();
+    assert_eq!(0, timing.get_ready_time());
+    assert_eq!(0, timing.get_dwell_time());
+
+    let timing = timing.ready_time(10).dwell_time(5);
+    assert_eq!(10, timing.get_ready_time());
+    assert_eq!(5, timing.get_dwell_time());
+}
+
+#[test]
+fn test_cs_timing_delays() {
+    let delays = RefCell::new([0u32; 2]);
+    let mut cs = TimedChipSelect::new(
+        get_cs(2),
+        RecordingDelay { delays: &delays },
+        CSTiming::new().ready_time(10).dwell_time(5),
+    );
+
+    cs.set_low().unwrap();
+    assert_eq!([1, 10], *delays.borrow());
+
+    cs.set_high().unwrap();
+    assert_eq!([2, 15], *delays.borrow());
+
+    cs.set_low().unwrap();
+    assert_eq!([3, 25], *delays.borrow());
+
+    cs.set_high().unwrap();
+    assert_eq!([4, 30], *delays.borrow());
+}
+
+#[test]
+fn test_cs_timing_no_delay_if_zero() {
+    let delays = RefCell::new([0u32; 2]);
+    let mut cs = TimedChipSelect::new(get_cs(1), RecordingDelay { delays: &delays }, CSTiming::new());
+
+    cs.set_low().unwrap();
+    cs.set_high().unwrap();
+    assert_eq!([0, 0], *delays.borrow());
+}
+
+#[test]
+fn test_cs_timing_pin_error() {
+    let mut pin = MockPin::new();
+    pin.expect_set_low().times(1).returning(move || Err(PinError::Error1));
+
+    let delays = RefCell::new([0u32; 2]);
+    let mut cs = TimedChipSelect::new(pin, RecordingDelay { delays: &delays }, CSTiming::new().ready_time(10));
+
+    assert_eq!(Err(PinError::Error1), cs.set_low());
+    assert_eq!([0, 0], *delays.borrow());
+}
+
+#[test]
+fn test_client_with_cs_timing() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .into_mock();
+
+    let delays = RefCell::new([0u32; 2]);
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(1)).with_cs_timing(
+        CSTiming::new().ready_time(10).dwell_time(5),
+        RecordingDelay { delays: &delays },
+    );
+
+    monitor
+        .start_conversion(ConversionRequest::cells(CellSelection::All).discharge_permitted(true))
+        .unwrap();
+    assert_eq!([2, 15], *delays.borrow());
+}
+
+/// Records the number of delays and the total delay time
+struct RecordingDelay<'a> {
+    delays: &'a RefCell<[u32; 2]>,
+}
+
+impl DelayUs<u32> for RecordingDelay<'_> {
+    fn delay_us(&mut self, us: u32) {
+        let mut delays = self.delays.borrow_mut();
+        delays[0] += 1;
+        delays[1] += us;
+    }
+}
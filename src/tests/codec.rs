@@ -0,0 +1,186 @@
+//! Tests for transport-independent frame codec
+use crate::codec::{
+    decode_chain, decode_command, decode_data_frame, decode_frame_words, decode_register_words, encode_chain,
+    encode_command, encode_data_frame, encode_register_words, frame_refs, frames, DataFrame, DataFrameRef, FrameError,
+};
+
+#[test]
+fn test_encode_command() {
+    assert_eq!([0x00, 0x04, 0x07, 0xC2], encode_command(0x0004));
+    assert_eq!([0x03, 0x60, 0xF4, 0x6C], encode_command(0x0360));
+}
+
+#[test]
+fn test_decode_command() {
+    assert_eq!(Ok(0x0004), decode_command(&[0x00, 0x04, 0x07, 0xC2]));
+    assert_eq!(
+        Err(FrameError::ChecksumMismatch),
+        decode_command(&[0x00, 0x04, 0x07, 0xC3])
+    );
+    assert_eq!(Err(FrameError::InvalidLength), decode_command(&[0x00, 0x04, 0x07]));
+}
+
+#[test]
+fn test_encode_data_frame() {
+    assert_eq!(
+        [0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE],
+        encode_data_frame(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6])
+    );
+}
+
+#[test]
+fn test_decode_data_frame() {
+    assert_eq!(
+        Ok([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22]),
+        decode_data_frame(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+    );
+    assert_eq!(
+        Err(FrameError::ChecksumMismatch),
+        decode_data_frame(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1D])
+    );
+    assert_eq!(Err(FrameError::InvalidLength), decode_data_frame(&[0x93, 0x61]));
+}
+
+#[test]
+fn test_decode_frame_words() {
+    assert_eq!(
+        Ok([24979, 7867, 8878]),
+        decode_frame_words(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+    );
+    assert_eq!(
+        Err(FrameError::ChecksumMismatch),
+        decode_frame_words(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1D])
+    );
+    assert_eq!(
+        Err(FrameError::InvalidLength),
+        decode_frame_words(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C, 0x00])
+    );
+}
+
+#[test]
+fn test_data_frame_from_bytes() {
+    let bytes = [0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C];
+    let frame = DataFrame::from_bytes(&bytes).unwrap();
+
+    assert_eq!([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22], frame.data);
+    assert_eq!([0x9A, 0x1C], frame.pec);
+    assert!(frame.is_valid());
+    assert_eq!([24979, 7867, 8878], frame.words());
+
+    assert_eq!(Err(FrameError::InvalidLength), DataFrame::from_bytes(&bytes[..7]));
+}
+
+#[test]
+fn test_data_frame_ref() {
+    let bytes = [0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C];
+    let frame = DataFrameRef::new(&bytes).unwrap();
+
+    // Data and PEC are borrowed from the given buffer
+    assert!(core::ptr::eq(&bytes[0], &frame.data()[0]));
+    assert_eq!(&bytes[6..], frame.pec());
+    assert!(frame.is_valid());
+    assert_eq!([24979, 7867, 8878], frame.words());
+    assert_eq!(DataFrame::from_bytes(&bytes).unwrap(), frame.to_owned());
+
+    assert_eq!(Err(FrameError::InvalidLength), DataFrameRef::new(&bytes[..7]));
+    assert!(!DataFrameRef::default().is_valid());
+}
+
+#[test]
+fn test_frame_refs() {
+    let response = [
+        0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C, 0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x95,
+    ];
+
+    let mut frames = frame_refs(&response).unwrap();
+    assert_eq!(2, frames.len());
+    assert!(frames.next().unwrap().is_valid());
+
+    let frame = frames.next().unwrap();
+    assert!(!frame.is_valid());
+    assert_eq!([26333, 7538, 7330], frame.words());
+
+    assert_eq!(
+        Err(FrameError::InvalidLength),
+        frame_refs(&response[..15]).map(|frames| frames.len())
+    );
+}
+
+#[test]
+fn test_frames() {
+    let response = [
+        0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C, 0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x95,
+    ];
+
+    let mut frames = frames(&response).unwrap();
+    assert_eq!(2, frames.len());
+    assert!(frames.next().unwrap().is_valid());
+
+    let frame = frames.next().unwrap();
+    assert!(!frame.is_valid());
+    assert_eq!([26333, 7538, 7330], frame.words());
+}
+
+#[test]
+fn test_frames_unaligned() {
+    let mut buffer = [0x0; 9];
+    buffer[1..].copy_from_slice(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C]);
+
+    // No alignment requirement, so any offset within the buffer is accepted
+    let mut frames = frames(&buffer[1..]).unwrap();
+    assert_eq!([24979, 7867, 8878], frames.next().unwrap().words());
+}
+
+#[test]
+fn test_frames_invalid_length() {
+    assert_eq!(
+        Err(FrameError::InvalidLength),
+        frames(&[0x0; 9]).map(|frames| frames.len())
+    );
+    assert_eq!(Ok(0), frames(&[]).map(|frames| frames.len()));
+}
+
+#[test]
+fn test_register_words() {
+    let data = [0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22];
+
+    assert_eq!([24979, 7867, 8878], decode_register_words(&data));
+    assert_eq!(data, encode_register_words(&[24979, 7867, 8878]));
+}
+
+#[test]
+fn test_decode_chain() {
+    let response = [
+        0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C, 0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94,
+    ];
+
+    let data: [[u8; 6]; 2] = decode_chain(&response).unwrap();
+    assert_eq!([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22], data[0]);
+    assert_eq!([0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C], data[1]);
+
+    let result: Result<[[u8; 6]; 3], FrameError> = decode_chain(&response);
+    assert_eq!(Err(FrameError::InvalidLength), result);
+}
+
+#[test]
+fn test_decode_chain_pec_error() {
+    let response = [
+        0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C, 0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x95,
+    ];
+
+    let result: Result<[[u8; 6]; 2], FrameError> = decode_chain(&response);
+    assert_eq!(Err(FrameError::ChecksumMismatch), result);
+}
+
+#[test]
+fn test_encode_chain() {
+    let mut output = [0x0; 16];
+    let data = [[0x1, 0x2, 0x3, 0x4, 0x5, 0x6], [0x7, 0x8, 0x9, 0xA, 0xB, 0xC]];
+
+    assert_eq!(Ok(16), encode_chain(&data, &mut output));
+    // Data of the last device is sent first
+    assert_eq!([0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0x28, 0xC0], output[0..8]);
+    assert_eq!([0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE], output[8..16]);
+
+    assert_eq!(Err(FrameError::InvalidLength), encode_chain(&data, &mut output[..10]));
+}
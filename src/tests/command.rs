@@ -0,0 +1,92 @@
+//! Tests for write-only command client
+use crate::command::{CommandClient, CommandError};
+use crate::ltc6813::{CellSelection, LTC6813};
+use crate::mocks::{get_cs, BusError, MockPin, MockWriteBus, PinError};
+use crate::monitor::{ADCMode, StatusGroup};
+
+#[test]
+fn test_start_conv_cells() {
+    let bus = get_bus(&[&[0x03, 0x70, 0xAF, 0x42]]);
+    let mut client: CommandClient<_, _, LTC6813> = CommandClient::new(bus, get_cs(1));
+
+    let timing = client.start_conv_cells(ADCMode::Normal, CellSelection::All, true).unwrap();
+    assert_eq!(2343, timing.regular);
+}
+
+#[test]
+fn test_measure_internal_parameters() {
+    let bus = get_bus(&[&[0x05, 0x68, 0x3B, 0xAE]]);
+    let mut client: CommandClient<_, _, LTC6813> = CommandClient::new(bus, get_cs(1));
+
+    let timing = client.measure_internal_parameters(ADCMode::Normal, StatusGroup::All).unwrap();
+    assert_eq!(1600, timing.regular);
+    assert_eq!(2000, timing.alternative);
+}
+
+#[test]
+fn test_clear_commands() {
+    let bus = get_bus(&[
+        &[0x07, 0x11, 0xC9, 0xC0],
+        &[0x07, 0x12, 0xDF, 0xA4],
+        &[0x07, 0x13, 0x54, 0x96],
+    ]);
+    let mut client: CommandClient<_, _, LTC6813> = CommandClient::new(bus, get_cs(3));
+
+    client.clear_cells().unwrap();
+    client.clear_aux().unwrap();
+    client.clear_status().unwrap();
+}
+
+#[test]
+fn test_mute_unmute() {
+    let bus = get_bus(&[&[0x00, 0x28, 0xE8, 0x0E], &[0x00, 0x29, 0x63, 0x3C]]);
+    let mut client: CommandClient<_, _, LTC6813> = CommandClient::new(bus, get_cs(2));
+
+    client.mute().unwrap();
+    client.unmute().unwrap();
+}
+
+#[test]
+fn test_transfer_error() {
+    let mut bus = MockWriteBus::new();
+    bus.expect_write().times(1).returning(move |_| Err(BusError::Error1));
+
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(|| Ok(()));
+
+    let mut client: CommandClient<_, _, LTC6813> = CommandClient::new(bus, cs);
+
+    match client.clear_cells().unwrap_err() {
+        CommandError::TransferError(error) => assert_eq!(BusError::Error1, error),
+        CommandError::CSPinError(_) => panic!("Unexpected error type"),
+    }
+}
+
+#[test]
+fn test_cs_error() {
+    let bus = MockWriteBus::new();
+
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(|| Err(PinError::Error1));
+
+    let mut client: CommandClient<_, _, LTC6813> = CommandClient::new(bus, cs);
+
+    match client.mute().unwrap_err() {
+        CommandError::CSPinError(error) => assert_eq!(PinError::Error1, error),
+        CommandError::TransferError(_) => panic!("Unexpected error type"),
+    }
+}
+
+fn get_bus(frames: &[&'static [u8; 4]]) -> MockWriteBus {
+    let mut bus = MockWriteBus::new();
+    let mut sequence = mockall::Sequence::new();
+
+    for &expected in frames.iter() {
+        bus.expect_write().times(1).in_sequence(&mut sequence).returning(move |data| {
+            assert_eq!(expected, data);
+            Ok(())
+        });
+    }
+
+    bus
+}
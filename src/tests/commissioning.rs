@@ -0,0 +1,314 @@
+//! Tests for commissioning routines
+use crate::commissioning::SwitchTestConfig;
+use crate::config::Configuration;
+use crate::ltc6810::LTC6810;
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{ADCMode, LTC681X};
+use embedded_hal::blocking::delay::DelayUs;
+
+#[test]
+fn test_switch_test_config() {
+    let config = SwitchTestConfig::new(50);
+    assert_eq!(50, config.get_min_dip());
+    assert_eq!(10_000, config.get_settle_time());
+    assert_eq!(ADCMode::Normal, config.get_mode());
+
+    let config = config.settle_time(1_000).mode(ADCMode::Fast);
+    assert_eq!(1_000, config.get_settle_time());
+    assert_eq!(ADCMode::Fast, config.get_mode());
+}
+
+#[test]
+fn test_discharge_switches() {
+    let bus = BusMockBuilder::new()
+        // Baseline
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Cell 1
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x01, 0x00, 0x36, 0xAE])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xAC, 0x8A, 0xA0, 0x8C, 0xA0, 0x8C, 0x79, 0xCE])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Cell 2
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x02, 0x00, 0x25, 0x48])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xAC, 0x8A, 0xA0, 0x8C, 0x92, 0x3E])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Cell 3
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Cell 4
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x08, 0x00, 0x4D, 0x1C])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xAC, 0x8A, 0xA0, 0x8C, 0xA0, 0x8C, 0x79, 0xCE])
+        // Cell 5
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x10, 0x00, 0xD2, 0x2C])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xAC, 0x8A, 0x48, 0x8A, 0xDC, 0x8A])
+        // Cell 6
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x20, 0x00, 0x67, 0x7E])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xAC, 0x8A, 0xBF, 0xBA])
+        // Switches turned off
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, LTC6810, 1> = LTC681X::ltc6810(bus, get_cs(29));
+    let mut delay = RecordingDelay::default();
+
+    let mut config = Configuration::default();
+    config.discharge_cell(crate::config::Cell::Cell2);
+
+    let test = SwitchTestConfig::new(400).settle_time(1_000);
+    let report = monitor.test_discharge_switches([config], &test, &mut delay).unwrap();
+
+    // 7 times settle time + longer conversion time (alternative mode)
+    assert_eq!(7 * (1_000 + 3_026), delay.total);
+
+    assert!(!report.passed());
+    assert_eq!([(0, 2)], report.failures().collect::<alloc::vec::Vec<_>>()[..]);
+
+    let cell1 = report.get(0, 0).unwrap();
+    assert_eq!(36_000, cell1.baseline);
+    assert_eq!(35_500, cell1.voltage);
+    assert_eq!(500, cell1.dip);
+    assert_eq!(0, cell1.neighbor_dip);
+    assert!(cell1.passed);
+
+    let cell3 = report.get(0, 2).unwrap();
+    assert_eq!(0, cell3.dip);
+    assert!(!cell3.passed);
+
+    // Sense wires of cell 6 affected by switch of cell 5
+    let cell5 = report.get(0, 4).unwrap();
+    assert_eq!(500, cell5.dip);
+    assert_eq!(600, cell5.neighbor_dip);
+
+    // Not available on LTC6810
+    assert_eq!(None, report.get(0, 6));
+    assert_eq!(None, report.get(1, 0));
+}
+
+#[test]
+fn test_check_wiring_order() {
+    let bus = BusMockBuilder::new()
+        // Baseline
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 1, cell 1
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x01, 0x00, 0x36, 0xAE])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xAC, 0x8A, 0xA0, 0x8C, 0xA0, 0x8C, 0x79, 0xCE])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 1, cell 2
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x02, 0x00, 0x25, 0x48])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xAC, 0x8A, 0xBF, 0xBA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 1, cell 3
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xAC, 0x8A, 0xA0, 0x8C, 0x92, 0x3E])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 1, cell 4
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x08, 0x00, 0x4D, 0x1C])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xAC, 0x8A, 0xA0, 0x8C, 0xA0, 0x8C, 0x79, 0xCE])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 1, cell 5
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x10, 0x00, 0xD2, 0x2C])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xAC, 0x8A, 0xA0, 0x8C, 0x92, 0x3E])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 1, cell 6
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x20, 0x00, 0x67, 0x7E])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xAC, 0x8A, 0xBF, 0xBA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 2, cell 1
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x01, 0x00, 0x36, 0xAE])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xAC, 0x8A, 0xA0, 0x8C, 0xA0, 0x8C, 0x79, 0xCE])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 2, cell 2
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x02, 0x00, 0x25, 0x48])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xAC, 0x8A, 0xA0, 0x8C, 0x92, 0x3E])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 2, cell 3
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xAC, 0x8A, 0xBF, 0xBA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Device 2, cell 4
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x08, 0x00, 0x4D, 0x1C])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xAC, 0x8A, 0xA0, 0x8C, 0xA0, 0x8C, 0x79, 0xCE])
+        // Device 2, cell 5
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x10, 0x00, 0xD2, 0x2C])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xAC, 0x8A, 0xA0, 0x8C, 0x92, 0x3E])
+        // Device 2, cell 6
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x20, 0x00, 0x67, 0x7E])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        .expect_register_read(&[0xA0, 0x8C, 0xA0, 0x8C, 0xA0, 0x8C, 0xDC, 0xEA])
+        // Switches turned off
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, LTC6810, 2> = LTC681X::ltc6810(bus, get_cs(53));
+    let mut delay = RecordingDelay::default();
+
+    let test = SwitchTestConfig::new(400).settle_time(1_000);
+    let report = monitor
+        .check_wiring_order([Configuration::default(), Configuration::default()], &test, &mut delay)
+        .unwrap();
+
+    assert_eq!(13 * (1_000 + 3_026), delay.total);
+
+    assert!(!report.passed());
+    assert_eq!(
+        [(0, 1), (0, 2), (1, 5)],
+        report.mismatches().collect::<alloc::vec::Vec<_>>()[..]
+    );
+
+    assert_eq!(Some((0, 0)), report.get(0, 0).unwrap().observed);
+    assert_eq!(500, report.get(0, 0).unwrap().dip);
+
+    // Swapped sense wires
+    assert_eq!(Some((0, 2)), report.get(0, 1).unwrap().observed);
+    assert_eq!(Some((0, 1)), report.get(0, 2).unwrap().observed);
+
+    assert_eq!(Some((1, 0)), report.get(1, 0).unwrap().observed);
+
+    // No dip observed
+    assert_eq!(None, report.get(1, 5).unwrap().observed);
+    assert_eq!(0, report.get(1, 5).unwrap().dip);
+
+    assert_eq!(None, report.get(0, 6));
+}
+
+#[derive(Default)]
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
@@ -0,0 +1,95 @@
+//! Tests for IR-drop compensation
+use crate::compensation::IrDropCompensation;
+use crate::config::{Cell, Configuration};
+use crate::ltc6813::{Channel, LTC6813};
+use crate::monitor::Voltage;
+use heapless::Vec;
+
+#[test]
+fn test_compensate() {
+    let compensation = IrDropCompensation::new(100_000, 10_000);
+
+    // No discharge
+    assert_eq!(33637, compensation.compensate(33637, 0));
+    // Full duty: 3.3637 V * 110 / 100
+    assert_eq!(37000, compensation.compensate(33637, 1000));
+    // Half duty: 3.3637 V * 110 / 105
+    assert_eq!(35238, compensation.compensate(33637, 500));
+    // Duty is limited to 100%
+    assert_eq!(37000, compensation.compensate(33637, 2000));
+}
+
+#[test]
+fn test_compensate_saturates() {
+    let compensation = IrDropCompensation::new(10_000, 10_000);
+    assert_eq!(u16::MAX, compensation.compensate(40000, 1000));
+
+    let compensation = IrDropCompensation::new(0, 10_000);
+    assert_eq!(u16::MAX, compensation.compensate(1, 1000));
+}
+
+#[test]
+fn test_compensate_discharging() {
+    let compensation = IrDropCompensation::new(100_000, 10_000);
+
+    let mut config = [Configuration::default(), Configuration::default()];
+    config[1].discharge_cell(Cell::Cell2);
+
+    let mut voltages = get_voltages();
+    compensation.compensate_discharging(&mut voltages, &config);
+
+    assert_eq!(33637, voltages[0][0].voltage);
+    assert_eq!(33637, voltages[0][1].voltage);
+    assert_eq!(33637, voltages[1][0].voltage);
+    assert_eq!(37000, voltages[1][1].voltage);
+
+    // GPIO is not changed
+    assert_eq!(33637, voltages[1][2].voltage);
+}
+
+#[test]
+fn test_compensate_with_duty() {
+    let compensation = IrDropCompensation::new(100_000, 10_000);
+
+    let mut voltages = get_voltages();
+    compensation.compensate_with(
+        &mut voltages,
+        |device, index| {
+            if device == 0 && index == 0 {
+                500
+            } else {
+                0
+            }
+        },
+    );
+
+    // 3.3637 V * 110 / 105
+    assert_eq!(35238, voltages[0][0].voltage);
+    assert_eq!(33637, voltages[0][1].voltage);
+    assert_eq!(33637, voltages[1][1].voltage);
+}
+
+fn get_voltages() -> Vec<Vec<Voltage<LTC6813>, 18>, 2> {
+    let mut device = Vec::new();
+    device
+        .extend_from_slice(&[
+            Voltage {
+                channel: Channel::Cell1,
+                voltage: 33637,
+            },
+            Voltage {
+                channel: Channel::Cell2,
+                voltage: 33637,
+            },
+            Voltage {
+                channel: Channel::GPIO2,
+                voltage: 33637,
+            },
+        ])
+        .unwrap();
+
+    let mut voltages = Vec::new();
+    voltages.push(device.clone()).unwrap();
+    voltages.push(device).unwrap();
+    voltages
+}
@@ -0,0 +1,202 @@
+//! Tests for datasheet timing enforcement
+use crate::compliance::{ComplianceError, CompliantClient, TimingPolicy, TimingViolation};
+use crate::health::Clock;
+use crate::ltc6813::{CellSelection, Register, LTC6813};
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{ADCMode, WakeProfile, LTC681X};
+use crate::timing::{T_BRIDGE_READY_US, T_READY_US, T_REFUP_US, T_WAKE_US};
+use core::cell::Cell;
+use embedded_hal::blocking::delay::DelayUs;
+
+#[test]
+fn test_initial_wakeup_and_reference_power_up() {
+    let bus = BusMockBuilder::new()
+        .expect_dummy_bytes(1)
+        .expect_dummy_bytes(1)
+        .expect_command(0x03, 0x70, 0xAF, 0x42)
+        .into_mock();
+
+    let now = Cell::new(0);
+    let client: LTC681X<_, _, _, LTC6813, 2> = LTC681X::ltc6813(bus, get_cs(3));
+    let mut client = CompliantClient::new(client, TestClock { now: &now }, TestDelay::new(&now));
+
+    let timing = client.start_conv_cells(ADCMode::Normal, CellSelection::All, true).unwrap();
+
+    // Reference power-up is added to the first conversion after wakeup
+    assert_eq!(2343 + T_REFUP_US, timing.regular);
+    assert_eq!(3041 + T_REFUP_US, timing.alternative);
+
+    // Wakeup of both cores, the conversion is started without delay
+    let (_, _, delay) = client.release();
+    assert_eq!([T_WAKE_US, T_WAKE_US], delay.delays[..2]);
+    assert_eq!(2, delay.count);
+}
+
+#[test]
+fn test_delay_pending_conversion() {
+    let bus = BusMockBuilder::new()
+        .expect_dummy_bytes(1)
+        .expect_command(0x03, 0x70, 0xAF, 0x42)
+        .expect_dummy_bytes(1)
+        .expect_command(0x00, 0x04, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let now = Cell::new(0);
+    let client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(bus, get_cs(4));
+    let mut client = CompliantClient::new(client, TestClock { now: &now }, TestDelay::new(&now));
+
+    let timing = client.start_conv_cells(ADCMode::Normal, CellSelection::All, true).unwrap();
+    now.set(now.get() + 500);
+
+    let result = client.read_register(Register::CellVoltageA).unwrap();
+    assert_eq!([24979, 7867, 8878], result[0]);
+
+    // Conversion includes the reference power-up, so the isoSPI port is rewoken after delaying
+    let (_, _, delay) = client.release();
+    assert_eq!(3, delay.count);
+    assert_eq!(timing.regular.max(timing.alternative) - 500, delay.delays[1]);
+    assert_eq!(T_READY_US, delay.delays[2]);
+}
+
+#[test]
+fn test_refuse_violations() {
+    let bus = BusMockBuilder::new()
+        .expect_dummy_bytes(1)
+        .expect_command(0x03, 0x70, 0xAF, 0x42)
+        .into_mock();
+
+    let now = Cell::new(0);
+    let client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(bus, get_cs(2));
+    let mut client =
+        CompliantClient::new(client, TestClock { now: &now }, TestDelay::new(&now)).policy(TimingPolicy::Refuse);
+
+    let timing = client.start_conv_cells(ADCMode::Normal, CellSelection::All, true).unwrap();
+
+    now.set(now.get() + 1_000);
+    let result = client.read_register(Register::CellVoltageA);
+    match result.unwrap_err() {
+        ComplianceError::Violation(violation) => assert_eq!(
+            TimingViolation::ConversionPending {
+                remaining_us: timing.regular.max(timing.alternative) - 1_000
+            },
+            violation
+        ),
+        ComplianceError::Client(_) => panic!("Unexpected error type"),
+    }
+
+    let result = client.start_conv_cells(ADCMode::Normal, CellSelection::All, true);
+    assert!(matches!(
+        result,
+        Err(ComplianceError::Violation(TimingViolation::ConversionPending { .. }))
+    ));
+}
+
+#[test]
+fn test_idle_rewake() {
+    let bus = BusMockBuilder::new()
+        .expect_dummy_bytes(1)
+        .expect_command(0x00, 0x04, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_dummy_bytes(1)
+        .expect_command(0x00, 0x04, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0x00, 0x04, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_dummy_bytes(1)
+        .expect_command(0x00, 0x04, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let now = Cell::new(0);
+    let client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(bus, get_cs(7));
+    let mut client = CompliantClient::new(client, TestClock { now: &now }, TestDelay::new(&now));
+
+    // Initial wakeup from sleep state
+    client.read_register(Register::CellVoltageA).unwrap();
+
+    // isoSPI port in idle state
+    now.set(now.get() + 5_000);
+    client.read_register(Register::CellVoltageA).unwrap();
+
+    // Still active
+    now.set(now.get() + 1_000);
+    client.read_register(Register::CellVoltageA).unwrap();
+
+    // Core in sleep state
+    now.set(now.get() + 2_000_000);
+    client.read_register(Register::CellVoltageA).unwrap();
+
+    let (_, _, delay) = client.release();
+    assert_eq!(3, delay.count);
+    assert_eq!([T_WAKE_US, T_READY_US, T_WAKE_US], delay.delays[..3]);
+}
+
+#[test]
+fn test_bridge_wake_profile() {
+    let bus = BusMockBuilder::new()
+        .expect_dummy_bytes(1)
+        .expect_dummy_bytes(1)
+        .expect_command(0x00, 0x04, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_dummy_bytes(1)
+        .expect_dummy_bytes(1)
+        .expect_command(0x00, 0x04, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let now = Cell::new(0);
+    let client: LTC681X<_, _, _, LTC6813, 1> =
+        LTC681X::ltc6813(bus, get_cs(6)).with_wake_profile(WakeProfile::ltc6820());
+    let mut client = CompliantClient::new(client, TestClock { now: &now }, TestDelay::new(&now));
+
+    // Bridge pulse precedes the wakeup from sleep state
+    client.read_register(Register::CellVoltageA).unwrap();
+
+    // Bridge pulse precedes the rewake of the idle isoSPI port
+    now.set(now.get() + 5_000);
+    client.read_register(Register::CellVoltageA).unwrap();
+
+    let (_, _, delay) = client.release();
+    assert_eq!(4, delay.count);
+    assert_eq!(
+        [T_BRIDGE_READY_US, T_WAKE_US, T_BRIDGE_READY_US, T_READY_US],
+        delay.delays[..4]
+    );
+}
+
+/// Clock returning the shared timestamp
+struct TestClock<'a> {
+    now: &'a Cell<u32>,
+}
+
+impl Clock for TestClock<'_> {
+    fn now_us(&mut self) -> u32 {
+        self.now.get()
+    }
+}
+
+/// Delay recording all delays and advancing the shared timestamp
+struct TestDelay<'a> {
+    now: &'a Cell<u32>,
+    delays: [u32; 8],
+    count: usize,
+}
+
+impl<'a> TestDelay<'a> {
+    fn new(now: &'a Cell<u32>) -> Self {
+        Self {
+            now,
+            delays: [0; 8],
+            count: 0,
+        }
+    }
+}
+
+impl DelayUs<u32> for TestDelay<'_> {
+    fn delay_us(&mut self, us: u32) {
+        self.delays[self.count] = us;
+        self.count += 1;
+        self.now.set(self.now.get() + us);
+    }
+}
@@ -0,0 +1,373 @@
+//! Tests for conversion request builder
+use crate::conversion::{select_cells, ConversionKind, ConversionRequest, PullDirection, POLL_INTERVAL};
+use crate::ltc6813::{CellSelection, Channel, GPIOSelection, LTC6813};
+use crate::mocks::{get_cs, BusMockBuilder, MockPin, PinError};
+use crate::monitor::{ADCMode, Error, LTC681XClient, StatusGroup, LTC681X};
+use embedded_hal::blocking::delay::DelayUs;
+
+#[test]
+fn test_conversion_request_defaults() {
+    let request: ConversionRequest<LTC6813> = ConversionRequest::cells(CellSelection::Group2);
+
+    assert_eq!(ADCMode::Normal, request.get_mode());
+    assert_eq!(ConversionKind::Cells, request.get_kind());
+    assert_eq!(CellSelection::Group2, request.get_cells());
+    assert_eq!(0b0000_0011_0110_0010, request.to_command());
+}
+
+#[test]
+fn test_conversion_request_adcv() {
+    let request: ConversionRequest<LTC6813> = ConversionRequest::cells(CellSelection::All)
+        .mode(ADCMode::Filtered)
+        .discharge_permitted(true);
+
+    assert_eq!(0b0000_0011_1111_0000, request.to_command());
+
+    let timing = request.expected_duration();
+    assert_eq!(201325, timing.regular);
+    assert_eq!(4437, timing.alternative);
+}
+
+#[test]
+fn test_conversion_request_adcvax() {
+    let request: ConversionRequest<LTC6813> = ConversionRequest::cells(CellSelection::Group3).with_gpio12();
+
+    assert_eq!(ConversionKind::CellsAndGpio12, request.get_kind());
+    assert_eq!(CellSelection::All, request.get_cells());
+    assert_eq!(0b0000_0101_0110_1111, request.to_command());
+
+    let timing = request.expected_duration();
+    assert_eq!(3149, timing.regular);
+    assert_eq!(4081, timing.alternative);
+}
+
+#[test]
+fn test_conversion_request_adcvsc() {
+    let request: ConversionRequest<LTC6813> = ConversionRequest::cells(CellSelection::All)
+        .mode(ADCMode::Fast)
+        .with_sum_of_cells();
+
+    assert_eq!(ConversionKind::CellsAndSum, request.get_kind());
+    assert_eq!(0b0000_0100_1110_0111, request.to_command());
+
+    let timing = request.expected_duration();
+    assert_eq!(1321, timing.regular);
+    assert_eq!(1525, timing.alternative);
+}
+
+#[test]
+fn test_conversion_request_adow() {
+    let request: ConversionRequest<LTC6813> = ConversionRequest::cells(CellSelection::All)
+        .open_wire(PullDirection::PullUp)
+        .discharge_permitted(true);
+    assert_eq!(0b0000_0011_0111_1000, request.to_command());
+
+    let request: ConversionRequest<LTC6813> =
+        ConversionRequest::cells(CellSelection::Group1).open_wire(PullDirection::PullDown);
+    assert_eq!(0b0000_0011_0010_1001, request.to_command());
+
+    let timing = request.expected_duration();
+    assert_eq!(407, timing.regular);
+    assert_eq!(523, timing.alternative);
+}
+
+#[test]
+fn test_select_cells() {
+    assert!(select_cells::<LTC6813>(0x0).is_empty());
+    assert_eq!(&[CellSelection::Group1], select_cells::<LTC6813>(0b1).as_slice());
+    assert_eq!(
+        &[CellSelection::Group1],
+        select_cells::<LTC6813>(0b1_0000_0100_0001).as_slice()
+    );
+    assert_eq!(
+        &[CellSelection::Group3, CellSelection::Group6],
+        select_cells::<LTC6813>(0b10_0000_0000_0000_0100).as_slice()
+    );
+    assert_eq!(&[CellSelection::All], select_cells::<LTC6813>(0x3_FFFF).as_slice());
+    assert_eq!(
+        &[CellSelection::All],
+        select_cells::<LTC6813>(0b11_1111_0000_0000_0000).as_slice()
+    );
+
+    // Bits beyond the cell count are ignored
+    assert!(select_cells::<LTC6813>(0xFFFC_0000).is_empty());
+}
+
+#[test]
+fn test_start_conversion_commands() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0111_1111, 0xC7, 0x7A)
+        .expect_command(0b0000_0100, 0b1110_0111, 0xB8, 0x30)
+        .expect_command(0b0000_0011, 0b0111_1000, 0x47, 0x4C)
+        .into_mock();
+
+    let mut cs = MockPin::new();
+    cs.expect_set_high().times(3).returning(move || Ok(()));
+    cs.expect_set_low().times(3).returning(move || Ok(()));
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+
+    let request = ConversionRequest::cells(CellSelection::All)
+        .discharge_permitted(true)
+        .with_gpio12();
+    let timing = monitor.start_conversion(request).unwrap();
+    assert_eq!(3149, timing.regular);
+
+    let request = ConversionRequest::cells(CellSelection::All)
+        .mode(ADCMode::Fast)
+        .with_sum_of_cells();
+    let timing = monitor.start_conversion(request).unwrap();
+    assert_eq!(1321, timing.regular);
+
+    let request = ConversionRequest::cells(CellSelection::All)
+        .discharge_permitted(true)
+        .open_wire(PullDirection::PullUp);
+    let timing = monitor.start_conversion(request).unwrap();
+    assert_eq!(2343, timing.regular);
+}
+
+#[test]
+fn test_conversion_handle_wait() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0001, 0x7F, 0x5E)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        // Register E
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(4));
+    let mut delay = RecordingDelay::default();
+
+    let conversion = monitor
+        .begin_conversion(ConversionRequest::cells(CellSelection::Group1))
+        .unwrap();
+    let timing = conversion.timing();
+    assert_eq!(CellSelection::Group1, conversion.request().get_cells());
+
+    let result = conversion.wait(&mut delay).unwrap();
+    assert_eq!(timing.regular.max(timing.alternative), delay.total);
+
+    assert_eq!(3, result[0].len());
+    assert_eq!(Channel::Cell1, result[0][0].channel);
+    assert_eq!(24979, result[0][0].voltage);
+    assert_eq!(Channel::Cell13, result[0][2].channel);
+    assert_eq!(25822, result[0][2].voltage);
+}
+
+#[test]
+fn test_conversion_handle_wait_sdo_polling() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0001, 0x7F, 0x5E)
+        .expect_poll(false)
+        .expect_poll(false)
+        .expect_poll(true)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        // Register E
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(4)).enable_sdo_polling();
+    let mut delay = RecordingDelay::default();
+
+    let conversion = monitor
+        .begin_conversion(ConversionRequest::cells(CellSelection::Group1))
+        .unwrap();
+    let result = conversion.wait(&mut delay).unwrap();
+    assert_eq!(2 * POLL_INTERVAL, delay.total);
+
+    assert_eq!(3, result[0].len());
+    assert_eq!(Channel::Cell7, result[0][1].channel);
+    assert_eq!(25441, result[0][1].voltage);
+}
+
+#[test]
+fn test_conversion_token() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0010, 0x69, 0x3A)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        // Register E
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(4));
+    let mut delay = RecordingDelay::default();
+
+    let conversion = monitor
+        .begin_conversion(ConversionRequest::cells(CellSelection::Group2))
+        .unwrap();
+    let timing = conversion.timing();
+
+    let token = conversion.complete(&mut delay).unwrap();
+    assert_eq!(timing.regular.max(timing.alternative), delay.total);
+    assert_eq!(CellSelection::Group2, token.cells());
+
+    let result = monitor.read_cell_voltages(token).unwrap();
+    assert_eq!(3, result[0].len());
+    assert_eq!(Channel::Cell2, result[0][0].channel);
+    assert_eq!(Channel::Cell14, result[0][2].channel);
+}
+
+#[test]
+fn test_gpio_conversion_handle_wait() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0110_0010, 0x4E, 0xF6)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1101, 0x64, 0xFE)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(3));
+    let mut delay = RecordingDelay::default();
+
+    let conversion = monitor.begin_gpio_conversion(ADCMode::Normal, GPIOSelection::Group2).unwrap();
+    assert_eq!(GPIOSelection::Group2, conversion.pins());
+    assert_eq!(1000, conversion.timing().alternative);
+
+    let result = conversion.wait(&mut delay).unwrap();
+    assert_eq!(1000, delay.total);
+
+    assert_eq!(2, result[0].len());
+    assert_eq!(Channel::GPIO2, result[0][0].channel);
+    assert_eq!(7867, result[0][0].voltage);
+    assert_eq!(Channel::GPIO7, result[0][1].channel);
+    assert_eq!(7869, result[0][1].voltage);
+}
+
+#[test]
+fn test_status_conversion_handle_wait_sdo_polling() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0110_1000, 0x3B, 0xAE)
+        .expect_poll(false)
+        .expect_poll(true)
+        .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
+        .expect_register_read(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D, 0x31, 0x8A])
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x00, 0xC8, 0x00, 0x66, 0x00, 0x1B, 0xF1, 0x40])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(3)).enable_sdo_polling();
+    let mut delay = RecordingDelay::default();
+
+    let conversion = monitor.begin_status_conversion(ADCMode::Normal, StatusGroup::All).unwrap();
+    assert_eq!(StatusGroup::All, conversion.group());
+
+    let result = conversion.wait(&mut delay).unwrap();
+    assert_eq!(POLL_INTERVAL, delay.total);
+    assert_eq!(75_318_000, result[0].total_voltage);
+    assert_eq!(5_120_000, result[0].digital_power);
+}
+
+#[test]
+fn test_conversion_handle_cs_error() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Err(PinError::Error1));
+
+    let bus = BusMockBuilder::new().into_mock();
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+
+    match monitor.begin_conversion(ConversionRequest::cells(CellSelection::All)) {
+        Err(Error::CSPinError(_)) => {}
+        _ => panic!("Unexpected result"),
+    }
+}
+
+#[test]
+fn test_measure_cell_voltages() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0001, 0x7F, 0x5E)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(4));
+    let mut delay = RecordingDelay::default();
+
+    let result = monitor
+        .measure_cell_voltages(ADCMode::Normal, CellSelection::Group1, &mut delay)
+        .unwrap();
+
+    assert_eq!(523, delay.total);
+    assert_eq!(Channel::Cell7, result[0][1].channel);
+    assert_eq!(25441, result[0][1].voltage);
+}
+
+#[test]
+fn test_measure_cell_voltages_sdo_polling() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0001, 0x7F, 0x5E)
+        .expect_poll(false)
+        .expect_poll(true)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(4)).enable_sdo_polling();
+    let mut delay = RecordingDelay::default();
+
+    let result = monitor
+        .measure_cell_voltages(ADCMode::Normal, CellSelection::Group1, &mut delay)
+        .unwrap();
+
+    assert_eq!(100, delay.total);
+    assert_eq!(24979, result[0][0].voltage);
+}
+
+#[test]
+fn test_measure_cell_voltages_sdo_polling_timeout() {
+    let mut bus = BusMockBuilder::new().expect_command(0b0000_0011, 0b0110_0001, 0x7F, 0x5E);
+    for _ in 0..12 {
+        bus = bus.expect_poll(false);
+    }
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus.into_mock(), get_cs(1)).enable_sdo_polling();
+    let mut delay = RecordingDelay::default();
+
+    match monitor.measure_cell_voltages(ADCMode::Normal, CellSelection::Group1, &mut delay) {
+        Err(Error::Timeout) => {}
+        _ => panic!("Unexpected result"),
+    }
+
+    // Timeout of 2 * 523 us
+    assert_eq!(1100, delay.total);
+}
+
+/// Delay mock summing up all delays
+#[derive(Default)]
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
@@ -0,0 +1,124 @@
+//! Tests for acquisition cycle deadline and jitter monitoring
+use crate::deadline::{CycleTiming, DeadlineMonitor};
+use crate::health::Clock;
+
+#[test]
+fn test_deadline_monitor_on_schedule() {
+    let mut monitor = DeadlineMonitor::new(1_000);
+
+    for cycle in 0..10 {
+        let timing = monitor.start(500 + cycle * 1_000 + cycle % 2 * 50);
+        assert!(!timing.excessive_jitter);
+        assert!(!monitor.finish(800 + cycle * 1_000));
+    }
+
+    assert_eq!(10, monitor.count());
+    assert_eq!(50, monitor.max_jitter());
+    assert!(monitor.is_holding());
+}
+
+#[test]
+fn test_deadline_monitor_early_start() {
+    let mut monitor = DeadlineMonitor::new(1_000).with_jitter_limit(20);
+
+    monitor.start(0);
+    assert_eq!(
+        CycleTiming {
+            jitter: -30,
+            excessive_jitter: true,
+            skipped: 0
+        },
+        monitor.start(970)
+    );
+
+    // Schedule is kept, the early start doesn't shift the next slot
+    assert_eq!(10, monitor.start(2_010).jitter);
+    assert_eq!(1, monitor.jitter_violations());
+    assert_eq!(30, monitor.max_jitter());
+}
+
+#[test]
+fn test_deadline_monitor_skipped_cycles() {
+    let mut monitor = DeadlineMonitor::new(1_000);
+
+    monitor.start(0);
+    assert_eq!(
+        CycleTiming {
+            jitter: 50,
+            excessive_jitter: false,
+            skipped: 2
+        },
+        monitor.start(3_050)
+    );
+
+    assert_eq!(0, monitor.start(4_000).jitter);
+    assert_eq!(2, monitor.skipped_cycles());
+    assert!(!monitor.is_holding());
+}
+
+#[test]
+fn test_deadline_monitor_missed_deadline() {
+    let mut monitor = DeadlineMonitor::new(1_000).with_deadline(600);
+
+    // No cycle started yet
+    assert!(!monitor.finish(100));
+
+    monitor.start(100);
+    assert!(!monitor.finish(700));
+
+    monitor.start(1_150);
+    assert!(monitor.finish(1_701));
+    assert_eq!(1, monitor.missed_deadlines());
+}
+
+#[test]
+fn test_deadline_monitor_wrap_around() {
+    let mut monitor = DeadlineMonitor::new(1_000);
+
+    monitor.start(u32::MAX - 499);
+    assert_eq!(-10, monitor.start(490).jitter);
+    assert!(!monitor.finish(1_000));
+}
+
+#[test]
+fn test_deadline_monitor_reset() {
+    let mut monitor = DeadlineMonitor::new(1_000).with_jitter_limit(5).with_deadline(100);
+
+    monitor.start(0);
+    monitor.start(1_010);
+    monitor.finish(1_200);
+    assert!(!monitor.is_holding());
+
+    monitor.reset();
+    assert_eq!(0, monitor.count());
+    assert_eq!(0, monitor.max_jitter());
+    assert!(monitor.is_holding());
+
+    // New schedule, configuration is kept
+    monitor.start(5_000);
+    assert!(monitor.start(6_010).excessive_jitter);
+    assert!(monitor.finish(6_200));
+}
+
+#[test]
+fn test_deadline_monitor_clock() {
+    let mut clock = TestClock { now: 0 };
+    let mut monitor = DeadlineMonitor::new(1_000);
+
+    monitor.start_with(&mut clock);
+    clock.now = 1_020;
+    assert_eq!(20, monitor.start_with(&mut clock).jitter);
+
+    clock.now = 2_100;
+    assert!(monitor.finish_with(&mut clock));
+}
+
+struct TestClock {
+    now: u32,
+}
+
+impl Clock for TestClock {
+    fn now_us(&mut self) -> u32 {
+        self.now
+    }
+}
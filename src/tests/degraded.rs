@@ -0,0 +1,132 @@
+//! Tests for degraded-mode operation tracking
+use crate::degraded::{DegradedTracker, Reading};
+use crate::ltc6813::{CellSelection, Channel, GPIOSelection, LTC6813};
+use crate::mocks::{get_cs, BusError, BusMockBuilder, MockPin, MockSPIBus};
+use crate::monitor::{Error, LTC681X};
+use heapless::Vec;
+
+#[test]
+fn test_read_voltages_tolerant_pec_error() {
+    let bus = BusMockBuilder::new()
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x95])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x43])
+        .expect_register_read(&[0x8A, 0x61, 0x61, 0x1F, 0xCF, 0x21, 0x01, 0xEE])
+        // Register E
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .expect_register_read(&[0x00, 0x63, 0x2F, 0x1F, 0x8B, 0x1F, 0xC1, 0x68])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(3));
+
+    let result = monitor.read_voltages_tolerant(CellSelection::Group1).unwrap();
+    assert_eq!(3, result[0].len());
+    assert_eq!(3, result[1].len());
+
+    assert_eq!(Channel::Cell1, result[0][0].channel);
+    assert_eq!(Some(24979), result[0][0].voltage);
+    assert_eq!(Channel::Cell1, result[1][0].channel);
+    assert_eq!(None, result[1][0].voltage);
+
+    assert_eq!(Channel::Cell7, result[0][1].channel);
+    assert_eq!(None, result[0][1].voltage);
+    assert_eq!(Channel::Cell7, result[1][1].channel);
+    assert_eq!(Some(24970), result[1][1].voltage);
+
+    assert_eq!(Some(25822), result[0][2].voltage);
+    assert_eq!(Some(25344), result[1][2].voltage);
+}
+
+#[test]
+fn test_read_voltages_tolerant_transfer_error() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let mut bus = MockSPIBus::new();
+    bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
+
+    let mut monitor: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(bus, cs);
+
+    let result = monitor.read_voltages_tolerant(CellSelection::Group1);
+    match result.unwrap_err() {
+        Error::TransferError(BusError::Error1) => {}
+        _ => panic!("Unexpected error type"),
+    }
+}
+
+#[test]
+fn test_tracker_degrade_and_recover() {
+    let mut tracker: DegradedTracker<2> = DegradedTracker::new();
+
+    tracker.update(&readings(Some(36_000), Some(37_000)), 1_000);
+    assert!(!tracker.any_degraded());
+    assert_eq!(Some(36_000), tracker.snapshot().device(0).cell_voltages[0]);
+    assert_eq!(Some(37_000), tracker.snapshot().device(0).cell_voltages[6]);
+
+    tracker.update(&readings(None, Some(37_100)), 1_100);
+    assert!(tracker.is_degraded(0));
+    assert!(!tracker.is_degraded(1));
+    assert_eq!(None, tracker.snapshot().device(0).cell_voltages[0]);
+    assert_eq!(Some(37_100), tracker.snapshot().device(0).cell_voltages[6]);
+
+    // Degraded since first failure
+    tracker.update(&readings(None, Some(37_200)), 1_200);
+    assert_eq!(Some(150), tracker.cell_degraded_for(0, 0, 1_250));
+    assert_eq!(None, tracker.cell_degraded_for(0, 6, 1_250));
+    assert_eq!(Some(150), tracker.degraded_for(0, 1_250));
+    assert_eq!(None, tracker.degraded_for(1, 1_250));
+
+    tracker.update(&readings(Some(36_100), Some(37_300)), 1_300);
+    assert!(!tracker.any_degraded());
+    assert_eq!(Some(36_100), tracker.snapshot().device(0).cell_voltages[0]);
+}
+
+#[test]
+fn test_tracker_mark_failed() {
+    let mut tracker: DegradedTracker<2> = DegradedTracker::new();
+
+    tracker.mark_failed(GPIOSelection::Group2, 500);
+    tracker.mark_failed(CellSelection::Group1, 600);
+
+    for device in 0..2 {
+        assert_eq!(Some(100), tracker.gpio_degraded_for(device, 1, 600));
+        assert_eq!(Some(100), tracker.gpio_degraded_for(device, 6, 600));
+        assert_eq!(None, tracker.gpio_degraded_for(device, 0, 600));
+        assert_eq!(Some(0), tracker.cell_degraded_for(device, 12, 600));
+        assert_eq!(Some(100), tracker.degraded_for(device, 600));
+    }
+
+    // Out of bounds channel index
+    assert_eq!(None, tracker.cell_degraded_for(0, 18, 600));
+}
+
+#[test]
+fn test_tracker_timestamp_wrap_around() {
+    let mut tracker: DegradedTracker<1> = DegradedTracker::new();
+
+    tracker.mark_failed(CellSelection::Group1, u32::MAX - 9);
+    assert_eq!(Some(20), tracker.degraded_for(0, 10));
+}
+
+fn readings(cell1: Option<u16>, cell7: Option<u16>) -> Vec<Vec<Reading<LTC6813>, 18>, 2> {
+    let mut readings: Vec<Vec<Reading<LTC6813>, 18>, 2> = Vec::new();
+    let _ = readings.push(Vec::new());
+    let _ = readings.push(Vec::new());
+
+    let _ = readings[0].push(Reading {
+        channel: Channel::Cell1,
+        voltage: cell1,
+    });
+    let _ = readings[0].push(Reading {
+        channel: Channel::Cell7,
+        voltage: cell7,
+    });
+
+    readings
+}
@@ -0,0 +1,27 @@
+//! Tests for runtime device detection
+use crate::detect::DetectedDevice;
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::LTC681X;
+
+#[test]
+fn test_detect_devices() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x00, 0xC8, 0x00, 0x66, 0x00, 0x1B, 0xF1, 0x40])
+        .expect_register_read(&[0xFF; 8])
+        .expect_register_read(&[0x00, 0xC8, 0x00, 0x66, 0x00, 0x1B, 0xF1, 0x40])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 3> = LTC681X::ltc6813(bus, get_cs(1));
+    let devices = client.detect_devices().unwrap();
+
+    assert_eq!(Some(DetectedDevice { revision: 1 }), devices[0]);
+    assert_eq!(None, devices[1]);
+    assert_eq!(Some(DetectedDevice { revision: 1 }), devices[2]);
+}
+
+#[test]
+fn test_detected_device_from_status_b() {
+    let device = DetectedDevice::from_status_b(&[0x00, 0x00, 0x00, 0x00, 0x00, 0b1010_0011]);
+    assert_eq!(10, device.revision);
+}
@@ -1,10 +1,12 @@
 //! Tests for static configuration of each device
+use crate::adbms1818::ADBMS1818;
+use crate::ltc6804::LTC6804;
 use crate::ltc6810::LTC6810;
 use crate::ltc6811::LTC6811;
 use crate::ltc6812::LTC6812;
 use crate::ltc6813::LTC6813;
 use crate::monitor::{ChannelIndex, ChannelType, DeviceTypes, GroupedRegisterIndex, RegisterAddress, RegisterLocator};
-use crate::{ltc6810, ltc6811, ltc6812, ltc6813};
+use crate::{adbms1818, ltc6804, ltc6810, ltc6811, ltc6812, ltc6813};
 use alloc::vec;
 use alloc::vec::Vec;
 
@@ -66,6 +68,23 @@ fn test_ltc6811_grouped_index() {
     assert_eq!(vec! {0, 1, 2, 3}, cells);
 }
 
+#[test]
+fn test_ltc6804_grouped_index() {
+    let mut aux = vec![];
+    let mut cells = vec![];
+
+    aux.push(ltc6804::Register::AuxiliaryA.to_index());
+    aux.push(ltc6804::Register::AuxiliaryB.to_index());
+
+    cells.push(ltc6804::Register::CellVoltageA.to_index());
+    cells.push(ltc6804::Register::CellVoltageB.to_index());
+    cells.push(ltc6804::Register::CellVoltageC.to_index());
+    cells.push(ltc6804::Register::CellVoltageD.to_index());
+
+    assert_eq!(vec! {0, 1}, aux);
+    assert_eq!(vec! {0, 1, 2, 3}, cells);
+}
+
 #[test]
 fn test_ltc6810_grouped_index() {
     let mut aux = vec![];
@@ -81,6 +100,42 @@ fn test_ltc6810_grouped_index() {
     assert_eq!(vec! {0, 1}, cells);
 }
 
+#[test]
+fn test_adbms1818_grouped_index() {
+    let mut aux = vec![];
+    let mut cells = vec![];
+
+    aux.push(adbms1818::Register::AuxiliaryA.to_index());
+    aux.push(adbms1818::Register::AuxiliaryB.to_index());
+    aux.push(adbms1818::Register::AuxiliaryC.to_index());
+    aux.push(adbms1818::Register::AuxiliaryD.to_index());
+
+    cells.push(adbms1818::Register::CellVoltageA.to_index());
+    cells.push(adbms1818::Register::CellVoltageB.to_index());
+    cells.push(adbms1818::Register::CellVoltageC.to_index());
+    cells.push(adbms1818::Register::CellVoltageD.to_index());
+    cells.push(adbms1818::Register::CellVoltageE.to_index());
+    cells.push(adbms1818::Register::CellVoltageF.to_index());
+
+    assert_eq!(vec! {0, 1, 2, 3}, aux);
+    assert_eq!(vec! {0, 1, 2, 3, 4, 5}, cells);
+}
+
+#[test]
+fn test_adbms1818_cell_register_locations_all() {
+    let locations = adbms1818::CellSelection::All.get_locations();
+    assert_cell_register_locations(locations.collect());
+}
+
+#[test]
+fn test_adbms1818_gpio_register_locations_all() {
+    let locations = adbms1818::GPIOSelection::All.get_locations();
+    assert_gpio_register_locations(locations.collect());
+
+    let locations: Vec<&RegisterAddress<ADBMS1818>> = adbms1818::GPIOSelection::All.get_locations().collect();
+    assert_eq!(adbms1818::Channel::SecondReference, locations[9].channel);
+}
+
 #[test]
 fn test_ltc6813_cell_register_locations_all() {
     let locations = ltc6813::CellSelection::All.get_locations();
@@ -157,6 +212,26 @@ fn test_ltc6811_cell_register_locations_groups() {
     assert_eq!(2, ltc6811::CellSelection::Pair6.get_locations().len());
 }
 
+#[test]
+fn test_ltc6804_cell_register_locations_all() {
+    let locations = ltc6804::CellSelection::All.get_locations();
+    assert_cell_register_locations(locations.collect());
+}
+
+#[test]
+fn test_ltc6804_cell_register_locations_groups() {
+    let locations = ltc6804::CellSelection::Pair1
+        .get_locations()
+        .chain(ltc6804::CellSelection::Pair2.get_locations())
+        .chain(ltc6804::CellSelection::Pair3.get_locations())
+        .chain(ltc6804::CellSelection::Pair4.get_locations())
+        .chain(ltc6804::CellSelection::Pair5.get_locations())
+        .chain(ltc6804::CellSelection::Pair6.get_locations());
+
+    assert_cell_register_locations(locations.collect());
+    assert_eq!(2, ltc6804::CellSelection::Pair1.get_locations().len());
+}
+
 #[test]
 fn test_ltc6810_cell_register_locations_all() {
     let locations = ltc6810::CellSelection::All.get_locations();
@@ -279,6 +354,15 @@ fn test_ltc6811_gpio_register_locations_groups() {
     assert_eq!(ltc6811::Channel::SecondReference, second_ref[0].channel);
 }
 
+#[test]
+fn test_ltc6804_gpio_register_locations_all() {
+    let locations = ltc6804::GPIOSelection::All.get_locations();
+    assert_gpio_register_locations(locations.collect());
+
+    let locations: Vec<&RegisterAddress<LTC6804>> = ltc6804::GPIOSelection::All.get_locations().collect();
+    assert_eq!(ltc6804::Channel::SecondReference, locations[5].channel);
+}
+
 /// Array representation of correct GPIO register locations
 const LTC6810_CORRECT_GPIO_LOCATIONS: [[usize; 3]; 4] = [[0, 0, 1], [1, 0, 2], [2, 1, 0], [3, 1, 1]];
 
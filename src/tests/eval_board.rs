@@ -0,0 +1,51 @@
+//! Tests for evaluation board profiles
+use crate::auxiliary::AuxChannel;
+use crate::config::GPIO;
+use crate::eval_board::{BoardProfile, DEMO_BOARD_THERMISTOR};
+
+const THERMISTOR: AuxChannel = AuxChannel::Thermistor {
+    thermistor: DEMO_BOARD_THERMISTOR,
+    ratiometric: true,
+};
+
+#[test]
+fn test_dc2259a() {
+    let profile = BoardProfile::dc2259a();
+
+    assert_eq!("DC2259A", profile.get_name());
+    assert_eq!(0x0FFF, profile.connected_cells());
+    assert_eq!(12, profile.connected_cell_count());
+    assert!(profile.is_cell_connected(11));
+    assert!(!profile.is_cell_connected(12));
+    assert!(!profile.is_cell_connected(40));
+
+    let map = profile.aux_map();
+    assert_eq!(THERMISTOR, map.get(GPIO::GPIO1));
+    assert_eq!(THERMISTOR, map.get(GPIO::GPIO5));
+    assert_eq!(AuxChannel::Unused, map.get(GPIO::GPIO6));
+}
+
+#[test]
+fn test_dc2350a() {
+    let profile = BoardProfile::dc2350a();
+
+    assert_eq!("DC2350A", profile.get_name());
+    assert_eq!(0x3FFFF, profile.connected_cells());
+    assert_eq!(18, profile.connected_cell_count());
+
+    let maps = profile.aux_maps::<2>();
+    assert_eq!(THERMISTOR, maps[1].get(GPIO::GPIO3));
+    assert_eq!(AuxChannel::Unused, maps[1].get(GPIO::GPIO9));
+}
+
+#[test]
+fn test_override_aux_channel() {
+    let profile = BoardProfile::dc2350a()
+        .with_aux_channel(GPIO::GPIO2, AuxChannel::Unused)
+        .with_aux_channel(GPIO::GPIO7, AuxChannel::Voltage);
+
+    let map = profile.aux_map();
+    assert_eq!(THERMISTOR, map.get(GPIO::GPIO1));
+    assert_eq!(AuxChannel::Unused, map.get(GPIO::GPIO2));
+    assert_eq!(AuxChannel::Voltage, map.get(GPIO::GPIO7));
+}
@@ -0,0 +1,109 @@
+//! Tests for latched fault flags
+use crate::config::Cell;
+use crate::fault::{Fault, FaultLatch};
+use crate::mocks::{MockPin, MockSPIBus};
+use crate::monitor::Error;
+use crate::status::{CellFlags, StatusB};
+
+#[test]
+fn test_latch_keeps_first_occurrence() {
+    let mut faults: FaultLatch<2> = FaultLatch::new();
+    assert!(!faults.any_fault());
+
+    assert!(faults.latch(1, Fault::OpenWire(Cell::Cell7), 100));
+    assert!(!faults.latch(1, Fault::OpenWire(Cell::Cell7), 200));
+    assert!(faults.latch(1, Fault::SelfTestFailed, 300));
+
+    assert_eq!(Some(100), faults.first_occurrence(1, Fault::OpenWire(Cell::Cell7)));
+    assert_eq!(Some(300), faults.device(1).self_test_failed);
+    assert!(faults.is_latched(1, Fault::OpenWire(Cell::Cell7)));
+    assert!(!faults.is_latched(1, Fault::OpenWire(Cell::Cell8)));
+
+    assert!(!faults.has_faults(0));
+    assert!(faults.has_faults(1));
+    assert!(faults.any_fault());
+}
+
+#[test]
+fn test_clear_faults() {
+    let mut faults: FaultLatch<2> = FaultLatch::new();
+    assert!(faults.latch_all(Fault::ThermalShutdown, 100));
+    assert!(!faults.latch_all(Fault::ThermalShutdown, 200));
+
+    faults.clear_faults();
+    assert!(!faults.any_fault());
+
+    assert!(faults.latch(0, Fault::ThermalShutdown, 300));
+    assert_eq!(Some(300), faults.first_occurrence(0, Fault::ThermalShutdown));
+}
+
+#[test]
+fn test_record_error() {
+    let mut faults: FaultLatch<2> = FaultLatch::new();
+
+    faults.record_error(&Error::<MockSPIBus, MockPin>::ReadOnlyRegister, 100);
+    assert!(!faults.any_fault());
+
+    faults.record_error(&Error::<MockSPIBus, MockPin>::ChecksumMismatch, 200);
+    assert_eq!(Some(200), faults.device(0).checksum_mismatch);
+    assert_eq!(Some(200), faults.device(1).checksum_mismatch);
+}
+
+#[test]
+fn test_record_cell_flags() {
+    let mut faults: FaultLatch<2> = FaultLatch::new();
+
+    faults.record_cell_flags(
+        &[
+            // C1UV, C2OV, C15OV
+            CellFlags {
+                under_voltage: 0b1,
+                over_voltage: 0b100_0000_0000_0010,
+            },
+            CellFlags::default(),
+        ],
+        100,
+    );
+
+    assert!(faults.is_latched(0, Fault::UnderVoltage(Cell::Cell1)));
+    assert!(!faults.is_latched(0, Fault::OverVoltage(Cell::Cell1)));
+    assert!(faults.is_latched(0, Fault::OverVoltage(Cell::Cell2)));
+    assert!(!faults.is_latched(0, Fault::UnderVoltage(Cell::Cell2)));
+    assert!(faults.is_latched(0, Fault::OverVoltage(Cell::Cell15)));
+    assert!(!faults.has_faults(1));
+
+    // Flags are still latched after the device cleared them
+    faults.record_cell_flags(&[CellFlags::default(); 2], 200);
+    assert_eq!(Some(100), faults.first_occurrence(0, Fault::UnderVoltage(Cell::Cell1)));
+}
+
+#[test]
+fn test_record_status_flags() {
+    let mut faults: FaultLatch<2> = FaultLatch::new();
+
+    faults.record_status_flags(
+        &[
+            // C1UV, C2OV, C12OV
+            StatusB::from_register(&[0x00, 0xC8, 0b0000_1001, 0x00, 0b1000_0000, 0x00]),
+            // THSD
+            StatusB::from_register(&[0x00, 0xC8, 0x00, 0x00, 0x00, 0b0000_0001]),
+        ],
+        100,
+    );
+
+    // OV/UV flags are covered by the cell flags
+    assert!(!faults.has_faults(0));
+    assert!(faults.is_latched(1, Fault::ThermalShutdown));
+
+    faults.record_status_flags(&[StatusB::from_register(&[0x0; 6]); 2], 200);
+    assert_eq!(Some(100), faults.first_occurrence(1, Fault::ThermalShutdown));
+}
+
+#[test]
+fn test_record_self_test() {
+    let mut faults: FaultLatch<2> = FaultLatch::new();
+    faults.record_self_test(&[true, false], 100);
+
+    assert!(!faults.has_faults(0));
+    assert_eq!(Some(100), faults.device(1).self_test_failed);
+}
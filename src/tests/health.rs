@@ -0,0 +1,140 @@
+//! Tests for conversion timing health statistics
+use crate::conversion::ConversionRequest;
+use crate::health::{Clock, ConversionHealth};
+use crate::ltc6813::{CellSelection, Channel};
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{CommandTime, LTC681X};
+use embedded_hal::blocking::delay::DelayUs;
+
+#[test]
+fn test_health_empty() {
+    let health = ConversionHealth::new();
+
+    assert_eq!(0, health.count());
+    assert_eq!(None, health.min());
+    assert_eq!(None, health.max());
+    assert_eq!(None, health.mean());
+    assert_eq!(0, health.long_count());
+    assert!(!health.is_running_long());
+}
+
+#[test]
+fn test_health_statistics() {
+    let mut health = ConversionHealth::new();
+    let expected = CommandTime::new(1_000, 2_000);
+
+    assert!(!health.record(900, expected));
+    assert!(!health.record(1_100, expected));
+    assert!(health.record(1_101, expected));
+
+    assert_eq!(3, health.count());
+    assert_eq!(Some(900), health.min());
+    assert_eq!(Some(1_101), health.max());
+    assert_eq!(Some(1_033), health.mean());
+    assert_eq!(1, health.long_count());
+}
+
+#[test]
+fn test_health_alternative_timing() {
+    let mut health = ConversionHealth::new().with_alternative_timing().with_tolerance(0);
+
+    assert!(!health.record(2_000, CommandTime::new(1_000, 2_000)));
+    assert!(health.record(2_001, CommandTime::new(1_000, 2_000)));
+}
+
+#[test]
+fn test_health_running_long() {
+    let mut health = ConversionHealth::new().with_consecutive_limit(2);
+    let expected = CommandTime::new(1_000, 2_000);
+
+    health.record(1_200, expected);
+    assert!(!health.is_running_long());
+
+    // Interrupted by regular conversion
+    health.record(1_000, expected);
+    health.record(1_200, expected);
+    assert!(!health.is_running_long());
+
+    health.record(1_200, expected);
+    assert!(health.is_running_long());
+    assert_eq!(3, health.long_count());
+
+    health.reset();
+    assert_eq!(0, health.count());
+    assert!(!health.is_running_long());
+
+    // Configuration is kept
+    health.record(1_200, expected);
+    health.record(1_200, expected);
+    assert!(health.is_running_long());
+}
+
+#[test]
+fn test_timed_conversion() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0001, 0x7F, 0x5E)
+        .expect_poll(false)
+        .expect_poll(true)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        // Register E
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(4)).enable_sdo_polling();
+    let mut clock = SequenceClock {
+        timestamps: [u32::MAX - 99, 400],
+        index: 0,
+    };
+    let mut delay = RecordingDelay::default();
+    let mut health = ConversionHealth::new();
+
+    let voltages = monitor
+        .timed_conversion(
+            ConversionRequest::cells(CellSelection::Group1),
+            &mut clock,
+            &mut delay,
+            &mut health,
+        )
+        .unwrap();
+
+    assert_eq!(Channel::Cell1, voltages[0][0].channel);
+    assert_eq!(24979, voltages[0][0].voltage);
+
+    // Expected regular timing: 407 us
+    assert_eq!(1, health.count());
+    assert_eq!(Some(500), health.mean());
+    assert_eq!(1, health.long_count());
+    assert_eq!(100, delay.total);
+}
+
+/// Returns the given timestamps in sequence
+struct SequenceClock {
+    timestamps: [u32; 2],
+    index: usize,
+}
+
+impl Clock for SequenceClock {
+    fn now_us(&mut self) -> u32 {
+        let timestamp = self.timestamps[self.index];
+        self.index += 1;
+        timestamp
+    }
+}
+
+/// Delay mock summing up all delays
+#[derive(Default)]
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
@@ -0,0 +1,204 @@
+//! Tests for configuration integrity checks
+use crate::config::{Cell, Configuration};
+use crate::integrity::{AuditedRegister, ConfigurationAudit, UnavailableRegisterError};
+use crate::ltc6804::LTC6804;
+use crate::ltc6810::pwm::Pwm;
+use crate::ltc6810::LTC6810;
+use crate::ltc6813::LTC6813;
+use crate::mocks::{get_cs, BusError, BusMockBuilder, MockPin, MockSPIBus};
+use crate::monitor::{Error, LTC681X};
+use crate::pwm::PwmDutyCycle;
+
+#[test]
+fn test_digest_default_configuration() {
+    let mut audit: ConfigurationAudit<LTC6813, 1> = ConfigurationAudit::new();
+    audit.set_configuration(&[Configuration::default()]);
+
+    assert_eq!(0x0E8F_EAE9, audit.digest());
+    assert_eq!(audit.digest(), audit.device_digest(0));
+}
+
+#[test]
+fn test_digest_changes_with_configuration() {
+    let mut audit: ConfigurationAudit<LTC6813, 2> = ConfigurationAudit::new();
+    let empty_digest = audit.digest();
+
+    audit.set_configuration(&[Configuration::default(), Configuration::default()]);
+    let default_digest = audit.digest();
+    assert_ne!(empty_digest, default_digest);
+    assert_eq!(audit.device_digest(0), audit.device_digest(1));
+
+    let mut config = Configuration::default();
+    config.discharge_cell(Cell::Cell1);
+    audit.set_configuration(&[Configuration::default(), config]);
+
+    assert_ne!(default_digest, audit.digest());
+    assert_ne!(audit.device_digest(0), audit.device_digest(1));
+}
+
+#[test]
+fn test_digest_considers_mask() {
+    let mut first: ConfigurationAudit<LTC6813, 1> =
+        ConfigurationAudit::new().with_mask(AuditedRegister::ConfigurationA, [0x07, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    let mut second = first;
+
+    first
+        .set_register(0, AuditedRegister::ConfigurationA, [0xF8, 0, 0, 0, 0, 0])
+        .unwrap();
+    second
+        .set_register(0, AuditedRegister::ConfigurationA, [0x08, 0, 0, 0, 0, 0])
+        .unwrap();
+
+    assert_eq!(first.digest(), second.digest());
+    assert!(first.matches(0, AuditedRegister::ConfigurationA, &[0x08, 0, 0, 0, 0, 0]));
+    assert!(!first.matches(0, AuditedRegister::ConfigurationA, &[0xF8, 0x01, 0, 0, 0, 0]));
+}
+
+#[test]
+fn test_set_configuration_without_register_b() {
+    let mut audit: ConfigurationAudit<LTC6810, 1> = ConfigurationAudit::new();
+    audit.set_configuration(&[Configuration::default()]);
+
+    assert_eq!(
+        Some([0xF8, 0, 0, 0, 0, 0]),
+        audit.get_register(0, AuditedRegister::ConfigurationA)
+    );
+    assert_eq!(None, audit.get_register(0, AuditedRegister::ConfigurationB));
+    assert_eq!(None, audit.get_register(0, AuditedRegister::Pwm));
+}
+
+#[test]
+fn test_audit_configuration_consistent() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(2));
+
+    let mut audit = ConfigurationAudit::new();
+    audit.set_configuration(&[Configuration::default(), Configuration::default()]);
+
+    let report = monitor.audit_configuration(&audit).unwrap();
+    assert!(report.is_consistent());
+    assert_eq!(0, report.mismatched_devices().count());
+    assert_eq!(audit.digest(), report.digest());
+}
+
+#[test]
+fn test_audit_configuration_mismatch() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        // Discharge of cell 17 unexpectedly active
+        .expect_register_read(&[0x0F, 0x10, 0x00, 0x00, 0x00, 0x00, 0xBA, 0xF8])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(2));
+
+    let mut audit = ConfigurationAudit::new();
+    audit.set_configuration(&[Configuration::default(), Configuration::default()]);
+
+    let report = monitor.audit_configuration(&audit).unwrap();
+    assert!(!report.is_consistent());
+    assert!(!report.is_mismatch(0, AuditedRegister::ConfigurationB));
+    assert!(!report.is_mismatch(1, AuditedRegister::ConfigurationA));
+    assert!(report.is_mismatch(1, AuditedRegister::ConfigurationB));
+    assert_eq!(1, report.mismatched_devices().next().unwrap());
+
+    assert_ne!(audit.digest(), report.digest());
+    assert_eq!(audit.device_digest(0), report.actual().device_digest(0));
+    assert_eq!(
+        Some([0x0F, 0x10, 0x00, 0x00, 0x00, 0x00]),
+        report.actual().get_register(1, AuditedRegister::ConfigurationB)
+    );
+}
+
+#[test]
+fn test_audit_configuration_masked_gpio_bits() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        // GPIO pins read back as low
+        .expect_register_read(&[0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0xAB, 0xE0])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6810(bus, get_cs(1));
+
+    let mut audit: ConfigurationAudit<LTC6810, 1> =
+        ConfigurationAudit::new().with_mask(AuditedRegister::ConfigurationA, [0x07, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    audit.set_configuration(&[Configuration::default()]);
+
+    let report = monitor.audit_configuration(&audit).unwrap();
+    assert!(report.is_consistent());
+    assert_eq!(audit.digest(), report.digest());
+}
+
+#[test]
+fn test_audit_configuration_pwm() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0010_0010, 0x9D, 0x56)
+        .expect_register_read(&[0x66, 0x66, 0x66, 0x00, 0x00, 0x00, 0x68, 0x50])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6810(bus, get_cs(1));
+
+    let mut pwm = Pwm::default();
+    pwm.set_duty_cycle(&PwmDutyCycle::_23_3);
+
+    let mut audit: ConfigurationAudit<LTC6810, 1> = ConfigurationAudit::new();
+    audit.set_pwm(&[pwm]).unwrap();
+
+    let report = monitor.audit_configuration(&audit).unwrap();
+    assert!(report.is_consistent());
+}
+
+#[test]
+fn test_set_pwm_unavailable_register() {
+    let mut audit: ConfigurationAudit<LTC6804, 1> = ConfigurationAudit::new();
+    audit.set_configuration(&[Configuration::default()]);
+    let digest = audit.digest();
+
+    assert_eq!(Err(UnavailableRegisterError {}), audit.set_pwm(&[Pwm::default()]));
+    assert_eq!(None, audit.get_register(0, AuditedRegister::Pwm));
+    assert_eq!(digest, audit.digest());
+}
+
+#[test]
+fn test_set_register_unavailable_register() {
+    let mut audit: ConfigurationAudit<LTC6810, 1> = ConfigurationAudit::new();
+
+    let result = audit.set_register(0, AuditedRegister::ConfigurationB, [0x0F, 0, 0, 0, 0, 0]);
+    assert_eq!(Err(UnavailableRegisterError {}), result);
+    assert_eq!(None, audit.get_register(0, AuditedRegister::ConfigurationB));
+
+    let result = audit.set_register(0, AuditedRegister::Pwm, [0x88, 0, 0, 0, 0, 0]);
+    assert_eq!(Ok(()), result);
+    assert_eq!(Some([0x88, 0, 0, 0, 0, 0]), audit.get_register(0, AuditedRegister::Pwm));
+}
+
+#[test]
+fn test_audit_configuration_transfer_error() {
+    let mut bus = MockSPIBus::new();
+    bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
+
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+
+    let mut audit = ConfigurationAudit::new();
+    audit.set_configuration(&[Configuration::default()]);
+
+    match monitor.audit_configuration(&audit) {
+        Err(Error::TransferError(BusError::Error1)) => {}
+        _ => panic!("Unexpected result"),
+    }
+}
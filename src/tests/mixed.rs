@@ -0,0 +1,107 @@
+//! Tests for mixed-device daisy chains
+use crate::mixed::{DeviceKind, MixedChain};
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{Error, LTC681X};
+use crate::snapshot::Snapshot;
+
+#[test]
+fn test_device_kind_positions() {
+    assert_eq!(Some(5), DeviceKind::LTC6810.cell_position(5));
+    assert_eq!(None, DeviceKind::LTC6810.cell_position(6));
+    assert_eq!(Some(17), DeviceKind::LTC6813.cell_position(17));
+
+    // S0 precedes GPIO 1 on LTC6810
+    assert_eq!(Some(1), DeviceKind::LTC6810.gpio_position(0));
+    assert_eq!(Some(4), DeviceKind::LTC6810.gpio_position(3));
+    assert_eq!(None, DeviceKind::LTC6810.gpio_position(4));
+
+    // Second reference follows GPIO 5
+    assert_eq!(Some(4), DeviceKind::LTC6813.gpio_position(4));
+    assert_eq!(Some(6), DeviceKind::LTC6813.gpio_position(5));
+    assert_eq!(None, DeviceKind::LTC6811.gpio_position(5));
+}
+
+#[test]
+fn test_read_cell_voltages_ignores_unavailable_groups() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_register_read(&[0xFF; 8])
+        .expect_command(0b0000_0000, 0b0000_1010, 0xC3, 0x04)
+        .expect_register_read(&[0x8A, 0x61, 0x61, 0x1F, 0xCF, 0x21, 0x01, 0xEE])
+        .expect_register_read(&[0xFF; 8])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6811(bus, get_cs(4));
+    let mut chain = MixedChain::new(client, [DeviceKind::LTC6811, DeviceKind::LTC6810]);
+
+    let mut snapshot: Snapshot<2> = Snapshot::new();
+    chain.read_cell_voltages(&mut snapshot).unwrap();
+
+    let device = snapshot.device(0);
+    assert_eq!(Some(24979), device.cell_voltages[0]);
+    assert_eq!(Some(26333), device.cell_voltages[3]);
+    assert_eq!(Some(25441), device.cell_voltages[6]);
+    assert_eq!(Some(8655), device.cell_voltages[11]);
+    assert_eq!(None, device.cell_voltages[12]);
+
+    let device = snapshot.device(1);
+    assert_eq!(Some(26333), device.cell_voltages[0]);
+    assert_eq!(Some(8878), device.cell_voltages[5]);
+    assert_eq!(None, device.cell_voltages[6]);
+}
+
+#[test]
+fn test_read_gpio_voltages_layout() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1110, 0x72, 0x9A)
+        .expect_register_read(&[0x98, 0x3A, 0x98, 0x3A, 0x30, 0x75, 0x88, 0xAC])
+        .expect_register_read(&[0x98, 0x3A, 0x98, 0x3A, 0x30, 0x75, 0x88, 0xAC])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6811(bus, get_cs(2));
+    let mut chain = MixedChain::new(client, [DeviceKind::LTC6811, DeviceKind::LTC6810]);
+
+    let mut snapshot: Snapshot<2> = Snapshot::new();
+    chain.read_gpio_voltages(&mut snapshot).unwrap();
+
+    let device = snapshot.device(0);
+    assert_eq!(Some(24979), device.gpio_voltages[0]);
+    assert_eq!(Some(15000), device.gpio_voltages[3]);
+    assert_eq!(Some(15000), device.gpio_voltages[4]);
+    assert_eq!(None, device.gpio_voltages[5]);
+
+    let device = snapshot.device(1);
+    assert_eq!(Some(7867), device.gpio_voltages[0]);
+    assert_eq!(Some(8878), device.gpio_voltages[1]);
+    assert_eq!(Some(15000), device.gpio_voltages[2]);
+    assert_eq!(Some(15000), device.gpio_voltages[3]);
+    assert_eq!(None, device.gpio_voltages[4]);
+}
+
+#[test]
+fn test_read_cell_voltages_checksum_mismatch_of_used_group() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xFF; 8])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6811(bus, get_cs(1));
+    let mut chain = MixedChain::new(client, [DeviceKind::LTC6811, DeviceKind::LTC6810]);
+
+    let mut snapshot: Snapshot<2> = Snapshot::new();
+    match chain.read_cell_voltages(&mut snapshot) {
+        Err(Error::ChecksumMismatch) => {}
+        _ => panic!("Unexpected result"),
+    }
+}
@@ -1,4 +1,44 @@
+mod addressed;
+mod auxiliary;
+mod balancing;
+mod battery;
+mod bringup;
+mod can;
+#[cfg(feature = "std")]
+mod capture;
+mod chip_select;
+mod codec;
+mod command;
+mod commissioning;
+mod compensation;
+#[cfg(feature = "timing-compliance")]
+mod compliance;
+mod conversion;
+mod deadline;
+mod degraded;
+mod detect;
 mod device_config;
+#[cfg(feature = "eval-boards")]
+mod eval_board;
+mod fault;
+mod health;
+mod integrity;
+mod mixed;
 mod monitor;
+mod open_wire;
 mod pec15;
+mod pwm;
+mod raw;
+mod reference;
 mod reg_config;
+mod register_group;
+mod ring;
+mod self_test;
+mod sequence;
+mod snapshot;
+mod status;
+mod strict;
+mod temperature;
+mod threshold;
+mod timing;
+mod voting;
@@ -1,9 +1,19 @@
 //! Tests for generic, device type independent, logic
-use crate::config::{Cell, Configuration, GPIO};
-use crate::ltc6813::{CellSelection, Channel, GPIOSelection, Register};
+#![allow(deprecated)]
+use crate::config::{Cell, Configuration, ConfigurationRegisters, GPIO};
+use crate::conversion::ConversionRequest;
+use crate::ltc6813::{CellSelection, Channel, GPIOSelection, Register, LTC6813};
 use crate::mocks::{BusError, BusMockBuilder, MockPin, MockSPIBus, PinError};
-use crate::monitor::{ADCMode, Error, LTC681XClient, PollClient, StatusGroup, LTC681X};
+use crate::monitor::{
+    ADCMode, CommandTime, Error, ErrorKind, InternalDeviceParameters, LTC681XClient, NoPolling, Operation, PollClient,
+    RegisterLocator, StatusGroup, TransferHook, Voltage, WakeProfile, LTC681X,
+};
+use crate::pwm::PwmRegisters;
+use crate::timing::{T_BRIDGE_READY_US, T_READY_US, T_WAKE_US};
 use alloc::string::ToString;
+use core::cell::RefCell;
+use embedded_hal::blocking::delay::DelayUs;
+use heapless::Vec;
 
 #[test]
 fn test_start_conv_cells_acc_modes() {
@@ -120,6 +130,7 @@ fn test_start_conv_cs_error() {
 fn test_start_conv_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -202,6 +213,27 @@ fn test_start_conv_gpio_groups() {
     assert_eq!(520, timing.alternative);
 }
 
+#[test]
+fn test_start_conv_gpio_redundant() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0000_0000, 0x82, 0x76)
+        .expect_command(0b0000_0101, 0b0000_0001, 0x09, 0x44)
+        .expect_command(0b0000_0100, 0b1000_0000, 0x4E, 0x1C)
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(3));
+    let timing = monitor.start_conv_gpio_redundant(ADCMode::Normal, GPIOSelection::All).unwrap();
+    assert_eq!(3862, timing.regular);
+    assert_eq!(5025, timing.alternative);
+
+    let timing = monitor
+        .start_conv_gpio_redundant(ADCMode::Normal, GPIOSelection::Group1)
+        .unwrap();
+    assert_eq!(788, timing.regular);
+
+    monitor.start_conv_gpio_redundant(ADCMode::Fast, GPIOSelection::All).unwrap();
+}
+
 #[test]
 fn test_start_conv_gpio_sdo_polling() {
     let mut cs = MockPin::new();
@@ -234,6 +266,7 @@ fn test_start_conv_gpio_cs_error() {
 fn test_start_conv_gpio_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -307,6 +340,7 @@ fn test_start_overlap_measurement_cs_error() {
 fn test_start_overlap_measurement_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -389,6 +423,40 @@ fn test_measure_internal_parameters_status_groups() {
     assert_eq!(520, timing.alternative);
 }
 
+#[test]
+fn test_measure_internal_parameters_redundant() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0000_1000, 0x6A, 0x78)
+        .expect_command(0b0000_0101, 0b0000_1010, 0xF7, 0x2E)
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(2));
+    let timing = monitor
+        .measure_internal_parameters_redundant(ADCMode::Normal, StatusGroup::All)
+        .unwrap();
+    assert_eq!(1_600, timing.regular);
+    assert_eq!(2_000, timing.alternative);
+
+    let timing = monitor
+        .measure_internal_parameters_redundant(ADCMode::Normal, StatusGroup::Temperature)
+        .unwrap();
+    assert_eq!(403, timing.regular);
+}
+
+#[test]
+fn test_clear_registers() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0111, 0b0001_0001, 0xC9, 0xC0)
+        .expect_command(0b0000_0111, 0b0001_0010, 0xDF, 0xA4)
+        .expect_command(0b0000_0111, 0b0001_0011, 0x54, 0x96)
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(3));
+    monitor.clear_cells().unwrap();
+    monitor.clear_aux().unwrap();
+    monitor.clear_status().unwrap();
+}
+
 #[test]
 fn test_measure_internal_parameters_sdo_polling() {
     let mut cs = MockPin::new();
@@ -421,6 +489,7 @@ fn test_measure_internal_parameters_cs_error() {
 fn test_measure_internal_parameters_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -459,7 +528,8 @@ fn test_sdo_polling_not_ready() {
 
 #[test]
 fn test_sdo_polling_transfer_error() {
-    let cs = MockPin::new();
+    let mut cs = MockPin::new();
+    cs.expect_set_high().times(1).returning(move || Ok(()));
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
 
@@ -603,10 +673,114 @@ fn test_read_cell_voltages_multiple_devices() {
     assert_eq!(8393, result[2][2]);
 }
 
+#[test]
+fn test_read_register_dummy_bytes() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .expect_dummy_bytes(8)
+        .expect_dummy_bytes(2)
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs_no_polling(1)).with_trailing_dummy_bytes(10);
+
+    let result = monitor.read_register(Register::CellVoltageA).unwrap();
+    assert_eq!(24979, result[0][0]);
+    assert_eq!(26333, result[1][0]);
+}
+
+#[test]
+fn test_write_register_dummy_bytes() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0x28, 0xC0])
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .into_mock();
+
+    // Trailing dummy bytes are only sent after reads
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs_no_polling(1)).with_trailing_dummy_bytes(3);
+
+    let data1 = [0x1, 0x2, 0x3, 0x4, 0x5, 0x6];
+    let data2 = [0x7, 0x8, 0x9, 0xA, 0xB, 0xC];
+
+    monitor.write_register(Register::ConfigurationB, [data1, data2]).unwrap();
+}
+
+#[test]
+fn test_read_register_frame_gap() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_dummy_bytes(2)
+        .into_mock();
+
+    let total = RefCell::new(0);
+    let mut monitor: LTC681X<_, _, _, _, 3, _> = LTC681X::ltc6813(bus, get_cs_no_polling(1))
+        .with_trailing_dummy_bytes(2)
+        .with_frame_gap(15, SharedDelay { total: &total });
+    assert_eq!(15, monitor.transfer_hook().gap());
+
+    let result = monitor.read_register(Register::CellVoltageA).unwrap();
+    assert_eq!(24979, result[0][0]);
+    assert_eq!(26333, result[1][0]);
+    assert_eq!(25441, result[2][0]);
+
+    // One pause between each two frames, no bytes are sent
+    assert_eq!(30, *total.borrow());
+}
+
+#[test]
+fn test_write_register_no_frame_gap() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0x28, 0xC0])
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .into_mock();
+
+    let total = RefCell::new(0);
+    let mut monitor: LTC681X<_, _, _, _, 2, _> =
+        LTC681X::ltc6813(bus, get_cs_no_polling(1)).with_frame_gap(15, SharedDelay { total: &total });
+
+    let data1 = [0x1, 0x2, 0x3, 0x4, 0x5, 0x6];
+    let data2 = [0x7, 0x8, 0x9, 0xA, 0xB, 0xC];
+
+    monitor.write_register(Register::ConfigurationB, [data1, data2]).unwrap();
+    assert_eq!(0, *total.borrow());
+}
+
+#[test]
+fn test_read_all_cell_voltages() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .expect_command(0b0000_0000, 0b0000_1010, 0xC3, 0x04)
+        .expect_register_read(&[0x8A, 0x61, 0x61, 0x1F, 0xCF, 0x21, 0x01, 0xEE])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs_no_polling(4));
+    let voltages = client.read_all_cell_voltages().unwrap();
+
+    assert_eq!([Some(24979), Some(7867), Some(8878)], voltages[0][0..3]);
+    assert_eq!([Some(26333), Some(7538), Some(7330)], voltages[0][3..6]);
+    assert_eq!([Some(25441), Some(7869), Some(8932)], voltages[0][6..9]);
+    assert_eq!([Some(24970), Some(8033), Some(8655)], voltages[0][9..12]);
+
+    // Not available on LTC6811
+    assert_eq!([None; 6], voltages[0][12..18]);
+}
+
 #[test]
 fn test_read_cell_voltages_pec_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let bus = BusMockBuilder::new()
         .expect_command(0b0000_0000, 0b0000_1011, 0x48, 0x36)
@@ -622,6 +796,24 @@ fn test_read_cell_voltages_pec_error() {
     }
 }
 
+#[test]
+fn test_error_kind() {
+    let error: Error<MockSPIBus, MockPin> = Error::TransferError(BusError::Error1);
+    assert_eq!(ErrorKind::Transfer, error.kind());
+
+    let error: Error<MockSPIBus, MockPin> = Error::CSPinError(PinError::Error1);
+    assert_eq!(ErrorKind::CSPin, error.kind());
+
+    let error: Error<MockSPIBus, MockPin> = Error::ChecksumMismatch;
+    assert_eq!(ErrorKind::ChecksumMismatch, error.kind());
+
+    let error: Error<MockSPIBus, MockPin> = Error::ReadOnlyRegister;
+    assert_eq!(ErrorKind::ReadOnlyRegister, error.kind());
+
+    let error: Error<MockSPIBus, MockPin> = Error::InconsistentRead;
+    assert_eq!(ErrorKind::InconsistentRead, error.kind());
+}
+
 #[test]
 fn test_read_cell_voltages_cs_error() {
     let mut cs = MockPin::new();
@@ -642,6 +834,7 @@ fn test_read_cell_voltages_cs_error() {
 fn test_read_cell_voltages_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -805,6 +998,7 @@ fn test_read_register_multiple_devices() {
 fn test_read_register_pec_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let bus = BusMockBuilder::new()
         .expect_command(0b0000_0000, 0b0000_1111, 0xF9, 0xA8)
@@ -840,6 +1034,7 @@ fn test_read_register_cs_error() {
 fn test_read_register_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -915,8 +1110,8 @@ fn test_write_register_conf_b() {
 fn test_write_register_multiple_devices() {
     let bus = BusMockBuilder::new()
         .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
-        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
         .expect_register_write(&[0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0x28, 0xC0])
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
         .into_mock();
 
     let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs_no_polling(1));
@@ -947,6 +1142,7 @@ fn test_write_register_cs_error() {
 fn test_write_register_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -960,6 +1156,187 @@ fn test_write_register_transfer_error() {
     }
 }
 
+#[test]
+fn test_write_coalescing_skips_identical_data() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0x28, 0xC0])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(2)).with_write_coalescing();
+
+    monitor
+        .write_register(Register::ConfigurationB, [[0x1, 0x2, 0x3, 0x4, 0x5, 0x6]])
+        .unwrap();
+    monitor
+        .write_register(Register::ConfigurationB, [[0x1, 0x2, 0x3, 0x4, 0x5, 0x6]])
+        .unwrap();
+    monitor
+        .write_register(Register::ConfigurationB, [[0x7, 0x8, 0x9, 0xA, 0xB, 0xC]])
+        .unwrap();
+    monitor
+        .write_register(Register::ConfigurationB, [[0x7, 0x8, 0x9, 0xA, 0xB, 0xC]])
+        .unwrap();
+}
+
+#[test]
+fn test_write_coalescing_force_and_invalidate() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(3)).with_write_coalescing();
+
+    let data = [[0x1, 0x2, 0x3, 0x4, 0x5, 0x6]];
+    monitor.write_register(Register::ConfigurationB, data).unwrap();
+    monitor.force_write_register(Register::ConfigurationB, data).unwrap();
+
+    monitor.invalidate_write_cache();
+    monitor.write_register(Register::ConfigurationB, data).unwrap();
+    monitor.write_register(Register::ConfigurationB, data).unwrap();
+
+    let result = monitor.force_write_register(Register::CellVoltageA, data);
+    assert!(matches!(result, Err(Error::ReadOnlyRegister)));
+}
+
+#[test]
+fn test_write_coalescing_failed_write_not_cached() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(2).returning(move || Ok(()));
+    cs.expect_set_high().times(2).returning(move || Ok(()));
+
+    let mut bus = MockSPIBus::new();
+    bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
+    bus.expect_transfer().times(2).returning(move |_| Ok(&[0xff; 8]));
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs).with_write_coalescing();
+
+    let result = monitor.write_register(Register::ConfigurationA, [[0x0; 6]]);
+    assert!(matches!(result, Err(Error::TransferError(_))));
+
+    monitor.write_register(Register::ConfigurationA, [[0x0; 6]]).unwrap();
+}
+
+#[test]
+fn test_write_coalescing_invalidated_on_pec_error() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(3).returning(move || Ok(()));
+    cs.expect_set_high().times(3).returning(move || Ok(()));
+
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .expect_command(0b0000_0000, 0b0000_1011, 0x48, 0x36)
+        .expect_register_read(&[0x2A, 0x63, 0x8E, 0x1E, 0xEC, 0x1F, 0x11, 0x0D])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs).with_write_coalescing();
+
+    let data = [[0x1, 0x2, 0x3, 0x4, 0x5, 0x6]];
+    monitor.write_register(Register::ConfigurationB, data).unwrap();
+
+    let result = monitor.read_register(Register::CellVoltageF);
+    assert!(matches!(result, Err(Error::ChecksumMismatch)));
+
+    // Sent again, as the register contents are unknown after a PEC error
+    monitor.write_register(Register::ConfigurationB, data).unwrap();
+}
+
+#[test]
+fn test_double_read_consistent() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0x00, 0x02, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0x00, 0x02, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0x00, 0x04, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0x00, 0x10, 0xED, 0x72)
+        .expect_register_read(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D, 0x31, 0x8A])
+        .expect_command(0x00, 0x10, 0xED, 0x72)
+        .expect_register_read(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D, 0x31, 0x8A])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(5)).with_double_read();
+
+    assert_eq!(
+        [0x00F8, 0, 0],
+        monitor.read_register(Register::ConfigurationA).unwrap()[0]
+    );
+
+    // Cell voltage registers are read once
+    assert_eq!(24979, monitor.read_register(Register::CellVoltageA).unwrap()[0][0]);
+
+    let raw = monitor.read_register_raw(Register::StatusA).unwrap();
+    assert_eq!([0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D], raw[0]);
+}
+
+#[test]
+fn test_double_read_inconsistent() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0x00, 0x02, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0x00, 0x02, 0x2B, 0x0A)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(2)).with_double_read();
+
+    let result = monitor.read_register(Register::ConfigurationA);
+    assert!(matches!(result, Err(Error::InconsistentRead)));
+}
+
+#[test]
+fn test_double_read_aux_d() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0x00, 0x0F, 0xF9, 0xA8)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0x00, 0x0F, 0xF9, 0xA8)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0x00, 0x0F, 0xF9, 0xA8)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0x00, 0x0F, 0xF9, 0xA8)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(4)).with_double_read();
+
+    // Holds the OV/UV flags of cell 13-18
+    let raw = monitor.read_register_raw(Register::AuxiliaryD).unwrap();
+    assert_eq!([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22], raw[0]);
+
+    let result = monitor.read_register_raw(Register::AuxiliaryD);
+    assert!(matches!(result, Err(Error::InconsistentRead)));
+}
+
+#[test]
+fn test_double_read_pec_error_of_second_read() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0x00, 0x02, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0x00, 0x02, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE3])
+        .into_mock();
+
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(2).returning(move || Ok(()));
+    cs.expect_set_high().times(2).returning(move || Ok(()));
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs).with_double_read();
+
+    let result = monitor.read_register_raw(Register::ConfigurationA);
+    assert!(matches!(result, Err(Error::ChecksumMismatch)));
+}
+
 #[test]
 fn test_write_configuration_correct_data() {
     let bus = BusMockBuilder::new()
@@ -1002,6 +1379,16 @@ fn test_write_configuration_correct_data() {
 fn test_write_configuration_multiple_devices() {
     let bus = BusMockBuilder::new()
         .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[
+            0b1010_1000,
+            0b0000_0000,
+            0b0000_0000,
+            0b0000_0000,
+            0b0011_1000,
+            0b0000_0000,
+            0x72,
+            0x5E,
+        ])
         .expect_register_write(&[
             0b1111_1000,
             0b1110_0001,
@@ -1012,17 +1399,17 @@ fn test_write_configuration_multiple_devices() {
             0x66,
             0xE0,
         ])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
         .expect_register_write(&[
-            0b1010_1000,
+            0b0110_1101,
             0b0000_0000,
             0b0000_0000,
             0b0000_0000,
-            0b0011_1000,
             0b0000_0000,
-            0x72,
-            0x5E,
+            0b0000_0000,
+            0x13,
+            0xD6,
         ])
-        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
         .expect_register_write(&[
             0b0000_1111,
             0b0000_0010,
@@ -1033,16 +1420,6 @@ fn test_write_configuration_multiple_devices() {
             0xA,
             0xFA,
         ])
-        .expect_register_write(&[
-            0b0110_1101,
-            0b0000_0000,
-            0b0000_0000,
-            0b0000_0000,
-            0b0000_0000,
-            0b0000_0000,
-            0x13,
-            0xD6,
-        ])
         .into_mock();
 
     let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs_no_polling(2));
@@ -1094,7 +1471,361 @@ fn test_write_configuration_ltc6810() {
 }
 
 #[test]
-fn test_write_configuration_cs_error() {
+fn test_write_configuration_with_device_index() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1F, 0x00, 0x00, 0x00, 0x00, 0x00, 0xCD, 0x8C])
+        .expect_register_write(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs_no_polling(2));
+
+    monitor
+        .write_configuration_with(|index| {
+            let mut config = Configuration::default();
+            match index {
+                0 => config.discharge_cell(Cell::Cell3),
+                _ => config.discharge_cell(Cell::Cell13),
+            }
+            config
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_write_configuration_ltc6804() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x52, 0xF7, 0xA7, 0x01, 0x08, 0x70, 0x2E])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6804(bus, get_cs_no_polling(1));
+
+    let mut config = Configuration::default();
+    config.set_ov_comp_voltage(4_300_000).unwrap();
+    config.set_uv_comp_voltage(3_000_000).unwrap();
+    config.discharge_cell(Cell::Cell1);
+    config.discharge_cell(Cell::Cell12);
+    config.discharge_cell(Cell::Cell13);
+
+    monitor.write_configuration([config]).unwrap();
+}
+
+#[test]
+fn test_write_pwm_ltc6804_not_supported() {
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6804(MockSPIBus::new(), MockPin::new());
+
+    let result = monitor.write_pwm([crate::ltc6810::pwm::Pwm::default()]);
+    assert!(matches!(result, Err(Error::ReadOnlyRegister)));
+}
+
+#[test]
+fn test_read_configuration() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x1F, 0x00, 0x00, 0x00, 0x00, 0x00, 0xCD, 0x8C])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(2));
+
+    let config: [Configuration; 1] = monitor.read_configuration().unwrap();
+    assert!(config[0].is_cell_discharging(Cell::Cell3));
+    assert!(config[0].is_cell_discharging(Cell::Cell13));
+    assert!(!config[0].is_cell_discharging(Cell::Cell1));
+    assert_eq!([0xF8, 0x00, 0x00, 0x00, 0x04, 0x00], config[0].register_a());
+    assert_eq!(Some([0x1F, 0x00, 0x00, 0x00, 0x00, 0x00]), config[0].register_b());
+}
+
+#[test]
+fn test_read_configuration_multiple_devices() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_register_read(&[0x1F, 0x00, 0x00, 0x00, 0x00, 0x00, 0xCD, 0x8C])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs_no_polling(2));
+
+    let config: [Configuration; 2] = monitor.read_configuration().unwrap();
+    assert!(config[0].is_cell_discharging(Cell::Cell3));
+    assert!(!config[0].is_cell_discharging(Cell::Cell13));
+    assert!(!config[1].is_cell_discharging(Cell::Cell3));
+    assert!(config[1].is_cell_discharging(Cell::Cell13));
+}
+
+#[test]
+fn test_read_configuration_ltc6810() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x01, 0x00, 0x36, 0xAE])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6810(bus, get_cs_no_polling(1));
+
+    let config: [crate::ltc6810::Configuration; 1] = monitor.read_configuration().unwrap();
+    assert!(config[0].is_cell_discharging(crate::ltc6810::config::Cell::Cell1));
+}
+
+#[test]
+fn test_update_configuration_changed_register_a() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(3));
+
+    let written = monitor
+        .update_configuration(|config: &mut [Configuration; 1]| config[0].discharge_cell(Cell::Cell3))
+        .unwrap();
+    assert!(written);
+}
+
+#[test]
+fn test_update_configuration_changed_register_b() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1F, 0x00, 0x00, 0x00, 0x00, 0x00, 0xCD, 0x8C])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(3));
+
+    let written = monitor
+        .update_configuration(|config: &mut [Configuration; 1]| {
+            assert!(config[0].is_cell_discharging(Cell::Cell3));
+            config[0].discharge_cell(Cell::Cell13);
+        })
+        .unwrap();
+    assert!(written);
+}
+
+/// Client implementing only the required trait methods, so the default implementations are used
+struct MinimalClient(LTC681X<MockSPIBus, MockPin, NoPolling, LTC6813, 1>);
+
+impl LTC681XClient<LTC6813, 1> for MinimalClient {
+    type Error = Error<MockSPIBus, MockPin>;
+
+    fn start_conv_cells(&mut self, mode: ADCMode, cells: CellSelection, dcp: bool) -> Result<CommandTime, Self::Error> {
+        self.0.start_conv_cells(mode, cells, dcp)
+    }
+
+    fn start_conversion(&mut self, request: ConversionRequest<LTC6813>) -> Result<CommandTime, Self::Error> {
+        self.0.start_conversion(request)
+    }
+
+    fn start_conv_gpio(&mut self, mode: ADCMode, pins: GPIOSelection) -> Result<CommandTime, Self::Error> {
+        self.0.start_conv_gpio(mode, pins)
+    }
+
+    fn start_conv_gpio_redundant(&mut self, mode: ADCMode, pins: GPIOSelection) -> Result<CommandTime, Self::Error> {
+        self.0.start_conv_gpio_redundant(mode, pins)
+    }
+
+    fn start_overlap_measurement(&mut self, mode: ADCMode, dcp: bool) -> Result<(), Self::Error> {
+        self.0.start_overlap_measurement(mode, dcp)
+    }
+
+    fn measure_internal_parameters(&mut self, mode: ADCMode, group: StatusGroup) -> Result<CommandTime, Self::Error> {
+        self.0.measure_internal_parameters(mode, group)
+    }
+
+    fn measure_internal_parameters_redundant(
+        &mut self,
+        mode: ADCMode,
+        group: StatusGroup,
+    ) -> Result<CommandTime, Self::Error> {
+        self.0.measure_internal_parameters_redundant(mode, group)
+    }
+
+    fn clear_cells(&mut self) -> Result<(), Self::Error> {
+        self.0.clear_cells()
+    }
+
+    fn clear_aux(&mut self) -> Result<(), Self::Error> {
+        self.0.clear_aux()
+    }
+
+    fn clear_status(&mut self) -> Result<(), Self::Error> {
+        self.0.clear_status()
+    }
+
+    fn read_register(&mut self, register: Register) -> Result<[[u16; 3]; 1], Self::Error> {
+        self.0.read_register(register)
+    }
+
+    fn write_register(&mut self, register: Register, data: [[u8; 6]; 1]) -> Result<(), Self::Error> {
+        self.0.write_register(register, data)
+    }
+
+    fn write_configuration<C: ConfigurationRegisters>(&mut self, config: [C; 1]) -> Result<(), Self::Error> {
+        self.0.write_configuration(config)
+    }
+
+    fn write_pwm<P: PwmRegisters>(&mut self, pwm: [P; 1]) -> Result<(), Self::Error> {
+        self.0.write_pwm(pwm)
+    }
+
+    fn read_voltages<R: RegisterLocator<LTC6813> + 'static>(
+        &mut self,
+        locator: R,
+    ) -> Result<Vec<Vec<Voltage<LTC6813>, 18>, 1>, Self::Error> {
+        self.0.read_voltages(locator)
+    }
+
+    fn read_overlap_result(&mut self) -> Result<[[u16; 4]; 1], Self::Error> {
+        self.0.read_overlap_result()
+    }
+
+    fn read_internal_device_parameters(&mut self) -> Result<Vec<InternalDeviceParameters, 1>, Self::Error> {
+        self.0.read_internal_device_parameters()
+    }
+}
+
+#[test]
+fn test_client_default_update_configuration() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1F, 0x00, 0x00, 0x00, 0x00, 0x00, 0xCD, 0x8C])
+        .into_mock();
+
+    let mut client = MinimalClient(LTC681X::ltc6813(bus, get_cs_no_polling(3)));
+
+    let written = client
+        .update_configuration(|config: &mut [Configuration; 1]| {
+            assert!(config[0].is_cell_discharging(Cell::Cell3));
+            config[0].discharge_cell(Cell::Cell13);
+        })
+        .unwrap();
+    assert!(written);
+}
+
+#[test]
+fn test_client_default_read_register_voltages() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let mut client = MinimalClient(LTC681X::ltc6813(bus, get_cs_no_polling(1)));
+
+    let voltages = client.read_register_voltages(Register::CellVoltageA).unwrap();
+    assert_eq!(Channel::Cell1, voltages[0][0].channel);
+    assert_eq!(24979, voltages[0][0].voltage);
+    assert_eq!(3, voltages[0].len());
+}
+
+#[test]
+fn test_update_configuration_multiple_devices() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        // Device 0: Cell 3 discharging
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        // Device 1: Default
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        // Data of the last device is sent first
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x01, 0x00, 0x36, 0xAE])
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1F, 0x00, 0x00, 0x00, 0x00, 0x00, 0xCD, 0x8C])
+        .expect_register_write(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs_no_polling(4));
+
+    let written = monitor
+        .update_configuration(|config: &mut [Configuration; 2]| {
+            assert!(config[0].is_cell_discharging(Cell::Cell3));
+            assert!(!config[1].is_cell_discharging(Cell::Cell3));
+
+            config[1].discharge_cell(Cell::Cell1);
+            config[1].discharge_cell(Cell::Cell13);
+        })
+        .unwrap();
+    assert!(written);
+}
+
+#[test]
+fn test_update_configuration_unchanged() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x04, 0x00, 0x02, 0x84])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(2));
+
+    let written = monitor
+        .update_configuration(|config: &mut [Configuration; 1]| config[0].discharge_cell(Cell::Cell3))
+        .unwrap();
+    assert!(!written);
+}
+
+#[test]
+fn test_update_configuration_ltc6810() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x01, 0x00, 0x36, 0xAE])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6810(bus, get_cs_no_polling(2));
+
+    let written = monitor
+        .update_configuration(|config: &mut [crate::ltc6810::Configuration; 1]| {
+            config[0].discharge_cell(crate::ltc6810::config::Cell::Cell1)
+        })
+        .unwrap();
+    assert!(written);
+}
+
+#[test]
+fn test_update_configuration_checksum_error() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00])
+        .into_mock();
+
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+
+    let result = monitor.update_configuration(|_: &mut [Configuration; 1]| {});
+    match result.unwrap_err() {
+        Error::ChecksumMismatch => {}
+        _ => panic!("Unexpected error type"),
+    }
+}
+
+#[test]
+fn test_write_configuration_cs_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Err(PinError::Error1));
 
@@ -1113,6 +1844,7 @@ fn test_write_configuration_cs_error() {
 fn test_write_configuration_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -1549,6 +2281,37 @@ fn test_read_voltages_gpio_group_6() {
     assert_eq!(7330, result[0][0].voltage);
 }
 
+#[test]
+fn test_read_register_voltages() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .expect_command(0b0000_0000, 0b0000_1111, 0xF9, 0xA8)
+        .expect_register_read(&[0x98, 0x3A, 0x00, 0x00, 0x00, 0x00, 0xEB, 0xB4])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(2));
+
+    let result = monitor.read_register_voltages(Register::CellVoltageB).unwrap();
+    assert_eq!(3, result[0].len());
+    assert_eq!(Channel::Cell4, result[0][0].channel);
+    assert_eq!(26333, result[0][0].voltage);
+    assert_eq!(Channel::Cell5, result[0][1].channel);
+    assert_eq!(7538, result[0][1].voltage);
+    assert_eq!(Channel::Cell6, result[0][2].channel);
+    assert_eq!(7330, result[0][2].voltage);
+
+    // Aux D just stores GPIO9
+    let result = monitor.read_register_voltages(Register::AuxiliaryD).unwrap();
+    assert_eq!(1, result[0].len());
+    assert_eq!(Channel::GPIO9, result[0][0].channel);
+    assert_eq!(15000, result[0][0].voltage);
+
+    // Not a voltage register, no transaction
+    let result = monitor.read_register_voltages(Register::ConfigurationA).unwrap();
+    assert!(result[0].is_empty());
+}
+
 #[test]
 fn test_read_voltages_gpio_all() {
     let bus = BusMockBuilder::new()
@@ -1623,6 +2386,7 @@ fn test_read_voltages_cell_cs_error() {
 fn test_read_voltages_cell_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -1636,7 +2400,258 @@ fn test_read_voltages_cell_transfer_error() {
     }
 }
 
+/// Sums up a weight per operation kind
+#[derive(Default)]
+struct CountingHook {
+    pre: usize,
+    post: usize,
+}
+
+impl CountingHook {
+    fn weight(operation: Operation) -> usize {
+        match operation {
+            Operation::Command => 1,
+            Operation::Read => 10,
+            Operation::Write => 100,
+            Operation::Poll => 1000,
+            Operation::Wake => 10000,
+        }
+    }
+}
+
+impl TransferHook for CountingHook {
+    fn pre_transfer(&mut self, operation: Operation) {
+        self.pre += Self::weight(operation);
+    }
+
+    fn post_transfer(&mut self, operation: Operation) {
+        self.post += Self::weight(operation);
+    }
+}
+
+#[test]
+fn test_transfer_hooks() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0000, 0xf4, 0x6c)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1, _> =
+        LTC681X::ltc6813(bus, get_cs_no_polling(3)).with_transfer_hook(CountingHook::default());
+
+    monitor.start_conv_cells(ADCMode::Normal, CellSelection::All, false).unwrap();
+    monitor.read_register(Register::CellVoltageA).unwrap();
+    monitor
+        .write_register(Register::ConfigurationB, [[0x1, 0x2, 0x3, 0x4, 0x5, 0x6]])
+        .unwrap();
+
+    assert_eq!(111, monitor.transfer_hook().pre);
+    assert_eq!(111, monitor.transfer_hook().post);
+}
+
+#[test]
+fn test_transfer_hooks_sdo_polling() {
+    let mut cs = MockPin::new();
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let mut bus = MockSPIBus::new();
+    bus.expect_transfer().times(1).returning(move |_| Ok(&[0x0]));
+    bus.expect_transfer().times(1).returning(move |_| Ok(&[0xff]));
+
+    let mut monitor: LTC681X<_, _, _, _, 1, _> = LTC681X::ltc6813(bus, cs)
+        .with_transfer_hook(CountingHook::default())
+        .enable_sdo_polling();
+
+    assert!(!monitor.adc_ready().unwrap());
+    assert!(monitor.adc_ready().unwrap());
+    assert_eq!(2000, monitor.transfer_hook().pre);
+    assert_eq!(2000, monitor.transfer_hook().post);
+}
+
+#[test]
+fn test_transfer_hooks_pec_error() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1D])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1, _> =
+        LTC681X::ltc6813(bus, get_cs_no_polling(1)).with_transfer_hook(CountingHook::default());
+
+    match monitor.read_register(Register::CellVoltageA).unwrap_err() {
+        Error::ChecksumMismatch => {}
+        _ => panic!("Unexpected error type"),
+    }
+
+    // CS is released by the mock expectation, post-transfer hook is invoked
+    assert_eq!(10, monitor.transfer_hook().pre);
+    assert_eq!(10, monitor.transfer_hook().post);
+}
+
+#[test]
+fn test_transfer_hooks_transfer_error() {
+    let mut bus = MockSPIBus::new();
+    bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
+
+    let mut monitor: LTC681X<_, _, _, _, 1, _> =
+        LTC681X::ltc6813(bus, get_cs_no_polling(1)).with_transfer_hook(CountingHook::default());
+
+    match monitor
+        .write_register(Register::ConfigurationB, [[0x1, 0x2, 0x3, 0x4, 0x5, 0x6]])
+        .unwrap_err()
+    {
+        Error::TransferError(_) => {}
+        _ => panic!("Unexpected error type"),
+    }
+
+    assert_eq!(100, monitor.transfer_hook().pre);
+    assert_eq!(100, monitor.transfer_hook().post);
+}
+
+#[test]
+fn test_transfer_hooks_cs_low_error() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Err(PinError::Error1));
+
+    let mut monitor: LTC681X<_, _, _, _, 1, _> =
+        LTC681X::ltc6813(MockSPIBus::new(), cs).with_transfer_hook(CountingHook::default());
+
+    match monitor.read_register(Register::CellVoltageA).unwrap_err() {
+        Error::CSPinError(_) => {}
+        _ => panic!("Unexpected error type"),
+    }
+
+    assert_eq!(10, monitor.transfer_hook().pre);
+    assert_eq!(10, monitor.transfer_hook().post);
+}
+
+#[test]
+fn test_transfer_hooks_cs_high_error() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Err(PinError::Error1));
+
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1, _> = LTC681X::ltc6813(bus, cs).with_transfer_hook(CountingHook::default());
+
+    match monitor.read_register(Register::CellVoltageA).unwrap_err() {
+        Error::CSPinError(_) => {}
+        _ => panic!("Unexpected error type"),
+    }
+
+    assert_eq!(10, monitor.transfer_hook().pre);
+    assert_eq!(10, monitor.transfer_hook().post);
+}
+
+#[test]
+fn test_transfer_hooks_command_error_sdo_polling() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let mut bus = MockSPIBus::new();
+    bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
+
+    let mut monitor: LTC681X<_, _, _, _, 1, _> = LTC681X::ltc6813(bus, cs)
+        .with_transfer_hook(CountingHook::default())
+        .enable_sdo_polling();
+
+    // CS is released, as no conversion was started
+    match monitor
+        .start_conversion(ConversionRequest::cells(CellSelection::All))
+        .unwrap_err()
+    {
+        Error::TransferError(_) => {}
+        _ => panic!("Unexpected error type"),
+    }
+
+    assert_eq!(1, monitor.transfer_hook().pre);
+    assert_eq!(1, monitor.transfer_hook().post);
+}
+
+#[test]
+fn test_transfer_hooks_wake_up() {
+    let bus = BusMockBuilder::new().expect_dummy_bytes(1).expect_dummy_bytes(1).into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2, _> =
+        LTC681X::ltc6813(bus, get_cs_no_polling(2)).with_transfer_hook(CountingHook::default());
+    let mut delay = RecordingDelay { total: 0 };
+
+    monitor.wake_up(&mut delay).unwrap();
+    assert_eq!(20000, monitor.transfer_hook().pre);
+    assert_eq!(20000, monitor.transfer_hook().post);
+}
+
 /// Creates a pin mock for no polling method
+#[test]
+fn test_adbms1818_read_serial_ids() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0010_1100, 0x59, 0x90)
+        .expect_register_read(&[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0xE9, 0xE8])
+        .expect_register_read(&[0xA1, 0xB2, 0xC3, 0xD4, 0xE5, 0xF6, 0x99, 0xD0])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::adbms1818(bus, get_cs_no_polling(1));
+
+    let ids = monitor.read_serial_ids().unwrap();
+    assert_eq!([0x11, 0x22, 0x33, 0x44, 0x55, 0x66], ids[0]);
+    assert_eq!([0xA1, 0xB2, 0xC3, 0xD4, 0xE5, 0xF6], ids[1]);
+}
+
+#[test]
+fn test_capabilities() {
+    let client: LTC681X<_, _, _, _, 4> = LTC681X::ltc6813(MockSPIBus::new(), MockPin::new());
+    let capabilities = client.capabilities();
+
+    assert_eq!(18, capabilities.cell_count);
+    assert_eq!(9, capabilities.gpio_count);
+    assert_eq!(6, capabilities.cell_register_groups);
+    assert_eq!(4, capabilities.aux_register_groups);
+    assert_eq!(2, capabilities.configuration_register_groups);
+    assert!(capabilities.overlap_measurement);
+    assert!(capabilities.pwm);
+    assert!(capabilities.s_control);
+    assert!(capabilities.pwm_b);
+    assert_eq!(4, capabilities.chain_length);
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(MockSPIBus::new(), MockPin::new());
+    let capabilities = client.capabilities();
+
+    assert_eq!(12, capabilities.cell_count);
+    assert_eq!(4, capabilities.cell_register_groups);
+    assert_eq!(2, capabilities.aux_register_groups);
+    assert!(!capabilities.pwm_b);
+    assert_eq!(1, capabilities.chain_length);
+
+    let client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6810(MockSPIBus::new(), MockPin::new());
+    let capabilities = client.capabilities();
+
+    assert_eq!(6, capabilities.cell_count);
+    assert_eq!(4, capabilities.gpio_count);
+    assert_eq!(2, capabilities.cell_register_groups);
+    assert_eq!(1, capabilities.configuration_register_groups);
+    assert!(!capabilities.overlap_measurement);
+    assert!(capabilities.pwm);
+    assert!(!capabilities.pwm_b);
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6804(MockSPIBus::new(), MockPin::new());
+    let capabilities = client.capabilities();
+
+    assert_eq!(12, capabilities.cell_count);
+    assert_eq!(5, capabilities.gpio_count);
+    assert_eq!(1, capabilities.configuration_register_groups);
+    assert!(!capabilities.overlap_measurement);
+    assert!(!capabilities.pwm);
+    assert!(!capabilities.s_control);
+}
+
 fn get_cs_no_polling(call_count: usize) -> MockPin {
     let mut cs = MockPin::new();
     cs.expect_set_high().times(call_count).returning(move || Ok(()));
@@ -1740,6 +2755,7 @@ fn test_read_overlap_results_multiple_devices() {
 fn test_read_overlap_result_pec_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let bus = BusMockBuilder::new()
         .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
@@ -1775,6 +2791,7 @@ fn test_read_overlap_result_cs_error() {
 fn test_read_overlap_result_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -1855,6 +2872,7 @@ fn test_ltc6813_read_internal_device_parameters_multiple_devices() {
 fn test_read_internal_device_parameters_pec_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let bus = BusMockBuilder::new()
         .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
@@ -1890,6 +2908,7 @@ fn test_read_internal_device_parameters_cs_error() {
 fn test_read_internal_device_parameters_transfer_error() {
     let mut cs = MockPin::new();
     cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
 
     let mut bus = MockSPIBus::new();
     bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
@@ -1902,3 +2921,72 @@ fn test_read_internal_device_parameters_transfer_error() {
         _ => panic!("Unexpected error type"),
     }
 }
+
+#[test]
+fn test_wake_up_bridge_profile() {
+    let bus = BusMockBuilder::new()
+        .expect_dummy_bytes(1)
+        .expect_dummy_bytes(1)
+        .expect_dummy_bytes(1)
+        .expect_dummy_bytes(1)
+        .expect_dummy_bytes(1)
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> =
+        LTC681X::ltc6813(bus, get_cs_no_polling(5)).with_wake_profile(WakeProfile::ltc6820());
+    let mut delay = RecordingDelay { total: 0 };
+
+    // Bridge pulse followed by one pulse per device
+    monitor.wake_up(&mut delay).unwrap();
+    assert_eq!(T_BRIDGE_READY_US + 2 * T_WAKE_US, delay.total);
+
+    // Direct connection: rewake of the idle isoSPI ports without bridge pulse
+    let (bus, cs) = monitor.release();
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, cs);
+    delay.total = 0;
+    monitor.wake_up_idle(&mut delay).unwrap();
+    assert_eq!(2 * T_READY_US, delay.total);
+}
+
+#[test]
+fn test_wake_up_invalidates_write_cache() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .expect_dummy_bytes(1)
+        .expect_command(0b0000_0000, 0b0010_0100, 0xB1, 0x9E)
+        .expect_register_write(&[0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x22, 0xEE])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs_no_polling(3)).with_write_coalescing();
+    let mut delay = RecordingDelay { total: 0 };
+
+    let data = [[0x1, 0x2, 0x3, 0x4, 0x5, 0x6]];
+    monitor.write_register(Register::ConfigurationB, data).unwrap();
+
+    // Register contents are lost in sleep state
+    monitor.wake_up(&mut delay).unwrap();
+    monitor.write_register(Register::ConfigurationB, data).unwrap();
+    assert_eq!(T_WAKE_US, delay.total);
+}
+
+struct RecordingDelay {
+    total: u32,
+}
+
+/// Delay summing up all delays in a shared cell, as the delay is owned by the client
+struct SharedDelay<'a> {
+    total: &'a RefCell<u32>,
+}
+
+impl DelayUs<u32> for SharedDelay<'_> {
+    fn delay_us(&mut self, us: u32) {
+        *self.total.borrow_mut() += us;
+    }
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
@@ -0,0 +1,144 @@
+//! Tests for open-wire detection
+use crate::conversion::PullDirection;
+use crate::ltc6813::GPIOSelection;
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{ADCMode, LTC681X};
+use embedded_hal::blocking::delay::DelayUs;
+
+#[test]
+fn test_detect_open_wires() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_1000, 0x1C, 0x62)
+        .expect_command(0b0000_0011, 0b0110_1000, 0x1C, 0x62)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x00, 0x00, 0x30, 0x75, 0x30, 0x75, 0x2F, 0xE2])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_1010, 0xC3, 0x04)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0011, 0b0010_1000, 0xFB, 0xE8)
+        .expect_command(0b0000_0011, 0b0010_1000, 0xFB, 0xE8)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0x30, 0x75, 0xB8, 0x88, 0x30, 0x75, 0x5C, 0xEC])
+        .expect_command(0b0000_0000, 0b0000_1010, 0xC3, 0x04)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x00, 0x00, 0xDA, 0x9A])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(12));
+    let mut delay = RecordingDelay::default();
+
+    let open_pins = client.detect_open_wires(ADCMode::Normal, &mut delay).unwrap();
+
+    // C0 (cell 1 pull-up = 0), C4 (delta of cell 5 < -400 mV), C12 (cell 12 pull-down = 0)
+    assert_eq!(0b1_0000_0001_0001, open_pins[0]);
+    assert_eq!(4 * 3033, delay.total);
+}
+
+#[test]
+fn test_detect_open_wires_sdo_polling() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_1000, 0x1C, 0x62)
+        .expect_poll(true)
+        .expect_command(0b0000_0011, 0b0110_1000, 0x1C, 0x62)
+        .expect_poll(true)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_1010, 0xC3, 0x04)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0011, 0b0010_1000, 0xFB, 0xE8)
+        .expect_poll(true)
+        .expect_command(0b0000_0011, 0b0010_1000, 0xFB, 0xE8)
+        .expect_poll(true)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .expect_command(0b0000_0000, 0b0000_1010, 0xC3, 0x04)
+        .expect_register_read(&[0x30, 0x75, 0x30, 0x75, 0x30, 0x75, 0x5F, 0x9C])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(12)).enable_sdo_polling();
+    let mut delay = RecordingDelay::default();
+    let open_pins = client.detect_open_wires(ADCMode::Normal, &mut delay).unwrap();
+
+    assert_eq!(0, open_pins[0]);
+    // All conversions completed at first poll
+    assert_eq!(0, delay.total);
+}
+
+#[test]
+fn test_start_conv_gpio_open_wire() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0100, 0b1001_0011, 0x03, 0x56)
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(1));
+    let timing = client
+        .start_conv_gpio_open_wire(ADCMode::Fast, GPIOSelection::Group3, PullDirection::PullDown)
+        .unwrap();
+
+    assert_eq!(380, timing.regular);
+    assert_eq!(439, timing.alternative);
+}
+
+#[test]
+fn test_detect_open_gpios() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0101_0000, 0x3E, 0xD2)
+        .expect_command(0b0000_0101, 0b0101_0000, 0x3E, 0xD2)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0x98, 0x3A, 0x98, 0x3A, 0x50, 0xC3, 0xEC, 0xA6])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1101, 0x64, 0xFE)
+        .expect_register_read(&[0x98, 0x3A, 0x98, 0x3A, 0x98, 0x3A, 0x7A, 0xA4])
+        // Register B
+        .expect_command(0b0000_0000, 0b0000_1110, 0x72, 0x9A)
+        .expect_register_read(&[0x98, 0x3A, 0x98, 0x3A, 0x30, 0x75, 0x88, 0xAC])
+        // Register D
+        .expect_command(0b0000_0000, 0b0000_1111, 0xF9, 0xA8)
+        .expect_register_read(&[0x98, 0x3A, 0x00, 0x00, 0x00, 0x00, 0xEB, 0xB4])
+        .expect_command(0b0000_0101, 0b0001_0000, 0xD9, 0x58)
+        .expect_command(0b0000_0101, 0b0001_0000, 0xD9, 0x58)
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0x98, 0x3A, 0x98, 0x3A, 0x00, 0x00, 0x0D, 0xAA])
+        .expect_command(0b0000_0000, 0b0000_1101, 0x64, 0xFE)
+        .expect_register_read(&[0x98, 0x3A, 0x98, 0x3A, 0x98, 0x3A, 0x7A, 0xA4])
+        .expect_command(0b0000_0000, 0b0000_1110, 0x72, 0x9A)
+        .expect_register_read(&[0x98, 0x3A, 0x98, 0x3A, 0x00, 0x00, 0x0D, 0xAA])
+        .expect_command(0b0000_0000, 0b0000_1111, 0xF9, 0xA8)
+        .expect_register_read(&[0x10, 0x27, 0x00, 0x00, 0x00, 0x00, 0x21, 0x82])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(12));
+    let mut delay = RecordingDelay::default();
+
+    let open_pins = client.detect_open_gpios(ADCMode::Normal, &mut delay).unwrap();
+
+    // GPIO3 and GPIO9 open, the deviation of the second reference is ignored
+    assert_eq!(0b1_0000_0100, open_pins[0]);
+    assert_eq!(4 * 5025, delay.total);
+}
+
+#[derive(Default)]
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
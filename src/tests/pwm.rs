@@ -0,0 +1,53 @@
+//! Tests for PWM register access
+use crate::mocks::{get_cs, BusMockBuilder, MockPin, MockSPIBus};
+use crate::monitor::LTC681X;
+use crate::pwm::PwmDutyCycle;
+
+#[test]
+fn test_pwm_duty_cycle_from_bits() {
+    assert_eq!(PwmDutyCycle::Off, PwmDutyCycle::from_bits(0x0));
+    assert_eq!(PwmDutyCycle::_3_3, PwmDutyCycle::from_bits(0x1));
+    assert_eq!(PwmDutyCycle::_30, PwmDutyCycle::from_bits(0x8));
+    assert_eq!(PwmDutyCycle::_50, PwmDutyCycle::from_bits(0xE));
+    assert_eq!(PwmDutyCycle::_50, PwmDutyCycle::from_bits(0xF));
+    assert_eq!(PwmDutyCycle::_3_3, PwmDutyCycle::from_bits(0xF1));
+
+    for bits in 0..15 {
+        assert_eq!(bits, PwmDutyCycle::from_bits(bits) as u8);
+    }
+}
+
+#[test]
+fn test_read_pwm() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0x00, 0x22, 0x9D, 0x56)
+        .expect_register_read(&[0x21, 0x43, 0x65, 0x87, 0xA9, 0xFE, 0x9D, 0x8E])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(1));
+    let duty_cycles = client.read_pwm().unwrap().unwrap();
+
+    assert_eq!(
+        [
+            PwmDutyCycle::_3_3,
+            PwmDutyCycle::_6_7,
+            PwmDutyCycle::_10,
+            PwmDutyCycle::_16_7,
+            PwmDutyCycle::_20,
+            PwmDutyCycle::_23_3,
+            PwmDutyCycle::_26_7,
+            PwmDutyCycle::_30,
+            PwmDutyCycle::_33_3,
+            PwmDutyCycle::_36_7,
+            PwmDutyCycle::_50,
+            PwmDutyCycle::_50,
+        ],
+        duty_cycles[0]
+    );
+}
+
+#[test]
+fn test_read_pwm_ltc6804_not_supported() {
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6804(MockSPIBus::new(), MockPin::new());
+    assert!(client.read_pwm().unwrap().is_none());
+}
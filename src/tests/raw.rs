@@ -0,0 +1,121 @@
+//! Tests for raw register data access
+use crate::ltc6813::{CellSelection, Channel, Register};
+use crate::mocks::{get_cs, BusMockBuilder, MockPin};
+use crate::monitor::{Error, LTC681X};
+
+#[test]
+fn test_read_register_raw() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(1));
+
+    let result = monitor.read_register_raw(Register::CellVoltageA).unwrap();
+    assert_eq!(
+        [
+            [0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22],
+            [0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C]
+        ],
+        result
+    );
+}
+
+#[test]
+fn test_read_register_raw_pec_error() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1D])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+
+    match monitor.read_register_raw(Register::CellVoltageA).unwrap_err() {
+        Error::ChecksumMismatch => {}
+        _ => panic!("Unexpected error type"),
+    }
+}
+
+#[test]
+fn test_read_voltages_with_raw() {
+    let bus = BusMockBuilder::new()
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register E
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(3));
+
+    let result = monitor.read_voltages_with_raw(CellSelection::Group1).unwrap();
+
+    assert_eq!(3, result.registers.len());
+    assert_eq!(Register::CellVoltageA, result.registers[0].register);
+    assert_eq!(Register::CellVoltageC, result.registers[1].register);
+    assert_eq!(Register::CellVoltageE, result.registers[2].register);
+    assert_eq!([0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22], result.registers[1].data[0]);
+    assert_eq!([0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C], result.registers[2].data[1]);
+
+    assert_eq!(2, result.voltages.len());
+    assert_eq!(3, result.voltages[0].len());
+    assert_eq!(3, result.voltages[1].len());
+
+    assert_eq!(Channel::Cell1, result.voltages[0][0].channel);
+    assert_eq!(24979, result.voltages[0][0].voltage);
+    assert_eq!(Channel::Cell7, result.voltages[0][1].channel);
+    assert_eq!(25441, result.voltages[0][1].voltage);
+    assert_eq!(Channel::Cell13, result.voltages[0][2].channel);
+    assert_eq!(25822, result.voltages[0][2].voltage);
+
+    assert_eq!(26333, result.voltages[1][0].voltage);
+    assert_eq!(24979, result.voltages[1][1].voltage);
+    assert_eq!(26333, result.voltages[1][2].voltage);
+}
+
+#[test]
+fn test_send_raw_command() {
+    let bus = BusMockBuilder::new().expect_command(0x07, 0x23, 0xB9, 0xE4).into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(1));
+    monitor.send_raw_command(0x0723).unwrap();
+}
+
+#[test]
+fn test_read_raw() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0x07, 0x22, 0x32, 0xD6)
+        .expect_register_read(&[0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9, 0x6D, 0xF8])
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(1));
+    let data = monitor.read_raw(0x0722).unwrap();
+
+    assert_eq!([0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9], data[0]);
+    assert_eq!([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22], data[1]);
+}
+
+#[test]
+fn test_write_raw() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0x07, 0x21, 0x24, 0xB2)
+        .expect_register_write(&[0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9, 0x6D, 0xF8])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(1));
+    monitor.write_raw(0x0721, &[[0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9]]).unwrap();
+}
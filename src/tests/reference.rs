@@ -0,0 +1,60 @@
+//! Tests for reference power-up handling
+use crate::ltc6811::GPIOSelection;
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{ADCMode, StatusGroup, LTC681X};
+use embedded_hal::blocking::delay::DelayUs;
+
+#[test]
+fn test_start_conv_gpio_referenced_powers_up_reference() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4F, 0x82])
+        .expect_command(0b0000_0101, 0b0110_0000, 0xD3, 0xA0)
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(4));
+    let mut delay = RecordingDelay::default();
+
+    let conversion = client
+        .start_conv_gpio_referenced(ADCMode::Normal, GPIOSelection::All, &mut delay)
+        .unwrap();
+
+    assert_eq!(GPIOSelection::All, conversion.pins());
+    assert_eq!(2_335, conversion.timing().regular);
+    assert_eq!(4_400, delay.total);
+}
+
+#[test]
+fn test_measure_internal_parameters_referenced_reference_already_on() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4F, 0x82])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_command(0b0000_0101, 0b0110_1000, 0x3B, 0xAE)
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(3));
+    let mut delay = RecordingDelay::default();
+
+    client
+        .measure_internal_parameters_referenced(ADCMode::Normal, StatusGroup::All, &mut delay)
+        .unwrap();
+
+    assert_eq!(0, delay.total);
+}
+
+#[derive(Default)]
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
@@ -1,4 +1,5 @@
-use crate::config::{Cell, Configuration, DigitalRedundancyPath, DischargeTimeout, GPIO};
+use crate::config::{Cell, Configuration, DigitalRedundancyPath, DischargeControl, DischargeTimeout, GPIO};
+use crate::{ltc6804, ltc6810, ltc6811, ltc6812};
 
 #[test]
 fn test_enable_gpio_pull_down_gpio1() {
@@ -515,6 +516,145 @@ fn test_eq_true() {
     assert_eq!(a, b);
 }
 
+#[test]
+fn test_bms_defaults() {
+    let config = Configuration::bms_defaults();
+
+    assert_eq!([0b1111_1110, 0x52, 0x17, 0xA4, 0x00, 0xA0], config.register_a);
+    assert_eq!(Configuration::default().register_b, config.register_b);
+}
+
+#[test]
+fn test_discharge_timeout_exceeding() {
+    assert_eq!(DischargeTimeout::HalfMinute, DischargeTimeout::exceeding(0).unwrap());
+    assert_eq!(
+        DischargeTimeout::HalfMinute,
+        DischargeTimeout::exceeding(29_999).unwrap()
+    );
+    assert_eq!(
+        DischargeTimeout::OneMinute,
+        DischargeTimeout::exceeding(30_000).unwrap()
+    );
+    assert_eq!(
+        DischargeTimeout::TenMinutes,
+        DischargeTimeout::exceeding(300_000).unwrap()
+    );
+    assert_eq!(
+        DischargeTimeout::TwoHours,
+        DischargeTimeout::exceeding(5_400_000).unwrap()
+    );
+    assert_eq!(
+        DischargeTimeout::TwoHours,
+        DischargeTimeout::exceeding(7_199_999).unwrap()
+    );
+}
+
+#[test]
+fn test_discharge_timeout_exceeding_out_of_range() {
+    assert!(DischargeTimeout::exceeding(7_200_000).is_err());
+    assert!(DischargeTimeout::exceeding(u32::MAX).is_err());
+}
+
+#[test]
+fn test_discharge_timeout_to_millis() {
+    assert_eq!(0, DischargeTimeout::Disabled.to_millis());
+    assert_eq!(30_000, DischargeTimeout::HalfMinute.to_millis());
+    assert_eq!(4_500_000, DischargeTimeout::SeventyFiveMinutes.to_millis());
+    assert_eq!(7_200_000, DischargeTimeout::TwoHours.to_millis());
+}
+
+#[test]
+fn test_ltc6810_gpio_pull_down() {
+    let mut config = ltc6810::Configuration::default();
+
+    config.enable_gpio_pull_down(ltc6810::config::GPIO::GPIO4);
+    assert_eq!(0b1011_1000, config.register_a[0]);
+
+    config.disable_gpio_pull_down(ltc6810::config::GPIO::GPIO4);
+    assert_eq!(0b1111_1000, config.register_a[0]);
+}
+
+#[test]
+fn test_ltc6810_discharge_control_ignores_unavailable_cells() {
+    let mut config = ltc6810::Configuration::default();
+
+    DischargeControl::discharge_cell(&mut config, Cell::Cell6);
+    DischargeControl::discharge_cell(&mut config, Cell::Cell7);
+    DischargeControl::discharge_cell(&mut config, Cell::Cell18);
+
+    assert_eq!(0b0010_0000, config.register_a[4]);
+    assert_eq!(0b0000_0000, config.register_a[5]);
+    assert!(DischargeControl::is_cell_discharging(&config, Cell::Cell6));
+    assert!(!DischargeControl::is_cell_discharging(&config, Cell::Cell7));
+    assert!(config.is_cell_discharging(ltc6810::config::Cell::Cell6));
+}
+
+#[test]
+fn test_ltc6810_channel_conversion() {
+    assert_eq!(
+        Ok(ltc6810::config::Cell::Cell6),
+        ltc6810::config::Cell::try_from(Cell::Cell6)
+    );
+    assert_eq!(Err(()), ltc6810::config::Cell::try_from(Cell::Cell7));
+    assert_eq!(
+        Ok(ltc6810::config::GPIO::GPIO4),
+        ltc6810::config::GPIO::try_from(GPIO::GPIO4)
+    );
+    assert_eq!(Err(()), ltc6810::config::GPIO::try_from(GPIO::GPIO5));
+}
+
+#[test]
+fn test_ltc6811_configuration_channels() {
+    let mut config = ltc6811::Configuration::default();
+
+    config.discharge_cell(ltc6811::Cell::Cell12);
+    config.enable_gpio_pull_down(ltc6811::GPIO::GPIO5);
+
+    assert_eq!(0b0111_1000, config.register_a[0]);
+    assert_eq!(0b0000_1000, config.register_a[5]);
+    assert!(config.is_cell_discharging(ltc6811::Cell::Cell12));
+    assert_eq!(Configuration::default().register_b, config.register_b);
+}
+
+#[test]
+fn test_ltc6812_configuration_channels() {
+    let mut config = ltc6812::Configuration::default();
+
+    config.discharge_cell(ltc6812::Cell::Cell15);
+    config.enable_gpio_pull_down(GPIO::GPIO9);
+
+    assert_eq!(0b0100_0111, config.register_b[0]);
+    assert!(config.is_cell_discharging(ltc6812::Cell::Cell15));
+}
+
+#[test]
+fn test_shared_discharge_control_ignores_unavailable_cells() {
+    let mut config = ltc6804::Configuration::default();
+
+    DischargeControl::discharge_cell(&mut config, Cell::Cell12);
+    DischargeControl::discharge_cell(&mut config, Cell::Cell13);
+    assert_eq!(0b0000_1000, config.register_a[5]);
+    assert_eq!(Configuration::default().register_b, config.register_b);
+    assert!(DischargeControl::is_cell_discharging(&config, Cell::Cell12));
+    assert!(!DischargeControl::is_cell_discharging(&config, Cell::Cell13));
+
+    let mut config = ltc6812::Configuration::default();
+    DischargeControl::discharge_cell(&mut config, Cell::Cell16);
+    assert_eq!(Configuration::default().register_b, config.register_b);
+}
+
+#[test]
+fn test_shared_channel_conversion() {
+    assert_eq!(Ok(ltc6811::Cell::Cell12), ltc6811::Cell::try_from(Cell::Cell12));
+    assert_eq!(Err(()), ltc6811::Cell::try_from(Cell::Cell13));
+    assert_eq!(Ok(ltc6811::GPIO::GPIO5), ltc6811::GPIO::try_from(GPIO::GPIO5));
+    assert_eq!(Err(()), ltc6811::GPIO::try_from(GPIO::GPIO6));
+    assert_eq!(Err(()), ltc6804::GPIO::try_from(GPIO::GPIO6));
+    assert_eq!(Ok(ltc6812::Cell::Cell15), ltc6812::Cell::try_from(Cell::Cell15));
+    assert_eq!(Err(()), ltc6812::Cell::try_from(Cell::Cell16));
+    assert_eq!(Cell::Cell15, ltc6812::Cell::Cell15.into());
+}
+
 /// Asserts that all register slots, except one, match the default values
 fn assert_default(except: usize, config: &Configuration) {
     let mut actual = [0u8; 12];
@@ -0,0 +1,82 @@
+//! Tests for generic register group access
+use crate::mocks::{get_cs, BusMockBuilder, MockPin};
+use crate::monitor::{Error, LTC681X};
+use crate::register_group::{RegisterGroup, WritableRegisterGroup};
+use crate::status::StatusB;
+
+#[derive(Debug, PartialEq)]
+struct Comm([u8; 6]);
+
+impl RegisterGroup for Comm {
+    const READ_COMMAND: u16 = 0x0722;
+
+    fn decode(data: &[u8; 6]) -> Self {
+        Comm(*data)
+    }
+}
+
+impl WritableRegisterGroup for Comm {
+    const WRITE_COMMAND: u16 = 0x0721;
+
+    fn encode(&self) -> [u8; 6] {
+        self.0
+    }
+}
+
+#[test]
+fn test_read_register_group_status_b() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x00, 0xC8, 0x00, 0x66, 0x00, 0x1B, 0xF1, 0x40])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(1));
+    let status: [StatusB; 1] = client.read_register_group().unwrap();
+
+    assert_eq!(StatusB::from_register(&[0x00, 0xC8, 0x00, 0x66, 0x00, 0x1B]), status[0]);
+}
+
+#[test]
+fn test_read_register_group_custom() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0x07, 0x22, 0x32, 0xD6)
+        .expect_register_read(&[0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9, 0x6D, 0xF8])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(1));
+    let comm: [Comm; 1] = client.read_register_group().unwrap();
+
+    assert_eq!(Comm([0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9]), comm[0]);
+}
+
+#[test]
+fn test_read_register_group_pec_error() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let bus = BusMockBuilder::new()
+        .expect_command(0x07, 0x22, 0x32, 0xD6)
+        .expect_register_read(&[0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9, 0x6D, 0xF9])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, cs);
+
+    match client.read_register_group::<Comm>().unwrap_err() {
+        Error::ChecksumMismatch => {}
+        _ => panic!("Unexpected error type"),
+    }
+}
+
+#[test]
+fn test_write_register_group() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0x07, 0x21, 0x24, 0xB2)
+        .expect_register_write(&[0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9, 0x6D, 0xF8])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(1));
+    client
+        .write_register_group(&[Comm([0x6A, 0x08, 0x00, 0x18, 0x0A, 0xA9])])
+        .unwrap();
+}
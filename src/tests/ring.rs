@@ -0,0 +1,138 @@
+//! Tests for dual-port isoSPI ring topology
+use crate::ltc6813::{CellSelection, GPIOSelection, Register};
+use crate::mocks::{get_cs, BusMockBuilder, MockPin};
+use crate::monitor::{ADCMode, LTC681X};
+use crate::ring::{Port, RingClient, RingError};
+
+#[test]
+fn test_read_register_reverse_direction() {
+    let bus_b = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .into_mock();
+
+    let port_a: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(BusMockBuilder::new().into_mock(), get_cs(0));
+    let port_b: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus_b, get_cs(1));
+    let mut client = RingClient::new(port_a, port_b);
+
+    assert_eq!(Port::A, client.active_port());
+    client.switch_to(Port::B);
+    assert_eq!(Port::B, client.active_port());
+
+    // Device closest to port A responds last via port B
+    let data = client.read_register(Register::CellVoltageA).unwrap();
+    assert_eq!([26333, 7538, 7330], data[0]);
+    assert_eq!([24979, 7867, 8878], data[1]);
+}
+
+#[test]
+fn test_write_register_reverse_direction() {
+    let bus_b = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_register_write(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .into_mock();
+
+    let port_a: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(BusMockBuilder::new().into_mock(), get_cs(0));
+    let port_b: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus_b, get_cs(1));
+    let mut client = RingClient::new(port_a, port_b);
+    client.switch_to(Port::B);
+
+    client
+        .write_register(
+            Register::ConfigurationA,
+            [
+                [0xF8, 0x00, 0x00, 0x00, 0x00, 0x00],
+                [0x0F, 0x00, 0x00, 0x00, 0x00, 0x00],
+            ],
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_read_register_redundant_broken_link() {
+    // Link between device 1 and 2 is broken
+    let bus_a = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xFF; 8])
+        .expect_register_read(&[0xFF; 8])
+        .into_mock();
+
+    let bus_b = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_register_read(&[0xFF; 8])
+        .into_mock();
+
+    let port_a: LTC681X<_, _, _, _, 3> = LTC681X::ltc6813(bus_a, get_cs(1));
+    let port_b: LTC681X<_, _, _, _, 3> = LTC681X::ltc6813(bus_b, get_cs(1));
+    let mut client = RingClient::new(port_a, port_b);
+
+    let frames = client.read_register_redundant(Register::CellVoltageA).unwrap();
+    assert_eq!(Some([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22]), frames[0]);
+    assert_eq!(Some([0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22]), frames[1]);
+    assert_eq!(Some([0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C]), frames[2]);
+}
+
+#[test]
+fn test_start_conversion_broadcast_broken_link() {
+    // Link between device 1 and 2 is broken, so device 2 is only reachable from port B
+    let bus_a = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0101, 0b0110_0000, 0xD3, 0xA0)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_register_read(&[0xFF; 8])
+        .into_mock();
+
+    let bus_b = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0111_0000, 0xAF, 0x42)
+        .expect_command(0b0000_0101, 0b0110_0000, 0xD3, 0xA0)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .expect_register_read(&[0xFF; 8])
+        .expect_register_read(&[0xFF; 8])
+        .into_mock();
+
+    let port_a: LTC681X<_, _, _, _, 3> = LTC681X::ltc6813(bus_a, get_cs(3));
+    let port_b: LTC681X<_, _, _, _, 3> = LTC681X::ltc6813(bus_b, get_cs(3));
+    let mut client = RingClient::new(port_a, port_b);
+
+    // Sent via both ports, although port A is active
+    let timing = client.start_conv_cells(ADCMode::Normal, CellSelection::All, true).unwrap();
+    assert_eq!(2343, timing.regular);
+    let timing = client.start_conv_gpio(ADCMode::Normal, GPIOSelection::All).unwrap();
+    assert_eq!(3862, timing.regular);
+
+    // Device beyond the broken link converted as well
+    let frames = client.read_register_redundant(Register::CellVoltageA).unwrap();
+    assert_eq!(Some([0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22]), frames[0]);
+    assert_eq!(Some([0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22]), frames[1]);
+    assert_eq!(Some([0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C]), frames[2]);
+}
+
+#[test]
+fn test_error_of_active_port() {
+    let bus_a = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0xFF; 8])
+        .into_mock();
+
+    // CS remains low after the PEC error
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let port_a: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus_a, cs);
+    let port_b: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(BusMockBuilder::new().into_mock(), get_cs(0));
+    let mut client = RingClient::new(port_a, port_b);
+
+    match client.read_register(Register::CellVoltageA) {
+        Err(RingError::PortA(_)) => {}
+        _ => panic!("Unexpected result"),
+    }
+}
@@ -0,0 +1,292 @@
+//! Tests for cell voltage self-test
+use crate::ltc6811::{CellSelection, Channel};
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{ADCMode, StatusGroup, LTC681X};
+use crate::self_test::SelfTestPattern;
+use embedded_hal::blocking::delay::DelayUs;
+
+#[test]
+fn test_cell_voltage_self_test_command() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0010_0111, 0xB4, 0x1C)
+        .expect_command(0b0000_0011, 0b0100_0111, 0xE5, 0xCA)
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(2));
+
+    let timing = client
+        .cell_voltage_self_test(ADCMode::Normal, SelfTestPattern::Pattern1)
+        .unwrap();
+    assert_eq!(2335, timing.regular);
+
+    client
+        .cell_voltage_self_test(ADCMode::Normal, SelfTestPattern::Pattern2)
+        .unwrap();
+}
+
+#[test]
+fn test_verify_cell_voltage_self_test_failure() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x00, 0x00, 0x55, 0x95, 0x9C, 0x38])
+        .expect_command(0b0000_0000, 0b0000_1010, 0xC3, 0x04)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6811(bus, get_cs(4));
+    let report = client
+        .verify_cell_voltage_self_test(ADCMode::Normal, SelfTestPattern::Pattern1)
+        .unwrap();
+
+    assert!(!report.passed());
+    assert_eq!(Some((1, Channel::Cell5)), report.failures().next());
+    assert_eq!(1, report.failures().count());
+    assert!(report.get(1).unwrap().contains(&(Channel::Cell5, 0x0)));
+    assert_eq!(12, report.get(0).unwrap().len());
+    assert!(report.get(2).is_none());
+}
+
+#[test]
+fn test_verify_cell_voltage_self_test_alternative_mode() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x53, 0x95, 0x53, 0x95, 0x53, 0x95, 0x20, 0xF6])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x53, 0x95, 0x53, 0x95, 0x53, 0x95, 0x20, 0xF6])
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0x65, 0x95, 0x65, 0x95, 0x65, 0x95, 0x98, 0x18])
+        .expect_command(0b0000_0000, 0b0000_1010, 0xC3, 0x04)
+        .expect_register_read(&[0x9A, 0x6A, 0x9A, 0x6A, 0x9A, 0x6A, 0x3C, 0x46])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(4));
+    let report = client
+        .verify_cell_voltage_self_test(ADCMode::Fast, SelfTestPattern::Pattern1)
+        .unwrap();
+
+    assert_eq!((0x9565, 0x9553), report.expected());
+    assert_eq!(
+        [(0, Channel::Cell10), (0, Channel::Cell11), (0, Channel::Cell12)],
+        report.failures().collect::<heapless::Vec<_, 3>>().as_slice()
+    );
+}
+
+#[test]
+fn test_aux_self_test_command() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0010_0111, 0x93, 0xD0)
+        .expect_command(0b0000_0101, 0b0100_0111, 0xC2, 0x06)
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(2));
+
+    let timing = client.aux_self_test(ADCMode::Normal, SelfTestPattern::Pattern1).unwrap();
+    assert_eq!(2335, timing.regular);
+
+    client.aux_self_test(ADCMode::Normal, SelfTestPattern::Pattern2).unwrap();
+}
+
+#[test]
+fn test_verify_aux_self_test() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0xAA, 0x6A, 0xAA, 0x6A, 0xAA, 0x6A, 0xA6, 0x94])
+        .expect_command(0b0000_0000, 0b0000_1110, 0x72, 0x9A)
+        .expect_register_read(&[0xAA, 0x6A, 0xAA, 0x6A, 0x00, 0x00, 0x43, 0xC6])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(2));
+    let report = client.verify_aux_self_test(ADCMode::Normal, SelfTestPattern::Pattern2).unwrap();
+
+    assert_eq!(
+        [(0, Channel::SecondReference)],
+        report.failures().collect::<heapless::Vec<_, 1>>().as_slice()
+    );
+    assert_eq!(6, report.get(0).unwrap().len());
+}
+
+#[test]
+fn test_status_self_test_command() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0010_1111, 0x7B, 0xDE)
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(1));
+
+    let timing = client.status_self_test(ADCMode::Normal, SelfTestPattern::Pattern1).unwrap();
+    assert_eq!(1600, timing.regular);
+}
+
+#[test]
+fn test_verify_status_self_test() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_register_read(&[0x55, 0x95, 0x55, 0x95, 0x55, 0x95, 0x02, 0xCA])
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x55, 0x95, 0x00, 0x00, 0x00, 0x10, 0x82, 0x5C])
+        .expect_register_read(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x99, 0x3C])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6811(bus, get_cs(2));
+    let report = client
+        .verify_status_self_test(ADCMode::Normal, SelfTestPattern::Pattern1)
+        .unwrap();
+
+    assert!(!report.passed());
+    assert_eq!(
+        [(1, StatusGroup::DigitalVoltage)],
+        report.failures().collect::<heapless::Vec<_, 1>>().as_slice()
+    );
+    assert_eq!(4, report.get(0).unwrap().len());
+}
+
+#[test]
+fn test_read_overlap_measurement() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0xA8, 0x61, 0xC6, 0x61, 0x00, 0x00, 0x06, 0xF0])
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xA8, 0x61, 0x0C, 0x62, 0x00, 0x00, 0xE6, 0xF4])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(2));
+    let result = client.read_overlap_measurement().unwrap();
+
+    let first = result[0].first.unwrap();
+    assert_eq!(25000, first.first_adc);
+    assert_eq!(25030, first.second_adc);
+    assert_eq!(3_000, first.deviation_uv());
+    assert!(first.matches(3_000));
+    assert!(!first.matches(2_999));
+
+    let second = result[0].second.unwrap();
+    assert_eq!(25000, second.first_adc);
+    assert_eq!(25100, second.second_adc);
+    assert_eq!(10_000, second.deviation_uv());
+
+    assert!(!result[0].matches(5_000));
+    assert!(result[0].matches(10_000));
+}
+
+#[test]
+fn test_read_overlap_measurement_single_cell() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0xA8, 0x61, 0xC6, 0x61, 0x00, 0x00, 0x06, 0xF0])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(1));
+    let result = client.read_overlap_measurement().unwrap();
+
+    assert_eq!(25030, result[0].first.unwrap().second_adc);
+    assert_eq!(None, result[0].second);
+    assert!(result[0].matches(5_000));
+}
+
+#[test]
+fn test_self_test_pattern_expected_values() {
+    assert_eq!(
+        (0x9555, 0x9555),
+        SelfTestPattern::Pattern1.expected_values(ADCMode::Normal)
+    );
+    assert_eq!(
+        (0x6AAA, 0x6AAA),
+        SelfTestPattern::Pattern2.expected_values(ADCMode::Filtered)
+    );
+    assert_eq!(
+        (0x6A9A, 0x6AAC),
+        SelfTestPattern::Pattern2.expected_values(ADCMode::Fast)
+    );
+    assert_eq!(
+        (0x9555, 0x9555),
+        SelfTestPattern::Pattern1.expected_values(ADCMode::Other)
+    );
+}
+
+#[test]
+fn test_diagnose_mux() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0111, 0b0001_0101, 0x78, 0x5E)
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x00, 0x7D, 0x00, 0x00, 0x00, 0x12, 0xD9, 0x42])
+        .expect_register_read(&[0x00, 0x7D, 0x00, 0x00, 0x00, 0x10, 0x44, 0x14])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6811(bus, get_cs(2));
+    let mut delay = RecordingDelay::default();
+
+    let result = client.diagnose_mux(&mut delay).unwrap();
+
+    assert_eq!([false, true], result);
+    assert_eq!(400, delay.total);
+}
+
+#[test]
+fn test_diagnose_mux_sdo_polling() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0111, 0b0001_0101, 0x78, 0x5E)
+        .expect_poll(false)
+        .expect_poll(true)
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x00, 0x7D, 0x00, 0x00, 0x00, 0x10, 0x44, 0x14])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(2)).enable_sdo_polling();
+    let mut delay = RecordingDelay::default();
+
+    assert_eq!([true], client.diagnose_mux(&mut delay).unwrap());
+    assert_eq!(100, delay.total);
+}
+
+#[test]
+fn test_compare_adc_modes() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0001, 0x7F, 0x5E)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_command(0b0000_0011, 0b1110_0001, 0x3B, 0x78)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x9D, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0xB2, 0xD2])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6811(bus, get_cs(6));
+    let mut delay = RecordingDelay::default();
+
+    let comparison = client
+        .compare_adc_modes(CellSelection::Pair1, ADCMode::Normal, ADCMode::Filtered, 50, &mut delay)
+        .unwrap();
+
+    assert_eq!(
+        &[(Channel::Cell1, 24979, 24979), (Channel::Cell7, 25441, 25501)],
+        comparison.get(0).unwrap()
+    );
+    assert!(!comparison.passed());
+
+    let mut failures = comparison.failures();
+    assert_eq!(Some((0, Channel::Cell7)), failures.next());
+    assert_eq!(None, failures.next());
+}
+
+#[derive(Default)]
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
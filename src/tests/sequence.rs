@@ -0,0 +1,96 @@
+//! Tests for conversion sequences
+use crate::ltc6813::{CellSelection, GPIOSelection};
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{ADCMode, LTC681X};
+use crate::sequence::Step;
+use embedded_hal::blocking::delay::DelayUs;
+
+#[test]
+fn test_run_sequence() {
+    let bus = BusMockBuilder::new()
+        // Cells
+        .expect_command(0b0000_0011, 0b0110_0001, 0x7F, 0x5E)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        // GPIOs
+        .expect_command(0b0000_0101, 0b0110_0010, 0x4E, 0xF6)
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1101, 0x64, 0xFE)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        // Status
+        .expect_command(0b0000_0101, 0b0110_1000, 0x3B, 0xAE)
+        .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
+        .expect_register_read(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D, 0x31, 0x8A])
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x00, 0xC8, 0x00, 0x66, 0x00, 0x1B, 0xF1, 0x40])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(10));
+    let mut delay = RecordingDelay { total: 0 };
+
+    let steps = [
+        Step::Cells(CellSelection::Group1),
+        Step::Gpio(GPIOSelection::Group2),
+        Step::Status,
+    ];
+    let result = client.run_sequence(ADCMode::Normal, &steps, &mut delay).unwrap();
+
+    // Longer (alternative) timing of each step
+    assert_eq!(523 + 1000 + 2000, delay.total);
+
+    let device = result.voltages.device(0);
+    assert_eq!(Some(24979), device.cell_voltages[0]);
+    assert_eq!(Some(25441), device.cell_voltages[6]);
+    assert_eq!(Some(25822), device.cell_voltages[12]);
+    assert_eq!(None, device.cell_voltages[1]);
+    assert_eq!(Some(7867), device.gpio_voltages[1]);
+    assert_eq!(Some(7869), device.gpio_voltages[6]);
+    assert_eq!(None, device.gpio_voltages[0]);
+
+    let internal = result.internal.unwrap();
+    assert_eq!(75_318_000, internal[0].total_voltage);
+    assert_eq!(5_120_000, internal[0].digital_power);
+}
+
+#[test]
+fn test_run_sequence_sdo_polling() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0011, 0b0110_0001, 0x7F, 0x5E)
+        .expect_poll(false)
+        .expect_poll(true)
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(4)).enable_sdo_polling();
+    let mut delay = RecordingDelay { total: 0 };
+
+    let result = client
+        .run_sequence(ADCMode::Normal, &[Step::Cells(CellSelection::Group1)], &mut delay)
+        .unwrap();
+
+    // Single poll interval
+    assert_eq!(100, delay.total);
+
+    assert_eq!(Some(24979), result.voltages.device(0).cell_voltages[0]);
+    assert!(result.internal.is_none());
+}
+
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
@@ -0,0 +1,92 @@
+//! Tests for measurement snapshots
+use crate::ltc6810;
+use crate::ltc6813::{Channel, LTC6813};
+use crate::mocks::{BusMockBuilder, MockPin};
+use crate::monitor::{Voltage, LTC681X};
+use crate::snapshot::Snapshot;
+use heapless::Vec;
+
+#[test]
+fn test_snapshot_update() {
+    let mut voltages: Vec<Vec<Voltage<LTC6813>, 18>, 2> = Vec::new();
+    let _ = voltages.push(Vec::new());
+    let _ = voltages.push(Vec::new());
+
+    let _ = voltages[0].push(Voltage {
+        channel: Channel::Cell1,
+        voltage: 36_000,
+    });
+    let _ = voltages[0].push(Voltage {
+        channel: Channel::GPIO9,
+        voltage: 15_000,
+    });
+    let _ = voltages[1].push(Voltage {
+        channel: Channel::Cell18,
+        voltage: 37_000,
+    });
+    let _ = voltages[1].push(Voltage {
+        channel: Channel::SecondReference,
+        voltage: 30_000,
+    });
+
+    let mut snapshot: Snapshot<2> = Snapshot::new();
+    snapshot.update(&voltages);
+
+    assert_eq!(Some(36_000), snapshot.device(0).cell_voltages[0]);
+    assert_eq!(None, snapshot.device(0).cell_voltages[1]);
+    assert_eq!(Some(15_000), snapshot.device(0).gpio_voltages[8]);
+    assert_eq!(Some(37_000), snapshot.device(1).cell_voltages[17]);
+    assert!(snapshot.device(1).gpio_voltages.iter().all(|voltage| voltage.is_none()));
+}
+
+#[test]
+fn test_snapshot_update_keeps_previous_values() {
+    let mut snapshot: Snapshot<1> = Snapshot::new();
+
+    let mut voltages: Vec<Vec<Voltage<ltc6810::LTC6810>, 18>, 1> = Vec::new();
+    let _ = voltages.push(Vec::new());
+    let _ = voltages[0].push(Voltage {
+        channel: ltc6810::Channel::Cell2,
+        voltage: 35_000,
+    });
+    snapshot.update(&voltages);
+
+    voltages[0].clear();
+    let _ = voltages[0].push(Voltage {
+        channel: ltc6810::Channel::S0,
+        voltage: 1_000,
+    });
+    let _ = voltages[0].push(Voltage {
+        channel: ltc6810::Channel::GPIO1,
+        voltage: 12_000,
+    });
+    snapshot.update(&voltages);
+
+    assert_eq!(Some(35_000), snapshot.device(0).cell_voltages[1]);
+    assert_eq!(Some(12_000), snapshot.device(0).gpio_voltages[0]);
+    assert_eq!(None, snapshot.device(0).gpio_voltages[1]);
+}
+
+#[test]
+fn test_read_cell() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0110, 0x9A, 0x94)
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .into_mock();
+
+    let mut cs = MockPin::new();
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+
+    let mut client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6811(bus, cs);
+
+    // Cell 5 of second device
+    assert_eq!(Some(7867), client.read_cell(1, 4).unwrap());
+
+    // Not available on LTC6811
+    assert_eq!(None, client.read_cell(0, 12).unwrap());
+
+    // Device out of bounds
+    assert_eq!(None, client.read_cell(2, 0).unwrap());
+}
@@ -0,0 +1,191 @@
+//! Tests for decoded status register groups
+use crate::config::Cell;
+use crate::ltc6804::LTC6804;
+use crate::ltc6813::LTC6813;
+use crate::mocks::{get_cs, BusMockBuilder, MockPin};
+use crate::monitor::{Error, LTC681X};
+use crate::status::{StatusA, StatusB};
+use alloc::string::ToString;
+
+#[test]
+fn test_read_status_a() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
+        .expect_register_read(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D, 0x31, 0x8A])
+        .expect_register_read(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D, 0x31, 0x8A])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(1));
+    let status = client.read_status_a().unwrap();
+
+    assert_eq!(75_318_000, status[0].total_voltage);
+    assert_eq!("56.31578", status[0].temperature.to_string());
+    assert_eq!(3_200_000, status[0].analog_power);
+    assert_eq!(status[0], status[1]);
+}
+
+#[test]
+fn test_status_a_from_register() {
+    let status = StatusA::from_register(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D]);
+
+    assert_eq!(25106, status.total_voltage_raw);
+    assert_eq!(25256, status.temperature_raw);
+    assert_eq!(32000, status.analog_power_raw);
+
+    assert_eq!(75_318_000, status.total_voltage);
+    assert_eq!("56.31578", status.temperature.to_string());
+    assert_eq!(3_200_000, status.analog_power);
+}
+
+#[test]
+fn test_status_a_from_register_temperature_overflow() {
+    let status = StatusA::from_register(&[0x00, 0x00, 0xF0, 0xD1, 0x00, 0x00]);
+
+    assert_eq!(53744, status.temperature_raw);
+    assert_eq!(fixed::types::I16F16::MAX, status.temperature);
+}
+
+#[test]
+fn test_ltc6804_read_status_a_temperature() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
+        .expect_register_read(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D, 0x31, 0x8A])
+        .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
+        .expect_register_read(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D, 0x31, 0x8A])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6804(bus, get_cs(2));
+
+    // 25256 * 100 uV / 7.5 mV/°C - 273 °C
+    let status = client.read_status_a().unwrap();
+    assert_eq!(25256, status[0].temperature_raw);
+    assert_eq!("63.74666", status[0].temperature.to_string());
+
+    let temperatures = client.read_internal_temperature().unwrap();
+    assert_eq!(637, temperatures[0]);
+}
+
+#[test]
+fn test_status_a_from_register_of_device_type() {
+    let data = [0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D];
+
+    assert_eq!(
+        StatusA::from_register(&data),
+        StatusA::from_register_of::<LTC6813>(&data)
+    );
+    assert_eq!(
+        "63.74666",
+        StatusA::from_register_of::<LTC6804>(&data).temperature.to_string()
+    );
+}
+
+#[test]
+fn test_read_internal_temperature() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
+        .expect_register_read(&[0x12, 0x62, 0xA8, 0x62, 0x00, 0x7D, 0x31, 0x8A])
+        .expect_register_read(&[0x00, 0x00, 0xF0, 0x51, 0x00, 0x00, 0x6F, 0x82])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(1));
+    let temperatures = client.read_internal_temperature().unwrap();
+
+    assert_eq!(563, temperatures[0]);
+    assert_eq!(0, temperatures[1]);
+}
+
+#[test]
+fn test_read_status_b() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x00, 0xC8, 0x00, 0x66, 0x00, 0x1B, 0xF1, 0x40])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(1));
+    let status = client.read_status_b().unwrap();
+
+    assert_eq!(5_120_000, status[0].digital_power);
+    assert_eq!(
+        [false, false, false, false, false, true, false, true, false, false, false, false],
+        status[0].under_voltage
+    );
+    assert_eq!(
+        [false, false, false, false, true, false, true, false, false, false, false, false],
+        status[0].over_voltage
+    );
+    assert!(status[0].thermal_shutdown);
+    assert!(status[0].mux_fail);
+    assert_eq!(1, status[0].revision);
+}
+
+#[test]
+fn test_status_b_from_register() {
+    let status = StatusB::from_register(&[0x10, 0x27, 0b0000_0011, 0x00, 0b1000_0000, 0b0011_0000]);
+
+    assert_eq!(1_000_000, status.digital_power);
+    assert!(status.under_voltage[0]);
+    assert!(status.over_voltage[0]);
+    assert!(status.over_voltage[11]);
+    assert!(!status.under_voltage[11]);
+    assert!(!status.thermal_shutdown);
+    assert!(!status.mux_fail);
+    assert_eq!(3, status.revision);
+}
+
+#[test]
+fn test_read_cell_flags_including_aux_d() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x00, 0xC8, 0x00, 0x66, 0x00, 0x1B, 0xF1, 0x40])
+        .expect_command(0b0000_0000, 0b0000_1111, 0xF9, 0xA8)
+        .expect_register_read(&[0x98, 0x3A, 0x00, 0x00, 0x06, 0xFA, 0xF7, 0xFE])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(2));
+    let flags = client.read_cell_flags().unwrap();
+
+    assert!(flags[0].is_over_voltage(Cell::Cell5));
+    assert!(flags[0].is_under_voltage(Cell::Cell6));
+    assert!(flags[0].is_over_voltage(Cell::Cell13));
+    assert!(flags[0].is_under_voltage(Cell::Cell14));
+    assert!(flags[0].is_over_voltage(Cell::Cell17));
+    assert!(flags[0].is_over_voltage(Cell::Cell18));
+    assert!(!flags[0].is_under_voltage(Cell::Cell18));
+
+    // Bits beyond cell 18 are cleared
+    assert_eq!(0b11_0001_0000_0101_0000, flags[0].over_voltage);
+    assert_eq!(0b00_0010_0000_1010_0000, flags[0].under_voltage);
+}
+
+#[test]
+fn test_read_cell_flags_masks_unavailable_cells() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0010, 0x70, 0x24)
+        .expect_register_read(&[0x00, 0xC8, 0xFF, 0xFF, 0x00, 0x00, 0x9B, 0xC8])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6810(bus, get_cs(1));
+    let flags = client.read_cell_flags().unwrap();
+
+    assert_eq!(0b11_1111, flags[0].over_voltage);
+    assert_eq!(0b11_1111, flags[0].under_voltage);
+    assert!(flags[0].any());
+}
+
+#[test]
+fn test_read_status_a_checksum_mismatch() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0001_0000, 0xED, 0x72)
+        .expect_register_read(&[0xFF; 8])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+    match client.read_status_a() {
+        Err(Error::ChecksumMismatch) => {}
+        _ => panic!("Unexpected result"),
+    }
+}
@@ -0,0 +1,80 @@
+//! Tests for strict wrapper
+use crate::ltc6813::{CellSelection, Channel, Register};
+use crate::mocks::{get_cs, BusError, BusMockBuilder, MockPin, MockSPIBus};
+use crate::monitor::{ADCMode, LTC681X};
+use crate::strict::StrictClient;
+
+#[test]
+fn test_strict_read_register() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x94])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(1));
+    let mut strict = StrictClient::new(client);
+
+    let result = strict.read_register(Register::CellVoltageA);
+    assert_eq!([[24979, 7867, 8878], [26333, 7538, 7330]], result);
+}
+
+#[test]
+fn test_strict_read_voltages() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_command(0b0000_0000, 0b0000_1000, 0x5E, 0x52)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .expect_command(0b0000_0000, 0b0000_1001, 0xD5, 0x60)
+        .expect_register_read(&[0xDE, 0x64, 0x8F, 0x21, 0x8A, 0x21, 0x8F, 0xDA])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(3));
+    let mut strict = StrictClient::new(client);
+
+    let result = strict.read_voltages(CellSelection::Group1);
+    assert_eq!(3, result[0].len());
+
+    assert_eq!(Channel::Cell1, result[0][0].channel);
+    assert_eq!(24979, result[0][0].voltage);
+
+    assert_eq!(Channel::Cell7, result[0][1].channel);
+    assert_eq!(25441, result[0][1].voltage);
+
+    assert_eq!(Channel::Cell13, result[0][2].channel);
+    assert_eq!(25822, result[0][2].voltage);
+}
+
+#[test]
+#[should_panic(
+    expected = "read_register (command [00, 04, 07, C2]) failed: PEC mismatch of device 1, data [DD, 66, 72, 1D, A2, 1C], PEC [11, 95]"
+)]
+fn test_strict_read_register_pec_mismatch() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0100, 0x07, 0xC2)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        .expect_register_read(&[0xDD, 0x66, 0x72, 0x1D, 0xA2, 0x1C, 0x11, 0x95])
+        .into_mock();
+
+    let client: LTC681X<_, _, _, _, 2> = LTC681X::ltc6813(bus, get_cs(1));
+    let mut strict = StrictClient::new(client);
+
+    strict.read_register(Register::CellVoltageA);
+}
+
+#[test]
+#[should_panic(expected = "start_conv_cells failed: TransferError")]
+fn test_strict_transfer_error() {
+    let mut bus = MockSPIBus::new();
+    bus.expect_transfer().times(1).returning(move |_| Err(BusError::Error1));
+
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(1).returning(move || Ok(()));
+    cs.expect_set_high().times(1).returning(move || Ok(()));
+
+    let client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+    let mut strict = StrictClient::new(client);
+
+    strict.start_conv_cells(ADCMode::Normal, CellSelection::All, true);
+}
@@ -0,0 +1,189 @@
+//! Tests for thermistor temperature measurement
+use crate::ltc6813::{Channel, GPIOSelection};
+use crate::mocks::{get_cs, BusMockBuilder};
+use crate::monitor::{ADCMode, LTC681X};
+use crate::temperature::{ln, TemperatureConfig, Thermistor, ThermistorModel, VoltageDivider};
+use embedded_hal::blocking::delay::DelayUs;
+use fixed::types::I16F16;
+
+const NTC_10K: ThermistorModel = ThermistorModel::Beta {
+    nominal_resistance: 10_000,
+    beta: 3435,
+};
+
+const DIVIDER: VoltageDivider = VoltageDivider {
+    supply_voltage: 3_000_000,
+    series_resistance: 10_000,
+};
+
+#[test]
+fn test_ln() {
+    assert!(ln(1.0).abs() < 1e-6);
+    assert!((ln(core::f32::consts::E) - 1.0).abs() < 1e-6);
+    assert!((ln(0.5) + core::f32::consts::LN_2).abs() < 1e-6);
+    assert!((ln(10_000.0) - 9.210_34).abs() < 1e-5);
+    assert!((ln(1.999_9) - 0.693_097).abs() < 1e-5);
+    assert!((ln(1e-40) + 92.103_4).abs() < 1e-3);
+    assert!(ln(0.0).is_nan());
+    assert!(ln(-1.0).is_nan());
+}
+
+#[test]
+fn test_divider_resistance() {
+    assert_eq!(Some(10_000.0), DIVIDER.resistance(1_500_000));
+    assert_eq!(Some(5_000.0), DIVIDER.resistance(1_000_000));
+    assert_eq!(None, DIVIDER.resistance(0));
+    assert_eq!(None, DIVIDER.resistance(3_000_000));
+    assert_eq!(None, DIVIDER.resistance(3_100_000));
+}
+
+#[test]
+fn test_beta_model() {
+    assert!((NTC_10K.temperature(10_000.0).unwrap() - 25.0).abs() < 0.01);
+    assert!((NTC_10K.temperature(3_554.5).unwrap() - 54.4).abs() < 0.1);
+    assert!(NTC_10K.temperature(28_704.0).unwrap().abs() < 0.01);
+    assert_eq!(None, NTC_10K.temperature(0.0));
+}
+
+#[test]
+fn test_steinhart_hart_model() {
+    // Coefficients of a typical 10k NTC
+    let model = ThermistorModel::SteinhartHart {
+        a: 1.009_249_5e-3,
+        b: 2.378_405e-4,
+        c: 2.019_202e-7,
+    };
+
+    assert!((model.temperature(10_000.0).unwrap() - 25.0).abs() < 0.5);
+    assert_eq!(None, model.temperature(-1.0));
+}
+
+#[test]
+fn test_thermistor_temperature() {
+    let thermistor = Thermistor::new(NTC_10K, DIVIDER);
+
+    let temperature = thermistor.temperature(1_500_000).unwrap();
+    assert!((temperature - I16F16::from_num(25)).abs() < I16F16::from_num(0.01));
+
+    assert_eq!(None, thermistor.temperature(0));
+    assert_eq!(None, thermistor.temperature(3_000_000));
+}
+
+#[test]
+fn test_ratiometric_temperature() {
+    let thermistor = Thermistor::new(NTC_10K, DIVIDER);
+
+    assert_eq!(Some(10_000.0), DIVIDER.ratiometric_resistance(1_450_000, 2_900_000));
+    assert_eq!(None, DIVIDER.ratiometric_resistance(1_450_000, 1_000_000));
+    assert_eq!(None, DIVIDER.ratiometric_resistance(1_450_000, 0));
+
+    let temperature = thermistor.ratiometric_temperature(1_450_000, 2_900_000).unwrap();
+    assert!((temperature - I16F16::from_num(25)).abs() < I16F16::from_num(0.01));
+}
+
+#[test]
+fn test_read_temperatures_ratiometric() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0110_0000, 0xD3, 0xA0)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1101, 0x64, 0xFE)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        // Register B
+        .expect_command(0b0000_0000, 0b0000_1110, 0x72, 0x9A)
+        .expect_register_read(&[0x00, 0x00, 0x00, 0x00, 0x48, 0x71, 0x88, 0x2C])
+        // Register D
+        .expect_command(0b0000_0000, 0b0000_1111, 0xF9, 0xA8)
+        .expect_register_read(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC2, 0x12])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(5));
+    let mut delay = RecordingDelay::default();
+
+    let thermistor = Thermistor::new(NTC_10K, DIVIDER);
+    let config = TemperatureConfig::new(GPIOSelection::Group2, thermistor).ratiometric();
+    assert!(config.is_ratiometric());
+
+    let result = monitor.read_temperatures(&config, &mut delay).unwrap();
+    assert_eq!(2, result[0].len());
+
+    // GPIO2 = 0.7867 V, VREF2 = 2.9 V
+    assert_eq!(Channel::GPIO2, result[0][0].channel);
+    assert_eq!(
+        thermistor.ratiometric_temperature(786_700, 2_900_000),
+        result[0][0].temperature
+    );
+    assert_ne!(thermistor.temperature(786_700), result[0][0].temperature);
+
+    assert_eq!(Channel::GPIO7, result[0][1].channel);
+    assert_eq!(
+        thermistor.ratiometric_temperature(786_900, 2_900_000),
+        result[0][1].temperature
+    );
+}
+
+#[test]
+fn test_read_temperatures_no_polling() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0110_0010, 0x4E, 0xF6)
+        // Register A
+        .expect_command(0b0000_0000, 0b0000_1100, 0xEF, 0xCC)
+        .expect_register_read(&[0x93, 0x61, 0xBB, 0x1E, 0xAE, 0x22, 0x9A, 0x1C])
+        // Register C
+        .expect_command(0b0000_0000, 0b0000_1101, 0x64, 0xFE)
+        .expect_register_read(&[0x61, 0x63, 0xBD, 0x1E, 0xE4, 0x22, 0x3F, 0x42])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(3));
+    let mut delay = RecordingDelay::default();
+
+    let config = TemperatureConfig::new(GPIOSelection::Group2, Thermistor::new(NTC_10K, DIVIDER));
+    let result = monitor.read_temperatures(&config, &mut delay).unwrap();
+
+    assert_eq!(1_000, delay.total);
+    assert_eq!(2, result[0].len());
+
+    assert_eq!(Channel::GPIO2, result[0][0].channel);
+    let temperature = result[0][0].temperature.unwrap();
+    assert!((temperature - I16F16::from_num(54.4)).abs() < I16F16::from_num(0.1));
+
+    assert_eq!(Channel::GPIO7, result[0][1].channel);
+    assert!(result[0][1].temperature.is_some());
+}
+
+#[test]
+fn test_read_temperatures_sdo_polling() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0101, 0b0110_0110, 0xFF, 0x68)
+        .expect_poll(false)
+        .expect_poll(true)
+        // Register B
+        .expect_command(0b0000_0000, 0b0000_1110, 0x72, 0x9A)
+        .expect_register_read(&[0x00, 0x00, 0x00, 0x00, 0x30, 0x75, 0x47, 0x14])
+        .into_mock();
+
+    let mut monitor: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(2)).enable_sdo_polling();
+
+    let mut delay = RecordingDelay::default();
+
+    let config = TemperatureConfig::new(GPIOSelection::Group6, Thermistor::new(NTC_10K, DIVIDER)).mode(ADCMode::Normal);
+    let result = monitor.read_temperatures(&config, &mut delay).unwrap();
+
+    // Second reference is skipped
+    assert_eq!(0, result[0].len());
+    assert_eq!(100, delay.total);
+}
+
+/// Delay mock summing up all delays
+#[derive(Default)]
+struct RecordingDelay {
+    total: u32,
+}
+
+impl DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.total += us;
+    }
+}
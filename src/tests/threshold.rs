@@ -0,0 +1,168 @@
+//! Tests for temperature-compensated OV/UV thresholds
+use crate::config::{Configuration, ConfigurationRegisters};
+use crate::mocks::{get_cs, BusMockBuilder, MockPin};
+use crate::monitor::LTC681X;
+use crate::snapshot::MAX_GPIOS;
+use crate::threshold::{LimitCurve, LimitPoint, ThresholdCompensator, VoltageLimits};
+use fixed::types::I16F16;
+
+#[test]
+fn test_limit_curve_interpolation() {
+    // Points are sorted on creation
+    let curve = LimitCurve::new([
+        LimitPoint::new(I16F16::from_num(45), 3_000_000, 4_100_000),
+        LimitPoint::new(I16F16::from_num(0), 3_000_000, 4_000_000),
+        LimitPoint::new(I16F16::from_num(10), 2_800_000, 4_200_000),
+    ]);
+
+    assert_eq!(limits(3_000_000, 4_000_000), curve.limits_at(I16F16::from_num(-20)));
+    assert_eq!(limits(3_000_000, 4_000_000), curve.limits_at(I16F16::from_num(0)));
+    assert_eq!(limits(2_900_000, 4_100_000), curve.limits_at(I16F16::from_num(5)));
+    assert_eq!(limits(2_800_000, 4_200_000), curve.limits_at(I16F16::from_num(10)));
+    assert_eq!(limits(2_880_000, 4_160_000), curve.limits_at(I16F16::from_num(24)));
+    assert_eq!(limits(3_000_000, 4_100_000), curve.limits_at(I16F16::from_num(60)));
+
+    assert_eq!(limits(3_000_000, 4_000_000), curve.most_restrictive());
+}
+
+#[test]
+fn test_target_limits_most_restrictive_temperature() {
+    let compensator: ThresholdCompensator<3, 2> = ThresholdCompensator::new(get_curve());
+
+    let mut temperatures = [[None; MAX_GPIOS]; 2];
+    temperatures[0][0] = Some(I16F16::from_num(5));
+    temperatures[0][3] = Some(I16F16::from_num(50));
+    temperatures[0][4] = Some(I16F16::from_num(20));
+
+    let target = compensator.target_limits(&temperatures);
+
+    // UV of 50 °C, OV of 5 °C
+    assert_eq!(limits(3_000_000, 4_100_000), target[0]);
+
+    // No temperature known
+    assert_eq!(limits(3_000_000, 4_000_000), target[1]);
+}
+
+#[test]
+fn test_update_step() {
+    let mut compensator: ThresholdCompensator<3, 1> = ThresholdCompensator::new(get_curve()).with_step(20_000);
+    assert_eq!(None, compensator.limits(0));
+
+    let mut temperatures = [[None; MAX_GPIOS]; 1];
+    temperatures[0][0] = Some(I16F16::from_num(5));
+
+    assert!(compensator.update(&temperatures));
+    assert_eq!(Some(limits(2_900_000, 4_100_000)), compensator.limits(0));
+
+    // Shift of 20 mV
+    temperatures[0][0] = Some(I16F16::from_num(6));
+    assert!(!compensator.update(&temperatures));
+    assert_eq!(Some(limits(2_900_000, 4_100_000)), compensator.limits(0));
+
+    // Shift of 40 mV
+    temperatures[0][0] = Some(I16F16::from_num(7));
+    assert!(compensator.update(&temperatures));
+    assert_eq!(Some(limits(2_860_000, 4_140_000)), compensator.limits(0));
+
+    compensator.reset();
+    assert_eq!(None, compensator.limits(0));
+}
+
+#[test]
+fn test_apply() {
+    let mut compensator: ThresholdCompensator<3, 2> = ThresholdCompensator::new(get_curve());
+    let mut temperatures = [[None; MAX_GPIOS]; 2];
+    temperatures[0][0] = Some(I16F16::from_num(5));
+    compensator.update(&temperatures);
+
+    let mut config = [Configuration::default(), Configuration::default()];
+    compensator.apply(&mut config).unwrap();
+
+    let mut expected = Configuration::default();
+    expected.set_uv_comp_voltage(2_900_000).unwrap();
+    expected.set_ov_comp_voltage(4_100_000).unwrap();
+    assert_eq!(expected.register_a(), config[0].register_a());
+
+    let mut expected = Configuration::default();
+    expected.set_uv_comp_voltage(3_000_000).unwrap();
+    expected.set_ov_comp_voltage(4_000_000).unwrap();
+    assert_eq!(expected.register_a(), config[1].register_a());
+}
+
+#[test]
+fn test_program() {
+    let bus = BusMockBuilder::new()
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0xD5, 0x26, 0xA0, 0x00, 0x00, 0x61, 0x20])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, get_cs(3));
+    let curve = LimitCurve::new([
+        LimitPoint::new(I16F16::from_num(0), 2_800_000, 4_000_000),
+        LimitPoint::new(I16F16::from_num(10), 2_800_000, 4_200_000),
+    ]);
+    let mut compensator: ThresholdCompensator<2, 1> = ThresholdCompensator::new(curve);
+
+    let mut temperatures = [[None; MAX_GPIOS]; 1];
+    temperatures[0][0] = Some(I16F16::from_num(5));
+
+    assert!(compensator.program(&mut client, &temperatures).unwrap());
+
+    // Unchanged, so no bus access
+    assert!(!compensator.program(&mut client, &temperatures).unwrap());
+}
+
+#[test]
+fn test_program_failed_retried() {
+    let mut cs = MockPin::new();
+    cs.expect_set_low().times(4).returning(move || Ok(()));
+    cs.expect_set_high().times(4).returning(move || Ok(()));
+
+    let bus = BusMockBuilder::new()
+        // PEC error
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE3])
+        // Retry
+        .expect_command(0b0000_0000, 0b0000_0010, 0x2B, 0x0A)
+        .expect_register_read(&[0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0xBE, 0xE2])
+        .expect_command(0b0000_0000, 0b0010_0110, 0x2C, 0xC8)
+        .expect_register_read(&[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E, 0x68])
+        .expect_command(0b0000_0000, 0b0000_0001, 0x3D, 0x6E)
+        .expect_register_write(&[0xF8, 0xD5, 0x26, 0xA0, 0x00, 0x00, 0x61, 0x20])
+        .into_mock();
+
+    let mut client: LTC681X<_, _, _, _, 1> = LTC681X::ltc6813(bus, cs);
+    let curve = LimitCurve::new([
+        LimitPoint::new(I16F16::from_num(0), 2_800_000, 4_000_000),
+        LimitPoint::new(I16F16::from_num(10), 2_800_000, 4_200_000),
+    ]);
+    let mut compensator: ThresholdCompensator<2, 1> = ThresholdCompensator::new(curve);
+
+    let mut temperatures = [[None; MAX_GPIOS]; 1];
+    temperatures[0][0] = Some(I16F16::from_num(5));
+
+    assert!(compensator.program(&mut client, &temperatures).is_err());
+    assert_eq!(None, compensator.limits(0));
+
+    assert!(compensator.program(&mut client, &temperatures).unwrap());
+    assert_eq!(Some(limits(2_800_000, 4_100_000)), compensator.limits(0));
+}
+
+fn get_curve() -> LimitCurve<3> {
+    LimitCurve::new([
+        LimitPoint::new(I16F16::from_num(0), 3_000_000, 4_000_000),
+        LimitPoint::new(I16F16::from_num(10), 2_800_000, 4_200_000),
+        LimitPoint::new(I16F16::from_num(45), 3_000_000, 4_100_000),
+    ])
+}
+
+fn limits(under_voltage: u32, over_voltage: u32) -> VoltageLimits {
+    VoltageLimits {
+        under_voltage,
+        over_voltage,
+    }
+}
@@ -0,0 +1,60 @@
+//! Tests for chain transaction latency estimation
+use crate::timing::estimate_latency;
+
+#[test]
+fn test_estimate_latency_single_device() {
+    let latency = estimate_latency(1_000_000, 1, 1);
+
+    assert_eq!(400, latency.wake);
+    assert_eq!(32, latency.command);
+    assert_eq!(96, latency.read);
+    assert_eq!(528, latency.total());
+}
+
+#[test]
+fn test_estimate_latency_rounds_up() {
+    let latency = estimate_latency(3_000_000, 2, 4);
+
+    assert_eq!(800, latency.wake);
+    assert_eq!(11, latency.command);
+    assert_eq!(214, latency.read);
+}
+
+#[test]
+fn test_estimate_latency_long_chain() {
+    let latency = estimate_latency(500_000, 16, 6);
+
+    assert_eq!(6_400, latency.wake);
+    assert_eq!(64, latency.command);
+    assert_eq!(12_672, latency.read);
+}
+
+#[test]
+fn test_estimate_latency_zero_frequency() {
+    let latency = estimate_latency(0, 1, 1);
+
+    assert_eq!(u32::MAX, latency.command);
+    assert_eq!(u32::MAX, latency.read);
+}
+
+#[test]
+fn test_estimate_latency_zero_frequency_total_saturates() {
+    let latency = estimate_latency(0, 1, 1);
+
+    assert_eq!(400, latency.wake);
+    assert_eq!(u32::MAX, latency.total());
+}
+
+#[test]
+fn test_estimate_latency_saturates() {
+    let latency = estimate_latency(1, usize::MAX, usize::MAX);
+
+    assert_eq!(u32::MAX, latency.wake);
+    assert_eq!(32_000_000, latency.command);
+    assert_eq!(u32::MAX, latency.read);
+    assert_eq!(u32::MAX, latency.total());
+
+    // Transfer time exceeding u32 without saturating the byte count
+    let latency = estimate_latency(1, 1, 1_000);
+    assert_eq!(u32::MAX, latency.read);
+}
@@ -0,0 +1,67 @@
+//! Tests for voting of redundant measurement paths
+use crate::snapshot::Snapshot;
+use crate::voting::Voter;
+
+#[test]
+fn test_vote_agreed() {
+    let mut first: Snapshot<2> = Snapshot::new();
+    let mut second: Snapshot<2> = Snapshot::new();
+
+    first.device_mut(0).cell_voltages[0] = Some(36_000);
+    second.device_mut(0).cell_voltages[0] = Some(36_050);
+    first.device_mut(1).gpio_voltages[8] = Some(15_001);
+    second.device_mut(1).gpio_voltages[8] = Some(15_000);
+
+    let result = Voter::new(50).vote(&first, &second);
+
+    assert!(!result.has_mismatch());
+    assert_eq!(Some(36_025), result.agreed.device(0).cell_voltages[0]);
+    assert_eq!(Some(15_000), result.agreed.device(1).gpio_voltages[8]);
+    assert_eq!(None, result.agreed.device(0).cell_voltages[1]);
+}
+
+#[test]
+fn test_vote_threshold_exceeded() {
+    let mut first: Snapshot<2> = Snapshot::new();
+    let mut second: Snapshot<2> = Snapshot::new();
+
+    first.device_mut(1).cell_voltages[17] = Some(36_000);
+    second.device_mut(1).cell_voltages[17] = Some(35_949);
+    first.device_mut(1).gpio_voltages[0] = Some(0);
+    second.device_mut(1).gpio_voltages[0] = Some(u16::MAX);
+
+    let result = Voter::new(50).vote(&first, &second);
+
+    assert!(result.has_mismatch());
+    assert!(!result.mismatches[0].any());
+    assert!(result.mismatches[1].cells[17]);
+    assert!(result.mismatches[1].gpios[0]);
+    assert_eq!(None, result.agreed.device(1).cell_voltages[17]);
+    assert_eq!(None, result.agreed.device(1).gpio_voltages[0]);
+}
+
+#[test]
+fn test_vote_missing_value() {
+    let mut first: Snapshot<1> = Snapshot::new();
+    let second: Snapshot<1> = Snapshot::new();
+
+    first.device_mut(0).cell_voltages[3] = Some(36_000);
+
+    let result = Voter::new(50).vote(&first, &second);
+
+    assert!(result.mismatches[0].cells[3]);
+    assert_eq!(1, result.mismatches[0].cells.iter().filter(|flag| **flag).count());
+    assert_eq!(None, result.agreed.device(0).cell_voltages[3]);
+}
+
+#[test]
+fn test_vote_large_values() {
+    let mut first: Snapshot<1> = Snapshot::new();
+    let mut second: Snapshot<1> = Snapshot::new();
+
+    first.device_mut(0).cell_voltages[0] = Some(u16::MAX);
+    second.device_mut(0).cell_voltages[0] = Some(u16::MAX - 1);
+
+    let result = Voter::new(1).vote(&first, &second);
+    assert_eq!(Some(u16::MAX - 1), result.agreed.device(0).cell_voltages[0]);
+}
@@ -0,0 +1,301 @@
+//! Temperature-compensated OV/UV thresholds
+//!
+//! The permitted cell voltage range of most chemistries depends on the temperature, e.g. charging to the full
+//! voltage is not permitted at low temperatures. The [ThresholdCompensator] derives the under- and over-voltage
+//! limits from a [LimitCurve] and the latest measured temperatures, and programs them as comparator voltages of
+//! the device configuration.
+//!
+//! For each device, the most restrictive limits of all its temperatures are used (highest UV, lowest OV). Devices
+//! without any valid temperature use the most restrictive limits of the whole curve. To avoid rewriting the
+//! configuration on every temperature change, the limits are only reprogrammed if they shift by more than the
+//! configured [step](ThresholdCompensator::with_step).
+//!
+//! The programmed limits may also be used as software thresholds, e.g. for cells 13 - 18 or for plausibility
+//! checks of the measured voltages.
+//!
+//! ````
+//! use fixed::types::I16F16;
+//! use ltc681x::example::{ExampleCSPin, ExampleSPIBus};
+//! use ltc681x::ltc6813::LTC6813;
+//! use ltc681x::monitor::LTC681X;
+//! use ltc681x::snapshot::MAX_GPIOS;
+//! use ltc681x::threshold::{LimitCurve, LimitPoint, ThresholdCompensator};
+//!
+//! let mut client: LTC681X<_, _, _, LTC6813, 1> = LTC681X::ltc6813(ExampleSPIBus::default(), ExampleCSPin{});
+//!
+//! // Over-voltage limit reduced to 4.0 V below 0 °C
+//! let curve = LimitCurve::new([
+//!     LimitPoint::new(I16F16::from_num(0), 2_800_000, 4_000_000),
+//!     LimitPoint::new(I16F16::from_num(10), 2_800_000, 4_200_000),
+//! ]);
+//! let mut compensator: ThresholdCompensator<2, 1> = ThresholdCompensator::new(curve).with_step(20_000);
+//!
+//! let mut temperatures = [[None; MAX_GPIOS]; 1];
+//! temperatures[0][0] = Some(I16F16::from_num(5));
+//! temperatures[0][1] = Some(I16F16::from_num(25));
+//!
+//! assert!(compensator.program(&mut client, &temperatures).unwrap());
+//! assert_eq!(4_100_000, compensator.limits(0).unwrap().over_voltage);
+//!
+//! // Shift of 10 mV is below the step, so the configuration is not written
+//! temperatures[0][0] = Some(I16F16::from_num(5.5));
+//! assert!(!compensator.program(&mut client, &temperatures).unwrap());
+//! assert_eq!(4_100_000, compensator.limits(0).unwrap().over_voltage);
+//! ````
+use crate::config::{Configuration, VoltageOutOfRangeError};
+use crate::monitor::{DeviceTypes, LTC681XClient};
+use crate::snapshot::MAX_GPIOS;
+use fixed::types::I16F16;
+
+/// Under- and over-voltage limits in uV
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct VoltageLimits {
+    /// Under-voltage limit in uV
+    pub under_voltage: u32,
+
+    /// Over-voltage limit in uV
+    pub over_voltage: u32,
+}
+
+impl VoltageLimits {
+    /// Returns true if one of the limits differs by more than the given step (uV)
+    fn exceeds_step(&self, other: &VoltageLimits, step: u32) -> bool {
+        self.under_voltage.abs_diff(other.under_voltage) > step || self.over_voltage.abs_diff(other.over_voltage) > step
+    }
+
+    /// Returns the more restrictive limits of both
+    fn restrict(&self, other: &VoltageLimits) -> VoltageLimits {
+        VoltageLimits {
+            under_voltage: self.under_voltage.max(other.under_voltage),
+            over_voltage: self.over_voltage.min(other.over_voltage),
+        }
+    }
+
+    /// Sets the limits as comparator voltages of the given configuration
+    pub fn apply(&self, config: &mut Configuration) -> Result<(), VoltageOutOfRangeError> {
+        config.set_uv_comp_voltage(self.under_voltage)?;
+        config.set_ov_comp_voltage(self.over_voltage)
+    }
+}
+
+/// Supporting point of the limit curve
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct LimitPoint {
+    /// Temperature in °C
+    pub temperature: I16F16,
+
+    /// Limits at this temperature
+    pub limits: VoltageLimits,
+}
+
+impl LimitPoint {
+    /// Creates a new point with the given under- and over-voltage limits in uV
+    pub const fn new(temperature: I16F16, under_voltage: u32, over_voltage: u32) -> Self {
+        Self {
+            temperature,
+            limits: VoltageLimits {
+                under_voltage,
+                over_voltage,
+            },
+        }
+    }
+}
+
+/// Temperature-dependent limits, linearly interpolated between the supporting points
+/// Temperatures outside the curve use the limits of the closest point.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct LimitCurve<const N: usize> {
+    /// Supporting points, sorted by temperature
+    points: [LimitPoint; N],
+}
+
+impl<const N: usize> LimitCurve<N> {
+    /// Creates a new curve of the given points, which are sorted by temperature
+    ///
+    /// Panics if no point is given.
+    pub fn new(mut points: [LimitPoint; N]) -> Self {
+        assert!(N > 0, "Limit curve requires at least one point");
+        points.sort_unstable_by_key(|point| point.temperature);
+
+        Self { points }
+    }
+
+    /// Returns the limits at the given temperature (°C)
+    pub fn limits_at(&self, temperature: I16F16) -> VoltageLimits {
+        let first = &self.points[0];
+        let last = &self.points[N - 1];
+
+        if temperature <= first.temperature {
+            return first.limits;
+        }
+
+        if temperature >= last.temperature {
+            return last.limits;
+        }
+
+        let index = self
+            .points
+            .iter()
+            .position(|point| point.temperature > temperature)
+            .unwrap_or(N - 1);
+        let lower = &self.points[index - 1];
+        let upper = &self.points[index];
+
+        VoltageLimits {
+            under_voltage: interpolate(lower, upper, temperature, |limits| limits.under_voltage),
+            over_voltage: interpolate(lower, upper, temperature, |limits| limits.over_voltage),
+        }
+    }
+
+    /// Returns the most restrictive limits of the whole curve
+    pub fn most_restrictive(&self) -> VoltageLimits {
+        self.points[1..]
+            .iter()
+            .fold(self.points[0].limits, |limits, point| limits.restrict(&point.limits))
+    }
+}
+
+/// Derives the comparator voltages from the measured temperatures
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ThresholdCompensator<const N: usize, const L: usize> {
+    /// Temperature-dependent limits
+    curve: LimitCurve<N>,
+
+    /// Minimum shift (uV) of the limits for reprogramming
+    step: u32,
+
+    /// Last programmed limits, None if not programmed yet
+    programmed: [Option<VoltageLimits>; L],
+}
+
+impl<const N: usize, const L: usize> ThresholdCompensator<N, L> {
+    /// Creates a new compensator for the given curve
+    /// Defaults to a step of 10 mV.
+    pub fn new(curve: LimitCurve<N>) -> Self {
+        Self {
+            curve,
+            step: 10_000,
+            programmed: [None; L],
+        }
+    }
+
+    /// Sets the minimum shift (uV) of the limits for reprogramming the configuration
+    pub fn with_step(mut self, step: u32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Returns the limits based on the given temperatures (°C), indexed by GPIO (index 0 = GPIO 1)
+    /// One array item per device in daisy chain.
+    pub fn target_limits(&self, temperatures: &[[Option<I16F16>; MAX_GPIOS]; L]) -> [VoltageLimits; L] {
+        core::array::from_fn(|device| {
+            temperatures[device]
+                .iter()
+                .flatten()
+                .map(|temperature| self.curve.limits_at(*temperature))
+                .reduce(|limits, other| limits.restrict(&other))
+                .unwrap_or_else(|| self.curve.most_restrictive())
+        })
+    }
+
+    /// Updates the limits based on the given temperatures, see [target_limits](Self::target_limits)
+    /// Limits of a device are only taken over if they shift by more than the configured step or were not set yet.
+    /// Returns true if the limits of any device changed.
+    pub fn update(&mut self, temperatures: &[[Option<I16F16>; MAX_GPIOS]; L]) -> bool {
+        match self.shifted_limits(temperatures) {
+            Some(limits) => {
+                self.programmed = limits;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the limits of all devices after an [update](Self::update), None if no limits changed
+    fn shifted_limits(&self, temperatures: &[[Option<I16F16>; MAX_GPIOS]; L]) -> Option<[Option<VoltageLimits>; L]> {
+        let mut changed = false;
+        let mut limits = self.programmed;
+
+        for (programmed, target) in limits.iter_mut().zip(self.target_limits(temperatures)) {
+            let shifted = match programmed {
+                None => true,
+                Some(current) => current.exceeds_step(&target, self.step),
+            };
+
+            if shifted {
+                *programmed = Some(target);
+                changed = true;
+            }
+        }
+
+        if changed {
+            Some(limits)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the current limits as comparator voltages of the given configurations
+    /// Configurations of devices without limits are left unchanged.
+    pub fn apply(&self, config: &mut [Configuration; L]) -> Result<(), VoltageOutOfRangeError> {
+        for (config, limits) in config.iter_mut().zip(self.programmed.iter()) {
+            if let Some(limits) = limits {
+                limits.apply(config)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Updates the limits and reprograms the device configuration if they changed, see [update](Self::update)
+    /// The configuration is updated by [read-modify-write](LTC681XClient::update_configuration).
+    ///
+    /// Returns true if the configuration has been written. Limits out of the comparator range leave the
+    /// corresponding comparator voltage unchanged. In case of an error, the programmed limits are kept.
+    pub fn program<T: DeviceTypes, C: LTC681XClient<T, L>>(
+        &mut self,
+        client: &mut C,
+        temperatures: &[[Option<I16F16>; MAX_GPIOS]; L],
+    ) -> Result<bool, C::Error> {
+        let programmed = match self.shifted_limits(temperatures) {
+            Some(limits) => limits,
+            None => return Ok(false),
+        };
+
+        let written = client.update_configuration(|config: &mut [Configuration; L]| {
+            for (config, limits) in config.iter_mut().zip(programmed.iter()) {
+                if let Some(limits) = limits {
+                    let _ = config.set_uv_comp_voltage(limits.under_voltage);
+                    let _ = config.set_ov_comp_voltage(limits.over_voltage);
+                }
+            }
+        })?;
+
+        // Only taken over after the configuration has been written, so a failed write is retried by the next call
+        self.programmed = programmed;
+        Ok(written)
+    }
+
+    /// Returns the last programmed limits of the given device, None if not programmed yet
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn limits(&self, device: usize) -> Option<VoltageLimits> {
+        self.programmed[device]
+    }
+
+    /// Forgets the programmed limits, so the next update reprograms all devices
+    pub fn reset(&mut self) {
+        self.programmed = [None; L];
+    }
+}
+
+/// Interpolates the selected limit linearly between both points
+fn interpolate<F: Fn(&VoltageLimits) -> u32>(lower: &LimitPoint, upper: &LimitPoint, temperature: I16F16, f: F) -> u32 {
+    let span = (upper.temperature.to_bits() - lower.temperature.to_bits()) as i64;
+    let offset = (temperature.to_bits() - lower.temperature.to_bits()) as i64;
+
+    let start = f(&lower.limits) as i64;
+    let end = f(&upper.limits) as i64;
+
+    (start + (end - start) * offset / span) as u32
+}
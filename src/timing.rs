@@ -0,0 +1,97 @@
+//! Estimation of chain transaction latency
+//!
+//! Allows system designers to validate whether a control loop period is achievable for a given SPI
+//! clock frequency and daisy chain length, before hardware exists.
+//!
+//! ````
+//! use ltc681x::timing::estimate_latency;
+//!
+//! // 1 MHz SPI clock, 4 devices in daisy chain, reading 6 registers (all cells of LTC6813)
+//! let latency = estimate_latency(1_000_000, 4, 6);
+//!
+//! // Waking up all devices from sleep state
+//! assert_eq!(1_600, latency.wake);
+//! // Sending a single command (4 bytes)
+//! assert_eq!(32, latency.command);
+//! // Reading 6 registers (4 command bytes + 8 data bytes per device)
+//! assert_eq!(1_728, latency.read);
+//!
+//! assert_eq!(3_360, latency.total());
+//! ````
+
+/// Maximum wakeup time of a single device from sleep state in microseconds (t_WAKE)
+pub const T_WAKE_US: u32 = 400;
+
+/// Maximum time from CS assertion until the isoSPI port is ready to transmit in microseconds (t_READY)
+pub const T_READY_US: u32 = 10;
+
+/// Conservative ready time of an isoSPI bridge (e.g. LTC6820) after its wake pulse in microseconds
+pub const T_BRIDGE_READY_US: u32 = 100;
+
+/// Minimum time without activity until the isoSPI port enters idle state in microseconds (t_IDLE)
+pub const T_IDLE_US: u32 = 4_300;
+
+/// Minimum watchdog timeout until the core enters sleep state in microseconds (t_SLEEP)
+pub const T_SLEEP_US: u32 = 1_800_000;
+
+/// Maximum reference power-up time after wakeup in microseconds (t_REFUP)
+pub const T_REFUP_US: u32 = 4_400;
+
+/// Number of bytes of a command frame (command + PEC)
+pub const COMMAND_FRAME_BYTES: u32 = 4;
+
+/// Number of bytes of a register data frame per device (6 data bytes + PEC)
+pub const DATA_FRAME_BYTES: u32 = 8;
+
+/// Estimated durations of a chain transaction in microseconds
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TransactionLatency {
+    /// Time for waking up all devices of the chain from sleep state
+    pub wake: u32,
+
+    /// Time for sending a single command
+    pub command: u32,
+
+    /// Time for reading the given number of registers of all devices
+    pub read: u32,
+}
+
+impl TransactionLatency {
+    /// Sum of all durations in microseconds, saturating at u32::MAX
+    pub fn total(&self) -> u32 {
+        self.wake.saturating_add(self.command).saturating_add(self.read)
+    }
+}
+
+/// Estimates wake, command and read durations based on the given parameters
+///
+/// All durations saturate at u32::MAX, which is also returned for transfers at a SPI frequency of zero.
+///
+/// # Arguments
+///
+/// * `spi_frequency`: SPI clock frequency in Hz
+/// * `chain_length`: Number of devices in daisy chain
+/// * `register_count`: Number of registers read per transaction
+pub fn estimate_latency(spi_frequency: u32, chain_length: usize, register_count: usize) -> TransactionLatency {
+    let chain_length = u32::try_from(chain_length).unwrap_or(u32::MAX);
+    let register_count = u32::try_from(register_count).unwrap_or(u32::MAX);
+
+    let frame_bytes = DATA_FRAME_BYTES.saturating_mul(chain_length);
+    let read_bytes = register_count.saturating_mul(COMMAND_FRAME_BYTES.saturating_add(frame_bytes));
+
+    TransactionLatency {
+        wake: T_WAKE_US.saturating_mul(chain_length),
+        command: bytes_to_micros(COMMAND_FRAME_BYTES, spi_frequency),
+        read: bytes_to_micros(read_bytes, spi_frequency),
+    }
+}
+
+/// Returns the transfer time of the given number of bytes in microseconds (rounded up, saturating at u32::MAX)
+fn bytes_to_micros(bytes: u32, spi_frequency: u32) -> u32 {
+    if spi_frequency == 0 {
+        return u32::MAX;
+    }
+
+    let bits = bytes as u64 * 8 * 1_000_000;
+    u32::try_from(bits.div_ceil(spi_frequency as u64)).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,123 @@
+//! Voting of redundant measurement paths
+//!
+//! In case two monitor chains measure the same cells (or the same chain is read via both isoSPI ports), the
+//! [Voter] compares both [snapshots](Snapshot) channel by channel. Channels within the configured threshold
+//! are agreed using the mean of both values, all others are flagged as mismatch.
+//!
+//! ````
+//! use ltc681x::snapshot::Snapshot;
+//! use ltc681x::voting::Voter;
+//!
+//! let mut primary: Snapshot<1> = Snapshot::new();
+//! let mut secondary: Snapshot<1> = Snapshot::new();
+//!
+//! primary.device_mut(0).cell_voltages[0] = Some(36_000);
+//! secondary.device_mut(0).cell_voltages[0] = Some(36_010);
+//!
+//! primary.device_mut(0).cell_voltages[1] = Some(36_000);
+//! secondary.device_mut(0).cell_voltages[1] = Some(34_000);
+//!
+//! // Maximum disagreement of 5 mV
+//! let result = Voter::new(50).vote(&primary, &secondary);
+//!
+//! assert_eq!(Some(36_005), result.agreed.device(0).cell_voltages[0]);
+//! assert!(!result.mismatches[0].cells[0]);
+//!
+//! assert_eq!(None, result.agreed.device(0).cell_voltages[1]);
+//! assert!(result.mismatches[0].cells[1]);
+//! ````
+//!
+//! ## Missing values
+//! Channels missing in both snapshots are not considered as mismatch. In case a channel is just missing in one
+//! snapshot, no vote is possible. So the channel is flagged and no agreed value is returned.
+use crate::snapshot::{Snapshot, MAX_CELLS, MAX_GPIOS};
+
+/// Flagged channels of a single device
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct DeviceMismatch {
+    /// True if the cell voltages disagree, index 0 = cell 1
+    pub cells: [bool; MAX_CELLS],
+
+    /// True if the GPIO voltages disagree, index 0 = GPIO 1
+    pub gpios: [bool; MAX_GPIOS],
+}
+
+impl DeviceMismatch {
+    /// Returns true if any channel of the device is flagged
+    pub fn any(&self) -> bool {
+        self.cells.iter().chain(self.gpios.iter()).any(|flag| *flag)
+    }
+}
+
+/// Result of comparing two snapshots
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct VoteResult<const L: usize> {
+    /// Agreed values, mismatching channels are None
+    pub agreed: Snapshot<L>,
+
+    /// Flagged channels, one item per device in daisy chain
+    pub mismatches: [DeviceMismatch; L],
+}
+
+impl<const L: usize> VoteResult<L> {
+    /// Returns true if any channel of any device is flagged
+    pub fn has_mismatch(&self) -> bool {
+        self.mismatches.iter().any(|device| device.any())
+    }
+}
+
+/// Compares the measurements of two redundant paths
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Voter {
+    /// Maximum accepted disagreement, raw value (100 uV per LSB)
+    threshold: u16,
+}
+
+impl Voter {
+    /// Creates a new voter with the given maximum disagreement (raw value, 100 uV per LSB)
+    pub fn new(threshold: u16) -> Self {
+        Self { threshold }
+    }
+
+    /// Returns the maximum accepted disagreement (raw value, 100 uV per LSB)
+    pub fn get_threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Compares both snapshots channel by channel
+    pub fn vote<const L: usize>(&self, first: &Snapshot<L>, second: &Snapshot<L>) -> VoteResult<L> {
+        let mut result = VoteResult {
+            agreed: Snapshot::new(),
+            mismatches: [DeviceMismatch::default(); L],
+        };
+
+        for device in 0..L {
+            let (a, b) = (first.device(device), second.device(device));
+            let agreed = result.agreed.device_mut(device);
+            let mismatch = &mut result.mismatches[device];
+
+            for index in 0..MAX_CELLS {
+                let (value, flag) = self.vote_channel(a.cell_voltages[index], b.cell_voltages[index]);
+                agreed.cell_voltages[index] = value;
+                mismatch.cells[index] = flag;
+            }
+
+            for index in 0..MAX_GPIOS {
+                let (value, flag) = self.vote_channel(a.gpio_voltages[index], b.gpio_voltages[index]);
+                agreed.gpio_voltages[index] = value;
+                mismatch.gpios[index] = flag;
+            }
+        }
+
+        result
+    }
+
+    /// Returns the agreed value and the mismatch flag of a single channel
+    fn vote_channel(&self, first: Option<u16>, second: Option<u16>) -> (Option<u16>, bool) {
+        match (first, second) {
+            (None, None) => (None, false),
+            (Some(a), Some(b)) if a.abs_diff(b) <= self.threshold => (Some(((a as u32 + b as u32) / 2) as u16), false),
+            _ => (None, true),
+        }
+    }
+}